@@ -0,0 +1,241 @@
+use crate::{BooleanResult, Polygon, SourceEdge};
+
+// The subject and clip polygons after their boundaries have been aligned. The
+// aligned polygons can have more vertices than the originals, so this also
+// stores how to map edges back to the original polygons.
+pub(crate) struct AlignedPolygons {
+  // The aligned subject polygon.
+  pub(crate) subject: Polygon,
+  // The aligned clip polygon.
+  pub(crate) clip: Polygon,
+  // The index of the original edge for each edge in the aligned subject.
+  subject_original_edges: Vec<Vec<usize>>,
+  // The index of the original edge for each edge in the aligned clip.
+  clip_original_edges: Vec<Vec<usize>>,
+}
+
+impl AlignedPolygons {
+  // Updates the source edges of `result` (computed from the aligned polygons)
+  // to refer to the edges of the original polygons.
+  pub(crate) fn remap_source_edges(&self, result: &mut BooleanResult) {
+    for source_edge in result.contour_source_edges.iter_mut().flatten() {
+      self.remap_source_edge(source_edge);
+    }
+    for source_edge in
+      result.contour_coincident_source_edges.iter_mut().flatten().flatten()
+    {
+      self.remap_source_edge(source_edge);
+    }
+  }
+
+  // Updates `source_edge` (an edge of the aligned polygons) to refer to the
+  // edge of the original polygons.
+  pub(crate) fn remap_source_edge(&self, source_edge: &mut SourceEdge) {
+    let original_edges = if source_edge.is_from_subject {
+      &self.subject_original_edges
+    } else {
+      &self.clip_original_edges
+    };
+    source_edge.edge = original_edges[source_edge.contour][source_edge.edge];
+  }
+
+  // Counts the vertices of `clip` (the clip before aligning) that were moved.
+  // Subject vertices are never moved, and inserted vertices are not counted.
+  pub(crate) fn moved_clip_vertices(&self, clip: &Polygon) -> usize {
+    let mut count = 0;
+    for (contour_index, original_edges) in
+      self.clip_original_edges.iter().enumerate()
+    {
+      for (index, &edge) in original_edges.iter().enumerate() {
+        // The first vertex of each original edge is the original vertex.
+        if (index == 0 || original_edges[index - 1] != edge)
+          && self.clip.contours[contour_index][index]
+            != clip.contours[contour_index][edge]
+        {
+          count += 1;
+        }
+      }
+    }
+    count
+  }
+}
+
+// Moves the boundaries of `subject` and `clip` that are within `tolerance` of
+// each other so that they coincide exactly. Clip vertices near subject vertices
+// are moved onto those subject vertices, and clip vertices near subject edges
+// are moved onto those edges (and inserted into them). Lastly, subject
+// vertices near clip edges are inserted into those clip edges. This takes
+// O(n*m) time where n and m are the number of vertices of each polygon.
+pub(crate) fn align_boundaries(
+  subject: &Polygon,
+  clip: &Polygon,
+  tolerance: f32,
+) -> AlignedPolygons {
+  let mut subject = subject.clone();
+  let mut clip = clip.clone();
+
+  let tolerance_squared = tolerance * tolerance;
+  for clip_point in clip.contours.iter_mut().flatten() {
+    let closest_subject_point = subject
+      .contours
+      .iter()
+      .flatten()
+      .map(|&subject_point| {
+        (subject_point, subject_point.distance_squared(*clip_point))
+      })
+      .filter(|&(_, distance_squared)| distance_squared <= tolerance_squared)
+      .min_by(|(_, a), (_, b)| a.total_cmp(b));
+    if let Some((subject_point, _)) = closest_subject_point {
+      *clip_point = subject_point;
+    }
+  }
+
+  let subject_original_edges = insert_nearby_points(
+    &mut subject,
+    &mut clip,
+    tolerance,
+    /* move_points= */ true,
+  );
+  let clip_original_edges = insert_nearby_points(
+    &mut clip,
+    &mut subject,
+    tolerance,
+    /* move_points= */ false,
+  );
+
+  AlignedPolygons { subject, clip, subject_original_edges, clip_original_edges }
+}
+
+// Inserts every point of `points` that is within `tolerance` of the interior of
+// an edge of `polygon` into that edge. If `move_points` is true, the point is
+// projected onto the edge (and the point in `points` is moved to match).
+// Otherwise, the point is inserted as is. Returns the index of the original
+// edge for each edge in the new `polygon`.
+fn insert_nearby_points(
+  polygon: &mut Polygon,
+  points: &mut Polygon,
+  tolerance: f32,
+  move_points: bool,
+) -> Vec<Vec<usize>> {
+  let mut original_edges = Vec::with_capacity(polygon.contours.len());
+  for contour in polygon.contours.iter_mut() {
+    let mut new_contour = Vec::with_capacity(contour.len());
+    let mut new_original_edges = Vec::with_capacity(contour.len());
+    for index in 0..contour.len() {
+      let next_index = if index == contour.len() - 1 { 0 } else { index + 1 };
+      let (start, end) = (contour[index], contour[next_index]);
+      new_contour.push(start);
+      new_original_edges.push(index);
+      if start == end {
+        continue;
+      }
+
+      let edge_vector = end - start;
+      let mut inserted_points = Vec::new();
+      for point in points.contours.iter_mut().flatten() {
+        if *point == start || *point == end {
+          continue;
+        }
+        let t =
+          (*point - start).dot(edge_vector) / edge_vector.length_squared();
+        if t <= 0.0 || 1.0 <= t {
+          continue;
+        }
+        let projected_point = start + edge_vector * t;
+        if projected_point.distance(*point) > tolerance {
+          continue;
+        }
+        if move_points {
+          *point = projected_point;
+        }
+        inserted_points.push((t, *point));
+      }
+      inserted_points.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+      inserted_points.dedup_by(|(_, a), (_, b)| a == b);
+      for (_, point) in inserted_points {
+        new_contour.push(point);
+        new_original_edges.push(index);
+      }
+    }
+    *contour = new_contour;
+    original_edges.push(new_original_edges);
+  }
+  original_edges
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::{union, union_with_options, BooleanOptions, Polygon};
+
+  #[test]
+  fn nearly_shared_boundaries_coincide() {
+    let subject = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(2.0, 2.0),
+        Vec2::new(0.0, 2.0),
+      ]],
+    };
+    let clip = Polygon {
+      contours: vec![vec![
+        Vec2::new(2.0005, 0.0),
+        Vec2::new(4.0, 0.0),
+        Vec2::new(4.0, 2.0),
+        Vec2::new(2.0005, 2.0),
+      ]],
+    };
+
+    // Without a tolerance, there is a sliver between the polygons.
+    assert_eq!(union(&subject, &clip).polygon.contours.len(), 2);
+
+    let options =
+      BooleanOptions { boundary_tolerance: 0.01, ..Default::default() };
+    let result = union_with_options(&subject, &clip, &options);
+    assert_eq!(
+      result.polygon,
+      Polygon {
+        contours: vec![vec![
+          Vec2::new(0.0, 0.0),
+          Vec2::new(2.0, 0.0),
+          Vec2::new(4.0, 0.0),
+          Vec2::new(4.0, 2.0),
+          Vec2::new(2.0, 2.0),
+          Vec2::new(0.0, 2.0),
+        ]]
+      }
+    );
+  }
+
+  #[test]
+  fn source_edges_refer_to_original_edges() {
+    let subject = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(2.0, 2.0),
+        Vec2::new(0.0, 2.0),
+      ]],
+    };
+    // The bottom edge is slightly above the subject's top edge, and the bottom
+    // left vertex is near the middle of the subject's top edge.
+    let clip = Polygon {
+      contours: vec![vec![
+        Vec2::new(1.0, 2.0005),
+        Vec2::new(3.0, 2.0005),
+        Vec2::new(3.0, 4.0),
+        Vec2::new(1.0, 4.0),
+      ]],
+    };
+
+    let options =
+      BooleanOptions { boundary_tolerance: 0.01, ..Default::default() };
+    let result = union_with_options(&subject, &clip, &options);
+    assert_eq!(result.polygon.contours.len(), 1);
+    for source_edge in result.contour_source_edges.iter().flatten() {
+      assert!(source_edge.edge < 4, "{source_edge:?}");
+    }
+  }
+}
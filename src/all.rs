@@ -0,0 +1,881 @@
+use std::collections::BinaryHeap;
+
+use glam::Vec2;
+
+use crate::{
+  classify_relation, compute_x_limit, create_events_for_polygon,
+  finish_boolean, join_contours,
+  overlap::{collect_contact_points, collect_overlap_segments},
+  select_result_events, sweep_events, try_boolean_with_options,
+  try_custom_boolean_with_options, validate_input, Arithmetic, BooleanOptions,
+  BooleanResult, BooleanRule, ClipError, EdgeContext, Event, EventRelation,
+  FillRule, InOut, Operation, Polygon, SweepError,
+};
+
+// The results of every built-in operation between the same subject and clip.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct AllResults {
+  // The region in both the subject and the clip.
+  pub intersection: BooleanResult,
+  // The region in either the subject or the clip.
+  pub union: BooleanResult,
+  // The region in exactly one of the subject and the clip.
+  pub xor: BooleanResult,
+  // The region in the subject but not the clip.
+  pub difference: BooleanResult,
+}
+
+impl AllResults {
+  // The result of `operation`.
+  pub fn get(&self, operation: Operation) -> &BooleanResult {
+    match operation {
+      Operation::Intersection => &self.intersection,
+      Operation::Union => &self.union,
+      Operation::XOR => &self.xor,
+      Operation::Difference => &self.difference,
+    }
+  }
+}
+
+// Computes every built-in operation between `subject` and `clip`. The edges are
+// only subdivided once, since which edges are in the result is the only part of
+// the sweep that depends on the operation, so this is faster than computing
+// the operations one at a time. Each result is the same as computing its
+// operation on its own, although for degenerate inputs (e.g. contours crossing
+// many times at the same points), contours may be traced through those points
+// differently. This panics if the polygons cannot be used in a boolean
+// operation (see `try_boolean_all`).
+pub fn boolean_all(subject: &Polygon, clip: &Polygon) -> AllResults {
+  unwrap_boolean_all(try_boolean_all_with_options(
+    subject,
+    clip,
+    &BooleanOptions::default(),
+  ))
+}
+
+// Same as `boolean_all`, but with `options`. Options that change the swept
+// polygons (the fill rules, `normalize_coordinates`, `auto_precision`,
+// `boundary_tolerance` and `weld_tolerance`) cannot share the subdivision, so
+// these compute each operation from scratch.
+pub fn boolean_all_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> AllResults {
+  unwrap_boolean_all(try_boolean_all_with_options(subject, clip, options))
+}
+
+// Same as `boolean_all`, but returns an error instead of panicking.
+pub fn try_boolean_all(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<AllResults, ClipError> {
+  try_boolean_all_with_options(subject, clip, &BooleanOptions::default())
+}
+
+// Same as `boolean_all_with_options`, but returns an error instead of
+// panicking.
+pub fn try_boolean_all_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> Result<AllResults, ClipError> {
+  let sweep = SharedSweep::new(subject, clip, options)?;
+  let compute = |operation| {
+    compute_overlay(
+      sweep.as_ref(),
+      subject,
+      clip,
+      Overlay::Operation(operation),
+      options,
+    )
+  };
+  Ok(AllResults {
+    intersection: compute(Operation::Intersection)?,
+    union: compute(Operation::Union)?,
+    xor: compute(Operation::XOR)?,
+    difference: compute(Operation::Difference)?,
+  })
+}
+
+// The three mutually exclusive regions of a subject and a clip, for
+// `overlay_pair`.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct OverlayPair {
+  // The region in the subject but not the clip.
+  pub subject_only: BooleanResult,
+  // The region in the clip but not the subject.
+  pub clip_only: BooleanResult,
+  // The region in both the subject and the clip.
+  pub both: BooleanResult,
+}
+
+// Computes the regions only in `subject`, only in `clip`, and in both (e.g. to
+// diff two revisions of a shape). Like `boolean_all`, the edges are only
+// subdivided once. Source edges refer to the original polygons in all three
+// results, so the edges of `clip_only` along the subject come from the
+// subject. This panics if the polygons cannot be used in a boolean operation
+// (see `try_overlay_pair`).
+pub fn overlay_pair(subject: &Polygon, clip: &Polygon) -> OverlayPair {
+  unwrap_overlay_pair(try_overlay_pair_with_options(
+    subject,
+    clip,
+    &BooleanOptions::default(),
+  ))
+}
+
+// Same as `overlay_pair`, but with `options` (see `boolean_all_with_options`).
+pub fn overlay_pair_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> OverlayPair {
+  unwrap_overlay_pair(try_overlay_pair_with_options(subject, clip, options))
+}
+
+// Same as `overlay_pair`, but returns an error instead of panicking.
+pub fn try_overlay_pair(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<OverlayPair, ClipError> {
+  try_overlay_pair_with_options(subject, clip, &BooleanOptions::default())
+}
+
+// Same as `overlay_pair_with_options`, but returns an error instead of
+// panicking.
+pub fn try_overlay_pair_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> Result<OverlayPair, ClipError> {
+  let sweep = SharedSweep::new(subject, clip, options)?;
+  let compute =
+    |overlay| compute_overlay(sweep.as_ref(), subject, clip, overlay, options);
+  Ok(OverlayPair {
+    subject_only: compute(Overlay::Operation(Operation::Difference))?,
+    clip_only: compute(Overlay::ClipOnly)?,
+    both: compute(Overlay::Operation(Operation::Intersection))?,
+  })
+}
+
+// Which of the polygons a face of an overlay is in (see `overlay`).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum FaceLabel {
+  SubjectOnly,
+  ClipOnly,
+  Both,
+}
+
+impl FaceLabel {
+  // Whether the face is in the subject.
+  pub fn in_subject(self) -> bool {
+    self != FaceLabel::ClipOnly
+  }
+
+  // Whether the face is in the clip.
+  pub fn in_clip(self) -> bool {
+    self != FaceLabel::SubjectOnly
+  }
+}
+
+// A connected region of the overlay of two polygons, with its holes.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Face {
+  pub label: FaceLabel,
+  pub polygon: Polygon,
+}
+
+// Computes every face of the arrangement of `subject` and `clip` (the
+// connected regions covered by either), labeled by which polygons cover them.
+// The faces come from `overlay_pair`, so the edges are only subdivided once and
+// neighbouring faces share their points exactly. Any boolean operation is the
+// union of the faces with matching labels (e.g. XOR is every face not labeled
+// `Both`). The faces are ordered by label (`SubjectOnly`, `ClipOnly`, then
+// `Both`). This panics if the polygons cannot be used in a boolean operation
+// (see `try_overlay`).
+pub fn overlay(subject: &Polygon, clip: &Polygon) -> Vec<Face> {
+  try_overlay(subject, clip).unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `overlay`, but with `options` (see `boolean_all_with_options`).
+pub fn overlay_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> Vec<Face> {
+  try_overlay_with_options(subject, clip, options)
+    .unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `overlay`, but returns an error instead of panicking.
+pub fn try_overlay(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<Vec<Face>, ClipError> {
+  try_overlay_with_options(subject, clip, &BooleanOptions::default())
+}
+
+// Same as `overlay_with_options`, but returns an error instead of panicking.
+pub fn try_overlay_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> Result<Vec<Face>, ClipError> {
+  let pair = try_overlay_pair_with_options(subject, clip, options)?;
+  Ok(
+    [
+      (FaceLabel::SubjectOnly, pair.subject_only),
+      (FaceLabel::ClipOnly, pair.clip_only),
+      (FaceLabel::Both, pair.both),
+    ]
+    .into_iter()
+    .flat_map(|(label, result)| {
+      result
+        .to_multi_polygon()
+        .polygons
+        .into_iter()
+        .map(move |polygon| Face { label, polygon })
+    })
+    .collect(),
+  )
+}
+
+// Computes the intersection of `subject` and `clip` along with the rest of the
+// subject (their difference), returning the kept and removed parts (e.g. for a
+// cookie cutter). Like `boolean_all`, the edges are only subdivided once, so
+// both parts share the points where the clip cuts the subject exactly. This
+// panics if the polygons cannot be used in a boolean operation (see
+// `try_intersection_with_remainder`).
+pub fn intersection_with_remainder(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> (BooleanResult, BooleanResult) {
+  try_intersection_with_remainder(subject, clip)
+    .unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `intersection_with_remainder`, but with `options` (see
+// `boolean_all_with_options`).
+pub fn intersection_with_remainder_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> (BooleanResult, BooleanResult) {
+  try_intersection_with_remainder_with_options(subject, clip, options)
+    .unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `intersection_with_remainder`, but returns an error instead of
+// panicking.
+pub fn try_intersection_with_remainder(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<(BooleanResult, BooleanResult), ClipError> {
+  try_intersection_with_remainder_with_options(
+    subject,
+    clip,
+    &BooleanOptions::default(),
+  )
+}
+
+// Same as `intersection_with_remainder_with_options`, but returns an error
+// instead of panicking.
+pub fn try_intersection_with_remainder_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> Result<(BooleanResult, BooleanResult), ClipError> {
+  try_with_remainder(
+    subject,
+    clip,
+    Operation::Intersection,
+    Operation::Difference,
+    options,
+  )
+}
+
+// Computes the difference of `subject` and `clip` along with the part of the
+// subject it removes (their intersection), returning the kept and removed
+// parts (e.g. for an eraser). The parts share their cut points as in
+// `intersection_with_remainder`. This panics if the polygons cannot be used in
+// a boolean operation (see `try_difference_with_remainder`).
+pub fn difference_with_remainder(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> (BooleanResult, BooleanResult) {
+  try_difference_with_remainder(subject, clip)
+    .unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `difference_with_remainder`, but with `options` (see
+// `boolean_all_with_options`).
+pub fn difference_with_remainder_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> (BooleanResult, BooleanResult) {
+  try_difference_with_remainder_with_options(subject, clip, options)
+    .unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `difference_with_remainder`, but returns an error instead of
+// panicking.
+pub fn try_difference_with_remainder(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<(BooleanResult, BooleanResult), ClipError> {
+  try_difference_with_remainder_with_options(
+    subject,
+    clip,
+    &BooleanOptions::default(),
+  )
+}
+
+// Same as `difference_with_remainder_with_options`, but returns an error
+// instead of panicking.
+pub fn try_difference_with_remainder_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> Result<(BooleanResult, BooleanResult), ClipError> {
+  try_with_remainder(
+    subject,
+    clip,
+    Operation::Difference,
+    Operation::Intersection,
+    options,
+  )
+}
+
+// Computes the `kept` and `removed` operations of `subject` and `clip` from a
+// single shared sweep.
+fn try_with_remainder(
+  subject: &Polygon,
+  clip: &Polygon,
+  kept: Operation,
+  removed: Operation,
+  options: &BooleanOptions,
+) -> Result<(BooleanResult, BooleanResult), ClipError> {
+  let sweep = SharedSweep::new(subject, clip, options)?;
+  let compute = |operation| {
+    compute_overlay(
+      sweep.as_ref(),
+      subject,
+      clip,
+      Overlay::Operation(operation),
+      options,
+    )
+  };
+  Ok((compute(kept)?, compute(removed)?))
+}
+
+// An overlay that can be computed from a `SharedSweep`.
+#[derive(Clone, Copy)]
+enum Overlay {
+  Operation(Operation),
+  // The region in the clip but not the subject.
+  ClipOnly,
+}
+
+impl Overlay {
+  // The rule selecting the edges of the overlay.
+  fn rule(&self) -> &dyn BooleanRule {
+    match self {
+      Overlay::Operation(operation) => operation,
+      Overlay::ClipOnly => &ClipOnlyRule,
+    }
+  }
+
+  // The x coordinate past which no event can affect the overlay (see
+  // `compute_x_limit`).
+  fn x_limit(
+    self,
+    subject_bounds: (Vec2, Vec2),
+    clip_bounds: (Vec2, Vec2),
+  ) -> f32 {
+    match self {
+      Overlay::Operation(operation) => {
+        compute_x_limit(subject_bounds, clip_bounds, operation)
+      }
+      // Custom rules always sweep both polygons entirely.
+      Overlay::ClipOnly => f32::INFINITY,
+    }
+  }
+
+  // Computes the overlay on its own.
+  fn compute_separately(
+    self,
+    subject: &Polygon,
+    clip: &Polygon,
+    options: &BooleanOptions,
+  ) -> Result<BooleanResult, ClipError> {
+    match self {
+      Overlay::Operation(operation) => {
+        try_boolean_with_options(subject, clip, operation, options)
+      }
+      Overlay::ClipOnly => {
+        try_custom_boolean_with_options(subject, clip, &ClipOnlyRule, options)
+      }
+    }
+  }
+}
+
+// Selects the region in the clip but not the subject.
+struct ClipOnlyRule;
+
+impl BooleanRule for ClipOnlyRule {
+  fn in_result(&self, edge: &EdgeContext) -> bool {
+    (edge.in_clip_below && !edge.in_subject_below)
+      != (edge.in_clip_above && !edge.in_subject_above)
+  }
+
+  fn result_transition(&self, edge: &EdgeContext) -> InOut {
+    if edge.in_clip_above && !edge.in_subject_above {
+      InOut::OutsideToInside
+    } else {
+      InOut::InsideToOutside
+    }
+  }
+}
+
+// A sweep over both polygons which every overlay can select its edges from,
+// since the subdivision of the edges does not depend on the overlay.
+struct SharedSweep {
+  processed_events: Vec<Event>,
+  event_relations: Vec<EventRelation>,
+  subject_bounds: (Vec2, Vec2),
+  clip_bounds: (Vec2, Vec2),
+  max_events: usize,
+}
+
+impl SharedSweep {
+  // Sweeps over `subject` and `clip`. Returns None if the overlays must be
+  // computed separately instead: options that change the swept polygons (the
+  // fill rules, `normalize_coordinates`, `auto_precision`,
+  // `boundary_tolerance` and `weld_tolerance`) cannot share the subdivision,
+  // polygons that cannot overlap are computed trivially, and inconsistent
+  // sweeps are recomputed in f64.
+  fn new(
+    subject: &Polygon,
+    clip: &Polygon,
+    options: &BooleanOptions,
+  ) -> Result<Option<Self>, ClipError> {
+    if options.fill_rules() != (FillRule::EvenOdd, FillRule::EvenOdd)
+      || options.normalize_coordinates
+      || options.auto_precision
+      || options.boundary_tolerance > 0.0
+      || options.weld_tolerance > 0.0
+    {
+      return Ok(None);
+    }
+    validate_input(subject, clip)?;
+    let max_events = options.max_events.unwrap_or(usize::MAX);
+    let edge_count = subject.num_edges().saturating_add(clip.num_edges());
+    if max_events < edge_count.saturating_mul(2) {
+      return Err(ClipError::CapacityExceeded { limit: max_events });
+    }
+
+    let (Some(subject_bounds), Some(clip_bounds)) =
+      (subject.compute_bounds(), clip.compute_bounds())
+    else {
+      return Ok(None);
+    };
+    if subject_bounds.1.x < clip_bounds.0.x
+      || subject_bounds.1.y < clip_bounds.0.y
+      || clip_bounds.1.x < subject_bounds.0.x
+      || clip_bounds.1.y < subject_bounds.0.y
+    {
+      return Ok(None);
+    }
+
+    let mut event_queue = BinaryHeap::new();
+    let mut event_relations = Vec::new();
+    for (polygon, is_subject) in [(subject, true), (clip, false)] {
+      create_events_for_polygon(
+        polygon,
+        is_subject,
+        Arithmetic::F32,
+        &mut event_queue,
+        &mut event_relations,
+        f32::INFINITY,
+      );
+    }
+    // XOR needs every event, so the sweep does not stop before the end.
+    match sweep_events(
+      event_queue,
+      &mut event_relations,
+      &Operation::XOR,
+      f32::INFINITY,
+      max_events,
+    ) {
+      Ok(processed_events) => Ok(Some(Self {
+        processed_events,
+        event_relations,
+        subject_bounds,
+        clip_bounds,
+        max_events,
+      })),
+      Err(SweepError::Inconsistent { .. }) => Ok(None),
+      Err(error) => Err(error.into_clip_error(max_events)),
+    }
+  }
+}
+
+// Computes `overlay` of `subject` and `clip` from `sweep`, or on its own if
+// there is no shared sweep.
+fn compute_overlay(
+  sweep: Option<&SharedSweep>,
+  subject: &Polygon,
+  clip: &Polygon,
+  overlay: Overlay,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  let Some(sweep) = sweep else {
+    return overlay.compute_separately(subject, clip, options);
+  };
+  let processed_events = &sweep.processed_events;
+  // The events the overlay's own sweep would process, so the data collected
+  // from them is the same.
+  let x_limit = overlay.x_limit(sweep.subject_bounds, sweep.clip_bounds);
+  let overlay_events = &processed_events
+    [..processed_events.partition_point(|event| event.point.x <= x_limit)];
+  let relation = options.classify_relation.then(|| {
+    classify_relation(
+      overlay_events,
+      &sweep.event_relations,
+      sweep.subject_bounds,
+      sweep.clip_bounds,
+      x_limit,
+    )
+  });
+  let overlap_segments =
+    if options.track_overlap_segments || options.collect_warnings {
+      collect_overlap_segments(overlay_events, &sweep.event_relations)
+    } else {
+      Vec::new()
+    };
+  let contact_points = if options.track_contact_points {
+    collect_contact_points(overlay_events, &sweep.event_relations)
+  } else {
+    Vec::new()
+  };
+
+  let event_relations = select_overlay_edges(
+    processed_events,
+    &sweep.event_relations,
+    overlay.rule(),
+  );
+  let result_events =
+    select_result_events(processed_events.clone(), &event_relations);
+  let mut result =
+    match join_contours(result_events, event_relations, overlay.rule()) {
+      Ok(result) => result,
+      Err(SweepError::Inconsistent { .. }) => {
+        return overlay.compute_separately(subject, clip, options)
+      }
+      Err(error) => return Err(error.into_clip_error(sweep.max_events)),
+    };
+  result.relation = relation;
+  result.overlap_segments = overlap_segments;
+  result.contact_points = contact_points;
+  Ok(finish_boolean(result, subject, clip, options))
+}
+
+// Unwraps the result of `try_boolean_all_with_options`, panicking with the
+// error message if it failed.
+fn unwrap_boolean_all(result: Result<AllResults, ClipError>) -> AllResults {
+  result.unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Unwraps the result of `try_overlay_pair_with_options`, panicking with the
+// error message if it failed.
+fn unwrap_overlay_pair(result: Result<OverlayPair, ClipError>) -> OverlayPair {
+  result.unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Recomputes which edges of a finished sweep (`processed_events` and their
+// `event_relations`) are in the result of `rule`. The transitions of the edges
+// do not depend on the rule, so only `in_result` and `prev_in_result` (which
+// follows the events below each event) change.
+fn select_overlay_edges(
+  processed_events: &[Event],
+  event_relations: &[EventRelation],
+  rule: &dyn BooleanRule,
+) -> Vec<EventRelation> {
+  let mut event_relations = event_relations.to_vec();
+  let mut left_events = vec![None; event_relations.len()];
+  for event in processed_events.iter().filter(|event| event.left) {
+    let relation = &mut event_relations[event.event_id];
+    relation.in_result = event.in_result(relation, rule);
+    left_events[event.event_id] = Some(event);
+  }
+  // The right events are in the result if their left events are.
+  for event in processed_events.iter().filter(|event| !event.left) {
+    let sibling_id = event_relations[event.event_id].sibling_id;
+    event_relations[event.event_id].in_result =
+      event_relations[sibling_id].in_result;
+  }
+
+  // The previous event in the result is found by following the events below
+  // each event until one is in the result. Vertical edges are skipped, since
+  // the events above them are only skimming the edge of the polygon (see
+  // `set_information`). Events can be below events processed before them (when
+  // their information was recomputed), so these are resolved recursively.
+  let mut resolved = vec![false; event_relations.len()];
+  for event in processed_events.iter().filter(|event| event.left) {
+    let mut pending = vec![event.event_id];
+    while let Some(&event_id) = pending.last() {
+      let prev_in_result = match event_relations[event_id].prev_event_id {
+        None => None,
+        Some(prev_event_id) => {
+          let prev_relation = &event_relations[prev_event_id];
+          let prev_is_vertical = left_events[prev_event_id]
+            .is_some_and(|prev_event| prev_event.is_vertical());
+          if prev_relation.in_result && !prev_is_vertical {
+            Some(prev_event_id)
+          } else if resolved[prev_event_id] {
+            prev_relation.prev_in_result
+          } else if pending.contains(&prev_event_id) {
+            // A cycle can only come from rounding errors, so just stop there.
+            None
+          } else {
+            pending.push(prev_event_id);
+            continue;
+          }
+        }
+      };
+      event_relations[event_id].prev_in_result = prev_in_result;
+      resolved[event_id] = true;
+      pending.pop();
+    }
+  }
+  event_relations
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+  use rand::{rngs::StdRng, Rng, SeedableRng};
+
+  use crate::tests::fixtures::square;
+  use crate::{
+    boolean_all, boolean_all_with_options, boolean_with_options,
+    custom_boolean_with_options, difference_with_remainder,
+    difference_with_remainder_with_options, intersection_with_remainder,
+    intersection_with_remainder_with_options, overlay, overlay_pair,
+    overlay_pair_with_options, try_boolean_all_with_options,
+    try_boolean_with_options, util::contour_signed_area, AllResults,
+    BooleanOptions, FaceLabel, FillRule, Operation, OutputOrientation, Polygon,
+    TrackingLevel,
+  };
+
+  use super::ClipOnlyRule;
+
+  // The area of `polygon`, whose holes are oriented opposite to its shells.
+  fn area(polygon: &Polygon) -> f32 {
+    polygon.contours.iter().map(|contour| contour_signed_area(contour)).sum()
+  }
+
+  fn assert_matches_operations(
+    results: &AllResults,
+    subject: &Polygon,
+    clip: &Polygon,
+    options: &BooleanOptions,
+  ) {
+    for operation in Operation::ALL {
+      assert_eq!(
+        *results.get(operation),
+        boolean_with_options(subject, clip, operation, options),
+        "{operation:?} of {subject:?} and {clip:?}"
+      );
+    }
+  }
+
+  #[test]
+  fn matches_each_operation() {
+    let subject = Polygon {
+      contours: vec![
+        square(Vec2::ZERO, 4.0),
+        // A hole, which the clip partly covers.
+        square(Vec2::new(1.0, 1.0), 2.0),
+      ],
+    };
+    let clip = Polygon {
+      contours: vec![
+        square(Vec2::new(2.0, 2.0), 4.0),
+        // Touches the subject along its bottom edge.
+        square(Vec2::new(1.0, -1.0), 1.0),
+      ],
+    };
+    let options = BooleanOptions::new().with_tracking(TrackingLevel::Full);
+    let results = boolean_all_with_options(&subject, &clip, &options);
+    assert_matches_operations(&results, &subject, &clip, &options);
+  }
+
+  #[test]
+  fn matches_each_operation_for_random_polygons() {
+    let mut rng = StdRng::seed_from_u64(3305);
+    // Tracking everything and keeping the swept orientation compares the
+    // recomputed hierarchy as well.
+    let options = BooleanOptions::new()
+      .with_tracking(TrackingLevel::Full)
+      .with_output_orientation(OutputOrientation::AsSwept);
+    for _ in 0..200 {
+      let mut random_contour = || {
+        (0..rng.gen_range(3..7))
+          .map(|_| Vec2::new(rng.gen_range(0.0..6.0), rng.gen_range(0.0..6.0)))
+          .collect::<Vec<_>>()
+      };
+      let subject =
+        Polygon { contours: vec![random_contour(), random_contour()] };
+      let clip = Polygon { contours: vec![random_contour()] };
+      // Random self-intersecting contours can defeat the sweep on their own;
+      // only compare inputs each operation handles.
+      if Operation::ALL.into_iter().any(|operation| {
+        try_boolean_with_options(&subject, &clip, operation, &options).is_err()
+      }) {
+        continue;
+      }
+      let results = try_boolean_all_with_options(&subject, &clip, &options)
+        .expect("the operations succeed on their own");
+      assert_matches_operations(&results, &subject, &clip, &options);
+    }
+  }
+
+  #[test]
+  fn computes_separately_for_fill_rules_and_disjoint_polygons() {
+    let subject = Polygon {
+      contours: vec![square(Vec2::ZERO, 2.0), square(Vec2::ONE, 2.0)],
+    };
+    let clip = Polygon { contours: vec![square(Vec2::new(2.0, 0.0), 2.0)] };
+    let options = BooleanOptions::new().with_fill_rule(FillRule::NonZero);
+    assert_matches_operations(
+      &boolean_all_with_options(&subject, &clip, &options),
+      &subject,
+      &clip,
+      &options,
+    );
+
+    let far_clip = Polygon { contours: vec![square(Vec2::splat(10.0), 1.0)] };
+    let results = boolean_all(&subject, &far_clip);
+    assert_matches_operations(
+      &results,
+      &subject,
+      &far_clip,
+      &BooleanOptions::default(),
+    );
+    assert!(results.intersection.is_empty);
+    assert!(
+      boolean_all(&Polygon::default(), &Polygon::default()).union.is_empty
+    );
+  }
+
+  #[test]
+  fn overlay_pair_matches_each_region() {
+    let subject = Polygon {
+      contours: vec![square(Vec2::ZERO, 4.0), square(Vec2::new(1.0, 1.0), 2.0)],
+    };
+    let clip = Polygon {
+      contours: vec![
+        square(Vec2::new(2.0, 2.0), 4.0),
+        // Disjoint from the subject, so it is entirely in `clip_only`.
+        square(Vec2::new(8.0, 0.0), 1.0),
+      ],
+    };
+    let options = BooleanOptions::new().with_tracking(TrackingLevel::Full);
+    let pair = overlay_pair_with_options(&subject, &clip, &options);
+    assert_eq!(
+      pair.subject_only,
+      boolean_with_options(&subject, &clip, Operation::Difference, &options)
+    );
+    assert_eq!(
+      pair.both,
+      boolean_with_options(&subject, &clip, Operation::Intersection, &options)
+    );
+    assert_eq!(
+      pair.clip_only,
+      custom_boolean_with_options(&subject, &clip, &ClipOnlyRule, &options)
+    );
+    // The large clip square loses the part the subject's shell covers outside
+    // its hole, leaving an L shape and the part inside the hole. Their edges
+    // along the subject come from the subject.
+    assert_eq!(pair.clip_only.polygon.contours.len(), 3);
+    assert!(pair
+      .clip_only
+      .contour_source_edges
+      .iter()
+      .flatten()
+      .any(|source_edge| source_edge.is_from_subject));
+
+    // Disjoint polygons are computed separately.
+    let far_clip = Polygon { contours: vec![square(Vec2::splat(10.0), 1.0)] };
+    let pair = overlay_pair(&subject, &far_clip);
+    assert!(pair.both.is_empty);
+    assert_eq!(pair.clip_only.polygon, far_clip);
+  }
+
+  #[test]
+  fn with_remainder_returns_kept_and_removed_parts() {
+    let subject = Polygon {
+      contours: vec![square(Vec2::ZERO, 4.0), square(Vec2::new(1.0, 1.0), 2.0)],
+    };
+    let clip = Polygon { contours: vec![square(Vec2::new(2.0, 2.0), 4.0)] };
+    let options = BooleanOptions::new().with_tracking(TrackingLevel::Full);
+    let intersection =
+      boolean_with_options(&subject, &clip, Operation::Intersection, &options);
+    let difference =
+      boolean_with_options(&subject, &clip, Operation::Difference, &options);
+    assert_eq!(
+      intersection_with_remainder_with_options(&subject, &clip, &options),
+      (intersection.clone(), difference.clone())
+    );
+    assert_eq!(
+      difference_with_remainder_with_options(&subject, &clip, &options),
+      (difference, intersection)
+    );
+
+    // A clip covering the whole subject removes all of it.
+    let cover = Polygon { contours: vec![square(Vec2::splat(-1.0), 6.0)] };
+    let (kept, removed) = difference_with_remainder(&subject, &cover);
+    assert!(kept.is_empty);
+    assert_eq!(removed.polygon.contours.len(), 2);
+    let (kept, removed) = intersection_with_remainder(&subject, &cover);
+    assert_eq!(kept.polygon.contours.len(), 2);
+    assert!(removed.is_empty);
+  }
+
+  #[test]
+  fn overlay_labels_each_face() {
+    // Two overlapping squares, and a clip square inside the subject's hole.
+    let subject = Polygon {
+      contours: vec![square(Vec2::ZERO, 4.0), square(Vec2::new(1.0, 1.0), 2.0)],
+    };
+    let clip = Polygon {
+      contours: vec![
+        square(Vec2::new(3.5, 3.5), 2.0),
+        square(Vec2::new(1.5, 1.5), 1.0),
+      ],
+    };
+    let faces = overlay(&subject, &clip);
+    let labels = faces.iter().map(|face| face.label).collect::<Vec<_>>();
+    assert_eq!(
+      labels,
+      [
+        FaceLabel::SubjectOnly,
+        FaceLabel::ClipOnly,
+        FaceLabel::ClipOnly,
+        FaceLabel::Both
+      ]
+    );
+    // The subject's face keeps its hole.
+    assert_eq!(faces[0].polygon.contours.len(), 2);
+    // The faces in the clip make up the clip.
+    let clip_area = faces
+      .iter()
+      .filter(|face| face.label.in_clip())
+      .map(|face| area(&face.polygon))
+      .sum::<f32>();
+    assert_eq!(clip_area, 4.0 + 1.0);
+    assert!(!FaceLabel::ClipOnly.in_subject());
+  }
+}
@@ -0,0 +1,174 @@
+use std::collections::BinaryHeap;
+
+use glam::Vec2;
+
+use crate::{
+  compute_x_limit, create_events_for_polygon, sweep_events,
+  sweep_with_f64_retry, validate_input, Arithmetic, ClipError, Operation,
+  Polygon, SweepError,
+};
+
+// Computes the area of the intersection of `subject` and `clip` without
+// building the result polygon. The sweep is the same as for `intersection`,
+// but instead of joining the result edges into contours, each edge adds its
+// share of the area (as in the shoelace formula). This is much cheaper when
+// only the area is needed (e.g. scoring the overlap of many pairs). This
+// panics if the polygons cannot be used in a boolean operation (see
+// `try_intersection_area`).
+pub fn intersection_area(subject: &Polygon, clip: &Polygon) -> f32 {
+  try_intersection_area(subject, clip).unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `intersection_area`, but returns an error instead of panicking.
+pub fn try_intersection_area(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<f32, ClipError> {
+  validate_input(subject, clip)?;
+  let (Some(subject_bounds), Some(clip_bounds)) =
+    (subject.compute_bounds(), clip.compute_bounds())
+  else {
+    return Ok(0.0);
+  };
+  if subject_bounds.1.x < clip_bounds.0.x
+    || subject_bounds.1.y < clip_bounds.0.y
+    || clip_bounds.1.x < subject_bounds.0.x
+    || clip_bounds.1.y < subject_bounds.0.y
+  {
+    return Ok(0.0);
+  }
+
+  let (edges, _) = sweep_with_f64_retry(|arithmetic| {
+    sweep_result_edges(subject, clip, subject_bounds, clip_bounds, arithmetic)
+  })
+  .map_err(|error| error.into_clip_error(usize::MAX))?;
+  Ok(area_of_edges(&edges))
+}
+
+// Sweeps over `subject` and `clip` (with the given bounds) with `arithmetic`,
+// returning the edges of their intersection oriented with the inside on their
+// left.
+fn sweep_result_edges(
+  subject: &Polygon,
+  clip: &Polygon,
+  subject_bounds: (Vec2, Vec2),
+  clip_bounds: (Vec2, Vec2),
+  arithmetic: Arithmetic,
+) -> Result<Vec<(Vec2, Vec2)>, SweepError> {
+  let operation = Operation::Intersection;
+  let x_limit = compute_x_limit(subject_bounds, clip_bounds, operation);
+  let mut event_queue = BinaryHeap::new();
+  let mut event_relations = Vec::new();
+  for (polygon, is_subject) in [(subject, true), (clip, false)] {
+    create_events_for_polygon(
+      polygon,
+      is_subject,
+      arithmetic,
+      &mut event_queue,
+      &mut event_relations,
+      x_limit,
+    );
+  }
+  let processed_events = sweep_events(
+    event_queue,
+    &mut event_relations,
+    &operation,
+    x_limit,
+    /* max_events= */ usize::MAX,
+  )?;
+
+  // Only the left events are needed, since each describes its whole edge (see
+  // `select_result_events` for why `in_result` is checked at the end).
+  Ok(
+    processed_events
+      .iter()
+      .filter(|event| event.left)
+      .filter_map(|event| {
+        let relation = &event_relations[event.event_id];
+        if !relation.in_result {
+          return None;
+        }
+        // The edge goes from left to right (or bottom to top), so it has the
+        // inside on its left if the inside is above (or to the left of) it.
+        Some(if event.result_in_out(relation, &operation) {
+          (relation.sibling_point, event.point)
+        } else {
+          (event.point, relation.sibling_point)
+        })
+      })
+      .collect(),
+  )
+}
+
+// The area enclosed by `edges` (which form closed loops with the inside on
+// their left).
+fn area_of_edges(edges: &[(Vec2, Vec2)]) -> f32 {
+  // Measuring from a point near the edges keeps the cross products small, so
+  // they lose less precision.
+  let Some(&(origin, _)) = edges.first() else {
+    return 0.0;
+  };
+  edges
+    .iter()
+    .map(|&(start, end)| (start - origin).perp_dot(end - origin))
+    .sum::<f32>()
+    * 0.5
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+  use rand::{rngs::StdRng, Rng, SeedableRng};
+
+  use crate::tests::fixtures::square;
+  use crate::{
+    intersection, intersection_area, try_intersection,
+    util::contour_signed_area, Polygon,
+  };
+
+  // The area of `polygon`, whose holes are oriented opposite to its shells.
+  fn area(polygon: &Polygon) -> f32 {
+    polygon.contours.iter().map(|contour| contour_signed_area(contour)).sum()
+  }
+
+  #[test]
+  fn computes_area_of_overlapping_squares() {
+    let subject = Polygon {
+      contours: vec![square(Vec2::ZERO, 4.0), square(Vec2::ONE, 2.0)],
+    };
+    let clip = Polygon { contours: vec![square(Vec2::splat(2.0), 4.0)] };
+    // The clip covers a quarter of the subject's square and hole.
+    assert_eq!(intersection_area(&subject, &clip), 4.0 - 1.0);
+    assert_eq!(intersection_area(&subject, &subject), 16.0 - 4.0);
+    // Touching polygons do not overlap.
+    let touching = Polygon { contours: vec![square(Vec2::new(4.0, 0.0), 1.0)] };
+    assert_eq!(intersection_area(&subject, &touching), 0.0);
+    assert_eq!(intersection_area(&subject, &Polygon::default()), 0.0);
+  }
+
+  #[test]
+  fn matches_area_of_intersection_for_random_polygons() {
+    let mut rng = StdRng::seed_from_u64(3320);
+    for _ in 0..200 {
+      let mut random_contour = || {
+        (0..rng.gen_range(3..7))
+          .map(|_| Vec2::new(rng.gen_range(0.0..6.0), rng.gen_range(0.0..6.0)))
+          .collect::<Vec<_>>()
+      };
+      let subject =
+        Polygon { contours: vec![random_contour(), random_contour()] };
+      let clip = Polygon { contours: vec![random_contour()] };
+      // Random self-intersecting contours can defeat the sweep on their own;
+      // only compare inputs the regular operation handles.
+      if try_intersection(&subject, &clip).is_err() {
+        continue;
+      }
+      let expected = area(&intersection(&subject, &clip).polygon);
+      let actual = intersection_area(&subject, &clip);
+      assert!(
+        (expected - actual).abs() <= 1e-4 * expected.abs().max(1.0),
+        "{expected} != {actual} for {subject:?} and {clip:?}"
+      );
+    }
+  }
+}
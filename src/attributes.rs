@@ -0,0 +1,316 @@
+use glam::{Vec2, Vec3, Vec4};
+
+use crate::{
+  try_boolean_with_options, BooleanOptions, BooleanResult, ClipError,
+  Operation, Polygon,
+};
+
+// Per-vertex data that can be interpolated along edges (e.g. texture
+// coordinates or colors), for `boolean_with_attributes`.
+pub trait Lerp: Clone {
+  // The value `t` of the way from `self` (at 0) to `other` (at 1).
+  fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+  fn lerp(&self, other: &Self, t: f32) -> Self {
+    self + (other - self) * t
+  }
+}
+
+impl Lerp for Vec2 {
+  fn lerp(&self, other: &Self, t: f32) -> Self {
+    Vec2::lerp(*self, *other, t)
+  }
+}
+
+impl Lerp for Vec3 {
+  fn lerp(&self, other: &Self, t: f32) -> Self {
+    Vec3::lerp(*self, *other, t)
+  }
+}
+
+impl Lerp for Vec4 {
+  fn lerp(&self, other: &Self, t: f32) -> Self {
+    Vec4::lerp(*self, *other, t)
+  }
+}
+
+impl<T: Lerp, const N: usize> Lerp for [T; N] {
+  fn lerp(&self, other: &Self, t: f32) -> Self {
+    std::array::from_fn(|index| self[index].lerp(&other[index], t))
+  }
+}
+
+impl BooleanResult {
+  // Interpolates per-vertex data of the inputs onto the vertices of `polygon`.
+  // `subject_attributes` and `clip_attributes` hold one value for each vertex
+  // of the subject and clip (with the same layout as their contours). Each
+  // vertex takes the value at its position along the source edge of the edge
+  // it starts, so vertices copied from the inputs keep their values and
+  // intersection vertices blend the end points of one of their source edges.
+  // The returned Vec has the same layout as `polygon.contours`. This panics if
+  // `contour_edge_parameters` was not computed (see
+  // `BooleanOptions::track_edge_parameters`) or if the attributes do not match
+  // the inputs.
+  pub fn interpolate_attributes<V: Lerp>(
+    &self,
+    subject_attributes: &[Vec<V>],
+    clip_attributes: &[Vec<V>],
+  ) -> Vec<Vec<V>> {
+    assert!(
+      self.polygon.contours.is_empty()
+        || !self.contour_edge_parameters.is_empty(),
+      "interpolating attributes requires tracking edge parameters"
+    );
+    self
+      .contour_source_edges
+      .iter()
+      .zip(self.contour_edge_parameters.iter())
+      .map(|(source_edges, edge_parameters)| {
+        source_edges
+          .iter()
+          .zip(edge_parameters.iter())
+          .map(|(source_edge, &[start, _])| {
+            let attributes = if source_edge.is_from_subject {
+              &subject_attributes[source_edge.contour]
+            } else {
+              &clip_attributes[source_edge.contour]
+            };
+            let next_vertex = (source_edge.edge + 1) % attributes.len();
+            attributes[source_edge.edge].lerp(&attributes[next_vertex], start)
+          })
+          .collect()
+      })
+      .collect()
+  }
+}
+
+// Computes `operation` of `subject` and `clip` along with the interpolated
+// per-vertex data of the result (see `BooleanResult::interpolate_attributes`).
+// `subject_attributes` and `clip_attributes` hold one value for each vertex of
+// the subject and clip. This panics if the operation cannot be computed or the
+// attributes do not match the inputs (see `try_boolean_with_attributes`).
+pub fn boolean_with_attributes<V: Lerp>(
+  subject: &Polygon,
+  subject_attributes: &[Vec<V>],
+  clip: &Polygon,
+  clip_attributes: &[Vec<V>],
+  operation: Operation,
+) -> (BooleanResult, Vec<Vec<V>>) {
+  try_boolean_with_attributes(
+    subject,
+    subject_attributes,
+    clip,
+    clip_attributes,
+    operation,
+  )
+  .unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `boolean_with_attributes`, but with `options`. Edge parameters are
+// always tracked.
+pub fn boolean_with_attributes_and_options<V: Lerp>(
+  subject: &Polygon,
+  subject_attributes: &[Vec<V>],
+  clip: &Polygon,
+  clip_attributes: &[Vec<V>],
+  operation: Operation,
+  options: &BooleanOptions,
+) -> (BooleanResult, Vec<Vec<V>>) {
+  try_boolean_with_attributes_and_options(
+    subject,
+    subject_attributes,
+    clip,
+    clip_attributes,
+    operation,
+    options,
+  )
+  .unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `boolean_with_attributes`, but returns an error instead of
+// panicking. Attributes that do not match the inputs are reported as invalid
+// input at the first vertex of a contour without exactly one value (or at the
+// first extra contour).
+pub fn try_boolean_with_attributes<V: Lerp>(
+  subject: &Polygon,
+  subject_attributes: &[Vec<V>],
+  clip: &Polygon,
+  clip_attributes: &[Vec<V>],
+  operation: Operation,
+) -> Result<(BooleanResult, Vec<Vec<V>>), ClipError> {
+  try_boolean_with_attributes_and_options(
+    subject,
+    subject_attributes,
+    clip,
+    clip_attributes,
+    operation,
+    &BooleanOptions::default(),
+  )
+}
+
+// Same as `boolean_with_attributes_and_options`, but returns an error instead
+// of panicking.
+pub fn try_boolean_with_attributes_and_options<V: Lerp>(
+  subject: &Polygon,
+  subject_attributes: &[Vec<V>],
+  clip: &Polygon,
+  clip_attributes: &[Vec<V>],
+  operation: Operation,
+  options: &BooleanOptions,
+) -> Result<(BooleanResult, Vec<Vec<V>>), ClipError> {
+  validate_attributes(
+    subject,
+    subject_attributes,
+    /* is_subject= */ true,
+  )?;
+  validate_attributes(clip, clip_attributes, /* is_subject= */ false)?;
+  let options =
+    BooleanOptions { track_edge_parameters: true, ..options.clone() };
+  let result = try_boolean_with_options(subject, clip, operation, &options)?;
+  let attributes =
+    result.interpolate_attributes(subject_attributes, clip_attributes);
+  Ok((result, attributes))
+}
+
+// Checks that `attributes` has one value for each vertex of `polygon`.
+fn validate_attributes<V>(
+  polygon: &Polygon,
+  attributes: &[Vec<V>],
+  is_subject: bool,
+) -> Result<(), ClipError> {
+  let invalid = |contour, vertex| ClipError::InvalidInput {
+    is_from_subject: is_subject,
+    contour,
+    vertex,
+  };
+  for (contour_index, contour) in polygon.contours.iter().enumerate() {
+    let Some(contour_attributes) = attributes.get(contour_index) else {
+      return Err(invalid(contour_index, 0));
+    };
+    if contour_attributes.len() != contour.len() {
+      return Err(invalid(
+        contour_index,
+        contour_attributes.len().min(contour.len()),
+      ));
+    }
+  }
+  if attributes.len() > polygon.contours.len() {
+    return Err(invalid(polygon.contours.len(), 0));
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::{Vec2, Vec3};
+
+  use crate::tests::fixtures::square_polygon;
+  use crate::{
+    boolean_with_attributes, try_boolean_with_attributes, ClipError, Lerp,
+    Operation,
+  };
+
+  #[test]
+  fn interpolates_attributes_at_intersections() {
+    let subject = square_polygon(Vec2::ZERO, 2.0);
+    let clip = square_polygon(Vec2::ONE, 2.0);
+    // Texture coordinates spanning each square.
+    let uvs = vec![vec![
+      Vec2::new(0.0, 0.0),
+      Vec2::new(1.0, 0.0),
+      Vec2::new(1.0, 1.0),
+      Vec2::new(0.0, 1.0),
+    ]];
+
+    let (result, attributes) = boolean_with_attributes(
+      &subject,
+      &uvs,
+      &clip,
+      &uvs,
+      Operation::Intersection,
+    );
+    // Each vertex of the result is halfway across the squares it is in, so it
+    // maps to its own position relative to the square it came from.
+    assert_eq!(result.polygon.contours.len(), 1);
+    for ((point, source_edge), uv) in result.polygon.contours[0]
+      .iter()
+      .zip(result.contour_source_edges[0].iter())
+      .zip(attributes[0].iter())
+    {
+      let min =
+        if source_edge.is_from_subject { Vec2::ZERO } else { Vec2::ONE };
+      assert_eq!(*uv, (*point - min) / 2.0);
+    }
+
+    // A grey level for each corner of both squares.
+    let greys = vec![vec![
+      Vec3::splat(0.0),
+      Vec3::splat(0.5),
+      Vec3::splat(1.0),
+      Vec3::splat(0.5),
+    ]];
+    let (result, attributes) = boolean_with_attributes(
+      &subject,
+      &greys,
+      &clip,
+      &greys,
+      Operation::Union,
+    );
+    assert_eq!(attributes[0].len(), result.polygon.contours[0].len());
+    // The union's vertex at (2, 1) is halfway along the subject's right edge
+    // and the clip's bottom edge.
+    let index = result.polygon.contours[0]
+      .iter()
+      .position(|&point| point == Vec2::new(2.0, 1.0))
+      .unwrap();
+    let expected_grey = if result.contour_source_edges[0][index].is_from_subject
+    {
+      0.75
+    } else {
+      0.25
+    };
+    assert_eq!(attributes[0][index], Vec3::splat(expected_grey));
+  }
+
+  #[test]
+  fn lerps_arrays() {
+    assert_eq!([0.0, 2.0].lerp(&[1.0, 4.0], 0.25), [0.25, 2.5]);
+  }
+
+  #[test]
+  fn rejects_mismatched_attributes() {
+    let subject = square_polygon(Vec2::ZERO, 2.0);
+    let clip = square_polygon(Vec2::ONE, 2.0);
+    let attributes = vec![vec![0.0, 1.0, 2.0, 3.0]];
+    assert_eq!(
+      try_boolean_with_attributes(
+        &subject,
+        &attributes,
+        &clip,
+        &[vec![0.0, 1.0]],
+        Operation::Union,
+      ),
+      Err(ClipError::InvalidInput {
+        is_from_subject: false,
+        contour: 0,
+        vertex: 2
+      })
+    );
+    assert_eq!(
+      try_boolean_with_attributes(
+        &subject,
+        &[],
+        &clip,
+        &attributes,
+        Operation::Union,
+      ),
+      Err(ClipError::InvalidInput {
+        is_from_subject: true,
+        contour: 0,
+        vertex: 0
+      })
+    );
+  }
+}
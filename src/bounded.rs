@@ -0,0 +1,152 @@
+use glam::Vec2;
+
+use crate::{
+  perform_boolean_with_bounds, unwrap_boolean, BooleanOptions, BooleanResult,
+  ClipError, Operation, Polygon, Rule,
+};
+
+// A polygon along with its cached bounds, for polygons used in many boolean
+// operations (e.g. the same obstacles clipped against many regions). Boolean
+// operations compute the bounds of both polygons every time to skip the sweep
+// for disjoint polygons, which walks every vertex. Operations between bounded
+// polygons reuse the cached bounds instead. The polygon cannot be modified in
+// place, since that would invalidate the bounds.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct BoundedPolygon {
+  // The wrapped polygon.
+  polygon: Polygon,
+  // The bounds of `polygon`, or None if it has no vertices.
+  bounds: Option<(Vec2, Vec2)>,
+}
+
+impl BoundedPolygon {
+  // Wraps `polygon`, computing its bounds once.
+  pub fn new(polygon: Polygon) -> Self {
+    let bounds = polygon.compute_bounds();
+    Self { polygon, bounds }
+  }
+
+  // The wrapped polygon.
+  pub fn polygon(&self) -> &Polygon {
+    &self.polygon
+  }
+
+  // Unwraps the polygon, e.g. to modify it.
+  pub fn into_polygon(self) -> Polygon {
+    self.polygon
+  }
+
+  // The cached bounds (min, max) of the polygon. This is the same as
+  // `self.polygon().compute_bounds()`.
+  pub fn bounds(&self) -> Option<(Vec2, Vec2)> {
+    self.bounds
+  }
+
+  // Computes `operation` of this polygon (as the subject) and `clip`. This is
+  // the same as `boolean(self.polygon(), clip.polygon(), operation)`.
+  pub fn boolean(
+    &self,
+    clip: &BoundedPolygon,
+    operation: Operation,
+  ) -> BooleanResult {
+    unwrap_boolean(self.try_boolean_with_options(
+      clip,
+      operation,
+      &BooleanOptions::default(),
+    ))
+  }
+
+  // Same as `boolean`, but with `options`. Options that change the swept
+  // polygons (the fill rules and `weld_tolerance`) compute the bounds of the
+  // changed polygons instead.
+  pub fn boolean_with_options(
+    &self,
+    clip: &BoundedPolygon,
+    operation: Operation,
+    options: &BooleanOptions,
+  ) -> BooleanResult {
+    unwrap_boolean(self.try_boolean_with_options(clip, operation, options))
+  }
+
+  // Same as `boolean`, but returns an error instead of panicking.
+  pub fn try_boolean(
+    &self,
+    clip: &BoundedPolygon,
+    operation: Operation,
+  ) -> Result<BooleanResult, ClipError> {
+    self.try_boolean_with_options(clip, operation, &BooleanOptions::default())
+  }
+
+  // Same as `boolean_with_options`, but returns an error instead of panicking.
+  pub fn try_boolean_with_options(
+    &self,
+    clip: &BoundedPolygon,
+    operation: Operation,
+    options: &BooleanOptions,
+  ) -> Result<BooleanResult, ClipError> {
+    perform_boolean_with_bounds(
+      &self.polygon,
+      &clip.polygon,
+      Some((self.bounds, clip.bounds)),
+      Rule::Operation(operation),
+      options,
+    )
+  }
+}
+
+impl From<Polygon> for BoundedPolygon {
+  fn from(polygon: Polygon) -> Self {
+    Self::new(polygon)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square;
+  use crate::{
+    boolean, boolean_with_options, BooleanOptions, BoundedPolygon, FillRule,
+    Operation, Polygon,
+  };
+
+  #[test]
+  fn matches_unbounded_operations() {
+    let subject = Polygon { contours: vec![square(Vec2::ZERO, 2.0)] };
+    let overlapping = Polygon { contours: vec![square(Vec2::ONE, 2.0)] };
+    let disjoint = Polygon { contours: vec![square(Vec2::splat(5.0), 1.0)] };
+    let bounded_subject = BoundedPolygon::new(subject.clone());
+    assert_eq!(bounded_subject.bounds(), Some((Vec2::ZERO, Vec2::splat(2.0))));
+
+    for clip in [overlapping, disjoint, Polygon::default()] {
+      let bounded_clip = BoundedPolygon::from(clip.clone());
+      for operation in Operation::ALL {
+        assert_eq!(
+          bounded_subject.boolean(&bounded_clip, operation),
+          boolean(&subject, &clip, operation)
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn recomputes_bounds_of_filled_polygons() {
+    // Under the non-zero rule, the reversed copy cancels out the square, so
+    // the subject is empty once filled.
+    let mut reversed = square(Vec2::ZERO, 2.0);
+    reversed.reverse();
+    let subject = Polygon { contours: vec![square(Vec2::ZERO, 2.0), reversed] };
+    let clip = Polygon { contours: vec![square(Vec2::ONE, 2.0)] };
+    let options = BooleanOptions::default().with_fill_rule(FillRule::NonZero);
+    let result = BoundedPolygon::new(subject.clone()).boolean_with_options(
+      &BoundedPolygon::new(clip.clone()),
+      Operation::Union,
+      &options,
+    );
+    assert_eq!(
+      result,
+      boolean_with_options(&subject, &clip, Operation::Union, &options)
+    );
+    assert_eq!(result.area(), 4.0);
+  }
+}
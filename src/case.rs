@@ -0,0 +1,340 @@
+use std::fmt;
+
+use glam::Vec2;
+
+use crate::{Operation, Polygon};
+
+// An error parsing a case written by `dump_case`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseCaseError {
+  // The (1-based) line the error is on, or None if the error is about the case
+  // as a whole (e.g. a missing operation).
+  pub line: Option<usize>,
+  // A description of the error.
+  pub message: String,
+}
+
+impl fmt::Display for ParseCaseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.line {
+      Some(line) => write!(f, "line {}: {}", line, self.message),
+      None => write!(f, "{}", self.message),
+    }
+  }
+}
+
+impl std::error::Error for ParseCaseError {}
+
+// Writes a boolean operation between `subject` and `clip` as text, so a
+// failing case can be attached to a bug report in one piece. Coordinates are
+// written as hexadecimal floats (e.g. `0x1.8p+1` for 3), which preserve their
+// exact bits (including non-finite values), followed by a comment with their
+// decimal values for readability. `parse_case` reads the text back.
+pub fn dump_case(
+  subject: &Polygon,
+  clip: &Polygon,
+  operation: Operation,
+) -> String {
+  let mut text =
+    String::from("# A boolean operation case (see `parse_case`).\n");
+  text += &format!("operation {}\n", operation_name(operation));
+  for (name, polygon) in [("subject", subject), ("clip", clip)] {
+    text += name;
+    text += "\n";
+    for contour in polygon.contours.iter() {
+      text += "contour\n";
+      for point in contour.iter() {
+        text += &format!(
+          "  {} {}  # ({}, {})\n",
+          format_hex_float(point.x),
+          format_hex_float(point.y),
+          point.x,
+          point.y
+        );
+      }
+    }
+  }
+  text
+}
+
+// Parses a case written by `dump_case` into the subject, the clip, and the
+// operation. Comments (starting with `#`) and blank lines are ignored, and
+// coordinates may also be written as decimal floats, so cases can be written
+// by hand.
+pub fn parse_case(
+  text: &str,
+) -> Result<(Polygon, Polygon, Operation), ParseCaseError> {
+  let mut operation = None;
+  let mut subject = Polygon::default();
+  let mut clip = Polygon::default();
+  // The polygon that contours are currently added to (true for the subject).
+  let mut in_subject = None;
+  for (index, line) in text.lines().enumerate() {
+    let error =
+      |message: String| ParseCaseError { line: Some(index + 1), message };
+    let line = line.split('#').next().unwrap().trim();
+    let tokens = line.split_whitespace().collect::<Vec<_>>();
+    match tokens.as_slice() {
+      [] => {}
+      ["operation", name] => {
+        operation = Some(
+          parse_operation(name)
+            .ok_or_else(|| error(format!("unknown operation `{name}`")))?,
+        );
+      }
+      ["subject"] => in_subject = Some(true),
+      ["clip"] => in_subject = Some(false),
+      ["contour"] => {
+        let polygon = match in_subject {
+          Some(true) => &mut subject,
+          Some(false) => &mut clip,
+          None => {
+            return Err(error("contour before `subject` or `clip`".to_string()))
+          }
+        };
+        polygon.contours.push(Vec::new());
+      }
+      [x, y] => {
+        let polygon = match in_subject {
+          Some(true) => &mut subject,
+          Some(false) => &mut clip,
+          None => {
+            return Err(error("vertex before `subject` or `clip`".to_string()))
+          }
+        };
+        let contour = polygon
+          .contours
+          .last_mut()
+          .ok_or_else(|| error("vertex before `contour`".to_string()))?;
+        let parse = |coordinate: &str| {
+          parse_float(coordinate)
+            .ok_or_else(|| error(format!("invalid coordinate `{coordinate}`")))
+        };
+        contour.push(Vec2::new(parse(x)?, parse(y)?));
+      }
+      _ => return Err(error(format!("unexpected line `{line}`"))),
+    }
+  }
+  let operation = operation.ok_or_else(|| ParseCaseError {
+    line: None,
+    message: "missing operation".to_string(),
+  })?;
+  Ok((subject, clip, operation))
+}
+
+fn operation_name(operation: Operation) -> &'static str {
+  match operation {
+    Operation::Intersection => "intersection",
+    Operation::Union => "union",
+    Operation::XOR => "xor",
+    Operation::Difference => "difference",
+  }
+}
+
+fn parse_operation(name: &str) -> Option<Operation> {
+  match name {
+    "intersection" => Some(Operation::Intersection),
+    "union" => Some(Operation::Union),
+    "xor" => Some(Operation::XOR),
+    "difference" => Some(Operation::Difference),
+    _ => None,
+  }
+}
+
+// Formats `value` as a hexadecimal float (as in C's `%a`), e.g. `-0x1.8p+1` for
+// -3. Subnormal values are written with a leading 0 (e.g. `0x0.000002p-126`).
+fn format_hex_float(value: f32) -> String {
+  if value.is_nan() {
+    return format!("nan:{:08x}", value.to_bits());
+  }
+  let sign = if value.is_sign_negative() { "-" } else { "" };
+  if value.is_infinite() {
+    return format!("{sign}inf");
+  }
+  let bits = value.to_bits();
+  let biased_exponent = ((bits >> 23) & 0xff) as i32;
+  // Shift the 23 bits of the mantissa to fill 6 hex digits.
+  let mantissa = (bits & 0x7fffff) << 1;
+  let (leading_digit, exponent) = match (biased_exponent, mantissa) {
+    (0, 0) => (0, 0),
+    (0, _) => (0, -126),
+    _ => (1, biased_exponent - 127),
+  };
+  let fraction = format!("{mantissa:06x}");
+  let fraction = fraction.trim_end_matches('0');
+  if fraction.is_empty() {
+    format!("{sign}0x{leading_digit}p{exponent:+}")
+  } else {
+    format!("{sign}0x{leading_digit}.{fraction}p{exponent:+}")
+  }
+}
+
+// Parses a float written by `format_hex_float`, or a decimal float.
+fn parse_float(text: &str) -> Option<f32> {
+  let (negative, unsigned) = match text.strip_prefix('-') {
+    Some(unsigned) => (true, unsigned),
+    None => (false, text.strip_prefix('+').unwrap_or(text)),
+  };
+  let value = if let Some(bits) = unsigned.strip_prefix("nan:") {
+    f32::from_bits(u32::from_str_radix(bits, 16).ok()?)
+  } else if let Some(hex) = unsigned.strip_prefix("0x") {
+    parse_hex_float(hex)?
+  } else {
+    return text.parse().ok();
+  };
+  Some(if negative { -value } else { value })
+}
+
+// Parses the unsigned part of a hexadecimal float after the `0x`, e.g.
+// `1.8p+1`.
+fn parse_hex_float(text: &str) -> Option<f32> {
+  let (digits, exponent) = match text.split_once('p') {
+    Some((digits, exponent)) => (digits, exponent.parse::<i32>().ok()?),
+    None => (text, 0),
+  };
+  let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+  let integer = integer.trim_start_matches('0');
+  let fraction = fraction.trim_end_matches('0');
+  // Up to 13 hex digits fit exactly in an f64, so rounding to f32 only happens
+  // once at the end.
+  if integer.len() + fraction.len() > 13 || digits.is_empty() {
+    return None;
+  }
+  let mut mantissa = 0u64;
+  for digit in integer.chars().chain(fraction.chars()) {
+    mantissa = mantissa * 16 + digit.to_digit(16)? as u64;
+  }
+  // Scaling by powers of 2 is exact in f64 for every exponent an f32 can have.
+  let mut exponent = exponent.checked_sub(4 * fraction.len() as i32)?;
+  let mut value = mantissa as f64;
+  while exponent > 0 && value.is_finite() {
+    value *= 2.0;
+    exponent -= 1;
+  }
+  while exponent < 0 && value != 0.0 {
+    value *= 0.5;
+    exponent += 1;
+  }
+  Some(value as f32)
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::{
+    case::{format_hex_float, parse_float},
+    dump_case, parse_case, Operation, ParseCaseError, Polygon,
+  };
+
+  #[test]
+  fn formats_hex_floats() {
+    assert_eq!(format_hex_float(3.0), "0x1.8p+1");
+    assert_eq!(format_hex_float(-0.1), "-0x1.99999ap-4");
+    assert_eq!(format_hex_float(0.0), "0x0p+0");
+    assert_eq!(format_hex_float(-0.0), "-0x0p+0");
+    assert_eq!(format_hex_float(f32::from_bits(1)), "0x0.000002p-126");
+    assert_eq!(format_hex_float(f32::NEG_INFINITY), "-inf");
+    assert_eq!(format_hex_float(f32::MAX), "0x1.fffffep+127");
+  }
+
+  #[test]
+  fn hex_floats_round_trip_exactly() {
+    for value in [
+      0.0,
+      -0.0,
+      1.0,
+      0.1,
+      -1234.5678,
+      f32::MIN_POSITIVE,
+      f32::from_bits(1),
+      f32::from_bits(0x007fffff),
+      f32::MAX,
+      f32::MIN,
+      f32::INFINITY,
+      f32::NAN,
+      f32::from_bits(0xffc00001),
+    ] {
+      let parsed = parse_float(&format_hex_float(value)).unwrap();
+      assert_eq!(parsed.to_bits(), value.to_bits(), "{value}");
+    }
+  }
+
+  #[test]
+  fn cases_round_trip() {
+    let subject = Polygon {
+      contours: vec![
+        vec![
+          Vec2::new(0.1, -0.0),
+          Vec2::new(1e-40, 2.0),
+          Vec2::new(1234.5678, 3.3),
+        ],
+        vec![Vec2::new(5.0, 5.0), Vec2::new(6.0, 5.0), Vec2::new(6.0, 6.0)],
+      ],
+    };
+    let clip = Polygon {
+      contours: vec![vec![
+        Vec2::new(-1.0, -1.0),
+        Vec2::new(1.0, -1.0),
+        Vec2::new(0.0, 1.0e30),
+      ]],
+    };
+    let text = dump_case(&subject, &clip, Operation::Difference);
+    let (parsed_subject, parsed_clip, operation) = parse_case(&text).unwrap();
+    assert_eq!(operation, Operation::Difference);
+    assert_eq!(parsed_subject, subject);
+    assert_eq!(parsed_subject.contours[0][0].y.to_bits(), (-0.0f32).to_bits());
+    assert_eq!(parsed_clip, clip);
+
+    let (subject, clip, _) =
+      parse_case(&dump_case(&Polygon::default(), &clip, Operation::XOR))
+        .unwrap();
+    assert_eq!(subject, Polygon::default());
+    assert_eq!(parsed_clip, clip);
+  }
+
+  #[test]
+  fn parses_hand_written_cases() {
+    let (subject, clip, operation) = parse_case(
+      "operation union\n\
+       subject\n\
+       contour\n\
+       0 0\n\
+       2 0 # comment\n\
+       \n\
+       0x1p+0 0x1.8p+1\n\
+       clip\n",
+    )
+    .unwrap();
+    assert_eq!(operation, Operation::Union);
+    assert_eq!(
+      subject.contours,
+      [[Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), Vec2::new(1.0, 3.0)]]
+    );
+    assert_eq!(clip, Polygon::default());
+  }
+
+  #[test]
+  fn reports_parse_errors() {
+    let error = |line, message: &str| {
+      Err(ParseCaseError { line, message: message.to_string() })
+    };
+    assert_eq!(parse_case("subject\n"), error(None, "missing operation"));
+    assert_eq!(
+      parse_case("operation add\n"),
+      error(Some(1), "unknown operation `add`")
+    );
+    assert_eq!(
+      parse_case("operation union\nsubject\n1 2\n"),
+      error(Some(3), "vertex before `contour`")
+    );
+    assert_eq!(
+      parse_case("operation union\nclip\ncontour\n1 x\n"),
+      error(Some(4), "invalid coordinate `x`")
+    );
+    assert_eq!(
+      parse_case("operation union\nclip\ncontour\n1 2 3\n"),
+      error(Some(4), "unexpected line `1 2 3`")
+    );
+  }
+}
@@ -0,0 +1,144 @@
+use crate::{
+  try_boolean_with_options, unwrap_boolean, BooleanOptions, BooleanResult,
+  ClipError, Operation, Polygon,
+};
+
+// Accumulates subject and clip polygons to compute a boolean operation of all
+// of them at once, like the Clipper library's workflow. The added subjects are
+// combined into one subject (and the clips into one clip), so executing sweeps
+// every operand together with a single set of events instead of folding
+// pairwise operations. Overlapping subjects (or clips) are resolved by
+// `BooleanOptions::fill_rule`, so with the default even-odd rule they cancel
+// out where they overlap; use `FillRule::NonZero` to merge them instead.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Clipper {
+  // The contours of all the added subjects, in the order they were added.
+  subject: Polygon,
+  // The contours of all the added clips, in the order they were added.
+  clip: Polygon,
+}
+
+impl Clipper {
+  // Creates a clipper without any polygons.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  // Adds `polygon` to the subject. The contours of the source edges of the
+  // result count the contours of all the added subjects in order.
+  pub fn add_subject(&mut self, polygon: &Polygon) -> &mut Self {
+    self.subject.contours.extend(polygon.contours.iter().cloned());
+    self
+  }
+
+  // Adds `polygon` to the clip. The contours of the source edges of the result
+  // count the contours of all the added clips in order.
+  pub fn add_clip(&mut self, polygon: &Polygon) -> &mut Self {
+    self.clip.contours.extend(polygon.contours.iter().cloned());
+    self
+  }
+
+  // Removes all the added polygons, so the clipper can be reused.
+  pub fn clear(&mut self) {
+    self.subject.contours.clear();
+    self.clip.contours.clear();
+  }
+
+  // The contours of all the added subjects.
+  pub fn subject(&self) -> &Polygon {
+    &self.subject
+  }
+
+  // The contours of all the added clips.
+  pub fn clip(&self) -> &Polygon {
+    &self.clip
+  }
+
+  // Computes `operation` of the added subjects and clips. This panics if the
+  // operation cannot be computed (see `try_execute`).
+  pub fn execute(&self, operation: Operation) -> BooleanResult {
+    unwrap_boolean(self.try_execute(operation))
+  }
+
+  // Same as `execute`, but with `options`.
+  pub fn execute_with_options(
+    &self,
+    operation: Operation,
+    options: &BooleanOptions,
+  ) -> BooleanResult {
+    unwrap_boolean(self.try_execute_with_options(operation, options))
+  }
+
+  // Same as `execute`, but returns an error instead of panicking.
+  pub fn try_execute(
+    &self,
+    operation: Operation,
+  ) -> Result<BooleanResult, ClipError> {
+    self.try_execute_with_options(operation, &BooleanOptions::default())
+  }
+
+  // Same as `execute_with_options`, but returns an error instead of panicking.
+  pub fn try_execute_with_options(
+    &self,
+    operation: Operation,
+    options: &BooleanOptions,
+  ) -> Result<BooleanResult, ClipError> {
+    try_boolean_with_options(&self.subject, &self.clip, operation, options)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square_polygon;
+  use crate::{
+    difference, union_all, BooleanOptions, Clipper, FillRule, Operation,
+    Polygon,
+  };
+
+  #[test]
+  fn executes_operation_of_accumulated_polygons() {
+    let mut clipper = Clipper::new();
+    clipper
+      .add_subject(&square_polygon(Vec2::ZERO, 4.0))
+      .add_subject(&square_polygon(Vec2::new(10.0, 0.0), 4.0))
+      .add_clip(&square_polygon(Vec2::new(2.0, 2.0), 4.0))
+      .add_clip(&square_polygon(Vec2::new(12.0, 2.0), 4.0));
+
+    let subject = Polygon {
+      contours: [
+        square_polygon(Vec2::ZERO, 4.0),
+        square_polygon(Vec2::new(10.0, 0.0), 4.0),
+      ]
+      .map(|polygon| polygon.contours[0].clone())
+      .to_vec(),
+    };
+    assert_eq!(clipper.subject(), &subject);
+    assert_eq!(
+      clipper.execute(Operation::Difference),
+      difference(&subject, clipper.clip())
+    );
+  }
+
+  #[test]
+  fn merges_overlapping_operands_with_non_zero_fill_rule() {
+    let squares = [
+      square_polygon(Vec2::ZERO, 4.0),
+      square_polygon(Vec2::new(2.0, 2.0), 4.0),
+    ];
+    let mut clipper = Clipper::new();
+    for polygon in squares.iter() {
+      clipper.add_subject(polygon);
+    }
+
+    let result = clipper.execute_with_options(
+      Operation::Union,
+      &BooleanOptions::new().with_fill_rule(FillRule::NonZero),
+    );
+    assert_eq!(result.polygon, union_all(&squares).polygon);
+
+    clipper.clear();
+    assert!(clipper.execute(Operation::Union).is_empty);
+  }
+}
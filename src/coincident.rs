@@ -0,0 +1,203 @@
+use crate::{BooleanResult, SourceEdge};
+
+// Which source edge to report for edges where the subject and clip coincide
+// (e.g. the shared boundary of two touching parcels in a union). Union and XOR
+// are symmetric, so neither polygon is the natural source of these edges.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CoincidentEdgeSource {
+  // Report the subject edge.
+  #[default]
+  PreferSubject,
+  // Report the clip edge.
+  PreferClip,
+  // Report both edges: the subject edge in
+  // `BooleanResult::contour_source_edges` and the clip edge in
+  // `BooleanResult::contour_coincident_source_edges` (see
+  // `BooleanResult::source_edge_pair`).
+  ReportBoth,
+}
+
+impl BooleanResult {
+  // The subject and clip source edges of the edge starting at vertex `edge` of
+  // the contour at `contour`. Only edges where the subject and clip coincide
+  // can have both, and only if the operation used
+  // `CoincidentEdgeSource::ReportBoth` (otherwise, just the reported edge is
+  // returned).
+  pub fn source_edge_pair(
+    &self,
+    contour: usize,
+    edge: usize,
+  ) -> (Option<SourceEdge>, Option<SourceEdge>) {
+    let source_edge = self.contour_source_edges[contour][edge];
+    let coincident_source_edge = self
+      .contour_coincident_source_edges
+      .get(contour)
+      .and_then(|coincident_source_edges| coincident_source_edges[edge]);
+    if source_edge.is_from_subject {
+      (Some(source_edge), coincident_source_edge)
+    } else {
+      (coincident_source_edge, Some(source_edge))
+    }
+  }
+}
+
+// Applies `source` to the source edges of `result`, whose coincident edges
+// report the subject edge and hold the clip edge in
+// `contour_coincident_source_edges` (if it was swept).
+pub(crate) fn apply_coincident_edge_source(
+  result: &mut BooleanResult,
+  source: CoincidentEdgeSource,
+) {
+  match source {
+    CoincidentEdgeSource::PreferSubject => {
+      result.contour_coincident_source_edges.clear();
+    }
+    CoincidentEdgeSource::PreferClip => {
+      for (source_edges, coincident_source_edges) in result
+        .contour_source_edges
+        .iter_mut()
+        .zip(result.contour_coincident_source_edges.iter())
+      {
+        for (source_edge, coincident_source_edge) in
+          source_edges.iter_mut().zip(coincident_source_edges.iter())
+        {
+          if let Some(coincident_source_edge) = coincident_source_edge {
+            *source_edge = *coincident_source_edge;
+          }
+        }
+      }
+      result.contour_coincident_source_edges.clear();
+    }
+    CoincidentEdgeSource::ReportBoth => {
+      if result.contour_coincident_source_edges.len()
+        != result.polygon.contours.len()
+      {
+        // Trivially computed results reuse the input contours, so no edges
+        // coincide.
+        result.contour_coincident_source_edges = result
+          .polygon
+          .contours
+          .iter()
+          .map(|contour| vec![None; contour.len()])
+          .collect();
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::rectangle;
+  use crate::{
+    union_with_options, BooleanOptions, CoincidentEdgeSource, Polygon,
+    SourceEdge,
+  };
+
+  // Two overlapping parcels whose bottom edges (edge 0 of each) share the
+  // boundary from (1, 0) to (2, 0).
+  fn parcels() -> (Polygon, Polygon) {
+    (
+      Polygon { contours: vec![rectangle(Vec2::ZERO, Vec2::new(2.0, 1.0))] },
+      Polygon {
+        contours: vec![rectangle(Vec2::new(1.0, 0.0), Vec2::new(3.0, 2.0))],
+      },
+    )
+  }
+
+  fn subject_edge(edge: usize) -> SourceEdge {
+    SourceEdge { is_from_subject: true, contour: 0, edge }
+  }
+
+  fn clip_edge(edge: usize) -> SourceEdge {
+    SourceEdge { is_from_subject: false, contour: 0, edge }
+  }
+
+  // Finds the edge from `start` to `end` (in either direction) in `contours`
+  // and returns its contour and edge index.
+  fn find_edge(
+    contours: &[Vec<Vec2>],
+    start: Vec2,
+    end: Vec2,
+  ) -> (usize, usize) {
+    for (contour_index, contour) in contours.iter().enumerate() {
+      for index in 0..contour.len() {
+        let next = contour[(index + 1) % contour.len()];
+        if (contour[index], next) == (start, end)
+          || (contour[index], next) == (end, start)
+        {
+          return (contour_index, index);
+        }
+      }
+    }
+    panic!("no edge from {start} to {end} in {contours:?}");
+  }
+
+  #[test]
+  fn reports_preferred_source_of_coincident_edges() {
+    let (subject, clip) = parcels();
+    let source_of_shared_edge = |coincident_edge_source| {
+      let options =
+        BooleanOptions { coincident_edge_source, ..Default::default() };
+      let result = union_with_options(&subject, &clip, &options);
+      assert!(result.contour_coincident_source_edges.is_empty());
+      let (contour, edge) = find_edge(
+        &result.polygon.contours,
+        Vec2::new(1.0, 0.0),
+        Vec2::new(2.0, 0.0),
+      );
+      result.contour_source_edges[contour][edge]
+    };
+    assert_eq!(
+      source_of_shared_edge(CoincidentEdgeSource::PreferSubject),
+      subject_edge(0)
+    );
+    assert_eq!(
+      source_of_shared_edge(CoincidentEdgeSource::PreferClip),
+      clip_edge(0)
+    );
+  }
+
+  #[test]
+  fn reports_both_sources_as_pair() {
+    let (subject, clip) = parcels();
+    let options = BooleanOptions {
+      coincident_edge_source: CoincidentEdgeSource::ReportBoth,
+      ..Default::default()
+    };
+    let result = union_with_options(&subject, &clip, &options);
+
+    let source_edge_pair = |start, end| {
+      let (contour, edge) = find_edge(&result.polygon.contours, start, end);
+      result.source_edge_pair(contour, edge)
+    };
+    assert_eq!(
+      source_edge_pair(Vec2::new(1.0, 0.0), Vec2::new(2.0, 0.0)),
+      (Some(subject_edge(0)), Some(clip_edge(0)))
+    );
+    // Edges from only one polygon have a single source.
+    assert_eq!(
+      source_edge_pair(Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0)),
+      (Some(subject_edge(0)), None)
+    );
+    assert_eq!(
+      source_edge_pair(Vec2::new(2.0, 0.0), Vec2::new(3.0, 0.0)),
+      (None, Some(clip_edge(0)))
+    );
+  }
+
+  #[test]
+  fn reports_both_for_trivial_results() {
+    let (subject, _) = parcels();
+    let far_clip = Polygon {
+      contours: vec![rectangle(Vec2::splat(10.0), Vec2::splat(11.0))],
+    };
+    let options = BooleanOptions {
+      coincident_edge_source: CoincidentEdgeSource::ReportBoth,
+      ..Default::default()
+    };
+    let result = union_with_options(&subject, &far_clip, &options);
+    assert_eq!(result.contour_coincident_source_edges, vec![vec![None; 4]; 2]);
+  }
+}
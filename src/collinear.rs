@@ -0,0 +1,179 @@
+use glam::Vec2;
+
+use crate::{point_relative_to_line, BooleanResult};
+
+// Merges consecutive collinear edges of every contour of `result` into single
+// edges, filling `contour_merged_source_edges` with the source edges of the
+// edges merged into each edge. Each merged edge keeps the other per-edge data
+// (e.g. `contour_source_edges`) of its first edge.
+pub(crate) fn merge_collinear_edges(result: &mut BooleanResult) {
+  let mut contour_merged_source_edges =
+    Vec::with_capacity(result.polygon.contours.len());
+  for contour_index in 0..result.polygon.contours.len() {
+    let contour = &result.polygon.contours[contour_index];
+    let kept_vertices = non_collinear_vertices(contour);
+    let source_edges = &result.contour_source_edges[contour_index];
+    // Each kept vertex starts an edge covering the edges up to the next kept
+    // vertex.
+    let merged_source_edges = kept_vertices
+      .iter()
+      .enumerate()
+      .map(|(index, &start)| {
+        let end = kept_vertices[(index + 1) % kept_vertices.len()];
+        let mut edge = start;
+        let mut merged = Vec::new();
+        loop {
+          merged.push(source_edges[edge]);
+          edge = (edge + 1) % source_edges.len();
+          if edge == end {
+            break merged;
+          }
+        }
+      })
+      .collect::<Vec<_>>();
+    result.retain_contour_vertices(contour_index, &kept_vertices);
+    contour_merged_source_edges.push(merged_source_edges);
+  }
+  result.contour_merged_source_edges = contour_merged_source_edges;
+}
+
+// Computes the indices of the vertices of `contour` that are not in the middle
+// of two collinear edges going the same way. All vertices are kept if fewer
+// than 3 would remain.
+fn non_collinear_vertices(contour: &[Vec2]) -> Vec<usize> {
+  let len = contour.len();
+  let kept_vertices = (0..len)
+    .filter(|&index| {
+      let previous = contour[(index + len - 1) % len];
+      let point = contour[index];
+      let next = contour[(index + 1) % len];
+      point_relative_to_line(previous, next, point) != std::cmp::Ordering::Equal
+        || (point - previous).dot(next - point) <= 0.0
+    })
+    .collect::<Vec<_>>();
+  if kept_vertices.len() < 3 {
+    return (0..len).collect();
+  }
+  kept_vertices
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::{
+    intersection_with_options, BooleanOptions, OutputOrientation, Polygon,
+    SourceEdge,
+  };
+
+  fn subject_edge(edge: usize) -> SourceEdge {
+    SourceEdge { is_from_subject: true, contour: 0, edge }
+  }
+
+  fn clip_edge(edge: usize) -> SourceEdge {
+    SourceEdge { is_from_subject: false, contour: 0, edge }
+  }
+
+  // The inputs of `partially_overlapping_edges_are_split`, whose intersection
+  // has three collinear vertices along its bottom edge.
+  fn octagon_in_square() -> (Polygon, Polygon) {
+    (
+      Polygon {
+        contours: vec![vec![
+          Vec2::new(1.0, 1.0),
+          Vec2::new(2.5, 1.0),
+          Vec2::new(4.0, 1.0),
+          Vec2::new(4.0, 4.0),
+          Vec2::new(3.9, 4.0),
+          Vec2::new(1.1, 4.0),
+          Vec2::new(1.0, 4.0),
+        ]],
+      },
+      Polygon {
+        contours: vec![vec![
+          Vec2::new(2.0, 1.0),
+          Vec2::new(3.0, 1.0),
+          Vec2::new(4.0, 2.0),
+          Vec2::new(4.0, 3.0),
+          Vec2::new(3.0, 4.0),
+          Vec2::new(2.0, 4.0),
+          Vec2::new(1.0, 3.0),
+          Vec2::new(1.0, 2.0),
+        ]],
+      },
+    )
+  }
+
+  #[test]
+  fn merges_collinear_edges() {
+    let (subject, clip) = octagon_in_square();
+    let options =
+      BooleanOptions { merge_collinear_edges: true, ..Default::default() };
+    let result = intersection_with_options(&subject, &clip, &options);
+    assert_eq!(
+      result.polygon,
+      Polygon {
+        contours: vec![vec![
+          Vec2::new(1.0, 2.0),
+          Vec2::new(2.0, 1.0),
+          Vec2::new(3.0, 1.0),
+          Vec2::new(4.0, 2.0),
+          Vec2::new(4.0, 3.0),
+          Vec2::new(3.0, 4.0),
+          Vec2::new(2.0, 4.0),
+          Vec2::new(1.0, 3.0),
+        ]]
+      }
+    );
+    assert_eq!(
+      result.contour_source_edges,
+      [[
+        clip_edge(7),
+        subject_edge(0),
+        clip_edge(1),
+        subject_edge(2),
+        clip_edge(3),
+        subject_edge(4),
+        clip_edge(5),
+        subject_edge(6),
+      ]]
+    );
+    assert_eq!(
+      result.contour_merged_source_edges,
+      [vec![
+        vec![clip_edge(7)],
+        vec![subject_edge(0), subject_edge(1)],
+        vec![clip_edge(1)],
+        vec![subject_edge(2)],
+        vec![clip_edge(3)],
+        vec![subject_edge(4)],
+        vec![clip_edge(5)],
+        vec![subject_edge(6)],
+      ]]
+    );
+  }
+
+  #[test]
+  fn merged_source_edges_follow_reversed_contours() {
+    let (subject, clip) = octagon_in_square();
+    let options = BooleanOptions {
+      merge_collinear_edges: true,
+      output_orientation: OutputOrientation::ClockwiseShells,
+      ..Default::default()
+    };
+    let result = intersection_with_options(&subject, &clip, &options);
+    let contour = &result.polygon.contours[0];
+    assert_eq!(contour.len(), 8);
+    // The bottom edge runs from (3, 1) to (2, 1) and still covers both subject
+    // edges, now in order along the reversed edge.
+    let bottom_edge =
+      contour.iter().position(|&point| point == Vec2::new(3.0, 1.0)).unwrap();
+    assert_eq!(contour[(bottom_edge + 1) % 8], Vec2::new(2.0, 1.0));
+    assert_eq!(
+      result.contour_merged_source_edges[0][bottom_edge],
+      [subject_edge(1), subject_edge(0)]
+    );
+    assert!(result.contour_merged_source_edges[0][bottom_edge]
+      .contains(&result.contour_source_edges[0][bottom_edge]));
+  }
+}
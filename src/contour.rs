@@ -0,0 +1,154 @@
+use glam::Vec2;
+
+use crate::{
+  hierarchy::{compute_contour_parents, contour_depth},
+  util::contour_signed_area,
+  BooleanResult, Polygon,
+};
+
+// Whether a contour bounds a filled region or an empty region.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ContourKind {
+  // The region inside the contour is filled (the contour is nested in an even
+  // number of other contours).
+  Shell,
+  // The region inside the contour is empty (the contour is nested in an odd
+  // number of other contours).
+  Hole,
+}
+
+// A contour along with whether it is a shell or a hole. This is an alternative
+// to the raw contours of `Polygon`, where shells and holes are only
+// distinguished by how they are nested.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Contour {
+  // The loop of vertices of the contour.
+  pub points: Vec<Vec2>,
+  // Whether the contour is a shell or a hole.
+  pub kind: ContourKind,
+}
+
+impl Contour {
+  // Creates a shell from `points`.
+  pub fn shell(points: Vec<Vec2>) -> Self {
+    Self { points, kind: ContourKind::Shell }
+  }
+
+  // Creates a hole from `points`.
+  pub fn hole(points: Vec<Vec2>) -> Self {
+    Self { points, kind: ContourKind::Hole }
+  }
+}
+
+impl Polygon {
+  // Creates a polygon from typed contours. Shells are oriented
+  // counter-clockwise and holes clockwise, regardless of the orientation of the
+  // given points, so holes can never be given the wrong orientation.
+  pub fn from_typed_contours(
+    contours: impl IntoIterator<Item = Contour>,
+  ) -> Polygon {
+    Polygon {
+      contours: contours
+        .into_iter()
+        .map(|contour| {
+          let mut points = contour.points;
+          let is_counter_clockwise = contour_signed_area(&points) > 0.0;
+          if is_counter_clockwise != (contour.kind == ContourKind::Shell) {
+            points.reverse();
+          }
+          points
+        })
+        .collect(),
+    }
+  }
+
+  // Converts the contours to typed contours. Whether a contour is a shell or a
+  // hole is determined by how many other contours it is nested in. Contours
+  // are assumed to not cross each other.
+  pub fn to_typed_contours(&self) -> Vec<Contour> {
+    typed_contours(&self.contours, &compute_contour_parents(self))
+  }
+}
+
+impl BooleanResult {
+  // Converts the contours of the result polygon to typed contours. This uses
+  // `contour_parents` if it was computed, and otherwise derives the hierarchy
+  // from the geometry.
+  pub fn to_typed_contours(&self) -> Vec<Contour> {
+    if self.contour_parents.is_empty() {
+      self.polygon.to_typed_contours()
+    } else {
+      typed_contours(&self.polygon.contours, &self.contour_parents)
+    }
+  }
+}
+
+// Creates typed contours for `contours`, where `contour_parents` is the index
+// of the contour that each contour is directly nested in.
+fn typed_contours(
+  contours: &[Vec<Vec2>],
+  contour_parents: &[Option<usize>],
+) -> Vec<Contour> {
+  contours
+    .iter()
+    .enumerate()
+    .map(|(index, points)| Contour {
+      points: points.clone(),
+      kind: if contour_depth(contour_parents, index) % 2 == 1 {
+        ContourKind::Hole
+      } else {
+        ContourKind::Shell
+      },
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::rectangle;
+  use crate::{Contour, ContourKind, Polygon};
+
+  #[test]
+  fn typed_contours_are_oriented() {
+    let mut clockwise_shell = rectangle(Vec2::splat(0.0), Vec2::splat(4.0));
+    clockwise_shell.reverse();
+    let polygon = Polygon::from_typed_contours([
+      Contour::shell(clockwise_shell),
+      Contour::hole(rectangle(Vec2::splat(1.0), Vec2::splat(2.0))),
+    ]);
+
+    let mut clockwise_hole = rectangle(Vec2::splat(1.0), Vec2::splat(2.0));
+    clockwise_hole.reverse();
+    assert_eq!(
+      polygon,
+      Polygon {
+        contours: vec![
+          rectangle(Vec2::splat(0.0), Vec2::splat(4.0)),
+          clockwise_hole
+        ]
+      }
+    );
+  }
+
+  #[test]
+  fn to_typed_contours_classifies_by_nesting() {
+    let polygon = Polygon {
+      contours: vec![
+        rectangle(Vec2::splat(1.0), Vec2::splat(2.0)),
+        rectangle(Vec2::splat(0.0), Vec2::splat(4.0)),
+        rectangle(Vec2::splat(1.5), Vec2::splat(1.6)),
+      ],
+    };
+
+    assert_eq!(
+      polygon
+        .to_typed_contours()
+        .iter()
+        .map(|contour| contour.kind)
+        .collect::<Vec<_>>(),
+      [ContourKind::Hole, ContourKind::Shell, ContourKind::Shell]
+    );
+  }
+}
@@ -0,0 +1,66 @@
+use glam::{DVec2, Vec2};
+
+use crate::Polygon;
+
+// A polygon with f64 coordinates. Boolean operations are computed in f32, so
+// this is only a container for moving data between f64 pipelines and this
+// crate. See `Polygon` for how the contours are interpreted.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Polygon64 {
+  pub contours: Vec<Vec<DVec2>>,
+}
+
+impl Polygon {
+  // Converts the polygon to f64 coordinates. This is lossless.
+  pub fn to_f64(&self) -> Polygon64 {
+    Polygon64 {
+      contours: self
+        .contours
+        .iter()
+        .map(|contour| contour.iter().map(|point| point.as_dvec2()).collect())
+        .collect(),
+    }
+  }
+}
+
+impl Polygon64 {
+  // Converts the polygon to f32 coordinates, rounding each coordinate to the
+  // nearest f32. Coordinates beyond the range of f32 become infinite (and so
+  // are rejected by `validate_input`).
+  pub fn to_f32_lossy(&self) -> Polygon {
+    Polygon {
+      contours: self
+        .contours
+        .iter()
+        .map(|contour| {
+          contour.iter().map(|point| point.as_vec2()).collect::<Vec<Vec2>>()
+        })
+        .collect(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::{DVec2, Vec2};
+
+  use crate::Polygon;
+
+  #[test]
+  fn converts_between_precisions() {
+    let polygon = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.1, 0.2),
+        Vec2::new(1.0, 0.2),
+        Vec2::new(1.0, 1.0),
+      ]],
+    };
+
+    let polygon_64 = polygon.to_f64();
+    assert_eq!(
+      polygon_64.contours[0][0],
+      DVec2::new(0.1f32 as f64, 0.2f32 as f64)
+    );
+    assert_eq!(polygon_64.to_f32_lossy(), polygon);
+  }
+}
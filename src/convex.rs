@@ -0,0 +1,721 @@
+use glam::Vec2;
+
+use crate::{
+  finish_boolean, try_intersection_with_options, unwrap_boolean,
+  util::{
+    contour_signed_area, edge_intersection_in_local_frame,
+    EdgeIntersectionResult,
+  },
+  validate_input, BooleanOptions, BooleanResult, ClipError,
+  CoincidentEdgeSource, FillRule, Polygon, SourceEdge,
+};
+
+impl Polygon {
+  // Whether the polygon is a single convex contour: every turn goes the same
+  // way (collinear vertices are allowed) and the contour winds around only
+  // once. Contours with zero-length edges are not considered convex.
+  pub fn is_convex(&self) -> bool {
+    let [contour] = self.contours.as_slice() else {
+      return false;
+    };
+    if contour.len() < 3 {
+      return false;
+    }
+    let edge_vector =
+      |index: usize| contour[(index + 1) % contour.len()] - contour[index];
+    let mut turn_sign = 0.0;
+    // The number of times the edges switch between going left and going
+    // right. A contour winding around once switches exactly twice.
+    let mut direction_changes = 0;
+    let mut last_direction = 0.0;
+    for index in 0..contour.len() {
+      let vector = edge_vector(index);
+      let next_vector = edge_vector((index + 1) % contour.len());
+      if vector == Vec2::ZERO {
+        return false;
+      }
+      let cross = vector.perp_dot(next_vector);
+      if cross == 0.0 {
+        // Collinear edges must continue in the same direction.
+        if vector.dot(next_vector) <= 0.0 {
+          return false;
+        }
+      } else if turn_sign == 0.0 {
+        turn_sign = cross.signum();
+      } else if cross.signum() != turn_sign {
+        return false;
+      }
+      if vector.x != 0.0 {
+        if last_direction != 0.0 && vector.x.signum() != last_direction {
+          direction_changes += 1;
+        }
+        last_direction = vector.x.signum();
+      }
+    }
+    // The first edge going left or right is compared with the last one as
+    // well, since the contour is closed.
+    let first_direction = (0..contour.len())
+      .map(|index| edge_vector(index).x)
+      .find(|&x| x != 0.0)
+      .map_or(0.0, f32::signum);
+    if first_direction != last_direction {
+      direction_changes += 1;
+    }
+    turn_sign != 0.0 && direction_changes == 2
+  }
+}
+
+// Computes the intersection of `subject` and `clip` like `intersection`, but in
+// linear time if both are convex (see `Polygon::is_convex`), which is common
+// for physics and collision shapes. Otherwise, this computes the regular
+// intersection. This panics if the operation cannot be computed (see
+// `try_convex_intersection`).
+pub fn convex_intersection(subject: &Polygon, clip: &Polygon) -> BooleanResult {
+  unwrap_boolean(try_convex_intersection(subject, clip))
+}
+
+// Same as `convex_intersection`, but with `options`. Options that need the
+// sweep (`classify_relation`, `track_overlap_segments`,
+// `track_contact_points`, `boundary_tolerance`, `weld_tolerance` and
+// `coincident_edge_source` other than `PreferSubject`) compute the regular
+// intersection instead.
+pub fn convex_intersection_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> BooleanResult {
+  unwrap_boolean(try_convex_intersection_with_options(subject, clip, options))
+}
+
+// Same as `convex_intersection`, but returns an error instead of panicking.
+pub fn try_convex_intersection(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<BooleanResult, ClipError> {
+  try_convex_intersection_with_options(
+    subject,
+    clip,
+    &BooleanOptions::default(),
+  )
+}
+
+// Same as `convex_intersection_with_options`, but returns an error instead of
+// panicking.
+pub fn try_convex_intersection_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  validate_input(subject, clip)?;
+  if options.classify_relation
+    || options.track_overlap_segments
+    || options.track_contact_points
+    || options.boundary_tolerance > 0.0
+    || options.weld_tolerance > 0.0
+    || options.coincident_edge_source != CoincidentEdgeSource::PreferSubject
+    // A single convex contour covers its inside with either of these rules,
+    // but the other rules depend on its orientation.
+    || !matches!(
+      options.fill_rules(),
+      (
+        FillRule::EvenOdd | FillRule::NonZero,
+        FillRule::EvenOdd | FillRule::NonZero
+      )
+    )
+    || !subject.is_convex()
+    || !clip.is_convex()
+  {
+    return try_intersection_with_options(subject, clip, options);
+  }
+  // Rounding can make the envelopes disagree about the shape of the
+  // intersection, in which case the sweep settles it.
+  match intersect_convex(
+    &ConvexChains::new(&subject.contours[0], /* is_subject= */ true),
+    &ConvexChains::new(&clip.contours[0], /* is_subject= */ false),
+  ) {
+    Some(result) => Ok(finish_boolean(result, subject, clip, options)),
+    None => try_intersection_with_options(subject, clip, options),
+  }
+}
+
+// Computes the convex hull of all the vertices of `polygon` (see
+// `convex_hull_of_points`).
+pub fn convex_hull(polygon: &Polygon) -> Vec<Vec2> {
+  convex_hull_of_points(polygon.contours.iter().flatten().copied())
+}
+
+// Computes the convex hull of `points` as a counter-clockwise contour starting
+// at the lowest point in sweep order (smallest x, then smallest y). Collinear
+// and duplicate points are left out, so fewer than 3 distinct points give a
+// degenerate hull of just those points. Points with NaN coordinates are
+// ignored.
+pub fn convex_hull_of_points(
+  points: impl IntoIterator<Item = Vec2>,
+) -> Vec<Vec2> {
+  let mut points =
+    points.into_iter().filter(|point| !point.is_nan()).collect::<Vec<_>>();
+  points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+  points.dedup();
+  if points.len() < 3 {
+    return points;
+  }
+
+  // Andrew's monotone chain: build the lower chain going right, then the
+  // upper chain going back left, dropping points where the chain does not turn
+  // left.
+  let turns_left = |hull: &[Vec2], point: Vec2| {
+    let (a, b) = (hull[hull.len() - 2], hull[hull.len() - 1]);
+    (b - a).perp_dot(point - b) > 0.0
+  };
+  let mut hull: Vec<Vec2> = Vec::with_capacity(points.len() + 1);
+  for &point in points.iter() {
+    while hull.len() >= 2 && !turns_left(&hull, point) {
+      hull.pop();
+    }
+    hull.push(point);
+  }
+  // The upper chain starts from the last point of the lower chain.
+  let lower_len = hull.len();
+  for &point in points.iter().rev().skip(1) {
+    while hull.len() > lower_len && !turns_left(&hull, point) {
+      hull.pop();
+    }
+    hull.push(point);
+  }
+  // The upper chain ends back at the first point.
+  hull.pop();
+  hull
+}
+
+// An edge of a convex contour, oriented from left to right.
+#[derive(Clone, Copy)]
+struct ChainEdge {
+  start: Vec2,
+  end: Vec2,
+  source_edge: SourceEdge,
+}
+
+impl ChainEdge {
+  // The y coordinate of the edge at `x`, which is exactly the y coordinate of
+  // an end point at its x coordinate.
+  fn y_at(&self, x: f32) -> f32 {
+    if x <= self.start.x {
+      self.start.y
+    } else if x >= self.end.x {
+      self.end.y
+    } else {
+      self.start.y
+        + (self.end.y - self.start.y)
+          * ((x - self.start.x) / (self.end.x - self.start.x))
+    }
+  }
+}
+
+// A convex contour split into its lower and upper chains, which bound it from
+// below and above between its leftmost and rightmost x coordinates.
+struct ConvexChains {
+  // The edges below the contour, from left to right.
+  lower: Vec<ChainEdge>,
+  // The edges above the contour, from left to right.
+  upper: Vec<ChainEdge>,
+  // The vertical edge at the leftmost x coordinate, if any.
+  left_side: Option<SourceEdge>,
+  // The vertical edge at the rightmost x coordinate, if any.
+  right_side: Option<SourceEdge>,
+  min_x: f32,
+  max_x: f32,
+}
+
+impl ConvexChains {
+  // Splits `contour`, which must be convex.
+  fn new(contour: &[Vec2], is_subject: bool) -> Self {
+    let is_counter_clockwise = contour_signed_area(contour) > 0.0;
+    // Start at the lowest of the leftmost vertices, so each chain is walked in
+    // one direction without wrapping around.
+    let start = (0..contour.len())
+      .min_by(|&a, &b| {
+        contour[a]
+          .x
+          .total_cmp(&contour[b].x)
+          .then(contour[a].y.total_cmp(&contour[b].y))
+      })
+      .unwrap();
+    let mut chains = ConvexChains {
+      lower: Vec::new(),
+      upper: Vec::new(),
+      left_side: None,
+      right_side: None,
+      min_x: contour[start].x,
+      max_x: contour.iter().map(|point| point.x).fold(f32::MIN, f32::max),
+    };
+    for offset in 0..contour.len() {
+      let edge = (start + offset) % contour.len();
+      let (from, to) = (contour[edge], contour[(edge + 1) % contour.len()]);
+      let source_edge =
+        SourceEdge { is_from_subject: is_subject, contour: 0, edge };
+      let chain_edge = if from.x < to.x {
+        ChainEdge { start: from, end: to, source_edge }
+      } else {
+        ChainEdge { start: to, end: from, source_edge }
+      };
+      // Counter-clockwise contours go right along the bottom and left along
+      // the top (and the other way around for clockwise contours).
+      let goes_right = from.x < to.x;
+      if from.x == to.x {
+        if from.x == chains.min_x {
+          chains.left_side = Some(source_edge);
+        } else {
+          chains.right_side = Some(source_edge);
+        }
+      } else if goes_right == is_counter_clockwise {
+        chains.lower.push(chain_edge);
+      } else {
+        chains.upper.push(chain_edge);
+      }
+    }
+    // The chain walked from right to left was collected backwards.
+    if is_counter_clockwise {
+      chains.upper.reverse();
+    } else {
+      chains.lower.reverse();
+    }
+    chains
+  }
+
+  // The x coordinates of the vertices of both chains, in increasing order.
+  fn vertex_xs(&self) -> Vec<f32> {
+    let chain_xs = |chain: &[ChainEdge]| {
+      chain
+        .iter()
+        .map(|edge| edge.start.x)
+        .chain(chain.last().map(|edge| edge.end.x))
+        .collect::<Vec<_>>()
+    };
+    merge_sorted(&chain_xs(&self.lower), &chain_xs(&self.upper))
+  }
+}
+
+// Merges the sorted `a` and `b` into one sorted list.
+fn merge_sorted(a: &[f32], b: &[f32]) -> Vec<f32> {
+  let mut merged = Vec::with_capacity(a.len() + b.len());
+  let (mut a_index, mut b_index) = (0, 0);
+  while a_index < a.len() || b_index < b.len() {
+    if b_index == b.len() || (a_index < a.len() && a[a_index] <= b[b_index]) {
+      merged.push(a[a_index]);
+      a_index += 1;
+    } else {
+      merged.push(b[b_index]);
+      b_index += 1;
+    }
+  }
+  merged
+}
+
+// The point where `edge` meets `next_edge` at about `x`. Shared vertices are
+// used exactly, and crossings are computed like the sweep does.
+fn joint(edge: &ChainEdge, next_edge: &ChainEdge, x: f32) -> Vec2 {
+  for point in [edge.start, edge.end] {
+    if point == next_edge.start || point == next_edge.end {
+      return point;
+    }
+  }
+  match edge_intersection_in_local_frame(
+    (edge.start, edge.end),
+    (next_edge.start, next_edge.end),
+  ) {
+    EdgeIntersectionResult::PointIntersection(point) => point,
+    // Rounding missed the crossing, so it must be very close to `x`.
+    _ => Vec2::new(x, edge.y_at(x)),
+  }
+}
+
+// Computes the intersection of the convex `subject` and `clip` by walking
+// their chains from left to right. The intersection is bounded below by the
+// higher of the lower chains and above by the lower of the upper chains, so it
+// is wherever the first is below the second. Returns None if rounding makes
+// the chains inconsistent.
+fn intersect_convex(
+  subject: &ConvexChains,
+  clip: &ConvexChains,
+) -> Option<BooleanResult> {
+  let min_x = subject.min_x.max(clip.min_x);
+  let max_x = subject.max_x.min(clip.max_x);
+  if min_x >= max_x {
+    return Some(BooleanResult::default());
+  }
+  // Between consecutive x coordinates, every chain is a single edge.
+  let mut xs = vec![min_x];
+  xs.extend(
+    merge_sorted(&subject.vertex_xs(), &clip.vertex_xs())
+      .into_iter()
+      .filter(|&x| min_x < x && x < max_x),
+  );
+  xs.push(max_x);
+  xs.dedup();
+
+  // The chains in the order subject lower, clip lower, subject upper and clip
+  // upper, along with the index of the edge currently spanning each interval.
+  let chains = [&subject.lower, &clip.lower, &subject.upper, &clip.upper];
+  let mut edge_indices = [0; 4];
+  // The intervals where the intersection has area, with the index of the
+  // chain bounding it from below and from above.
+  let mut pieces: Vec<(f32, f32, [ChainEdge; 2])> = Vec::new();
+  for window in xs.windows(2) {
+    let (x0, x1) = (window[0], window[1]);
+    for (chain, edge_index) in chains.iter().zip(edge_indices.iter_mut()) {
+      while chain[*edge_index].end.x <= x0 {
+        *edge_index += 1;
+      }
+    }
+    let edges: [ChainEdge; 4] =
+      std::array::from_fn(|chain| chains[chain][edge_indices[chain]]);
+    // Split the interval wherever two of the edges cross, so the bounds are
+    // each a single edge in every piece.
+    let mut splits = vec![x0, x1];
+    for (a, b) in [(0, 1), (2, 3), (0, 2), (0, 3), (1, 2), (1, 3)] {
+      let start_difference = edges[a].y_at(x0) - edges[b].y_at(x0);
+      let end_difference = edges[a].y_at(x1) - edges[b].y_at(x1);
+      if start_difference * end_difference < 0.0 {
+        let x = x0
+          + (x1 - x0)
+            * (start_difference / (start_difference - end_difference));
+        if x0 < x && x < x1 {
+          splits.push(x);
+        }
+      }
+    }
+    splits.sort_by(f32::total_cmp);
+    splits.dedup();
+    for split in splits.windows(2) {
+      let middle = (split[0] + split[1]) * 0.5;
+      // Ties go to the subject, like coincident edges in the sweep.
+      let lower =
+        if edges[1].y_at(middle) > edges[0].y_at(middle) { 1 } else { 0 };
+      let upper =
+        if edges[3].y_at(middle) < edges[2].y_at(middle) { 3 } else { 2 };
+      if edges[upper].y_at(middle) > edges[lower].y_at(middle) {
+        pieces.push((split[0], split[1], [edges[lower], edges[upper]]));
+      }
+    }
+  }
+  let (Some(first_piece), Some(last_piece)) = (pieces.first(), pieces.last())
+  else {
+    return Some(BooleanResult::default());
+  };
+  // The intersection of convex polygons is convex, so it spans one interval.
+  if pieces.windows(2).any(|window| window[0].1 != window[1].0) {
+    return None;
+  }
+
+  // The vertical edge (or vertex) bounding the intersection at `x`, given the
+  // edges below and above it there.
+  let side = |x: f32,
+              bound_x: f32,
+              [lower, upper]: [ChainEdge; 2],
+              sides: [(f32, Option<SourceEdge>); 2]| {
+    if x != bound_x {
+      // The chains cross here, closing the intersection in a vertex.
+      let point = joint(&lower, &upper, x);
+      return (point, point, None);
+    }
+    let lower_point = Vec2::new(x, lower.y_at(x));
+    let upper_point = Vec2::new(x, upper.y_at(x));
+    let side = sides
+      .iter()
+      .find(|(side_x, side)| *side_x == x && side.is_some())
+      .and_then(|(_, side)| *side);
+    match side {
+      Some(side) if lower_point != upper_point => {
+        (lower_point, upper_point, Some(side))
+      }
+      // Without a vertical edge, the chains meet at a vertex.
+      _ => (lower_point, lower_point, None),
+    }
+  };
+  let (left_lower, left_upper, left_side) = side(
+    first_piece.0,
+    min_x,
+    first_piece.2,
+    [(subject.min_x, subject.left_side), (clip.min_x, clip.left_side)],
+  );
+  let (right_lower, right_upper, right_side) = side(
+    last_piece.1,
+    max_x,
+    last_piece.2,
+    [(subject.max_x, subject.right_side), (clip.max_x, clip.right_side)],
+  );
+
+  // Traces the bound of the pieces (0 for below, 1 for above) from left to
+  // right, returning its vertices and the source of each edge between them.
+  let trace_bound = |bound: usize, start: Vec2, end: Vec2| {
+    let mut points = vec![start];
+    let mut source_edges = vec![pieces[0].2[bound].source_edge];
+    for window in pieces.windows(2) {
+      let (edge, next_edge) = (window[0].2[bound], window[1].2[bound]);
+      if edge.source_edge != next_edge.source_edge {
+        points.push(joint(&edge, &next_edge, window[0].1));
+        source_edges.push(next_edge.source_edge);
+      }
+    }
+    points.push(end);
+    (points, source_edges)
+  };
+  let (lower_points, lower_sources) = trace_bound(0, left_lower, right_lower);
+  let (upper_points, upper_sources) = trace_bound(1, left_upper, right_upper);
+
+  // Walk counter-clockwise: along the lower bound, up the right side, back
+  // along the upper bound and down the left side.
+  let mut contour = Vec::new();
+  let mut source_edges = Vec::new();
+  contour.extend_from_slice(&lower_points[..lower_points.len() - 1]);
+  source_edges.extend_from_slice(&lower_sources);
+  if let Some(right_side) = right_side {
+    contour.push(right_lower);
+    source_edges.push(right_side);
+  }
+  contour.extend(upper_points.iter().rev());
+  source_edges.extend(upper_sources.iter().rev());
+  if let Some(left_side) = left_side {
+    source_edges.push(left_side);
+  } else {
+    // The upper bound ends at the start of the lower bound.
+    contour.pop();
+  }
+  if contour.len() < 3
+    || (0..contour.len())
+      .any(|index| contour[index] == contour[(index + 1) % contour.len()])
+  {
+    return None;
+  }
+
+  let mut result = BooleanResult {
+    polygon: Polygon { contours: vec![contour] },
+    contour_source_edges: vec![source_edges],
+    ..Default::default()
+  };
+  result.update_bounds();
+  Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+  use rand::{rngs::StdRng, Rng, SeedableRng};
+
+  use crate::tests::fixtures::rectangle_polygon;
+  use crate::{
+    convex_hull, convex_hull_of_points, convex_intersection,
+    convex_intersection_with_options, intersection,
+    try_intersection_with_options, util::contour_signed_area, BooleanOptions,
+    OutputOrientation, Polygon,
+  };
+
+  // Creates a convex polygon with `count` vertices on an ellipse.
+  fn ellipse(center: Vec2, radii: Vec2, count: usize, phase: f32) -> Polygon {
+    Polygon {
+      contours: vec![(0..count)
+        .map(|index| {
+          let angle =
+            phase + index as f32 / count as f32 * std::f32::consts::TAU;
+          center + radii * Vec2::new(angle.cos(), angle.sin())
+        })
+        .collect()],
+    }
+  }
+
+  // The total area of `polygon`, assuming holes are oriented opposite to
+  // shells.
+  fn area(polygon: &Polygon) -> f32 {
+    polygon.contours.iter().map(|contour| contour_signed_area(contour)).sum()
+  }
+
+  #[test]
+  fn computes_convex_hull() {
+    // An arrow shape, whose notch is not on the hull.
+    let polygon = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 1.0),
+        Vec2::new(4.0, 0.0),
+        Vec2::new(2.0, 4.0),
+      ]],
+    };
+    assert_eq!(
+      convex_hull(&polygon),
+      [Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(2.0, 4.0)]
+    );
+
+    // Collinear, duplicate and interior points are left out.
+    let points = [
+      Vec2::new(1.0, 1.0),
+      Vec2::new(0.0, 0.0),
+      Vec2::new(1.0, 0.0),
+      Vec2::new(2.0, 0.0),
+      Vec2::new(2.0, 2.0),
+      Vec2::new(0.0, 2.0),
+      Vec2::new(2.0, 2.0),
+    ];
+    let hull = convex_hull_of_points(points);
+    assert_eq!(
+      hull,
+      [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(2.0, 2.0),
+        Vec2::new(0.0, 2.0)
+      ]
+    );
+    assert!(Polygon { contours: vec![hull] }.is_convex());
+
+    // Degenerate inputs give degenerate hulls.
+    let line = [Vec2::ZERO, Vec2::new(2.0, 2.0), Vec2::ONE];
+    assert_eq!(convex_hull_of_points(line), [Vec2::ZERO, Vec2::new(2.0, 2.0)]);
+    assert_eq!(convex_hull(&Polygon::default()), []);
+  }
+
+  #[test]
+  fn detects_convex_polygons() {
+    assert!(rectangle_polygon(Vec2::ZERO, Vec2::ONE).is_convex());
+    assert!(ellipse(Vec2::ZERO, Vec2::ONE, 7, 0.0).is_convex());
+    // A collinear vertex.
+    assert!(Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(1.0, 1.0),
+      ]]
+    }
+    .is_convex());
+
+    // An L shape.
+    assert!(!Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(2.0, 1.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(1.0, 2.0),
+        Vec2::new(0.0, 2.0),
+      ]]
+    }
+    .is_convex());
+    // A pentagram turns the same way at every vertex, but winds twice.
+    let pentagram = Polygon {
+      contours: vec![(0..5)
+        .map(|index| {
+          let angle = index as f32 * 2.0 / 5.0 * std::f32::consts::TAU;
+          Vec2::new(angle.cos(), angle.sin())
+        })
+        .collect()],
+    };
+    assert!(!pentagram.is_convex());
+    // Holes are never convex.
+    assert!(!Polygon {
+      contours: vec![
+        rectangle_polygon(Vec2::ZERO, Vec2::splat(4.0)).contours[0].clone(),
+        rectangle_polygon(Vec2::ONE, Vec2::splat(2.0)).contours[0].clone(),
+      ]
+    }
+    .is_convex());
+  }
+
+  #[test]
+  fn matches_intersection_for_rectangles() {
+    let subject = rectangle_polygon(Vec2::ZERO, Vec2::new(4.0, 3.0));
+    for clip in [
+      rectangle_polygon(Vec2::new(1.0, 1.0), Vec2::new(6.0, 2.0)),
+      rectangle_polygon(Vec2::new(-1.0, -1.0), Vec2::new(2.0, 5.0)),
+      // Shares the bottom and left edges.
+      rectangle_polygon(Vec2::ZERO, Vec2::new(2.0, 2.0)),
+      // Only touches the subject.
+      rectangle_polygon(Vec2::new(4.0, 0.0), Vec2::new(5.0, 1.0)),
+      rectangle_polygon(Vec2::new(10.0, 0.0), Vec2::new(11.0, 1.0)),
+    ] {
+      assert_eq!(
+        convex_intersection(&subject, &clip),
+        intersection(&subject, &clip),
+        "clip = {clip:?}"
+      );
+    }
+  }
+
+  #[test]
+  fn matches_intersection_area_for_random_convex_polygons() {
+    let mut rng = StdRng::seed_from_u64(3311);
+    let options = BooleanOptions::new()
+      .with_output_orientation(OutputOrientation::CounterClockwiseShells);
+    for _ in 0..200 {
+      let mut random_ellipse = || {
+        ellipse(
+          Vec2::new(rng.gen_range(0.0..4.0), rng.gen_range(0.0..4.0)),
+          Vec2::new(rng.gen_range(0.5..3.0), rng.gen_range(0.5..3.0)),
+          rng.gen_range(3..12),
+          rng.gen_range(0.0..std::f32::consts::TAU),
+        )
+      };
+      let subject = random_ellipse();
+      let mut clip = random_ellipse();
+      if rng.gen_bool(0.5) {
+        clip.contours[0].reverse();
+      }
+      // Only compare inputs the sweep handles.
+      let Ok(regular) =
+        try_intersection_with_options(&subject, &clip, &options)
+      else {
+        continue;
+      };
+      let fast = convex_intersection_with_options(&subject, &clip, &options);
+      assert_eq!(
+        fast.polygon.contours.len(),
+        regular.polygon.contours.len(),
+        "subject = {subject:?}, clip = {clip:?}"
+      );
+      assert!(
+        (area(&fast.polygon) - area(&regular.polygon)).abs() < 1e-4,
+        "subject = {subject:?}, clip = {clip:?}"
+      );
+      // Each vertex of the fast result is a vertex of the regular one, with
+      // the same source edge.
+      for (point, source_edge) in fast
+        .polygon
+        .contours
+        .iter()
+        .flatten()
+        .zip(fast.contour_source_edges.iter().flatten())
+      {
+        assert!(
+          regular
+            .polygon
+            .contours
+            .iter()
+            .flatten()
+            .zip(regular.contour_source_edges.iter().flatten())
+            .any(|(regular_point, regular_source_edge)| {
+              regular_point.distance(*point) < 1e-4
+                && regular_source_edge == source_edge
+            }),
+          "subject = {subject:?}, clip = {clip:?}"
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn falls_back_for_concave_polygons() {
+    let subject = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(4.0, 0.0),
+        Vec2::new(4.0, 4.0),
+        Vec2::new(2.0, 1.0),
+        Vec2::new(0.0, 4.0),
+      ]],
+    };
+    let clip = rectangle_polygon(Vec2::new(1.0, -1.0), Vec2::new(3.0, 3.0));
+    assert_eq!(
+      convex_intersection(&subject, &clip),
+      intersection(&subject, &clip)
+    );
+  }
+}
@@ -0,0 +1,284 @@
+// Rendering the state of the sweep to SVG, enabled by the `debug-render`
+// feature. The sweep reports every step to a recorder, which only exists
+// while `render_sweep` is running on the current thread, so sweeps elsewhere
+// only pay for checking that there is no recorder.
+
+use std::{
+  cell::RefCell, cmp::Reverse, collections::BinaryHeap, fmt::Write as _,
+};
+
+use glam::Vec2;
+
+use crate::{
+  try_boolean_with_options, BooleanOptions, BooleanResult, ClipError, Event,
+  EventRelation, Operation, Polygon, SweepLineEvent,
+};
+
+// Which steps of the sweep `render_sweep` renders.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RenderSteps {
+  // Every step of every sweep (the sweep is retried with normalized
+  // coordinates if it fails, and both sweeps are rendered).
+  All,
+  // Only the last step of each sweep that fails.
+  OnFailure,
+}
+
+// Computes `operation` between `subject` and `clip` with `options`, rendering
+// the state of the sweep to SVG at the steps selected by `steps`. Each frame
+// shows the state right before an event is processed:
+// * the event (a red dot) and the sweep line through it (dashed),
+// * the edges in the sweep line (blue, with their order from bottom to top in
+//   their tooltips),
+// * the edges already swept (green if they are in the result, grey
+//   otherwise), as subdivided so far,
+// * and the queued events (orange dots, hollow for right events).
+// Frames are in the coordinates of the sweep, so frames of a retried sweep
+// show normalized coordinates. Operations computed without a sweep (e.g. for
+// disjoint polygons) have no frames.
+pub fn render_sweep(
+  subject: &Polygon,
+  clip: &Polygon,
+  operation: Operation,
+  options: &BooleanOptions,
+  steps: RenderSteps,
+) -> (Result<BooleanResult, ClipError>, Vec<String>) {
+  RECORDER.with(|recorder| {
+    *recorder.borrow_mut() =
+      Some(Recorder { steps, step: 0, frames: Vec::new(), last_frame: None })
+  });
+  let result = try_boolean_with_options(subject, clip, operation, options);
+  let recorder = RECORDER.with(|recorder| recorder.borrow_mut().take());
+  (result, recorder.map_or_else(Vec::new, |recorder| recorder.frames))
+}
+
+struct Recorder {
+  steps: RenderSteps,
+  // The number of steps rendered so far (across all sweeps).
+  step: usize,
+  // The frames to return.
+  frames: Vec<String>,
+  // The frame of the latest step, kept in case the sweep fails.
+  last_frame: Option<String>,
+}
+
+thread_local! {
+  static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+// Reports that the sweep is about to process `event`.
+pub(crate) fn render_step(
+  event: &Event,
+  sweep_line: &[SweepLineEvent],
+  event_queue: &BinaryHeap<Reverse<Event>>,
+  processed_events: &[Event],
+  event_relations: &[EventRelation],
+) {
+  RECORDER.with(|recorder| {
+    let mut recorder = recorder.borrow_mut();
+    let Some(recorder) = recorder.as_mut() else {
+      return;
+    };
+    let frame = render_frame(
+      recorder.step,
+      event,
+      sweep_line,
+      event_queue,
+      processed_events,
+      event_relations,
+    );
+    recorder.step += 1;
+    match recorder.steps {
+      RenderSteps::All => recorder.frames.push(frame),
+      RenderSteps::OnFailure => recorder.last_frame = Some(frame),
+    }
+  });
+}
+
+// Reports that the sweep failed at the latest step.
+pub(crate) fn render_failure() {
+  RECORDER.with(|recorder| {
+    if let Some(recorder) = recorder.borrow_mut().as_mut() {
+      if let Some(frame) = recorder.last_frame.take() {
+        recorder.frames.push(frame);
+      }
+    }
+  });
+}
+
+fn render_frame(
+  step: usize,
+  event: &Event,
+  sweep_line: &[SweepLineEvent],
+  event_queue: &BinaryHeap<Reverse<Event>>,
+  processed_events: &[Event],
+  event_relations: &[EventRelation],
+) -> String {
+  // The edge of a left event, as subdivided so far.
+  let edge = |event: &Event| {
+    (event.point, event_relations[event.event_id].sibling_point)
+  };
+  let swept_edges = processed_events
+    .iter()
+    .filter(|event| event.left)
+    .map(|event| (edge(event), event_relations[event.event_id].in_result));
+  let sweep_line_edges = sweep_line.iter().map(|event| edge(&event.0));
+
+  let points = swept_edges
+    .clone()
+    .flat_map(|((start, end), _)| [start, end])
+    .chain(sweep_line_edges.clone().flat_map(|(start, end)| [start, end]))
+    .chain(event_queue.iter().map(|Reverse(event)| event.point))
+    .chain([event.point]);
+  let (min, max) = points
+    .fold((event.point, event.point), |(min, max), point| {
+      (min.min(point), max.max(point))
+    });
+  let size = (max - min).max_element().max(f32::EPSILON);
+  let (min, max) = (min - size * 0.05, max + size * 0.05);
+  let radius = size * 0.008;
+
+  let mut svg = String::new();
+  // The y axis is flipped, so the view box covers the negated y range.
+  let _ = writeln!(
+    svg,
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">",
+    min.x,
+    -max.y,
+    max.x - min.x,
+    max.y - min.y
+  );
+  let _ = writeln!(
+    svg,
+    "<!-- step {step}: {} event {} at ({}, {}), {} edges in the sweep line, {} \
+     events queued -->",
+    if event.left { "left" } else { "right" },
+    event.event_id,
+    event.point.x,
+    event.point.y,
+    sweep_line.len(),
+    event_queue.len()
+  );
+  let _ = writeln!(
+    svg,
+    "<g transform=\"scale(1, -1)\" fill=\"none\" stroke-width=\"1.5\" \
+     vector-effect=\"non-scaling-stroke\">"
+  );
+  let line = |svg: &mut String, (start, end): (Vec2, Vec2), style, title| {
+    let _ = writeln!(
+      svg,
+      "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {style} \
+       vector-effect=\"non-scaling-stroke\"><title>{title}</title></line>",
+      start.x, start.y, end.x, end.y
+    );
+  };
+  let _ = writeln!(
+    svg,
+    "<line x1=\"{x}\" y1=\"{}\" x2=\"{x}\" y2=\"{}\" stroke=\"red\" \
+     stroke-dasharray=\"4 4\" vector-effect=\"non-scaling-stroke\"/>",
+    min.y,
+    max.y,
+    x = event.point.x,
+  );
+  for ((start, end), in_result) in swept_edges {
+    line(
+      &mut svg,
+      (start, end),
+      if in_result { "stroke=\"green\"" } else { "stroke=\"grey\"" },
+      format!("swept edge ({start}, {end})"),
+    );
+  }
+  for (index, sweep_line_event) in sweep_line.iter().enumerate() {
+    line(
+      &mut svg,
+      edge(&sweep_line_event.0),
+      "stroke=\"blue\"",
+      format!(
+        "sweep line edge {index} (event {})",
+        sweep_line_event.0.event_id
+      ),
+    );
+  }
+  for Reverse(queued_event) in event_queue.iter() {
+    let _ = writeln!(
+      svg,
+      "<circle cx=\"{}\" cy=\"{}\" r=\"{radius}\" stroke=\"orange\" \
+       fill=\"{}\" vector-effect=\"non-scaling-stroke\"><title>{} event \
+       {}</title></circle>",
+      queued_event.point.x,
+      queued_event.point.y,
+      if queued_event.left { "orange" } else { "none" },
+      if queued_event.left { "left" } else { "right" },
+      queued_event.event_id
+    );
+  }
+  let _ = writeln!(
+    svg,
+    "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"red\"/>",
+    event.point.x,
+    event.point.y,
+    radius * 1.5
+  );
+  svg += "</g>\n</svg>\n";
+  svg
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square_polygon;
+  use crate::{
+    render_sweep, union, BooleanOptions, ClipError, Operation, RenderSteps,
+  };
+
+  #[test]
+  fn renders_every_step() {
+    let subject = square_polygon(Vec2::ZERO, 2.0);
+    let clip = square_polygon(Vec2::ONE, 2.0);
+    let (result, frames) = render_sweep(
+      &subject,
+      &clip,
+      Operation::Union,
+      &BooleanOptions::default(),
+      RenderSteps::All,
+    );
+    assert_eq!(result, Ok(union(&subject, &clip)));
+    // Two events per edge, plus two for each of the 4 splits.
+    assert_eq!(frames.len(), 24);
+    assert!(frames[0].starts_with("<svg "));
+    assert!(frames[0].contains(
+      "<!-- step 0: left event 0 at (0, 0), 0 edges in the sweep line, 15 \
+       events queued -->"
+    ));
+    assert!(frames[5].contains("sweep line edge 1"));
+    assert!(frames[23].contains("stroke=\"green\""));
+  }
+
+  #[test]
+  fn only_renders_failures_when_requested() {
+    let subject = square_polygon(Vec2::ZERO, 2.0);
+    let clip = square_polygon(Vec2::ONE, 2.0);
+    let (result, frames) = render_sweep(
+      &subject,
+      &clip,
+      Operation::Union,
+      &BooleanOptions::default(),
+      RenderSteps::OnFailure,
+    );
+    assert!(result.is_ok());
+    assert!(frames.is_empty());
+
+    // Splitting the edges needs more events than allowed.
+    let options = BooleanOptions::new().with_max_events(16);
+    let (result, frames) = render_sweep(
+      &subject,
+      &clip,
+      Operation::Union,
+      &options,
+      RenderSteps::OnFailure,
+    );
+    assert_eq!(result, Err(ClipError::CapacityExceeded { limit: 16 }));
+    assert_eq!(frames.len(), 1);
+  }
+}
@@ -0,0 +1,164 @@
+use glam::Vec2;
+
+use crate::{util::contour_signed_area, BooleanResult};
+
+impl BooleanResult {
+  // Removes needle-like spikes and sliver contours, which near-tangent
+  // intersections can introduce. A vertex is a spike if the angle between its
+  // two edges is less than `min_angle` (in radians) and removing it changes the
+  // area by less than `min_area`. Spikes are removed repeatedly (removing one
+  // can make its neighbours spikes), and the edge before each removed vertex is
+  // extended to the next kept vertex, keeping its source edge. Afterwards,
+  // contours with an area less than `min_area` are removed (along with their
+  // per-contour data).
+  pub fn denoise(&mut self, min_area: f32, min_angle: f32) {
+    for contour_index in 0..self.polygon.contours.len() {
+      let kept_vertices = despiked_vertices(
+        &self.polygon.contours[contour_index],
+        min_area,
+        min_angle,
+      );
+      self.retain_contour_vertices(contour_index, &kept_vertices);
+    }
+    self.update_bounds();
+
+    let contours = &self.polygon.contours;
+    let keep = contours
+      .iter()
+      .map(|contour| {
+        contour.len() >= 3 && contour_signed_area(contour).abs() >= min_area
+      })
+      .collect::<Vec<_>>();
+    self.retain_contours_by_index(|contour_index| keep[contour_index]);
+  }
+}
+
+// Computes the indices of the vertices of `contour` that remain after
+// repeatedly removing spikes (see `BooleanResult::denoise`). At least 3
+// vertices are always kept.
+fn despiked_vertices(
+  contour: &[Vec2],
+  min_area: f32,
+  min_angle: f32,
+) -> Vec<usize> {
+  let len = contour.len();
+  let mut previous =
+    (0..len).map(|index| (index + len - 1) % len).collect::<Vec<_>>();
+  let mut next = (0..len).map(|index| (index + 1) % len).collect::<Vec<_>>();
+  let mut removed = vec![false; len];
+  let mut remaining = len;
+  // The vertices that may be spikes. Removing a spike makes its neighbours
+  // candidates again.
+  let mut candidates = (0..len).rev().collect::<Vec<_>>();
+  while let Some(vertex) = candidates.pop() {
+    if removed[vertex] || remaining <= 3 {
+      continue;
+    }
+    let (before, after) = (previous[vertex], next[vertex]);
+    if !is_spike(
+      contour[before],
+      contour[vertex],
+      contour[after],
+      min_area,
+      min_angle,
+    ) {
+      continue;
+    }
+    removed[vertex] = true;
+    remaining -= 1;
+    next[before] = after;
+    previous[after] = before;
+    candidates.push(after);
+    candidates.push(before);
+  }
+  (0..len).filter(|&index| !removed[index]).collect()
+}
+
+// Determines whether `point` is a spike between `before` and `after`.
+fn is_spike(
+  before: Vec2,
+  point: Vec2,
+  after: Vec2,
+  min_area: f32,
+  min_angle: f32,
+) -> bool {
+  let (to_before, to_after) = (before - point, after - point);
+  if to_before == Vec2::ZERO || to_after == Vec2::ZERO {
+    // The angle is undefined for degenerate edges.
+    return false;
+  }
+  let cross = to_before.perp_dot(to_after).abs();
+  let angle = cross.atan2(to_before.dot(to_after));
+  angle < min_angle && cross * 0.5 < min_area
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square_polygon;
+  use crate::{union, Polygon, SourceEdge};
+
+  // A thin triangle poking out of the right side of the square from (0, 0) to
+  // (4, 4).
+  fn needle() -> Polygon {
+    Polygon {
+      contours: vec![vec![
+        Vec2::new(3.0, 1.99),
+        Vec2::new(8.0, 2.0),
+        Vec2::new(3.0, 2.01),
+      ]],
+    }
+  }
+
+  #[test]
+  fn removes_spikes() {
+    let mut result = union(&square_polygon(Vec2::ZERO, 4.0), &needle());
+    let tip = Vec2::new(8.0, 2.0);
+    assert!(result.polygon.contours[0].contains(&tip));
+
+    result.denoise(0.1, 0.1);
+    let contour = &result.polygon.contours[0];
+    assert!(!contour.contains(&tip));
+    assert_eq!(contour.len(), 6);
+    assert_eq!(result.contour_source_edges[0].len(), 6);
+    // The edge to the tip is extended to the next vertex, keeping its source.
+    let base = contour
+      .iter()
+      .position(|&point| point.x == 4.0 && 1.0 < point.y && point.y < 2.0);
+    assert_eq!(
+      result.contour_source_edges[0][base.unwrap()],
+      SourceEdge { is_from_subject: false, contour: 0, edge: 0 }
+    );
+    assert_eq!(result.bounds, Some((Vec2::ZERO, Vec2::new(4.0, 4.0))));
+  }
+
+  #[test]
+  fn keeps_large_or_wide_features() {
+    let original = union(&square_polygon(Vec2::ZERO, 4.0), &needle());
+    // The spike is sharp, but removing it changes the area too much.
+    let mut result = original.clone();
+    result.denoise(0.01, 0.1);
+    assert_eq!(result.polygon, original.polygon);
+    // The spike is small, but not sharp enough.
+    let mut result = original.clone();
+    result.denoise(0.1, 0.001);
+    assert_eq!(result.polygon, original.polygon);
+  }
+
+  #[test]
+  fn removes_sliver_contours() {
+    let sliver = Polygon {
+      contours: vec![vec![
+        Vec2::new(10.0, 0.0),
+        Vec2::new(14.0, 0.0),
+        Vec2::new(14.0, 0.01),
+      ]],
+    };
+    let mut result = union(&square_polygon(Vec2::ZERO, 4.0), &sliver);
+    assert_eq!(result.polygon.contours.len(), 2);
+    result.denoise(0.1, 0.0);
+    assert_eq!(result.polygon, square_polygon(Vec2::ZERO, 4.0));
+    assert_eq!(result.contour_source_edges.len(), 1);
+  }
+}
@@ -0,0 +1,95 @@
+use glam::Vec2;
+
+use crate::{
+  util::{closest_point_on_segment, point_in_contour},
+  Polygon,
+};
+
+impl Polygon {
+  // Whether `point` is inside the polygon (using the even-odd rule). Points
+  // exactly on the boundary may be considered either inside or outside.
+  pub fn contains_point(&self, point: Vec2) -> bool {
+    self
+      .contours
+      .iter()
+      .filter(|contour| point_in_contour(point, contour))
+      .count()
+      % 2
+      == 1
+  }
+
+  // Finds the closest point to `point` on the boundary of the polygon. Returns
+  // None if the polygon has no vertices.
+  pub fn closest_boundary_point(&self, point: Vec2) -> Option<Vec2> {
+    self
+      .iter_edges()
+      .map(|(_, _, start, end)| closest_point_on_segment((start, end), point))
+      .min_by(|a, b| {
+        a.distance_squared(point).total_cmp(&b.distance_squared(point))
+      })
+  }
+
+  // Finds the closest point to `point` in the polygon: `point` itself if it is
+  // inside, otherwise the closest point on the boundary. This clamps a point
+  // into the polygon (e.g. keeping an agent inside a navigable region). Returns
+  // None if the polygon has no vertices.
+  pub fn closest_point(&self, point: Vec2) -> Option<Vec2> {
+    if self.contains_point(point) {
+      return Some(point);
+    }
+    self.closest_boundary_point(point)
+  }
+
+  // Computes the distance from `point` to the boundary of the polygon, which
+  // is negative if `point` is inside. Returns infinity if the polygon has no
+  // vertices (every point is infinitely far outside).
+  pub fn signed_distance(&self, point: Vec2) -> f32 {
+    let Some(closest) = self.closest_boundary_point(point) else {
+      return f32::INFINITY;
+    };
+    let distance = closest.distance(point);
+    if self.contains_point(point) {
+      -distance
+    } else {
+      distance
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square;
+  use crate::{difference, Polygon};
+
+  #[test]
+  fn clamps_points_into_region_with_hole() {
+    // A 4x4 room with a 2x2 pillar in the middle.
+    let region = difference(
+      &Polygon { contours: vec![square(Vec2::ZERO, 4.0)] },
+      &Polygon { contours: vec![square(Vec2::ONE, 2.0)] },
+    )
+    .polygon;
+
+    let inside = Vec2::new(0.5, 2.0);
+    assert_eq!(region.closest_point(inside), Some(inside));
+    assert_eq!(region.signed_distance(inside), -0.5);
+    // Points in the pillar are pushed out to its closest side.
+    let in_pillar = Vec2::new(2.8, 2.0);
+    assert_eq!(region.closest_point(in_pillar), Some(Vec2::new(3.0, 2.0)));
+    assert!((region.signed_distance(in_pillar) - 0.2).abs() < 1e-6);
+    // Points outside the room are pulled back to its boundary.
+    let outside = Vec2::new(6.0, 5.0);
+    assert_eq!(region.closest_point(outside), Some(Vec2::splat(4.0)));
+    assert_eq!(region.signed_distance(outside), 5.0f32.sqrt());
+  }
+
+  #[test]
+  fn empty_polygon_is_infinitely_far() {
+    let empty = Polygon::default();
+    assert!(!empty.contains_point(Vec2::ZERO));
+    assert_eq!(empty.closest_point(Vec2::ZERO), None);
+    assert_eq!(empty.signed_distance(Vec2::ZERO), f32::INFINITY);
+  }
+}
@@ -0,0 +1,198 @@
+use std::fmt;
+
+use glam::Vec2;
+
+use crate::{SourceEdge, MAX_COORDINATE_MAGNITUDE};
+
+// The stage of the sweep in which it became inconsistent.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SweepStage {
+  // Inserting the edge of a new (left) event into the sweep line. The sweep
+  // now skips events that are already in the sweep line, so this is no longer
+  // reported.
+  EventCreation,
+  // Processing the edges in the sweep line, which is where edges are
+  // subdivided at their intersections and removed once the sweep passes them.
+  Subdivision,
+  // Joining the result edges into closed contours.
+  ContourJoining,
+}
+
+// An error that prevents a boolean operation from being computed.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ClipError {
+  // One of the input polygons contains a vertex with a NaN or infinite
+  // coordinate.
+  InvalidInput {
+    // Whether the vertex is in the subject polygon (otherwise, the clip
+    // polygon).
+    is_from_subject: bool,
+    // The index of the contour containing the vertex.
+    contour: usize,
+    // The index of the vertex in the contour.
+    vertex: usize,
+  },
+  // One of the input polygons contains a vertex with a coordinate whose
+  // magnitude exceeds `MAX_COORDINATE_MAGNITUDE`. Comparisons of such
+  // coordinates can overflow, so they cannot be ordered reliably.
+  CoordinateOutOfRange {
+    // Whether the vertex is in the subject polygon (otherwise, the clip
+    // polygon).
+    is_from_subject: bool,
+    // The index of the contour containing the vertex.
+    contour: usize,
+    // The index of the vertex in the contour.
+    vertex: usize,
+  },
+  // The operation needed more events (two per edge, plus more for each split)
+  // than `BooleanOptions::max_events`.
+  CapacityExceeded {
+    // The maximum number of events that was allowed.
+    limit: usize,
+  },
+  // Rounding errors made the sweep inconsistent (e.g. an edge could not be
+  // found in the sweep line, or the result edges could not be joined into
+  // closed contours), even after recomputing the sweep in f64. This generally
+  // indicates numerically degenerate inputs.
+  InconsistentSweep {
+    // The stage of the sweep that failed.
+    stage: SweepStage,
+    // The point of the event being processed when the sweep failed.
+    point: Vec2,
+    // The edge of the inputs that the event belongs to.
+    source_edge: SourceEdge,
+    // The number of edges in the sweep line when the sweep failed. This is
+    // always 0 for `SweepStage::ContourJoining`, which happens after the
+    // sweep.
+    sweep_line_len: usize,
+  },
+}
+
+impl fmt::Display for ClipError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ClipError::InvalidInput { is_from_subject, contour, vertex } => write!(
+        f,
+        "the {} polygon has a non-finite coordinate at contour {}, vertex {}",
+        if *is_from_subject { "subject" } else { "clip" },
+        contour,
+        vertex
+      ),
+      ClipError::CoordinateOutOfRange { is_from_subject, contour, vertex } => {
+        write!(
+          f,
+          "the {} polygon has a coordinate at contour {}, vertex {} whose \
+           magnitude exceeds {:e}",
+          if *is_from_subject { "subject" } else { "clip" },
+          contour,
+          vertex,
+          MAX_COORDINATE_MAGNITUDE
+        )
+      }
+      ClipError::CapacityExceeded { limit } => {
+        write!(f, "the operation requires more than {} events", limit)
+      }
+      ClipError::InconsistentSweep {
+        stage,
+        point,
+        source_edge,
+        sweep_line_len,
+      } => write!(
+        f,
+        "the sweep became inconsistent due to rounding errors, even in f64 \
+         ({} at ({}, {}) on edge {} of contour {} of the {} polygon, with {} \
+         edges in the sweep line)",
+        match stage {
+          SweepStage::EventCreation => "event creation",
+          SweepStage::Subdivision => "subdivision",
+          SweepStage::ContourJoining => "contour joining",
+        },
+        point.x,
+        point.y,
+        source_edge.edge,
+        source_edge.contour,
+        if source_edge.is_from_subject { "subject" } else { "clip" },
+        sweep_line_len
+      ),
+    }
+  }
+}
+
+impl std::error::Error for ClipError {}
+
+// Something suspicious about a boolean operation that did not prevent it from
+// being computed. These are only collected if
+// `BooleanOptions::collect_warnings` is set.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ClipWarning {
+  // An edge of one of the inputs has zero length (its end points are the
+  // same), so it was ignored.
+  DegenerateEdge {
+    // The edge.
+    source_edge: SourceEdge,
+  },
+  // Vertices of the inputs were moved by `BooleanOptions::weld_tolerance` or
+  // `BooleanOptions::boundary_tolerance`, so the result is computed from
+  // slightly different inputs.
+  VerticesSnapped {
+    // The number of vertices that moved.
+    count: usize,
+  },
+  // Rounding errors made the sweep inconsistent, so it was recomputed with
+  // more precision (see `BooleanResult::precision_escalated`).
+  PrecisionEscalated,
+  // The boundaries of the subject and clip coincide, which is where rounding
+  // errors are most likely to produce slivers.
+  CoincidentEdges {
+    // The number of result edges where the subject and clip coincide, plus
+    // the number of overlap segments (coincident boundaries that are not in
+    // the result, see `BooleanResult::overlap_segments`).
+    count: usize,
+  },
+  // Contours that collapsed to fewer than 3 vertices after collapsing
+  // duplicate vertices (see `BooleanOptions::duplicate_vertex_tolerance`) were
+  // removed.
+  CollapsedContoursRemoved {
+    // The number of removed contours.
+    count: usize,
+  },
+  // Sliver contours with an area below `BooleanOptions::min_contour_area`
+  // were removed.
+  SliverContoursRemoved {
+    // The number of removed contours.
+    count: usize,
+  },
+}
+
+impl fmt::Display for ClipWarning {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ClipWarning::DegenerateEdge { source_edge } => write!(
+        f,
+        "edge {} of contour {} of the {} polygon has zero length",
+        source_edge.edge,
+        source_edge.contour,
+        if source_edge.is_from_subject { "subject" } else { "clip" }
+      ),
+      ClipWarning::VerticesSnapped { count } => {
+        write!(f, "{} input vertices were snapped", count)
+      }
+      ClipWarning::PrecisionEscalated => write!(
+        f,
+        "the sweep became inconsistent due to rounding errors and was \
+         recomputed with more precision"
+      ),
+      ClipWarning::CoincidentEdges { count } => write!(
+        f,
+        "the subject and clip boundaries coincide in {} places",
+        count
+      ),
+      ClipWarning::CollapsedContoursRemoved { count } => {
+        write!(f, "{} collapsed contours were removed", count)
+      }
+      ClipWarning::SliverContoursRemoved { count } => {
+        write!(f, "{} sliver contours were removed", count)
+      }
+    }
+  }
+}
@@ -0,0 +1,216 @@
+use std::collections::BinaryHeap;
+
+use crate::{
+  create_events_for_polygon, sweep_events, Arithmetic, EdgeCoincidenceType,
+  Operation, Polygon,
+};
+
+// The subdivided edges of a subject and clip polygon, along with the labels
+// needed to select the edges of any boolean operation. The data is stored in
+// flat buffers so it can be uploaded directly to the GPU (or any other
+// consumer) without this crate depending on a GPU API.
+//
+// The contract: every edge of both polygons is split at every intersection, so
+// edges only ever meet at their end points (or overlap exactly, see
+// `SAME_TRANSITION`, `DIFFERENT_TRANSITION`, and `DUPLICATE`). Each edge is
+// then in the result of an operation according to these rules (applied in
+// order):
+//
+// * `DUPLICATE` edges are never in the result.
+// * `SAME_TRANSITION` edges are only in the result of intersection and union.
+// * `DIFFERENT_TRANSITION` edges are only in the result of difference.
+// * For intersection, edges without `OTHER_IN_OUT` are in the result.
+// * For union, edges with `OTHER_IN_OUT` are in the result.
+// * For difference, subject edges with `OTHER_IN_OUT` and clip edges without
+//   `OTHER_IN_OUT` are in the result.
+// * For XOR, all edges are in the result.
+//
+// The selected edges form closed loops which can be stitched together by
+// matching end points.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct EdgeSoup {
+  // The end points of each edge as `[left.x, left.y, right.x, right.y]`. The
+  // left point has the lower x coordinate (or the lower y coordinate for
+  // vertical edges).
+  pub edges: Vec<[f32; 4]>,
+  // The labels of each edge. This is a combination of the flags below and has
+  // one entry per edge in `edges`.
+  pub labels: Vec<u32>,
+}
+
+impl EdgeSoup {
+  // The edge comes from the subject polygon (otherwise, the clip polygon).
+  pub const FROM_SUBJECT: u32 = 1 << 0;
+  // The edge is an inside-outside transition of its own polygon. In other
+  // words, the region just above the edge is outside the polygon the edge comes
+  // from.
+  pub const IN_OUT: u32 = 1 << 1;
+  // The edge is outside the other polygon (the clip for subject edges, and the
+  // subject for clip edges).
+  pub const OTHER_IN_OUT: u32 = 1 << 2;
+  // The edge overlaps an edge of the other polygon with the same in-out
+  // transition. Only one edge of the overlapping pair has this flag.
+  pub const SAME_TRANSITION: u32 = 1 << 3;
+  // The edge overlaps an edge of the other polygon with a different in-out
+  // transition. Only one edge of the overlapping pair has this flag.
+  pub const DIFFERENT_TRANSITION: u32 = 1 << 4;
+  // The edge overlaps an edge of the other polygon and that edge represents
+  // both (it has `SAME_TRANSITION` or `DIFFERENT_TRANSITION`).
+  pub const DUPLICATE: u32 = 1 << 5;
+}
+
+// Subdivides the edges of `subject` and `clip` and exports them with their
+// labels, without selecting the edges for any particular operation. See
+// `EdgeSoup` for how to use the labels.
+pub fn export_edge_soup(subject: &Polygon, clip: &Polygon) -> EdgeSoup {
+  let mut event_queue = BinaryHeap::new();
+  let mut event_relations = Vec::new();
+  create_events_for_polygon(
+    subject,
+    /* is_subject= */ true,
+    Arithmetic::F32,
+    &mut event_queue,
+    &mut event_relations,
+    f32::INFINITY,
+  );
+  create_events_for_polygon(
+    clip,
+    /* is_subject= */ false,
+    Arithmetic::F32,
+    &mut event_queue,
+    &mut event_relations,
+    f32::INFINITY,
+  );
+
+  // XOR does not skip any events, and the labels we export do not depend on
+  // the operation.
+  let processed_events = sweep_events(
+    event_queue,
+    &mut event_relations,
+    &Operation::XOR,
+    f32::INFINITY,
+    /* max_events= */ usize::MAX,
+  )
+  .unwrap_or_else(|error| panic!("{}", error.into_clip_error(usize::MAX)));
+
+  let mut edge_soup = EdgeSoup::default();
+  for event in processed_events.iter().filter(|event| event.left) {
+    let relation = &event_relations[event.event_id];
+    edge_soup.edges.push([
+      event.point.x,
+      event.point.y,
+      relation.sibling_point.x,
+      relation.sibling_point.y,
+    ]);
+
+    let mut label = 0;
+    if event.is_subject {
+      label |= EdgeSoup::FROM_SUBJECT;
+    }
+    if relation.in_out {
+      label |= EdgeSoup::IN_OUT;
+    }
+    if relation.other_in_out {
+      label |= EdgeSoup::OTHER_IN_OUT;
+    }
+    label |= match relation.edge_coincidence_type {
+      EdgeCoincidenceType::NoCoincidence => 0,
+      EdgeCoincidenceType::SameTransition => EdgeSoup::SAME_TRANSITION,
+      EdgeCoincidenceType::DifferentTransition => {
+        EdgeSoup::DIFFERENT_TRANSITION
+      }
+      EdgeCoincidenceType::DuplicateCoincidence => EdgeSoup::DUPLICATE,
+    };
+    edge_soup.labels.push(label);
+  }
+  edge_soup
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::{
+    difference, export_edge_soup, intersection, union, xor, EdgeSoup,
+    Operation, Polygon,
+  };
+
+  // Determines whether an edge with `label` is in the result of `operation`,
+  // following the contract documented on `EdgeSoup`.
+  fn in_result(label: u32, operation: Operation) -> bool {
+    let from_subject = label & EdgeSoup::FROM_SUBJECT != 0;
+    let other_in_out = label & EdgeSoup::OTHER_IN_OUT != 0;
+    if label & EdgeSoup::DUPLICATE != 0 {
+      return false;
+    }
+    if label & EdgeSoup::SAME_TRANSITION != 0 {
+      return operation == Operation::Intersection
+        || operation == Operation::Union;
+    }
+    if label & EdgeSoup::DIFFERENT_TRANSITION != 0 {
+      return operation == Operation::Difference;
+    }
+    match operation {
+      Operation::Intersection => !other_in_out,
+      Operation::Union => other_in_out,
+      Operation::Difference => from_subject == other_in_out,
+      Operation::XOR => true,
+    }
+  }
+
+  // Checks that selecting edges from the edge soup of `subject` and `clip`
+  // produces the same number of edges as each boolean operation.
+  fn check_edge_soup_selection(subject: &Polygon, clip: &Polygon) {
+    let edge_soup = export_edge_soup(subject, clip);
+    assert_eq!(edge_soup.edges.len(), edge_soup.labels.len());
+
+    for (operation, result) in [
+      (Operation::Intersection, intersection(subject, clip)),
+      (Operation::Union, union(subject, clip)),
+      (Operation::Difference, difference(subject, clip)),
+      (Operation::XOR, xor(subject, clip)),
+    ] {
+      let selected_edges = edge_soup
+        .labels
+        .iter()
+        .filter(|&&label| in_result(label, operation))
+        .count();
+      let result_edges =
+        result.polygon.contours.iter().map(|contour| contour.len()).sum();
+      assert_eq!(selected_edges, result_edges);
+    }
+  }
+
+  #[test]
+  fn edge_soup_labels_select_operation_edges() {
+    let subject = Polygon {
+      contours: vec![vec![
+        Vec2::new(1.0, 1.0),
+        Vec2::new(3.0, 1.0),
+        Vec2::new(3.0, 3.0),
+        Vec2::new(1.0, 3.0),
+      ]],
+    };
+    // Crosses the top and right edges of the subject.
+    let crossing_clip = Polygon {
+      contours: vec![vec![
+        Vec2::new(2.0, 2.0),
+        Vec2::new(4.0, 2.0),
+        Vec2::new(4.0, 4.0),
+        Vec2::new(2.0, 4.0),
+      ]],
+    };
+    // Partially shares the bottom and left edges of the subject.
+    let coincident_clip = Polygon {
+      contours: vec![vec![
+        Vec2::new(1.0, 1.0),
+        Vec2::new(2.0, 1.0),
+        Vec2::new(2.0, 2.0),
+        Vec2::new(1.0, 2.0),
+      ]],
+    };
+
+    check_edge_soup_selection(&subject, &crossing_clip);
+    check_edge_soup_selection(&subject, &coincident_clip);
+  }
+}
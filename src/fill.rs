@@ -0,0 +1,598 @@
+use std::{
+  cmp::Reverse,
+  collections::{BTreeMap, BinaryHeap, HashSet},
+};
+
+use glam::Vec2;
+
+use crate::{
+  create_events_for_edge, finish_boolean, split_polygon_edges,
+  sweep_event_queue, sweep_with_f64_retry, validate_input, Arithmetic,
+  BooleanOptions, BooleanResult, ClipError, Event, EventRelation, Operation,
+  Polygon, SourceEdge, SweepError,
+};
+
+// The rule deciding which points are inside a polygon whose contours overlap
+// each other or themselves.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum FillRule {
+  // Points are inside if the contours cross any ray from them an odd number of
+  // times. This is the rule boolean operations use.
+  #[default]
+  EvenOdd,
+  // Points are inside if the contours wind around them a non-zero number of
+  // times (counting counter-clockwise turns as positive and clockwise turns as
+  // negative). Font outlines and SVG paths commonly use this rule.
+  NonZero,
+  // Points are inside if the contours wind around them a positive number of
+  // times, so clockwise contours cut holes out of counter-clockwise ones
+  // however they are nested.
+  Positive,
+  // Points are inside if the contours wind around them a negative number of
+  // times (the opposite of `Positive`).
+  Negative,
+}
+
+impl FillRule {
+  // Whether a point that the contours wind around `winding` times is inside.
+  fn is_inside(self, winding: i32) -> bool {
+    match self {
+      FillRule::EvenOdd => winding % 2 != 0,
+      FillRule::NonZero => winding != 0,
+      FillRule::Positive => winding > 0,
+      FillRule::Negative => winding < 0,
+    }
+  }
+}
+
+impl Polygon {
+  // Converts a soup of contours (which may overlap, self-intersect, be nested
+  // and have any winding) into non-overlapping shells and holes covering the
+  // area inside the polygon according to `fill_rule`. Shells are
+  // counter-clockwise and holes are clockwise. This panics if the polygon
+  // cannot be used in a boolean operation (see `try_normalize`).
+  pub fn normalize(&self, fill_rule: FillRule) -> Polygon {
+    self.try_normalize(fill_rule).unwrap_or_else(|error| panic!("{error}"))
+  }
+
+  // Same as `normalize`, but returns an error instead of panicking. Finding
+  // the winding numbers compares every split edge with every other split edge
+  // spanning it, so this can take O(n^2) time for n edges.
+  pub fn try_normalize(
+    &self,
+    fill_rule: FillRule,
+  ) -> Result<Polygon, ClipError> {
+    validate_input(self, &Polygon::default())?;
+    Ok(fill_polygon(self, fill_rule)?.polygon)
+  }
+}
+
+// Resolves the contours of `polygon` into non-overlapping shells and holes
+// according to `fill_rule` (see `Polygon::normalize`). The source edges of the
+// result refer to the edges of `polygon`. `polygon` must be valid (see
+// `validate_input`).
+fn fill_polygon(
+  polygon: &Polygon,
+  fill_rule: FillRule,
+) -> Result<BooleanResult, ClipError> {
+  let result = fill_contours(
+    polygon,
+    &vec![0; polygon.contours.len()],
+    &[fill_rule],
+    |coverage| coverage.count > 0,
+  )?;
+  Ok(finish_boolean(
+    result,
+    polygon,
+    &Polygon::default(),
+    &BooleanOptions::default(),
+  ))
+}
+
+// How many of the polygons combined by `fill_contours` contain a point.
+#[derive(Clone, Copy)]
+pub(crate) struct Coverage {
+  // The number of polygons containing the point.
+  pub(crate) count: usize,
+  // Whether the first polygon contains the point.
+  pub(crate) in_first: bool,
+  // The polygons containing the point as a bitmask (only for the first 64
+  // polygons).
+  pub(crate) mask: u64,
+}
+
+// Combines several polygons whose contours are all in `polygon`, where
+// `contour_polygons` is the index of the polygon each contour belongs to. Each
+// polygon contains the points inside it according to its rule in `fill_rules`
+// (which has one rule per polygon), and the result covers the points whose
+// `Coverage` satisfies `is_inside`. The edges of all the polygons are split in
+// a single sweep, and the source edges of the result refer to the edges of
+// `polygon`.
+// If rounding errors make the sweeps inconsistent, they are recomputed in f64
+// (like `sweep_with_precision_fallback`). The result still has to be finished
+// (see `finish_boolean`). `polygon` must be valid (see `validate_input`).
+pub(crate) fn fill_contours(
+  polygon: &Polygon,
+  contour_polygons: &[usize],
+  fill_rules: &[FillRule],
+  is_inside: impl Fn(Coverage) -> bool,
+) -> Result<BooleanResult, ClipError> {
+  let Some(bounds) = polygon.compute_bounds() else {
+    return Ok(BooleanResult::default());
+  };
+  let (mut result, precision_escalated) = sweep_with_f64_retry(|arithmetic| {
+    sweep_filled_contours(
+      polygon,
+      bounds,
+      contour_polygons,
+      fill_rules,
+      &is_inside,
+      arithmetic,
+    )
+  })
+  .map_err(|error| error.into_clip_error(usize::MAX))?;
+  result.precision_escalated = precision_escalated;
+  Ok(result)
+}
+
+// Splits the arrangement of several polygons (like `fill_contours`) into the
+// regions covered by each set of polygons. Returns the region of each
+// non-empty set along with the bitmask of the polygons in it (see
+// `Coverage::mask`), ordered by mask. Each region still has to be finished
+// (see `finish_boolean`). `polygon` must be valid (see `validate_input`).
+pub(crate) fn overlay_contours(
+  polygon: &Polygon,
+  contour_polygons: &[usize],
+  fill_rules: &[FillRule],
+) -> Result<Vec<(u64, BooleanResult)>, ClipError> {
+  let Some(bounds) = polygon.compute_bounds() else {
+    return Ok(Vec::new());
+  };
+  let (mut regions, precision_escalated) = sweep_with_f64_retry(|arithmetic| {
+    sweep_overlay_contours(
+      polygon,
+      bounds,
+      contour_polygons,
+      fill_rules,
+      arithmetic,
+    )
+  })
+  .map_err(|error| error.into_clip_error(usize::MAX))?;
+  for (_, result) in regions.iter_mut() {
+    result.precision_escalated = precision_escalated;
+  }
+  Ok(regions)
+}
+
+// Sweeps the contours for `fill_contours` with `arithmetic`. `bounds` are the
+// bounds of `polygon`.
+fn sweep_filled_contours(
+  polygon: &Polygon,
+  bounds: (Vec2, Vec2),
+  contour_polygons: &[usize],
+  fill_rules: &[FillRule],
+  is_inside: impl Fn(Coverage) -> bool,
+  arithmetic: Arithmetic,
+) -> Result<BooleanResult, SweepError> {
+  // Keep the edges where the fill changes, oriented with the inside on their
+  // left.
+  let split_edges =
+    oriented_split_edges(polygon, contour_polygons, arithmetic)?;
+  let mut windings = Windings::new(fill_rules.len());
+  let mut event_queue = BinaryHeap::new();
+  let mut event_relations = Vec::new();
+  for &(start, end, _, source_edge) in unique_edges(&split_edges) {
+    let (coverage_above, coverage_below) =
+      edge_coverage(&split_edges, start, end, &mut windings, fill_rules);
+    let inside_above = is_inside(coverage_above);
+    if inside_above == is_inside(coverage_below) {
+      continue;
+    }
+    let (start, end) = orient_edge(start, end, inside_above);
+    if let Some(events) = create_events_for_edge(
+      start,
+      end,
+      source_edge,
+      arithmetic,
+      &mut event_relations,
+    ) {
+      event_queue.extend(events.map(Reverse));
+    }
+  }
+  join_filled_edges(event_queue, event_relations, bounds)
+}
+
+// Sweeps the contours for `overlay_contours` with `arithmetic`. `bounds` are
+// the bounds of `polygon`.
+fn sweep_overlay_contours(
+  polygon: &Polygon,
+  bounds: (Vec2, Vec2),
+  contour_polygons: &[usize],
+  fill_rules: &[FillRule],
+  arithmetic: Arithmetic,
+) -> Result<Vec<(u64, BooleanResult)>, SweepError> {
+  // An edge bounds the regions on both of its sides, unless the same polygons
+  // cover both of them.
+  let split_edges =
+    oriented_split_edges(polygon, contour_polygons, arithmetic)?;
+  let mut windings = Windings::new(fill_rules.len());
+  let mut region_edges = BTreeMap::<u64, (BinaryHeap<_>, Vec<_>)>::new();
+  for &(start, end, _, source_edge) in unique_edges(&split_edges) {
+    let (coverage_above, coverage_below) =
+      edge_coverage(&split_edges, start, end, &mut windings, fill_rules);
+    if coverage_above.mask == coverage_below.mask {
+      continue;
+    }
+    for (mask, inside_above) in
+      [(coverage_above.mask, true), (coverage_below.mask, false)]
+    {
+      if mask == 0 {
+        continue;
+      }
+      let (start, end) = orient_edge(start, end, inside_above);
+      let (event_queue, event_relations) =
+        region_edges.entry(mask).or_default();
+      if let Some(events) = create_events_for_edge(
+        start,
+        end,
+        source_edge,
+        arithmetic,
+        event_relations,
+      ) {
+        event_queue.extend(events.map(Reverse));
+      }
+    }
+  }
+  region_edges
+    .into_iter()
+    .map(|(mask, (event_queue, event_relations))| {
+      Ok((mask, join_filled_edges(event_queue, event_relations, bounds)?))
+    })
+    .collect()
+}
+
+// Splits the edges of `polygon` wherever they intersect, so the winding
+// numbers only change across edges, and orients each edge like its source
+// edge. Each split edge comes with the polygon its contour belongs to (see
+// `contour_polygons`). The sweep is computed with `arithmetic`.
+fn oriented_split_edges(
+  polygon: &Polygon,
+  contour_polygons: &[usize],
+  arithmetic: Arithmetic,
+) -> Result<Vec<(Vec2, Vec2, usize, SourceEdge)>, SweepError> {
+  Ok(
+    split_polygon_edges(polygon, /* is_subject= */ true, arithmetic)?
+      .into_iter()
+      .map(|(left_point, right_point, source_edge)| {
+        let contour = &polygon.contours[source_edge.contour];
+        let source_direction = contour[(source_edge.edge + 1) % contour.len()]
+          - contour[source_edge.edge];
+        let polygon_index = contour_polygons[source_edge.contour];
+        if source_direction.dot(right_point - left_point) >= 0.0 {
+          (left_point, right_point, polygon_index, source_edge)
+        } else {
+          (right_point, left_point, polygon_index, source_edge)
+        }
+      })
+      .collect(),
+  )
+}
+
+// The split edges with coincident edges removed. Coincident edges all change
+// the winding numbers (see `edge_coverage`), but only one of them is kept.
+fn unique_edges(
+  split_edges: &[(Vec2, Vec2, usize, SourceEdge)],
+) -> impl Iterator<Item = &(Vec2, Vec2, usize, SourceEdge)> {
+  let mut visited_edges = HashSet::new();
+  split_edges.iter().filter(move |&&(start, end, _, _)| {
+    let key = if start.x < end.x || (start.x == end.x && start.y < end.y) {
+      (start.to_array().map(f32::to_bits), end.to_array().map(f32::to_bits))
+    } else {
+      (end.to_array().map(f32::to_bits), start.to_array().map(f32::to_bits))
+    };
+    visited_edges.insert(key)
+  })
+}
+
+// Sweeps the edges in `event_queue` (which enclose each region exactly once,
+// with the inside on their left) to join them into contours.
+fn join_filled_edges(
+  event_queue: BinaryHeap<Reverse<Event>>,
+  event_relations: Vec<EventRelation>,
+  bounds: (Vec2, Vec2),
+) -> Result<BooleanResult, SweepError> {
+  sweep_event_queue(
+    event_queue,
+    event_relations,
+    bounds,
+    bounds,
+    &Operation::Union,
+    f32::INFINITY,
+    &BooleanOptions::default(),
+  )
+}
+
+// The subject and clip polygons after resolving their contours with a
+// `FillRule`. This stores how to map edges back to the original polygons.
+pub(crate) struct FilledPolygons {
+  // The resolved subject, with source edges referring to the original subject.
+  subject: BooleanResult,
+  // The resolved clip, with source edges referring to the original clip.
+  clip: BooleanResult,
+}
+
+impl FilledPolygons {
+  // Resolves the contours of `subject` according to `subject_fill_rule` and
+  // the contours of `clip` according to `clip_fill_rule`. Both polygons must
+  // be valid (see `validate_input`).
+  pub(crate) fn new(
+    subject: &Polygon,
+    clip: &Polygon,
+    subject_fill_rule: FillRule,
+    clip_fill_rule: FillRule,
+  ) -> Result<Self, ClipError> {
+    Ok(Self {
+      subject: fill_polygon(subject, subject_fill_rule)?,
+      clip: fill_polygon(clip, clip_fill_rule)?,
+    })
+  }
+
+  // The resolved subject polygon.
+  pub(crate) fn subject(&self) -> &Polygon {
+    &self.subject.polygon
+  }
+
+  // The resolved clip polygon.
+  pub(crate) fn clip(&self) -> &Polygon {
+    &self.clip.polygon
+  }
+
+  // Updates the source edges of `result` (computed from the resolved
+  // polygons) to refer to the edges of the original polygons.
+  pub(crate) fn remap_source_edges(&self, result: &mut BooleanResult) {
+    for source_edge in result.contour_source_edges.iter_mut().flatten().chain(
+      result.contour_coincident_source_edges.iter_mut().flatten().flatten(),
+    ) {
+      self.remap_source_edge(source_edge);
+    }
+  }
+
+  // Updates `source_edge` (an edge of the resolved polygons) to refer to the
+  // edge of the original polygons.
+  pub(crate) fn remap_source_edge(&self, source_edge: &mut SourceEdge) {
+    let filled =
+      if source_edge.is_from_subject { &self.subject } else { &self.clip };
+    let original =
+      filled.contour_source_edges[source_edge.contour][source_edge.edge];
+    source_edge.contour = original.contour;
+    source_edge.edge = original.edge;
+  }
+}
+
+// The winding numbers of each polygon around the points just above and just
+// below a split edge. Only the polygons with edges above or along the split
+// edge are touched, so resetting is cheap even for many polygons.
+struct Windings {
+  // The winding number of each polygon around the point above the edge.
+  above: Vec<i32>,
+  // The change of the winding number of each polygon from above the edge to
+  // below it.
+  change: Vec<i32>,
+  // The polygons whose winding numbers were touched.
+  touched: Vec<usize>,
+}
+
+impl Windings {
+  fn new(polygon_count: usize) -> Self {
+    Self {
+      above: vec![0; polygon_count],
+      change: vec![0; polygon_count],
+      touched: Vec::new(),
+    }
+  }
+
+  // Determines the coverage above and below the edge according to the rule of
+  // each polygon in `fill_rules`, and resets the winding numbers.
+  fn take_coverage(&mut self, fill_rules: &[FillRule]) -> (Coverage, Coverage) {
+    self.touched.sort_unstable();
+    self.touched.dedup();
+    let mut above = Coverage { count: 0, in_first: false, mask: 0 };
+    let mut below = above;
+    for &polygon_index in self.touched.iter() {
+      let winding_above = std::mem::take(&mut self.above[polygon_index]);
+      let winding_below =
+        winding_above + std::mem::take(&mut self.change[polygon_index]);
+      for (coverage, winding) in
+        [(&mut above, winding_above), (&mut below, winding_below)]
+      {
+        if fill_rules[polygon_index].is_inside(winding) {
+          coverage.count += 1;
+          coverage.in_first |= polygon_index == 0;
+          if polygon_index < 64 {
+            coverage.mask |= 1 << polygon_index;
+          }
+        }
+      }
+    }
+    self.touched.clear();
+    (above, below)
+  }
+}
+
+// Determines the coverage above and below the split edge from `start` to `end`.
+// `split_edges` are all the (oriented) split edges along with the polygon they
+// belong to.
+fn edge_coverage(
+  split_edges: &[(Vec2, Vec2, usize, SourceEdge)],
+  start: Vec2,
+  end: Vec2,
+  windings: &mut Windings,
+  fill_rules: &[FillRule],
+) -> (Coverage, Coverage) {
+  // Vertical edges are rotated a quarter turn so a vertical ray can be cast
+  // from them as well. Rotating keeps the winding numbers the same.
+  let frame = if start.x == end.x {
+    |point: Vec2| Vec2::new(point.y, -point.x)
+  } else {
+    |point: Vec2| point
+  };
+  let is_coincident = |(other_start, other_end): (Vec2, Vec2)| {
+    (other_start == start && other_end == end)
+      || (other_start == end && other_end == start)
+  };
+
+  // Count the windings around a point just above the middle of the edge by
+  // casting a ray upwards from the middle. Edges pointing left wind
+  // counter-clockwise around the points below them.
+  let middle = frame((start + end) * 0.5);
+  for &(other_start, other_end, polygon_index, _) in split_edges.iter() {
+    let winding =
+      if frame(other_end).x < frame(other_start).x { 1 } else { -1 };
+    if is_coincident((other_start, other_end)) {
+      // The points below the edge are wound around by the edge as well.
+      windings.change[polygon_index] += winding;
+      windings.touched.push(polygon_index);
+      continue;
+    }
+    let (a, b) = (frame(other_start), frame(other_end));
+    let (left, right) = if a.x < b.x { (a, b) } else { (b, a) };
+    // Half-open, so rays through a vertex count one of its edges.
+    if !(left.x <= middle.x && middle.x < right.x) {
+      continue;
+    }
+    let y =
+      left.y + (right.y - left.y) * (middle.x - left.x) / (right.x - left.x);
+    if middle.y < y {
+      windings.above[polygon_index] += winding;
+      windings.touched.push(polygon_index);
+    }
+  }
+
+  windings.take_coverage(fill_rules)
+}
+
+// Orients the split edge from `start` to `end` with the inside on its left,
+// where the inside is above the edge if `inside_above` (or to the left of a
+// vertical edge, like in `edge_coverage`).
+fn orient_edge(start: Vec2, end: Vec2, inside_above: bool) -> (Vec2, Vec2) {
+  // An edge pointing right (rotated like in `edge_coverage`) has the region
+  // above it on its left.
+  let points_right =
+    if start.x == end.x { start.y < end.y } else { start.x < end.x };
+  if points_right == inside_above {
+    (start, end)
+  } else {
+    (end, start)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square;
+  use crate::{util::contour_signed_area, FillRule, Polygon};
+
+  fn reversed(mut contour: Vec<Vec2>) -> Vec<Vec2> {
+    contour.reverse();
+    contour
+  }
+
+  // The signed area of each contour of `polygon`, sorted.
+  fn contour_areas(polygon: &Polygon) -> Vec<f32> {
+    let mut areas = polygon
+      .contours
+      .iter()
+      .map(|contour| contour_signed_area(contour))
+      .collect::<Vec<_>>();
+    areas.sort_by(f32::total_cmp);
+    areas
+  }
+
+  #[test]
+  fn overlapping_contours_with_same_winding() {
+    let polygon = Polygon {
+      contours: vec![
+        square(Vec2::new(0.0, 0.0), 2.0),
+        square(Vec2::new(1.0, 1.0), 2.0),
+      ],
+    };
+
+    // The overlap is wound twice, so it is only filled with non-zero.
+    assert_eq!(contour_areas(&polygon.normalize(FillRule::NonZero)), [7.0]);
+    assert_eq!(
+      contour_areas(&polygon.normalize(FillRule::EvenOdd)),
+      [3.0, 3.0]
+    );
+  }
+
+  #[test]
+  fn nested_contours_depend_on_winding() {
+    let outer = square(Vec2::new(0.0, 0.0), 3.0);
+    let inner = square(Vec2::new(1.0, 1.0), 1.0);
+
+    let same_winding = Polygon { contours: vec![outer.clone(), inner.clone()] };
+    assert_eq!(
+      contour_areas(&same_winding.normalize(FillRule::NonZero)),
+      [9.0]
+    );
+    assert_eq!(
+      contour_areas(&same_winding.normalize(FillRule::EvenOdd)),
+      [-1.0, 9.0]
+    );
+
+    // Holes come out clockwise, regardless of the input orientation.
+    let opposite_winding = Polygon { contours: vec![reversed(outer), inner] };
+    assert_eq!(
+      contour_areas(&opposite_winding.normalize(FillRule::NonZero)),
+      [-1.0, 9.0]
+    );
+  }
+
+  #[test]
+  fn signed_rules_depend_on_orientation() {
+    // A counter-clockwise square overlapping a clockwise square.
+    let polygon = Polygon {
+      contours: vec![
+        square(Vec2::new(0.0, 0.0), 2.0),
+        reversed(square(Vec2::new(1.0, 1.0), 2.0)),
+      ],
+    };
+
+    assert_eq!(contour_areas(&polygon.normalize(FillRule::Positive)), [3.0]);
+    assert_eq!(contour_areas(&polygon.normalize(FillRule::Negative)), [3.0]);
+    assert_eq!(
+      contour_areas(&polygon.normalize(FillRule::NonZero)),
+      [3.0, 3.0]
+    );
+    // The positive part is the counter-clockwise square without the overlap.
+    assert_eq!(
+      polygon.normalize(FillRule::Positive).compute_bounds(),
+      Some((Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0)))
+    );
+    assert_eq!(
+      polygon.normalize(FillRule::Negative).compute_bounds(),
+      Some((Vec2::new(1.0, 1.0), Vec2::new(3.0, 3.0)))
+    );
+  }
+
+  #[test]
+  fn duplicated_contour() {
+    let contour = square(Vec2::new(0.0, 0.0), 1.0);
+    let polygon = Polygon { contours: vec![contour.clone(), contour.clone()] };
+    assert_eq!(
+      polygon.normalize(FillRule::NonZero),
+      Polygon { contours: vec![contour] }
+    );
+    assert_eq!(polygon.normalize(FillRule::EvenOdd), Polygon::default());
+
+    // Opposite windings cancel out.
+    let polygon = Polygon {
+      contours: vec![
+        square(Vec2::new(0.0, 0.0), 1.0),
+        reversed(square(Vec2::new(0.0, 0.0), 1.0)),
+      ],
+    };
+    assert_eq!(polygon.normalize(FillRule::NonZero), Polygon::default());
+  }
+}
@@ -0,0 +1,425 @@
+use std::cmp::Ordering;
+
+use glam::{Affine2, Vec2};
+
+use crate::{
+  point_relative_to_line, try_contains, util::point_in_contour, validate_input,
+  ClipError, Polygon,
+};
+
+// A container polygon that has been prepared for testing whether many pieces
+// fit inside it (e.g. for nesting/packing searches). Preparing the container
+// buckets its edges into a uniform grid, so each test only compares the edges
+// of the piece with the container edges near them.
+#[derive(Clone, Debug)]
+pub struct PreparedContainer {
+  // The original container polygon.
+  container: Polygon,
+  // The bounds of `container`, or None if it has no vertices.
+  bounds: Option<(Vec2, Vec2)>,
+  // The edges of `container` as `(start, end)`.
+  edges: Vec<(Vec2, Vec2)>,
+  // The number of grid cells along each axis.
+  grid_size: usize,
+  // The indices of the edges overlapping each grid cell, in row-major order.
+  cells: Vec<Vec<usize>>,
+}
+
+impl PreparedContainer {
+  // Prepares `container`. This panics if `container` cannot be used in a
+  // boolean operation (see `try_new`).
+  pub fn new(container: &Polygon) -> Self {
+    Self::try_new(container).unwrap_or_else(|error| panic!("{error}"))
+  }
+
+  // Prepares `container`, returning an error if it cannot be used in a boolean
+  // operation (e.g. it contains non-finite coordinates).
+  pub fn try_new(container: &Polygon) -> Result<Self, ClipError> {
+    validate_input(&Polygon::default(), container)?;
+    let edges = container
+      .iter_edges()
+      .map(|(_, _, start, end)| (start, end))
+      .collect::<Vec<_>>();
+    // About one edge per cell, up to a limit on the memory used by the grid.
+    let grid_size = ((edges.len() as f32).sqrt().ceil() as usize).clamp(1, 256);
+    let mut prepared = Self {
+      container: container.clone(),
+      bounds: container.compute_bounds(),
+      edges,
+      grid_size,
+      cells: vec![Vec::new(); grid_size * grid_size],
+    };
+    for (index, &(start, end)) in prepared.edges.iter().enumerate() {
+      let (min_cell, max_cell) =
+        prepared.cell_range(start.min(end), start.max(end));
+      for y in min_cell.1..=max_cell.1 {
+        for x in min_cell.0..=max_cell.0 {
+          prepared.cells[y * grid_size + x].push(index);
+        }
+      }
+    }
+    Ok(prepared)
+  }
+
+  // The container polygon that was prepared.
+  pub fn container(&self) -> &Polygon {
+    &self.container
+  }
+
+  // Determines whether `piece` (after applying `transform`) fits inside the
+  // container, i.e. the difference of the piece and the container is empty.
+  // Pieces touching the boundary of the container (e.g. sharing an edge or a
+  // vertex with it) still fit, as long as no part of them is outside. This
+  // returns false as soon as the bounds of the piece leave the bounds of the
+  // container or an edge of the piece crosses an edge of the container. Only
+  // pieces that touch the container's boundary without crossing it fall back
+  // to a sweep (see `contains`). This panics if the transformed piece cannot
+  // be used in a boolean operation (see `try_fits`).
+  pub fn fits(&self, piece: &Polygon, transform: Affine2) -> bool {
+    self.try_fits(piece, transform).unwrap_or_else(|error| panic!("{error}"))
+  }
+
+  // Same as `fits`, but returns an error instead of panicking. Errors refer to
+  // the piece as the subject and the container as the clip, like
+  // `try_difference(piece, container)`.
+  pub fn try_fits(
+    &self,
+    piece: &Polygon,
+    transform: Affine2,
+  ) -> Result<bool, ClipError> {
+    let piece = Polygon {
+      contours: piece
+        .contours
+        .iter()
+        .map(|contour| {
+          contour
+            .iter()
+            .map(|&point| transform.transform_point2(point))
+            .collect()
+        })
+        .collect(),
+    };
+    validate_input(&piece, &Polygon::default())?;
+    let Some(piece_bounds) = piece.compute_bounds() else {
+      return Ok(true);
+    };
+    let Some(bounds) = self.bounds else {
+      return Ok(false);
+    };
+    if piece_bounds.0.x < bounds.0.x
+      || piece_bounds.0.y < bounds.0.y
+      || bounds.1.x < piece_bounds.1.x
+      || bounds.1.y < piece_bounds.1.y
+    {
+      return Ok(false);
+    }
+
+    let mut touches_boundary = false;
+    for (_, _, start, end) in piece.iter_edges() {
+      let (min_cell, max_cell) =
+        self.cell_range(start.min(end), start.max(end));
+      for y in min_cell.1..=max_cell.1 {
+        for x in min_cell.0..=max_cell.0 {
+          for &edge_index in self.cells[y * self.grid_size + x].iter() {
+            let (other_start, other_end) = self.edges[edge_index];
+            match classify_contact(start, end, other_start, other_end) {
+              Contact::None => {}
+              Contact::Crossing => return Ok(false),
+              Contact::Touching => touches_boundary = true,
+            }
+          }
+        }
+      }
+    }
+    if touches_boundary {
+      return try_contains(&self.container, &piece).map_err(|mut error| {
+        // The container is the subject of the sweep.
+        if let ClipError::InconsistentSweep { source_edge, .. } = &mut error {
+          source_edge.is_from_subject = !source_edge.is_from_subject;
+        }
+        error
+      });
+    }
+
+    // The boundaries do not meet, so each contour is entirely inside or
+    // outside the other polygon. The piece fits if all of its contours are
+    // inside the container and none of the container's contours (e.g. holes)
+    // are inside the piece.
+    Ok(
+      piece
+        .contours
+        .iter()
+        .filter_map(|contour| contour.first())
+        .all(|&point| polygon_contains(&self.container, point))
+        && self
+          .container
+          .contours
+          .iter()
+          .filter_map(|contour| contour.first())
+          .all(|&point| !polygon_contains(&piece, point)),
+    )
+  }
+
+  // Computes the range of grid cells (inclusive) overlapped by the box from
+  // `min` to `max`. Parts of the box outside the grid are clamped to the edge
+  // cells.
+  fn cell_range(
+    &self,
+    min: Vec2,
+    max: Vec2,
+  ) -> ((usize, usize), (usize, usize)) {
+    let Some((bounds_min, bounds_max)) = self.bounds else {
+      return ((0, 0), (0, 0));
+    };
+    let cell_size = (bounds_max - bounds_min) / self.grid_size as f32;
+    let cell = |point: Vec2| {
+      let cell = ((point - bounds_min) / cell_size).floor();
+      let clamp = |value: f32| {
+        // NaN (from grids with zero size) becomes 0.
+        (value.max(0.0) as usize).min(self.grid_size - 1)
+      };
+      (clamp(cell.x), clamp(cell.y))
+    };
+    (cell(min), cell(max))
+  }
+}
+
+// Determines whether `piece` (after applying `transform`) fits inside
+// `container` (see `PreparedContainer::fits`). This prepares the container for
+// a single test, so prefer `PreparedContainer` when testing many pieces (or
+// placements) against the same container. This panics if either polygon cannot
+// be used in a boolean operation (see `try_fits_inside`).
+pub fn fits_inside(
+  piece: &Polygon,
+  container: &Polygon,
+  transform: Affine2,
+) -> bool {
+  try_fits_inside(piece, container, transform)
+    .unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `fits_inside`, but returns an error instead of panicking.
+pub fn try_fits_inside(
+  piece: &Polygon,
+  container: &Polygon,
+  transform: Affine2,
+) -> Result<bool, ClipError> {
+  PreparedContainer::try_new(container)?.try_fits(piece, transform)
+}
+
+// How two edges meet.
+enum Contact {
+  // The edges do not meet.
+  None,
+  // The edges cross at a point inside both edges.
+  Crossing,
+  // The edges may meet at an end point or overlap.
+  Touching,
+}
+
+// Determines how the edge from `start` to `end` meets the edge from
+// `other_start` to `other_end`.
+fn classify_contact(
+  start: Vec2,
+  end: Vec2,
+  other_start: Vec2,
+  other_end: Vec2,
+) -> Contact {
+  let start_side = point_relative_to_line(other_start, other_end, start);
+  let end_side = point_relative_to_line(other_start, other_end, end);
+  let other_start_side = point_relative_to_line(start, end, other_start);
+  let other_end_side = point_relative_to_line(start, end, other_end);
+  let separated = |a: Ordering, b: Ordering| a == b && a != Ordering::Equal;
+  if separated(start_side, end_side)
+    || separated(other_start_side, other_end_side)
+  {
+    return Contact::None;
+  }
+  if start_side != Ordering::Equal
+    && end_side != Ordering::Equal
+    && other_start_side != Ordering::Equal
+    && other_end_side != Ordering::Equal
+  {
+    return Contact::Crossing;
+  }
+  // Collinear edges only meet if their boxes overlap.
+  let (min, max) = (start.min(end), start.max(end));
+  let (other_min, other_max) =
+    (other_start.min(other_end), other_start.max(other_end));
+  if max.x < other_min.x
+    || max.y < other_min.y
+    || other_max.x < min.x
+    || other_max.y < min.y
+  {
+    Contact::None
+  } else {
+    Contact::Touching
+  }
+}
+
+// Determines whether `point` is inside `polygon` (using the even-odd rule).
+fn polygon_contains(polygon: &Polygon, point: Vec2) -> bool {
+  polygon
+    .contours
+    .iter()
+    .filter(|contour| point_in_contour(point, contour))
+    .count()
+    % 2
+    == 1
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::{Affine2, Vec2};
+
+  use crate::tests::fixtures::rectangle;
+  use crate::{
+    convex_hull, difference, fits_inside, try_fits_inside, ClipError, Polygon,
+    PreparedContainer,
+  };
+
+  #[test]
+  fn fits_around_holes() {
+    // A 10x10 sheet with a hole from (4, 4) to (6, 6).
+    let container = Polygon {
+      contours: vec![
+        rectangle(Vec2::ZERO, Vec2::splat(10.0)),
+        rectangle(Vec2::splat(4.0), Vec2::splat(6.0)),
+      ],
+    };
+    let piece =
+      Polygon { contours: vec![rectangle(Vec2::ZERO, Vec2::new(3.0, 2.0))] };
+    let prepared = PreparedContainer::new(&container);
+
+    let fits = |transform| prepared.fits(&piece, transform);
+    assert!(fits(Affine2::from_translation(Vec2::new(1.0, 1.0))));
+    // Touching the boundary of the sheet (and the hole) still fits.
+    assert!(fits(Affine2::IDENTITY));
+    assert!(fits(Affine2::from_translation(Vec2::new(1.0, 2.0))));
+    // Outside the bounds of the sheet.
+    assert!(!fits(Affine2::from_translation(Vec2::new(8.0, 1.0))));
+    // Crossing into the hole.
+    assert!(!fits(Affine2::from_translation(Vec2::new(3.0, 3.0))));
+    // Rotated a quarter turn, the piece is 2 wide and 3 tall.
+    assert!(fits(Affine2::from_angle_translation(
+      std::f32::consts::FRAC_PI_2,
+      Vec2::new(9.0, 6.5),
+    )));
+
+    // Covering the whole hole without touching it.
+    let large_piece =
+      Polygon { contours: vec![rectangle(Vec2::splat(3.0), Vec2::splat(7.0))] };
+    assert!(!prepared.fits(&large_piece, Affine2::IDENTITY));
+    // A piece with a hole around the container's hole fits.
+    let frame_piece = Polygon {
+      contours: vec![
+        rectangle(Vec2::splat(3.0), Vec2::splat(7.0)),
+        rectangle(Vec2::splat(3.5), Vec2::splat(6.5)),
+      ],
+    };
+    assert!(prepared.fits(&frame_piece, Affine2::IDENTITY));
+  }
+
+  #[test]
+  fn fits_inside_convex_hull() {
+    // Pieces touching the boundary of the container still fit, including a
+    // piece (a hexagon next to two overlapping pentagons) sharing the vertices
+    // of its own convex hull.
+    let piece = Polygon {
+      contours: vec![
+        vec![
+          Vec2::new(4.8319483, 6.6074014),
+          Vec2::new(4.459722, 6.018876),
+          Vec2::new(4.220068, 4.2768865),
+          Vec2::new(6.390216, 3.3070683),
+          Vec2::new(7.2207556, 5.4471307),
+          Vec2::new(6.254247, 7.001663),
+        ],
+        vec![
+          Vec2::new(4.502071, 14.098532),
+          Vec2::new(4.145477, 16.463327),
+          Vec2::new(5.969262, 16.661295),
+          Vec2::new(6.2385583, 15.42746),
+          Vec2::new(5.814107, 14.014476),
+        ],
+        vec![
+          Vec2::new(5.8645077, 13.474894),
+          Vec2::new(6.4443197, 15.405068),
+          Vec2::new(6.0764537, 17.09052),
+          Vec2::new(3.5851138, 16.820091),
+          Vec2::new(4.0722313, 13.589717),
+        ],
+      ],
+    };
+    let hull = Polygon { contours: vec![convex_hull(&piece)] };
+    assert!(fits_inside(&piece, &hull, Affine2::IDENTITY));
+    assert!(!fits_inside(
+      &piece,
+      &hull,
+      Affine2::from_translation(Vec2::new(0.0, 0.001))
+    ));
+  }
+
+  #[test]
+  fn try_fits_inside_returns_errors() {
+    let container =
+      Polygon { contours: vec![rectangle(Vec2::ZERO, Vec2::splat(4.0))] };
+    let piece = Polygon { contours: vec![rectangle(Vec2::ZERO, Vec2::ONE)] };
+    assert_eq!(
+      try_fits_inside(&piece, &container, Affine2::IDENTITY),
+      Ok(true)
+    );
+    assert_eq!(
+      try_fits_inside(
+        &piece,
+        &container,
+        Affine2::from_translation(Vec2::new(f32::NAN, 0.0))
+      ),
+      Err(ClipError::InvalidInput {
+        is_from_subject: true,
+        contour: 0,
+        vertex: 0
+      })
+    );
+    let invalid_container = Polygon {
+      contours: vec![vec![
+        Vec2::ZERO,
+        Vec2::new(f32::INFINITY, 1.0),
+        Vec2::ONE,
+      ]],
+    };
+    assert_eq!(
+      try_fits_inside(&piece, &invalid_container, Affine2::IDENTITY),
+      Err(ClipError::InvalidInput {
+        is_from_subject: false,
+        contour: 0,
+        vertex: 1
+      })
+    );
+  }
+
+  #[test]
+  fn matches_difference() {
+    let container = Polygon {
+      contours: vec![
+        rectangle(Vec2::ZERO, Vec2::splat(8.0)),
+        rectangle(Vec2::splat(2.0), Vec2::splat(4.0)),
+      ],
+    };
+    let piece =
+      Polygon { contours: vec![rectangle(Vec2::ZERO, Vec2::splat(2.0))] };
+    for x in 0..16 {
+      for y in 0..16 {
+        let translation = Vec2::new(x as f32, y as f32) * 0.5 - 1.0;
+        let transform = Affine2::from_translation(translation);
+        let moved_piece =
+          Polygon { contours: vec![rectangle(translation, translation + 2.0)] };
+        assert_eq!(
+          fits_inside(&piece, &container, transform),
+          difference(&moved_piece, &container).is_empty,
+          "translation = {translation}"
+        );
+      }
+    }
+  }
+}
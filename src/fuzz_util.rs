@@ -0,0 +1,218 @@
+// Helpers for fuzzing boolean operations: generators of random polygons and an
+// oracle checking invariants that every result must satisfy. These are
+// enabled by the `fuzz_util` feature.
+
+use std::f32::consts::TAU;
+
+use glam::Vec2;
+use rand::Rng;
+
+use crate::{
+  try_difference, try_intersection, try_union, try_xor,
+  util::contour_signed_area, BooleanResult, Polygon,
+};
+
+// Generates a random simple contour with `vertex_count` vertices (at least
+// 3), within `radius` of `center`. The vertices are spread around `center` at
+// random angles and distances, with less than half a turn between neighbors,
+// so the contour is star-shaped around `center`: it never intersects itself,
+// and scaling it towards `center` nests the scaled contour inside it. It may be
+// clockwise or counter-clockwise.
+pub fn random_simple_contour(
+  rng: &mut impl Rng,
+  vertex_count: usize,
+  center: Vec2,
+  radius: f32,
+) -> Vec<Vec2> {
+  let vertex_count = vertex_count.max(3);
+  let rotation = rng.gen_range(0.0..TAU);
+  let mut contour = (0..vertex_count)
+    .map(|index| {
+      let angle = rotation
+        + (index as f32 + rng.gen_range(0.0..0.4)) * TAU / vertex_count as f32;
+      center + Vec2::from_angle(angle) * rng.gen_range(0.2..=1.0) * radius
+    })
+    .collect::<Vec<_>>();
+  if rng.gen_bool(0.5) {
+    contour.reverse();
+  }
+  contour
+}
+
+// Generates a random simple polygon with up to `max_shells` shells (at least
+// 1), each with up to `max_vertices` vertices, within the box from (0, 0) to
+// (`size`, `size`). Shells are placed in separate cells of a grid, so they
+// never overlap, and may have a hole (a scaled down copy of the shell). The
+// contours may be clockwise or counter-clockwise.
+pub fn random_polygon(
+  rng: &mut impl Rng,
+  max_shells: usize,
+  max_vertices: usize,
+  size: f32,
+) -> Polygon {
+  let shell_count = rng.gen_range(1..=max_shells.max(1));
+  let grid_size = (shell_count as f32).sqrt().ceil() as usize;
+  let cell_size = size / grid_size as f32;
+  let mut contours = Vec::new();
+  for cell in rand::seq::index::sample(rng, grid_size * grid_size, shell_count)
+  {
+    let radius = rng.gen_range(0.25..=0.5) * cell_size;
+    let cell_min =
+      Vec2::new((cell % grid_size) as f32, (cell / grid_size) as f32)
+        * cell_size;
+    let center = cell_min
+      + Vec2::new(
+        rng.gen_range(radius..=cell_size - radius),
+        rng.gen_range(radius..=cell_size - radius),
+      );
+    let vertex_count = rng.gen_range(3..=max_vertices.max(3));
+    let shell = random_simple_contour(rng, vertex_count, center, radius);
+    if rng.gen_bool(0.5) {
+      let scale = rng.gen_range(0.2..0.8);
+      let mut hole = shell
+        .iter()
+        .map(|&point| center + (point - center) * scale)
+        .collect::<Vec<_>>();
+      if rng.gen_bool(0.5) {
+        hole.reverse();
+      }
+      contours.push(hole);
+    }
+    contours.push(shell);
+  }
+  Polygon { contours }
+}
+
+// Computes all four operations of `subject` and `clip` and checks the
+// invariants their results must satisfy:
+//
+// * Contours are closed loops of at least 3 distinct consecutive vertices, with
+//   one source edge per edge.
+// * Contours are simple: no two edges of the result cross (they may only touch
+//   at vertices).
+// * The areas agree: the union is the intersection plus the XOR, and the union
+//   is the intersection plus both differences.
+//
+// Returns a description of the first violated invariant (or the first error
+// returned by an operation). Fuzzers can panic with it, so the failing inputs
+// are reported.
+pub fn check_invariants(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<(), String> {
+  let results = [
+    ("intersection", try_intersection(subject, clip)),
+    ("union", try_union(subject, clip)),
+    ("difference", try_difference(subject, clip)),
+    ("reverse difference", try_difference(clip, subject)),
+    ("XOR", try_xor(subject, clip)),
+  ]
+  .map(|(name, result)| {
+    (name, result.map_err(|error| format!("{name}: {error}")))
+  });
+  for (name, result) in results.iter() {
+    let result = result.as_ref().map_err(Clone::clone)?;
+    check_contours(result).map_err(|error| format!("{name}: {error}"))?;
+  }
+
+  let [intersection_area, union_area, difference_area, reverse_area, xor_area] =
+    results.each_ref().map(|(_, result)| area(result.as_ref().unwrap()));
+  let tolerance = 1e-3 * union_area.abs().max(1e-3);
+  let check_area = |description: &str, actual: f32, expected: f32| {
+    if (actual - expected).abs() <= tolerance {
+      Ok(())
+    } else {
+      Err(format!(
+        "area of {description} is {actual}, but should be {expected}"
+      ))
+    }
+  };
+  check_area("union", union_area, intersection_area + xor_area)?;
+  check_area(
+    "union",
+    union_area,
+    intersection_area + difference_area + reverse_area,
+  )?;
+  Ok(())
+}
+
+// Checks that the contours of `result` are closed and simple.
+fn check_contours(result: &BooleanResult) -> Result<(), String> {
+  if result.contour_source_edges.len() != result.polygon.contours.len() {
+    return Err("source edges do not match the contours".into());
+  }
+  let mut edges = Vec::new();
+  for (index, contour) in result.polygon.contours.iter().enumerate() {
+    if contour.len() < 3 {
+      return Err(format!("contour {index} has fewer than 3 vertices"));
+    }
+    if result.contour_source_edges[index].len() != contour.len() {
+      return Err(format!(
+        "contour {index} has the wrong number of source edges"
+      ));
+    }
+    for vertex in 0..contour.len() {
+      let next_vertex = (vertex + 1) % contour.len();
+      if contour[vertex] == contour[next_vertex] {
+        return Err(format!("contour {index} repeats vertex {vertex}"));
+      }
+      edges.push((contour[vertex], contour[next_vertex]));
+    }
+  }
+
+  for (index, &(start, end)) in edges.iter().enumerate() {
+    for &(other_start, other_end) in edges[index + 1..].iter() {
+      if edges_cross(start, end, other_start, other_end) {
+        return Err(format!(
+          "edges ({start}, {end}) and ({other_start}, {other_end}) cross"
+        ));
+      }
+    }
+  }
+  Ok(())
+}
+
+// Determines whether the edges cross at a point strictly inside both of them.
+fn edges_cross(
+  start: Vec2,
+  end: Vec2,
+  other_start: Vec2,
+  other_end: Vec2,
+) -> bool {
+  let side = |a: Vec2, b: Vec2, point: Vec2| (b - a).perp_dot(point - a);
+  let (start_side, end_side) =
+    (side(other_start, other_end, start), side(other_start, other_end, end));
+  let (other_start_side, other_end_side) =
+    (side(start, end, other_start), side(start, end, other_end));
+  start_side * end_side < 0.0 && other_start_side * other_end_side < 0.0
+}
+
+// Computes the area of `result`, whose shells are counter-clockwise and holes
+// are clockwise (the default orientation).
+fn area(result: &BooleanResult) -> f32 {
+  result
+    .polygon
+    .contours
+    .iter()
+    .map(|contour| contour_signed_area(contour))
+    .sum()
+}
+
+#[cfg(test)]
+mod tests {
+  use rand::{rngs::StdRng, SeedableRng};
+
+  use super::{check_invariants, random_polygon};
+
+  #[test]
+  fn random_polygons_satisfy_invariants() {
+    let mut rng = StdRng::seed_from_u64(3286);
+    for _ in 0..500 {
+      let subject = random_polygon(&mut rng, 3, 8, 100.0);
+      let clip = random_polygon(&mut rng, 3, 8, 100.0);
+      if let Err(error) = check_invariants(&subject, &clip) {
+        panic!("{error}\nsubject = {subject:?}\nclip = {clip:?}");
+      }
+    }
+  }
+}
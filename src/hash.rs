@@ -0,0 +1,130 @@
+use glam::Vec2;
+
+use crate::{BooleanResult, Polygon};
+
+impl Polygon {
+  // Computes a hash of the polygon's geometry that does not depend on which
+  // vertex each contour starts at, nor on the order of the contours. The
+  // orientation of each contour does matter. The hash is computed from the
+  // exact bits of the coordinates (treating -0 and 0 as the same) with a fixed
+  // algorithm (64-bit FNV-1a), so it is the same on every platform and Rust
+  // version. This makes it suitable for detecting nondeterminism across
+  // platforms, or as a cache key for results.
+  pub fn canonical_hash(&self) -> u64 {
+    let mut contour_hashes = self
+      .contours
+      .iter()
+      .map(|contour| {
+        let start = canonical_start(contour);
+        let mut hasher = Fnv1a::new();
+        hasher.write_u64(contour.len() as u64);
+        for &point in contour[start..].iter().chain(contour[..start].iter()) {
+          let (x, y) = point_bits(point);
+          hasher.write_u64(((x as u64) << 32) | y as u64);
+        }
+        hasher.finish()
+      })
+      .collect::<Vec<_>>();
+    contour_hashes.sort_unstable();
+    let mut hasher = Fnv1a::new();
+    hasher.write_u64(contour_hashes.len() as u64);
+    for contour_hash in contour_hashes {
+      hasher.write_u64(contour_hash);
+    }
+    hasher.finish()
+  }
+}
+
+impl BooleanResult {
+  // Computes the canonical hash of the resulting polygon (see
+  // `Polygon::canonical_hash`). The per-edge data is not included.
+  pub fn canonical_hash(&self) -> u64 {
+    self.polygon.canonical_hash()
+  }
+}
+
+// The bits of `point`, treating -0 and 0 as the same.
+fn point_bits(point: Vec2) -> (u32, u32) {
+  let point = point + Vec2::ZERO;
+  (point.x.to_bits(), point.y.to_bits())
+}
+
+// Finds the vertex a canonical rotation of `contour` starts at: the rotation
+// whose sequence of vertex bits is lexicographically smallest.
+fn canonical_start(contour: &[Vec2]) -> usize {
+  let bits = contour.iter().map(|&point| point_bits(point)).collect::<Vec<_>>();
+  let rotation =
+    |start: usize| bits[start..].iter().chain(bits[..start].iter());
+  (0..bits.len()).min_by(|&a, &b| rotation(a).cmp(rotation(b))).unwrap_or(0)
+}
+
+// The 64-bit FNV-1a hash. Unlike `std::hash::DefaultHasher`, its output is
+// specified, so it never changes between Rust versions.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+  fn new() -> Self {
+    Self(0xcbf29ce484222325)
+  }
+
+  fn write_u64(&mut self, value: u64) {
+    for byte in value.to_le_bytes() {
+      self.0 ^= byte as u64;
+      self.0 = self.0.wrapping_mul(0x100000001b3);
+    }
+  }
+
+  fn finish(&self) -> u64 {
+    self.0
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::{union, Polygon};
+
+  fn polygon(contours: &[&[(f32, f32)]]) -> Polygon {
+    Polygon {
+      contours: contours
+        .iter()
+        .map(|contour| contour.iter().map(|&(x, y)| Vec2::new(x, y)).collect())
+        .collect(),
+    }
+  }
+
+  #[test]
+  fn hash_ignores_start_vertex_and_contour_order() {
+    let polygon_1 = polygon(&[
+      &[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)],
+      &[(1.0, 1.0), (1.0, 2.0), (2.0, 1.0)],
+    ]);
+    let polygon_2 = polygon(&[
+      &[(2.0, 1.0), (1.0, 1.0), (1.0, 2.0)],
+      &[(4.0, 4.0), (-0.0, 4.0), (0.0, 0.0), (4.0, 0.0)],
+    ]);
+    assert_eq!(polygon_1.canonical_hash(), polygon_2.canonical_hash());
+    // The algorithm is fixed, so the hash never changes.
+    assert_eq!(polygon_1.canonical_hash(), 11618529702338300209);
+    assert_eq!(
+      union(&polygon_1, &Polygon::default()).canonical_hash(),
+      union(&polygon_2, &Polygon::default()).canonical_hash()
+    );
+  }
+
+  #[test]
+  fn hash_depends_on_geometry() {
+    let square = polygon(&[&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]]);
+    let moved = polygon(&[&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.5), (0.0, 4.0)]]);
+    let reversed =
+      polygon(&[&[(0.0, 0.0), (0.0, 4.0), (4.0, 4.0), (4.0, 0.0)]]);
+    let split =
+      polygon(&[&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0)], &[(0.0, 4.0)]]);
+    let hash = square.canonical_hash();
+    assert_ne!(hash, moved.canonical_hash());
+    assert_ne!(hash, reversed.canonical_hash());
+    assert_ne!(hash, split.canonical_hash());
+    assert_ne!(hash, Polygon::default().canonical_hash());
+  }
+}
@@ -0,0 +1,199 @@
+use glam::Vec2;
+
+use crate::{
+  snap::{non_degenerate_vertices, VertexSnapper},
+  Polygon,
+};
+
+// Closes gaps narrower than `tolerance` between `subject` and `clip`, which are
+// intended to share a border (e.g. neighbouring map tiles). Vertices of both
+// polygons within `tolerance` of each other are merged, and vertices within
+// `tolerance` of an edge of the other polygon are inserted into that edge. The
+// returned polygons then share their borders exactly, so a union of them has
+// no hairline holes or slivers along the seam. Edges that collapse are removed,
+// as are contours that collapse to fewer than 3 vertices.
+pub fn heal_gaps(
+  subject: &Polygon,
+  clip: &Polygon,
+  tolerance: f32,
+) -> (Polygon, Polygon) {
+  let mut snapper = VertexSnapper::new(tolerance);
+  let mut weld = |polygon: &Polygon| {
+    polygon
+      .contours
+      .iter()
+      .map(|contour| {
+        let contour =
+          contour.iter().map(|&point| snapper.snap(point)).collect::<Vec<_>>();
+        non_degenerate_vertices(&contour)
+          .into_iter()
+          .map(|index| contour[index])
+          .collect::<Vec<_>>()
+      })
+      .collect::<Vec<_>>()
+  };
+  let subject = weld(subject);
+  let clip = weld(clip);
+
+  let subject_vertices = SortedVertices::new(&subject);
+  let clip_vertices = SortedVertices::new(&clip);
+  let heal =
+    |contours: &[Vec<Vec2>], other_vertices: &SortedVertices| Polygon {
+      contours: contours
+        .iter()
+        .map(|contour| {
+          insert_nearby_vertices(contour, other_vertices, tolerance)
+        })
+        .filter(|contour| contour.len() >= 3)
+        .collect(),
+    };
+  (heal(&subject, &clip_vertices), heal(&clip, &subject_vertices))
+}
+
+// The vertices of a polygon sorted by x, so the vertices near an edge can be
+// found with a binary search.
+struct SortedVertices(Vec<Vec2>);
+
+impl SortedVertices {
+  fn new(contours: &[Vec<Vec2>]) -> Self {
+    let mut vertices = contours.iter().flatten().copied().collect::<Vec<_>>();
+    vertices.sort_by(|a, b| a.x.total_cmp(&b.x));
+    Self(vertices)
+  }
+
+  // The vertices whose x coordinate is in `min_x..=max_x`.
+  fn in_x_range(&self, min_x: f32, max_x: f32) -> &[Vec2] {
+    let start = self.0.partition_point(|vertex| vertex.x < min_x);
+    let end = self.0.partition_point(|vertex| vertex.x <= max_x);
+    &self.0[start..end]
+  }
+}
+
+// Inserts the vertices of `other_vertices` that are within `tolerance` of the
+// interior of an edge of `contour` into that edge (in order along the edge).
+// Vertices at the ends of an edge are skipped, since they are already shared.
+fn insert_nearby_vertices(
+  contour: &[Vec2],
+  other_vertices: &SortedVertices,
+  tolerance: f32,
+) -> Vec<Vec2> {
+  let mut healed = Vec::with_capacity(contour.len());
+  for (index, &start) in contour.iter().enumerate() {
+    let end = contour[(index + 1) % contour.len()];
+    healed.push(start);
+
+    let vector = end - start;
+    let length_squared = vector.length_squared();
+    // Pairs of (parameter along the edge, vertex).
+    let mut inserted = other_vertices
+      .in_x_range(
+        start.x.min(end.x) - tolerance,
+        start.x.max(end.x) + tolerance,
+      )
+      .iter()
+      .filter(|&&vertex| vertex != start && vertex != end)
+      .filter_map(|&vertex| {
+        let t = (vertex - start).dot(vector) / length_squared;
+        (0.0 < t
+          && t < 1.0
+          && vertex.distance_squared(start + t * vector)
+            <= tolerance * tolerance)
+          .then_some((t, vertex))
+      })
+      .collect::<Vec<_>>();
+    inserted.sort_by(|a, b| a.0.total_cmp(&b.0));
+    for (_, vertex) in inserted {
+      // Vertices shared by several contours of the other polygon only need to
+      // be inserted once.
+      if healed.last() != Some(&vertex) {
+        healed.push(vertex);
+      }
+    }
+  }
+  healed
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::{heal_gaps, union, Polygon};
+
+  // A tile from (0, 0) to (1, 1), and its right neighbour, whose left border is
+  // slightly off and has an extra vertex in the middle.
+  fn tiles() -> (Polygon, Polygon) {
+    (
+      Polygon {
+        contours: vec![vec![
+          Vec2::new(0.0, 0.0),
+          Vec2::new(1.0, 0.0),
+          Vec2::new(1.0, 1.0),
+          Vec2::new(0.0, 1.0),
+        ]],
+      },
+      Polygon {
+        contours: vec![vec![
+          Vec2::new(1.0001, 0.0),
+          Vec2::new(2.0, 0.0),
+          Vec2::new(2.0, 1.0),
+          Vec2::new(1.0001, 1.0),
+          Vec2::new(1.0002, 0.5),
+        ]],
+      },
+    )
+  }
+
+  #[test]
+  fn heals_gaps_between_tiles() {
+    let (left, right) = tiles();
+    // Without healing, the tiles do not touch, so the union keeps both.
+    assert_eq!(union(&left, &right).polygon.contours.len(), 2);
+
+    let (left, right) = heal_gaps(&left, &right, 0.001);
+    assert_eq!(
+      left,
+      Polygon {
+        contours: vec![vec![
+          Vec2::new(0.0, 0.0),
+          Vec2::new(1.0, 0.0),
+          Vec2::new(1.0002, 0.5),
+          Vec2::new(1.0, 1.0),
+          Vec2::new(0.0, 1.0),
+        ]]
+      }
+    );
+    assert_eq!(
+      right,
+      Polygon {
+        contours: vec![vec![
+          Vec2::new(1.0, 0.0),
+          Vec2::new(2.0, 0.0),
+          Vec2::new(2.0, 1.0),
+          Vec2::new(1.0, 1.0),
+          Vec2::new(1.0002, 0.5),
+        ]]
+      }
+    );
+    assert_eq!(
+      union(&left, &right).polygon,
+      Polygon {
+        contours: vec![vec![
+          Vec2::new(0.0, 0.0),
+          Vec2::new(1.0, 0.0),
+          Vec2::new(2.0, 0.0),
+          Vec2::new(2.0, 1.0),
+          Vec2::new(1.0, 1.0),
+          Vec2::new(0.0, 1.0),
+        ]]
+      }
+    );
+  }
+
+  #[test]
+  fn leaves_gaps_wider_than_tolerance() {
+    let (left, right) = tiles();
+    let (healed_left, healed_right) = heal_gaps(&left, &right, 0.00001);
+    assert_eq!(healed_left, left);
+    assert_eq!(healed_right, right);
+  }
+}
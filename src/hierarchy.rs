@@ -0,0 +1,315 @@
+use glam::Vec2;
+
+use crate::{
+  util::{contour_signed_area, point_in_contour},
+  BooleanResult, Polygon, SourceEdge,
+};
+
+// Information about a contour of a `BooleanResult`, used to decide which
+// contours to keep in `BooleanResult::retain_contours`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ContourInfo<'a> {
+  // The index of the contour in the result.
+  pub index: usize,
+  // The source of each edge in the contour.
+  pub source_edges: &'a [SourceEdge],
+  // The index of the contour this contour is directly nested in (if any).
+  pub parent: Option<usize>,
+  // Whether the contour is a hole (otherwise, a shell).
+  pub is_hole: bool,
+}
+
+// The nesting of the contours of a polygon: which contours are shells, which
+// are holes, and which contour each one is directly nested in.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ContourHierarchy {
+  // The index of the contour that each contour is directly nested in (if any).
+  pub parents: Vec<Option<usize>>,
+  // The indices of the contours directly nested in each contour (e.g. the
+  // holes of a shell), in increasing order.
+  pub children: Vec<Vec<usize>>,
+  // The indices of the contours not nested in any other contour (the outermost
+  // shells), in increasing order.
+  pub roots: Vec<usize>,
+}
+
+impl ContourHierarchy {
+  // Builds the hierarchy from the index of the contour that each contour is
+  // directly nested in (e.g. `BooleanResult::contour_parents`).
+  pub fn from_parents(parents: Vec<Option<usize>>) -> Self {
+    let mut children = vec![Vec::new(); parents.len()];
+    let mut roots = Vec::new();
+    for (index, parent) in parents.iter().enumerate() {
+      match parent {
+        Some(parent) => children[*parent].push(index),
+        None => roots.push(index),
+      }
+    }
+    Self { parents, children, roots }
+  }
+
+  // Computes the number of contours that the contour at `index` is nested in.
+  pub fn depth(&self, index: usize) -> u32 {
+    contour_depth(&self.parents, index)
+  }
+
+  // Returns whether the contour at `index` is a hole, i.e. it is nested in an
+  // odd number of contours.
+  pub fn is_hole(&self, index: usize) -> bool {
+    self.depth(index) % 2 == 1
+  }
+
+  // Iterates over the indices of the shells (including islands inside holes),
+  // in increasing order. The holes of a shell are its `children`.
+  pub fn shells(&self) -> impl Iterator<Item = usize> + '_ {
+    (0..self.parents.len()).filter(|&index| !self.is_hole(index))
+  }
+}
+
+// Computes the hierarchy of the contours of `polygon` based on containment (see
+// `ContourHierarchy`), regardless of their orientation. Contours are assumed to
+// not cross each other (e.g. a resolved polygon, see `Polygon::normalize`), but
+// they may touch at vertices. This takes O(n^2) time for n contours.
+pub fn contour_hierarchy(polygon: &Polygon) -> ContourHierarchy {
+  ContourHierarchy::from_parents(compute_contour_parents(polygon))
+}
+
+impl BooleanResult {
+  // Computes the hierarchy of the contours of the result (see
+  // `contour_hierarchy`). This uses `contour_parents` if it was computed.
+  pub fn contour_hierarchy(&self) -> ContourHierarchy {
+    if self.contour_parents.len() == self.polygon.contours.len() {
+      ContourHierarchy::from_parents(self.contour_parents.clone())
+    } else {
+      contour_hierarchy(&self.polygon)
+    }
+  }
+
+  // Removes the contours for which `predicate` returns false. Removing a
+  // contour also removes every contour nested inside it (e.g. removing a shell
+  // removes its holes, and any shells in those holes), so the result stays a
+  // consistent polygon. The source edges (and any other per-contour data) are
+  // kept in sync. If `contour_parents` was not computed, the hierarchy is
+  // derived from the geometry.
+  pub fn retain_contours(
+    &mut self,
+    mut predicate: impl FnMut(&[Vec2], &ContourInfo) -> bool,
+  ) {
+    let contour_parents = if self.contour_parents.is_empty() {
+      compute_contour_parents(&self.polygon)
+    } else {
+      self.contour_parents.clone()
+    };
+
+    let mut keep = Vec::with_capacity(self.polygon.contours.len());
+    for (index, contour) in self.polygon.contours.iter().enumerate() {
+      let info = ContourInfo {
+        index,
+        source_edges: &self.contour_source_edges[index],
+        parent: contour_parents[index],
+        is_hole: contour_depth(&contour_parents, index) % 2 == 1,
+      };
+      keep.push(predicate(contour, &info));
+    }
+
+    // A contour is only kept if it and all of its ancestors are kept.
+    let keep = (0..keep.len())
+      .map(|index| {
+        let mut current = Some(index);
+        while let Some(current_index) = current {
+          if !keep[current_index] {
+            return false;
+          }
+          current = contour_parents[current_index];
+        }
+        true
+      })
+      .collect::<Vec<_>>();
+    self.retain_contours_by_index(|index| keep[index]);
+  }
+}
+
+// Computes the number of contours that the contour at `index` is nested in.
+pub(crate) fn contour_depth(
+  contour_parents: &[Option<usize>],
+  index: usize,
+) -> u32 {
+  let mut depth = 0;
+  let mut current = contour_parents[index];
+  while let Some(parent) = current {
+    depth += 1;
+    current = contour_parents[parent];
+  }
+  depth
+}
+
+// Computes the index of the contour that each contour of `polygon` is directly
+// nested in, based on containment. The parent of a contour is the smallest
+// contour containing it. Contours are assumed to not cross each other. They may
+// touch at vertices (e.g. a hole touching its shell), so containment is tested
+// with the midpoint of the first edge of each contour rather than a vertex.
+pub(crate) fn compute_contour_parents(polygon: &Polygon) -> Vec<Option<usize>> {
+  let areas = polygon
+    .contours
+    .iter()
+    .map(|contour| contour_signed_area(contour).abs())
+    .collect::<Vec<_>>();
+  polygon
+    .contours
+    .iter()
+    .enumerate()
+    .map(|(index, contour)| {
+      let point = match contour.as_slice() {
+        [] => return None,
+        [point] => *point,
+        [start, end, ..] => (*start + *end) * 0.5,
+      };
+      polygon
+        .contours
+        .iter()
+        .enumerate()
+        .filter(|&(other_index, other_contour)| {
+          other_index != index
+            && areas[index] < areas[other_index]
+            && point_in_contour(point, other_contour)
+        })
+        .min_by(|(a, _), (b, _)| areas[*a].total_cmp(&areas[*b]))
+        .map(|(other_index, _)| other_index)
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::rectangle;
+  use crate::{
+    contour_hierarchy, xor_with_options, BooleanOptions, BooleanResult,
+    ContourHierarchy, OutputOrientation, Polygon, SourceEdge,
+  };
+
+  use super::compute_contour_parents;
+
+  #[test]
+  fn sweep_hierarchy_matches_geometry_for_nested_contours() {
+    // Each square nests in the previous one, so the XOR alternates between
+    // shells and holes.
+    let subject = Polygon {
+      contours: vec![
+        rectangle(Vec2::splat(0.0), Vec2::splat(10.0)),
+        rectangle(Vec2::splat(2.0), Vec2::splat(8.0)),
+        rectangle(Vec2::splat(20.0), Vec2::splat(30.0)),
+      ],
+    };
+    let clip = Polygon {
+      contours: vec![
+        rectangle(Vec2::splat(1.0), Vec2::splat(9.0)),
+        rectangle(Vec2::splat(3.0), Vec2::splat(7.0)),
+        rectangle(Vec2::splat(21.0), Vec2::splat(29.0)),
+      ],
+    };
+    let options = BooleanOptions {
+      track_hierarchy: true,
+      output_orientation: OutputOrientation::AsSwept,
+      ..Default::default()
+    };
+
+    let result = xor_with_options(&subject, &clip, &options);
+    assert_eq!(result.polygon.contours.len(), 6);
+    assert_eq!(
+      result.contour_parents,
+      compute_contour_parents(&result.polygon)
+    );
+  }
+
+  #[test]
+  fn builds_hierarchy_of_nested_contours() {
+    // A shell with a hole containing an island, and a separate shell. The
+    // orientation of the contours does not matter.
+    let polygon = Polygon {
+      contours: vec![
+        rectangle(Vec2::splat(2.0), Vec2::splat(8.0)),
+        rectangle(Vec2::splat(20.0), Vec2::splat(30.0)),
+        rectangle(Vec2::splat(0.0), Vec2::splat(10.0)),
+        rectangle(Vec2::splat(3.0), Vec2::splat(7.0)),
+      ],
+    };
+    let hierarchy = contour_hierarchy(&polygon);
+    assert_eq!(
+      hierarchy,
+      ContourHierarchy {
+        parents: vec![Some(2), None, None, Some(0)],
+        children: vec![vec![3], vec![], vec![0], vec![]],
+        roots: vec![1, 2],
+      }
+    );
+    assert_eq!(
+      (0..4).map(|index| hierarchy.is_hole(index)).collect::<Vec<_>>(),
+      [true, false, false, false]
+    );
+    assert_eq!(hierarchy.depth(3), 2);
+    assert_eq!(hierarchy.shells().collect::<Vec<_>>(), [1, 2, 3]);
+  }
+
+  #[test]
+  fn retain_contours_removes_nested_contours() {
+    let source_edges =
+      |contour| vec![SourceEdge { is_from_subject: true, contour, edge: 0 }; 4];
+    let mut result = BooleanResult {
+      polygon: Polygon {
+        contours: vec![
+          rectangle(Vec2::splat(0.0), Vec2::splat(10.0)),
+          rectangle(Vec2::splat(1.0), Vec2::splat(9.0)),
+          rectangle(Vec2::splat(2.0), Vec2::splat(8.0)),
+          rectangle(Vec2::splat(20.0), Vec2::splat(30.0)),
+          rectangle(Vec2::splat(21.0), Vec2::splat(29.0)),
+        ],
+      },
+      contour_source_edges: (0..5).map(source_edges).collect(),
+      ..Default::default()
+    };
+
+    let mut seen_holes = vec![];
+    // Remove the outermost shell.
+    result.retain_contours(|_, info| {
+      seen_holes.push(info.is_hole);
+      info.index != 0
+    });
+
+    assert_eq!(seen_holes, [false, true, false, false, true]);
+    assert_eq!(
+      result,
+      BooleanResult {
+        polygon: Polygon {
+          contours: vec![
+            rectangle(Vec2::splat(20.0), Vec2::splat(30.0)),
+            rectangle(Vec2::splat(21.0), Vec2::splat(29.0))
+          ]
+        },
+        contour_source_edges: vec![source_edges(3), source_edges(4)],
+        ..Default::default()
+      }
+    );
+  }
+
+  #[test]
+  fn retain_contours_updates_hierarchy() {
+    let mut result = BooleanResult {
+      polygon: Polygon {
+        contours: vec![
+          rectangle(Vec2::splat(0.0), Vec2::splat(10.0)),
+          rectangle(Vec2::splat(20.0), Vec2::splat(30.0)),
+          rectangle(Vec2::splat(21.0), Vec2::splat(29.0)),
+        ],
+      },
+      contour_source_edges: vec![vec![SourceEdge::default(); 4]; 3],
+      contour_parents: vec![None, None, Some(1)],
+      ..Default::default()
+    };
+
+    result.retain_contours(|_, info| info.index != 0);
+
+    assert_eq!(result.contour_parents, [None, Some(0)]);
+  }
+}
@@ -5,27 +5,251 @@ use std::{
   f32::{EPSILON, INFINITY},
 };
 
+use align::align_boundaries;
+use coincident::apply_coincident_edge_source;
+use collinear::merge_collinear_edges;
+use fill::FilledPolygons;
 use glam::Vec2;
-use util::{edge_intersection, EdgeIntersectionResult};
+use hierarchy::compute_contour_parents;
+use normalize::CoordinateNormalization;
+use order::order_contours;
+use orientation::orient_contours;
+use overlap::{collect_contact_points, collect_overlap_segments};
+use provenance::{
+  compute_edge_parameters, compute_unmodified_edges, compute_vertex_errors,
+  result_equals_input,
+};
+use relation::classify_relation;
+use snap::{vertices_apart_from_next, weld_polygons};
+use util::{
+  edge_intersection_f64, edge_intersection_in_local_frame,
+  EdgeIntersectionResult,
+};
 
+mod align;
+mod all;
+mod area;
+mod attributes;
+mod bounded;
+mod case;
+mod clipper;
+mod coincident;
+mod collinear;
+mod contour;
+mod convert;
+mod convex;
+#[cfg(feature = "debug-render")]
+mod debug_render;
+mod denoise;
+mod distance;
+mod error;
+mod export;
+mod fill;
+mod fit;
+#[cfg(feature = "fuzz_util")]
+pub mod fuzz_util;
+mod hash;
+mod heal;
+mod hierarchy;
+mod measure;
+mod minkowski;
+mod monotone;
+mod multi;
+mod nary;
+mod navmesh;
+mod near_touch;
+mod normalize;
+mod offset;
+mod options;
+mod order;
+mod orientation;
+mod overlap;
+mod partial;
+mod path;
+mod pinch;
+mod precision;
+mod predicates;
+mod prepared;
+mod provenance;
+#[cfg(feature = "reference")]
+pub mod reference;
+mod relation;
+mod resolve;
+mod rule;
+mod sanitize;
+mod segments;
+mod snap;
+mod split;
+#[cfg(feature = "strict-checks")]
+mod strict;
+mod tile;
+#[cfg(feature = "timings")]
+mod timings;
+#[cfg(feature = "toolpath")]
+mod toolpath;
+mod transform;
+mod triangulate;
 mod util;
+mod validate;
+mod vertical;
+
+pub use all::{
+  boolean_all, boolean_all_with_options, difference_with_remainder,
+  difference_with_remainder_with_options, intersection_with_remainder,
+  intersection_with_remainder_with_options, overlay, overlay_pair,
+  overlay_pair_with_options, overlay_with_options, try_boolean_all,
+  try_boolean_all_with_options, try_difference_with_remainder,
+  try_difference_with_remainder_with_options, try_intersection_with_remainder,
+  try_intersection_with_remainder_with_options, try_overlay, try_overlay_pair,
+  try_overlay_pair_with_options, try_overlay_with_options, AllResults, Face,
+  FaceLabel, OverlayPair,
+};
+pub use area::{intersection_area, try_intersection_area};
+pub use attributes::{
+  boolean_with_attributes, boolean_with_attributes_and_options,
+  try_boolean_with_attributes, try_boolean_with_attributes_and_options, Lerp,
+};
+pub use bounded::BoundedPolygon;
+pub use case::{dump_case, parse_case, ParseCaseError};
+pub use clipper::Clipper;
+pub use coincident::CoincidentEdgeSource;
+pub use contour::{Contour, ContourKind};
+pub use convert::Polygon64;
+pub use convex::{
+  convex_hull, convex_hull_of_points, convex_intersection,
+  convex_intersection_with_options, try_convex_intersection,
+  try_convex_intersection_with_options,
+};
+#[cfg(feature = "debug-render")]
+pub use debug_render::{render_sweep, RenderSteps};
+pub use error::{ClipError, ClipWarning, SweepStage};
+pub use export::{export_edge_soup, EdgeSoup};
+pub use fill::FillRule;
+pub use fit::{fits_inside, try_fits_inside, PreparedContainer};
+pub use heal::heal_gaps;
+pub use hierarchy::{contour_hierarchy, ContourHierarchy, ContourInfo};
+pub use minkowski::{
+  erode, erode_with_options, try_erode, try_erode_with_options,
+};
+pub use multi::{
+  boolean_multi, boolean_multi_with_options, try_boolean_multi,
+  try_boolean_multi_with_options, MultiPolygon,
+};
+pub use nary::{
+  difference_all, difference_all_with_options, intersection_all,
+  intersection_all_with_options, overlay_all, overlay_all_with_options,
+  try_difference_all, try_difference_all_with_options, try_intersection_all,
+  try_intersection_all_with_options, try_overlay_all,
+  try_overlay_all_with_options, try_union_all, try_union_all_with_options,
+  union_all, union_all_with_options, CoverageFace,
+};
+pub use navmesh::{build_navmesh, NavEdge, NavMesh, WallSource};
+pub use near_touch::{find_near_touches, NearTouch};
+pub use offset::{
+  offset, offset_with_options, try_offset, try_offset_with_options, JoinStyle,
+};
+pub use options::{BooleanOptions, TrackingLevel};
+pub use order::ContourOrder;
+pub use orientation::{OutputOrientation, Winding};
+pub use partial::{partial_boolean, PartialResult};
+pub use path::{boundary_path, BoundaryPath};
+pub use pinch::PinchPoint;
+pub use precision::Precision;
+pub use predicates::{
+  contains, disjoint, intersects, touches, try_contains, try_disjoint,
+  try_intersects, try_touches,
+};
+pub use prepared::{PreparedClip, PreparedSubject};
+pub use provenance::{CompressedSourceEdges, SourceEdgeRun};
+pub use relation::PolygonRelation;
+pub use resolve::{
+  resolve_self_intersections, resolve_self_intersections_with_options,
+  try_resolve_self_intersections, try_resolve_self_intersections_with_options,
+};
+pub use rule::{
+  custom_boolean, custom_boolean_with_options, try_custom_boolean,
+  try_custom_boolean_with_options, BooleanRule, EdgeContext, InOut,
+};
+pub use sanitize::SanitizedPolygon;
+pub use segments::{
+  classify_segments, try_classify_segments, SegmentPiece, SegmentSide,
+};
+pub use split::{
+  split_by_line, split_by_line_with_options, try_split_by_line,
+  try_split_by_line_with_options,
+};
+pub use tile::{tile, try_tile, Grid, Tile};
+#[cfg(feature = "timings")]
+pub use timings::Timings;
+#[cfg(feature = "toolpath")]
+pub use toolpath::{
+  export_toolpath, CutDirection, ToolpathFormat, ToolpathOptions,
+};
+pub use util::contour_signed_area;
+pub use validate::ValidationIssue;
+pub use vertical::{find_vertical_ambiguities, VerticalAmbiguity};
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Default)]
 pub struct Polygon {
   pub contours: Vec<Vec<Vec2>>,
 }
 
+// Extends `bounds` (min, max) to include `point`.
+fn extend_bounds(
+  bounds: Option<(Vec2, Vec2)>,
+  point: Vec2,
+) -> Option<(Vec2, Vec2)> {
+  Some(match bounds {
+    None => (point, point),
+    Some((min, max)) => (min.min(point), max.max(point)),
+  })
+}
+
+// The bounding box (min, max) of a polygon, or None if it has no vertices.
+type Bounds = Option<(Vec2, Vec2)>;
+
+// Combines two bounding boxes (min, max) into one that contains both.
+fn union_bounds(
+  bounds: Option<(Vec2, Vec2)>,
+  other: (Vec2, Vec2),
+) -> Option<(Vec2, Vec2)> {
+  Some(match bounds {
+    None => other,
+    Some((min, max)) => (min.min(other.0), max.max(other.1)),
+  })
+}
+
 impl Polygon {
   // Computes the bounding box (min, max) of the polygon. Returns None if there
   // are no vertices.
   pub fn compute_bounds(&self) -> Option<(Vec2, Vec2)> {
-    self.contours.iter().flatten().fold(None, |bounds, &point| {
-      Some(match bounds {
-        None => (point, point),
-        Some((min, max)) => (min.min(point), max.max(point)),
+    self.contours.iter().flatten().copied().fold(None, extend_bounds)
+  }
+
+  // Iterates over every edge of the polygon as `(contour_index, edge_index,
+  // start, end)`. Contours are closed, so the last edge of each contour goes
+  // from its last vertex back to its first vertex.
+  pub fn iter_edges(
+    &self,
+  ) -> impl Iterator<Item = (usize, usize, Vec2, Vec2)> + '_ {
+    self.contours.iter().enumerate().flat_map(|(contour_index, contour)| {
+      contour.iter().enumerate().map(move |(edge_index, &start)| {
+        let end = contour[(edge_index + 1) % contour.len()];
+        (contour_index, edge_index, start, end)
       })
     })
   }
+
+  // Computes the number of vertices in all contours of the polygon.
+  pub fn num_vertices(&self) -> usize {
+    self.contours.iter().map(Vec::len).sum()
+  }
+
+  // Computes the number of edges in all contours of the polygon. Since
+  // contours are closed, this is the same as the number of vertices.
+  pub fn num_edges(&self) -> usize {
+    self.num_vertices()
+  }
 }
 
 // The source of an edge.
@@ -41,7 +265,7 @@ pub struct SourceEdge {
 }
 
 // The result of performing a boolean operation.
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug, Default)]
 pub struct BooleanResult {
   // The resulting polygon.
   pub polygon: Polygon,
@@ -49,95 +273,1090 @@ pub struct BooleanResult {
   // per contour in `polygon` and each entry will have the same number of edges
   // as that contour in `polygon`.
   pub contour_source_edges: Vec<Vec<SourceEdge>>,
+  // For each edge in `polygon` where the subject and clip coincide, the source
+  // edge from the polygon that `contour_source_edges` does not report (None
+  // for other edges). This has the same layout as `contour_source_edges`. This
+  // is only computed if `BooleanOptions::coincident_edge_source` is
+  // `CoincidentEdgeSource::ReportBoth`, and is empty otherwise.
+  pub contour_coincident_source_edges: Vec<Vec<Option<SourceEdge>>>,
+  // The source edges of the edges merged into each edge in `polygon`, in order
+  // along the edge. The edge's entry in `contour_source_edges` is one of them.
+  // This has the same layout as `contour_source_edges`. This is only computed
+  // if `BooleanOptions::merge_collinear_edges` is set, and is empty otherwise.
+  pub contour_merged_source_edges: Vec<Vec<Vec<SourceEdge>>>,
+  // Whether each edge in `polygon` is an exact copy of its source edge (the
+  // edge was not split or shortened). This has the same layout as
+  // `contour_source_edges`. This is only computed if
+  // `BooleanOptions::track_unmodified_edges` is set, and is empty otherwise.
+  pub contour_unmodified_edges: Vec<Vec<bool>>,
+  // The parametric positions `[start, end]` of each edge in `polygon` along its
+  // source edge, where 0 is the start of the source edge (the vertex at
+  // `SourceEdge::edge`) and 1 is its end. This is useful for interpolating
+  // per-vertex data of the inputs. The positions can be decreasing if the edge
+  // runs in the opposite direction of its source edge. This has the same
+  // layout as `contour_source_edges`. This is only computed if
+  // `BooleanOptions::track_edge_parameters` is set, and is empty otherwise.
+  pub contour_edge_parameters: Vec<Vec<[f32; 2]>>,
+  // The estimated numerical error bound of each vertex in `polygon`. This has
+  // the same layout as `polygon.contours`. Vertices copied from the inputs are
+  // exact (0), while vertices computed as intersections carry rounding error.
+  // Vertices moved by `BooleanOptions::weld_tolerance` or
+  // `BooleanOptions::boundary_tolerance` are treated as intersections, so the
+  // distance they were moved is not included. This is only computed if
+  // `BooleanOptions::track_vertex_errors` is set, and is empty otherwise.
+  pub contour_vertex_errors: Vec<Vec<f32>>,
+  // The index of the contour that each contour in `polygon` is directly
+  // nested in (if any). Contours nested in an even number of contours are
+  // shells, and the rest are holes. This is only computed if
+  // `BooleanOptions::track_hierarchy` is set, and is empty otherwise.
+  pub contour_parents: Vec<Option<usize>>,
+  // How the subject and clip relate to each other. This is only computed if
+  // `BooleanOptions::classify_relation` is set, and is None otherwise.
+  pub relation: Option<PolygonRelation>,
+  // The segments (ordered left to right) where the boundaries of the subject
+  // and clip coincide with the polygons on opposite sides. These are the parts
+  // of the intersection with zero area (e.g. the shared edge of two touching
+  // squares), which are never part of `polygon`. This is only computed if
+  // `BooleanOptions::track_overlap_segments` is set, and is empty otherwise.
+  pub overlap_segments: Vec<(Vec2, Vec2)>,
+  // The points where the boundaries of the subject and clip meet, other than
+  // along coincident edges (see `overlap_segments`). Together with
+  // `overlap_segments`, this distinguishes polygons that touch (e.g. at a
+  // corner) from disjoint polygons, since both have an empty intersection.
+  // Points where the boundaries cross are included as well. This is only
+  // computed if `BooleanOptions::track_contact_points` is set, and is empty
+  // otherwise.
+  pub contact_points: Vec<Vec2>,
+  // The bounding box (min, max) of each contour in `polygon`, or None for
+  // empty contours. These are computed while the contours are joined, so
+  // culling the result does not require scanning its points again.
+  pub contour_bounds: Vec<Option<(Vec2, Vec2)>>,
+  // The bounding box (min, max) of `polygon`, or None if it has no contours.
+  // This is the same as `polygon.compute_bounds()`.
+  pub bounds: Option<(Vec2, Vec2)>,
+  // Whether the operation had to be recomputed with more precision because
+  // rounding errors made the first attempt inconsistent. The result is still
+  // valid, but this indicates the inputs are numerically difficult.
+  pub precision_escalated: bool,
+  // Suspicious things about the operation that did not prevent it from being
+  // computed (e.g. zero-length input edges or removed sliver contours), in the
+  // order they happened. This is only computed if
+  // `BooleanOptions::collect_warnings` is set, and is empty otherwise.
+  pub warnings: Vec<ClipWarning>,
+  // The time spent in each phase of the sweep. This is only computed if
+  // `BooleanOptions::collect_timings` is set and the operation was not
+  // computed trivially (e.g. for disjoint polygons), and is None otherwise.
+  // If the operation was retried with more precision, this is the time spent
+  // by the last attempt.
+  #[cfg(feature = "timings")]
+  pub timings: Option<Timings>,
+  // Whether `polygon` covers exactly the same area as the subject (e.g. the
+  // union with a clip nested in the subject). This is derived from the source
+  // edges, so it is cheaper than comparing the polygons afterwards. It can be
+  // false for some equal areas (e.g. when coincident edges were taken from the
+  // clip), but is never true for different areas. This is only computed if
+  // `BooleanOptions::track_input_equality` is set, and is false otherwise.
+  pub equals_subject: bool,
+  // The same as `equals_subject`, but for the clip.
+  pub equals_clip: bool,
+  // Whether `polygon` has no contours.
+  pub is_empty: bool,
+}
+
+impl BooleanResult {
+  // Appends the contours of `other` (and their per-contour data) to this
+  // result. Indices in `other.contour_parents` are shifted to refer to the
+  // appended contours. Optional data (e.g. `contour_parents`) is only kept if
+  // both results have it, and `relation` is only kept if both results have the
+  // same relation. This is useful for combining the results of operations on
+  // separate areas (e.g. tiles).
+  pub fn merge(&mut self, mut other: BooleanResult) {
+    // Whether `values` holds the optional per-contour data for `result`. Empty
+    // results are considered to hold any data.
+    fn has_data<T>(result: &BooleanResult, values: &[T]) -> bool {
+      !values.is_empty() || result.polygon.contours.is_empty()
+    }
+    let keep_coincident_source_edges =
+      has_data(self, &self.contour_coincident_source_edges)
+        && has_data(&other, &other.contour_coincident_source_edges);
+    let keep_merged_source_edges =
+      has_data(self, &self.contour_merged_source_edges)
+        && has_data(&other, &other.contour_merged_source_edges);
+    let keep_unmodified_edges = has_data(self, &self.contour_unmodified_edges)
+      && has_data(&other, &other.contour_unmodified_edges);
+    let keep_edge_parameters = has_data(self, &self.contour_edge_parameters)
+      && has_data(&other, &other.contour_edge_parameters);
+    let keep_vertex_errors = has_data(self, &self.contour_vertex_errors)
+      && has_data(&other, &other.contour_vertex_errors);
+    let keep_parents = has_data(self, &self.contour_parents)
+      && has_data(&other, &other.contour_parents);
+    let contour_offset = self.polygon.contours.len();
+    let other_is_empty = other.polygon.contours.is_empty();
+
+    self.polygon.contours.append(&mut other.polygon.contours);
+    self.contour_source_edges.append(&mut other.contour_source_edges);
+    if keep_coincident_source_edges {
+      self
+        .contour_coincident_source_edges
+        .append(&mut other.contour_coincident_source_edges);
+    } else {
+      self.contour_coincident_source_edges.clear();
+    }
+    if keep_merged_source_edges {
+      self
+        .contour_merged_source_edges
+        .append(&mut other.contour_merged_source_edges);
+    } else {
+      self.contour_merged_source_edges.clear();
+    }
+    if keep_unmodified_edges {
+      self.contour_unmodified_edges.append(&mut other.contour_unmodified_edges);
+    } else {
+      self.contour_unmodified_edges.clear();
+    }
+    if keep_edge_parameters {
+      self.contour_edge_parameters.append(&mut other.contour_edge_parameters);
+    } else {
+      self.contour_edge_parameters.clear();
+    }
+    if keep_vertex_errors {
+      self.contour_vertex_errors.append(&mut other.contour_vertex_errors);
+    } else {
+      self.contour_vertex_errors.clear();
+    }
+    if keep_parents {
+      self.contour_parents.extend(
+        other
+          .contour_parents
+          .iter()
+          .map(|parent| parent.map(|parent| parent + contour_offset)),
+      );
+    } else {
+      self.contour_parents.clear();
+    }
+    self.overlap_segments.append(&mut other.overlap_segments);
+    self.contact_points.append(&mut other.contact_points);
+    self.contour_bounds.append(&mut other.contour_bounds);
+    if let Some(other_bounds) = other.bounds {
+      self.bounds = union_bounds(self.bounds, other_bounds);
+    }
+    if self.relation != other.relation {
+      self.relation = None;
+    }
+    self.precision_escalated |= other.precision_escalated;
+    self.warnings.append(&mut other.warnings);
+    // The merged result only equals an input if one of the results was empty.
+    if contour_offset == 0 {
+      self.equals_subject = other.equals_subject;
+      self.equals_clip = other.equals_clip;
+    } else if !other_is_empty {
+      self.equals_subject = false;
+      self.equals_clip = false;
+    }
+    self.is_empty = self.polygon.contours.is_empty();
+  }
+
+  // Keeps only the vertices at `kept_vertices` (in order) of the contour at
+  // `contour_index`. The per-edge data of the edges starting at those vertices
+  // is kept as well. This does not update the bounds (see `update_bounds`).
+  pub(crate) fn retain_contour_vertices(
+    &mut self,
+    contour_index: usize,
+    kept_vertices: &[usize],
+  ) {
+    fn retain_indices<T: Clone>(values: &mut Vec<T>, indices: &[usize]) {
+      *values = indices.iter().map(|&index| values[index].clone()).collect();
+    }
+    if kept_vertices.len() != self.polygon.contours[contour_index].len() {
+      self.equals_subject = false;
+      self.equals_clip = false;
+    }
+    retain_indices(&mut self.polygon.contours[contour_index], kept_vertices);
+    retain_indices(
+      &mut self.contour_source_edges[contour_index],
+      kept_vertices,
+    );
+    if let Some(coincident_source_edges) =
+      self.contour_coincident_source_edges.get_mut(contour_index)
+    {
+      retain_indices(coincident_source_edges, kept_vertices);
+    }
+    if let Some(merged_source_edges) =
+      self.contour_merged_source_edges.get_mut(contour_index)
+    {
+      retain_indices(merged_source_edges, kept_vertices);
+    }
+    if let Some(unmodified_edges) =
+      self.contour_unmodified_edges.get_mut(contour_index)
+    {
+      retain_indices(unmodified_edges, kept_vertices);
+    }
+    if let Some(edge_parameters) =
+      self.contour_edge_parameters.get_mut(contour_index)
+    {
+      retain_indices(edge_parameters, kept_vertices);
+    }
+    if let Some(vertex_errors) =
+      self.contour_vertex_errors.get_mut(contour_index)
+    {
+      retain_indices(vertex_errors, kept_vertices);
+    }
+  }
+
+  // Recomputes `contour_bounds` and `bounds` from the points of `polygon`.
+  // This must be called after moving or removing points.
+  pub(crate) fn update_bounds(&mut self) {
+    self.contour_bounds = self
+      .polygon
+      .contours
+      .iter()
+      .map(|contour| contour.iter().copied().fold(None, extend_bounds))
+      .collect();
+    self.bounds =
+      self.contour_bounds.iter().flatten().copied().fold(None, union_bounds);
+  }
+
+  // Reverses the contour at `contour_index`, keeping its per-edge data
+  // attached to the same edges.
+  pub(crate) fn reverse_contour(&mut self, contour_index: usize) {
+    // Reversing the vertices also reverses each edge, so the edge starting at
+    // vertex `i` becomes the edge starting at vertex `i + 1` (before
+    // reversing). Rotating after reversing restores that pairing.
+    fn reverse_edges<T>(values: &mut [T]) {
+      values.reverse();
+      if !values.is_empty() {
+        values.rotate_left(1);
+      }
+    }
+    self.polygon.contours[contour_index].reverse();
+    reverse_edges(&mut self.contour_source_edges[contour_index]);
+    if let Some(coincident_source_edges) =
+      self.contour_coincident_source_edges.get_mut(contour_index)
+    {
+      reverse_edges(coincident_source_edges);
+    }
+    if let Some(merged_source_edges) =
+      self.contour_merged_source_edges.get_mut(contour_index)
+    {
+      reverse_edges(merged_source_edges);
+      for merged in merged_source_edges.iter_mut() {
+        merged.reverse();
+      }
+    }
+    if let Some(unmodified_edges) =
+      self.contour_unmodified_edges.get_mut(contour_index)
+    {
+      reverse_edges(unmodified_edges);
+    }
+    if let Some(edge_parameters) =
+      self.contour_edge_parameters.get_mut(contour_index)
+    {
+      reverse_edges(edge_parameters);
+      for [start, end] in edge_parameters.iter_mut() {
+        std::mem::swap(start, end);
+      }
+    }
+    if let Some(vertex_errors) =
+      self.contour_vertex_errors.get_mut(contour_index)
+    {
+      vertex_errors.reverse();
+    }
+  }
+
+  // Reorders the contours (and their per-contour data) so that the contour at
+  // `order[i]` becomes the contour at `i`. `order` must be a permutation of the
+  // contour indices. Indices in `contour_parents` are updated to match.
+  pub(crate) fn reorder_contours(&mut self, order: &[usize]) {
+    fn reorder<T>(values: &mut Vec<T>, order: &[usize]) {
+      if values.is_empty() {
+        return;
+      }
+      let mut old_values =
+        std::mem::take(values).into_iter().map(Some).collect::<Vec<_>>();
+      *values =
+        order.iter().map(|&index| old_values[index].take().unwrap()).collect();
+    }
+    reorder(&mut self.polygon.contours, order);
+    reorder(&mut self.contour_source_edges, order);
+    reorder(&mut self.contour_coincident_source_edges, order);
+    reorder(&mut self.contour_merged_source_edges, order);
+    reorder(&mut self.contour_unmodified_edges, order);
+    reorder(&mut self.contour_edge_parameters, order);
+    reorder(&mut self.contour_vertex_errors, order);
+    reorder(&mut self.contour_bounds, order);
+    if !self.contour_parents.is_empty() {
+      let mut new_indices = vec![0; order.len()];
+      for (new_index, &old_index) in order.iter().enumerate() {
+        new_indices[old_index] = new_index;
+      }
+      self.contour_parents = order
+        .iter()
+        .map(|&old_index| {
+          self.contour_parents[old_index].map(|parent| new_indices[parent])
+        })
+        .collect();
+    }
+  }
+
+  // Removes the contours (and their per-contour data) for which `keep` returns
+  // false. `keep` is called with the index of each contour in order.
+  pub(crate) fn retain_contours_by_index(
+    &mut self,
+    keep: impl FnMut(usize) -> bool,
+  ) {
+    fn retain_mask<T>(values: &mut Vec<T>, mask: &[bool]) {
+      let mut index = 0;
+      values.retain(|_| {
+        index += 1;
+        mask[index - 1]
+      });
+    }
+    let mask = (0..self.polygon.contours.len()).map(keep).collect::<Vec<_>>();
+    if mask.contains(&false) {
+      self.equals_subject = false;
+      self.equals_clip = false;
+    }
+    retain_mask(&mut self.polygon.contours, &mask);
+    retain_mask(&mut self.contour_source_edges, &mask);
+    retain_mask(&mut self.contour_bounds, &mask);
+    self.is_empty = self.polygon.contours.is_empty();
+    self.bounds =
+      self.contour_bounds.iter().flatten().copied().fold(None, union_bounds);
+    if !self.contour_coincident_source_edges.is_empty() {
+      retain_mask(&mut self.contour_coincident_source_edges, &mask);
+    }
+    if !self.contour_merged_source_edges.is_empty() {
+      retain_mask(&mut self.contour_merged_source_edges, &mask);
+    }
+    if !self.contour_unmodified_edges.is_empty() {
+      retain_mask(&mut self.contour_unmodified_edges, &mask);
+    }
+    if !self.contour_edge_parameters.is_empty() {
+      retain_mask(&mut self.contour_edge_parameters, &mask);
+    }
+    if !self.contour_vertex_errors.is_empty() {
+      retain_mask(&mut self.contour_vertex_errors, &mask);
+    }
+    if !self.contour_parents.is_empty() {
+      let mut new_indices = Vec::with_capacity(mask.len());
+      let mut next_index = 0;
+      for &keep in mask.iter() {
+        new_indices.push(if keep { Some(next_index) } else { None });
+        next_index += keep as usize;
+      }
+      // Contours whose parent was removed are reparented to the closest
+      // ancestor that was kept.
+      let old_parents = std::mem::take(&mut self.contour_parents);
+      let new_parent = |mut parent: Option<usize>| {
+        while let Some(parent_index) = parent {
+          if new_indices[parent_index].is_some() {
+            return new_indices[parent_index];
+          }
+          parent = old_parents[parent_index];
+        }
+        None
+      };
+      self.contour_parents = (0..mask.len())
+        .filter(|&index| mask[index])
+        .map(|index| new_parent(old_parents[index]))
+        .collect();
+    }
+  }
 }
 
 pub fn intersection(subject: &Polygon, clip: &Polygon) -> BooleanResult {
-  perform_boolean(subject, clip, Operation::Intersection)
+  unwrap_boolean(perform_boolean(
+    subject,
+    clip,
+    Rule::Operation(Operation::Intersection),
+    &BooleanOptions::default(),
+  ))
+}
+
+pub fn intersection_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> BooleanResult {
+  unwrap_boolean(perform_boolean(
+    subject,
+    clip,
+    Rule::Operation(Operation::Intersection),
+    options,
+  ))
 }
 
 pub fn union(subject: &Polygon, clip: &Polygon) -> BooleanResult {
-  perform_boolean(subject, clip, Operation::Union)
+  unwrap_boolean(perform_boolean(
+    subject,
+    clip,
+    Rule::Operation(Operation::Union),
+    &BooleanOptions::default(),
+  ))
+}
+
+pub fn union_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> BooleanResult {
+  unwrap_boolean(perform_boolean(
+    subject,
+    clip,
+    Rule::Operation(Operation::Union),
+    options,
+  ))
 }
 
 pub fn difference(subject: &Polygon, clip: &Polygon) -> BooleanResult {
-  perform_boolean(subject, clip, Operation::Difference)
+  unwrap_boolean(perform_boolean(
+    subject,
+    clip,
+    Rule::Operation(Operation::Difference),
+    &BooleanOptions::default(),
+  ))
+}
+
+pub fn difference_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> BooleanResult {
+  unwrap_boolean(perform_boolean(
+    subject,
+    clip,
+    Rule::Operation(Operation::Difference),
+    options,
+  ))
 }
 
 pub fn xor(subject: &Polygon, clip: &Polygon) -> BooleanResult {
-  perform_boolean(subject, clip, Operation::XOR)
+  unwrap_boolean(perform_boolean(
+    subject,
+    clip,
+    Rule::Operation(Operation::XOR),
+    &BooleanOptions::default(),
+  ))
+}
+
+pub fn xor_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> BooleanResult {
+  unwrap_boolean(perform_boolean(
+    subject,
+    clip,
+    Rule::Operation(Operation::XOR),
+    options,
+  ))
+}
+
+// The `try_*` variants return an error instead of panicking when the operation
+// cannot be computed (see `ClipError`), so applications can fall back (e.g. to
+// a simplified input) rather than crash.
+
+pub fn try_intersection(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<BooleanResult, ClipError> {
+  perform_boolean(
+    subject,
+    clip,
+    Rule::Operation(Operation::Intersection),
+    &BooleanOptions::default(),
+  )
+}
+
+pub fn try_intersection_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  perform_boolean(
+    subject,
+    clip,
+    Rule::Operation(Operation::Intersection),
+    options,
+  )
+}
+
+pub fn try_union(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<BooleanResult, ClipError> {
+  perform_boolean(
+    subject,
+    clip,
+    Rule::Operation(Operation::Union),
+    &BooleanOptions::default(),
+  )
+}
+
+pub fn try_union_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  perform_boolean(subject, clip, Rule::Operation(Operation::Union), options)
+}
+
+pub fn try_difference(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<BooleanResult, ClipError> {
+  perform_boolean(
+    subject,
+    clip,
+    Rule::Operation(Operation::Difference),
+    &BooleanOptions::default(),
+  )
+}
+
+pub fn try_difference_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  perform_boolean(
+    subject,
+    clip,
+    Rule::Operation(Operation::Difference),
+    options,
+  )
+}
+
+pub fn try_xor(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<BooleanResult, ClipError> {
+  perform_boolean(
+    subject,
+    clip,
+    Rule::Operation(Operation::XOR),
+    &BooleanOptions::default(),
+  )
+}
+
+pub fn try_xor_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  perform_boolean(subject, clip, Rule::Operation(Operation::XOR), options)
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum Operation {
+// The generic variants compute the operation given by `operation`, so the
+// operation can be selected at runtime (e.g. from a UI or a config file).
+
+pub fn boolean(
+  subject: &Polygon,
+  clip: &Polygon,
+  operation: Operation,
+) -> BooleanResult {
+  unwrap_boolean(try_boolean(subject, clip, operation))
+}
+
+pub fn boolean_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  operation: Operation,
+  options: &BooleanOptions,
+) -> BooleanResult {
+  unwrap_boolean(try_boolean_with_options(subject, clip, operation, options))
+}
+
+pub fn try_boolean(
+  subject: &Polygon,
+  clip: &Polygon,
+  operation: Operation,
+) -> Result<BooleanResult, ClipError> {
+  perform_boolean(
+    subject,
+    clip,
+    Rule::Operation(operation),
+    &BooleanOptions::default(),
+  )
+}
+
+pub fn try_boolean_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  operation: Operation,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  perform_boolean(subject, clip, Rule::Operation(operation), options)
+}
+
+// Unwraps the result of a boolean operation, panicking with the error message
+// if it failed.
+fn unwrap_boolean(result: Result<BooleanResult, ClipError>) -> BooleanResult {
+  result.unwrap_or_else(|error| panic!("{error}"))
+}
+
+// The largest magnitude of a coordinate that can be used in a boolean
+// operation. Ordering edges multiplies differences of coordinates, so larger
+// coordinates can overflow and cannot be compared.
+pub const MAX_COORDINATE_MAGNITUDE: f32 = 1e18;
+
+// Checks that `subject` and `clip` can be used in a boolean operation. Boolean
+// operations panic with (or for `try_*` variants, return) the returned error if
+// this fails, so this can be used to reject bad inputs upfront. Once the inputs
+// are valid, every comparison in the sweep is well-defined.
+pub fn validate_input(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<(), ClipError> {
+  for (polygon, is_from_subject) in [(subject, true), (clip, false)] {
+    for (contour_index, contour) in polygon.contours.iter().enumerate() {
+      for (vertex, point) in contour.iter().enumerate() {
+        if !point.is_finite() {
+          return Err(ClipError::InvalidInput {
+            is_from_subject,
+            contour: contour_index,
+            vertex,
+          });
+        }
+        if MAX_COORDINATE_MAGNITUDE < point.abs().max_element() {
+          return Err(ClipError::CoordinateOutOfRange {
+            is_from_subject,
+            contour: contour_index,
+            vertex,
+          });
+        }
+      }
+    }
+  }
+  Ok(())
+}
+
+// A built-in boolean operation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Operation {
+  // The region in both the subject and the clip.
   Intersection,
+  // The region in either the subject or the clip.
   Union,
+  // The region in exactly one of the subject and the clip.
   XOR,
+  // The region in the subject but not the clip.
   Difference,
 }
 
+impl Operation {
+  // Every operation, e.g. for listing them in a UI.
+  pub const ALL: [Operation; 4] = [
+    Operation::Intersection,
+    Operation::Union,
+    Operation::XOR,
+    Operation::Difference,
+  ];
+}
+
+// The rule selecting the edges of a boolean operation. The built-in operations
+// are kept apart from custom rules, since their results can be computed
+// trivially for disjoint polygons and their sweeps can stop early.
+#[derive(Clone, Copy)]
+enum Rule<'a> {
+  Operation(Operation),
+  Custom(&'a dyn BooleanRule),
+}
+
+impl BooleanRule for Rule<'_> {
+  fn in_result(&self, edge: &EdgeContext) -> bool {
+    match self {
+      Rule::Operation(operation) => operation.in_result(edge),
+      Rule::Custom(rule) => rule.in_result(edge),
+    }
+  }
+
+  fn result_transition(&self, edge: &EdgeContext) -> InOut {
+    match self {
+      Rule::Operation(operation) => operation.result_transition(edge),
+      Rule::Custom(rule) => rule.result_transition(edge),
+    }
+  }
+}
+
 fn perform_boolean(
   subject: &Polygon,
   clip: &Polygon,
-  operation: Operation,
+  rule: Rule,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  perform_boolean_with_bounds(subject, clip, None, rule, options)
+}
+
+// Same as `perform_boolean`, but with the bounds of `subject` and `clip` if
+// they are already known (see `BoundedPolygon`), so they are not computed
+// again. They are only used if the polygons are swept as they are.
+fn perform_boolean_with_bounds(
+  subject: &Polygon,
+  clip: &Polygon,
+  known_bounds: Option<(Bounds, Bounds)>,
+  rule: Rule,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  validate_input(subject, clip)?;
+  if let Some(max_events) = options.max_events {
+    // Each edge starts with two events, so fail before allocating any of them
+    // if that is already too many.
+    let edge_count = subject.num_edges().saturating_add(clip.num_edges());
+    if max_events < edge_count.saturating_mul(2) {
+      return Err(ClipError::CapacityExceeded { limit: max_events });
+    }
+  }
+
+  // The polygons resolved by the fill rule (if it is not even-odd).
+  let filled_polygons = match options.fill_rules() {
+    (FillRule::EvenOdd, FillRule::EvenOdd) => None,
+    (subject_fill_rule, clip_fill_rule) => Some(FilledPolygons::new(
+      subject,
+      clip,
+      subject_fill_rule,
+      clip_fill_rule,
+    )?),
+  };
+  let (filled_subject, filled_clip) = match &filled_polygons {
+    Some(filled_polygons) => {
+      (filled_polygons.subject(), filled_polygons.clip())
+    }
+    None => (subject, clip),
+  };
+
+  // The warnings about the inputs, which come before any from the sweep.
+  let mut warnings = Vec::new();
+  if options.collect_warnings {
+    warnings.extend(degenerate_edge_warnings(subject, clip));
+  }
+  // The number of input vertices moved by welding or aligning.
+  let mut snapped_vertices = 0;
+
+  // The polygons that are actually swept (after welding, if enabled).
+  let welded_polygons;
+  let (swept_subject, swept_clip) = if options.weld_tolerance > 0.0 {
+    welded_polygons =
+      weld_polygons(filled_subject, filled_clip, options.weld_tolerance);
+    if options.collect_warnings {
+      snapped_vertices += [
+        (filled_subject, &welded_polygons.0),
+        (filled_clip, &welded_polygons.1),
+      ]
+      .iter()
+      .map(|(polygon, welded)| {
+        polygon
+          .contours
+          .iter()
+          .flatten()
+          .zip(welded.contours.iter().flatten())
+          .filter(|(point, welded_point)| point != welded_point)
+          .count()
+      })
+      .sum::<usize>();
+    }
+    (&welded_polygons.0, &welded_polygons.1)
+  } else {
+    (filled_subject, filled_clip)
+  };
+
+  let result = if options.boundary_tolerance > 0.0 {
+    let aligned_polygons =
+      align_boundaries(swept_subject, swept_clip, options.boundary_tolerance);
+    if options.collect_warnings {
+      snapped_vertices += aligned_polygons.moved_clip_vertices(swept_clip);
+    }
+    compute_boolean(
+      &aligned_polygons.subject,
+      &aligned_polygons.clip,
+      rule,
+      options,
+    )
+    .map(|mut result| {
+      aligned_polygons.remap_source_edges(&mut result);
+      result
+    })
+    .map_err(|mut error| {
+      if let ClipError::InconsistentSweep { source_edge, .. } = &mut error {
+        aligned_polygons.remap_source_edge(source_edge);
+      }
+      error
+    })
+  } else {
+    match known_bounds {
+      Some((subject_bounds, clip_bounds))
+        if filled_polygons.is_none() && options.weld_tolerance <= 0.0 =>
+      {
+        compute_boolean_with_bounds(
+          swept_subject,
+          swept_clip,
+          subject_bounds,
+          clip_bounds,
+          rule,
+          options,
+        )
+      }
+      _ => compute_boolean(swept_subject, swept_clip, rule, options),
+    }
+  };
+  let mut result = match &filled_polygons {
+    Some(filled_polygons) => {
+      let mut result = result.map_err(|mut error| {
+        if let ClipError::InconsistentSweep { source_edge, .. } = &mut error {
+          filled_polygons.remap_source_edge(source_edge);
+        }
+        error
+      })?;
+      filled_polygons.remap_source_edges(&mut result);
+      result
+    }
+    None => result?,
+  };
+  if snapped_vertices > 0 {
+    warnings.push(ClipWarning::VerticesSnapped { count: snapped_vertices });
+  }
+  warnings.append(&mut result.warnings);
+  result.warnings = warnings;
+  Ok(finish_boolean(result, subject, clip, options))
+}
+
+// Creates a warning for every zero-length edge of `subject` and `clip`.
+fn degenerate_edge_warnings<'a>(
+  subject: &'a Polygon,
+  clip: &'a Polygon,
+) -> impl Iterator<Item = ClipWarning> + 'a {
+  [(subject, true), (clip, false)].into_iter().flat_map(
+    |(polygon, is_from_subject)| {
+      polygon.iter_edges().filter(|(_, _, start, end)| start == end).map(
+        move |(contour, edge, _, _)| ClipWarning::DegenerateEdge {
+          source_edge: SourceEdge { is_from_subject, contour, edge },
+        },
+      )
+    },
+  )
+}
+
+// Applies the post-processing requested by `options` to the `result` of a
+// boolean operation of `subject` and `clip` (e.g. orienting contours and
+// computing optional data), and clears any data that was not requested.
+fn finish_boolean(
+  mut result: BooleanResult,
+  subject: &Polygon,
+  clip: &Polygon,
+  options: &BooleanOptions,
 ) -> BooleanResult {
-  // Turns `polygon` into the corresponding `BooleanResult`.
-  fn polygon_to_boolean_result(
-    polygon: &Polygon,
-    is_subject: bool,
-  ) -> BooleanResult {
-    BooleanResult {
-      polygon: polygon.clone(),
-      contour_source_edges: polygon
-        .contours
-        .iter()
-        .enumerate()
-        .map(|(contour_index, contour)| {
-          (0..contour.len())
-            .map(|index| SourceEdge {
-              is_from_subject: is_subject,
-              contour: contour_index,
-              edge: index,
-            })
-            .collect()
-        })
-        .collect(),
+  // Trivially computed results reuse the input contours, so they have no
+  // hierarchy from the sweep.
+  let is_trivial =
+    result.contour_parents.len() != result.polygon.contours.len();
+  if is_trivial
+    && (options.track_hierarchy
+      || options.contour_order != ContourOrder::Discovery)
+  {
+    // The hierarchy has to be derived from the geometry instead.
+    result.contour_parents = compute_contour_parents(&result.polygon);
+  }
+  if options.collect_warnings {
+    if result.precision_escalated {
+      result.warnings.push(ClipWarning::PrecisionEscalated);
+    }
+    let coincident_edges = result
+      .contour_coincident_source_edges
+      .iter()
+      .flatten()
+      .filter(|source_edge| source_edge.is_some())
+      .count()
+      + result.overlap_segments.len();
+    if coincident_edges > 0 {
+      result
+        .warnings
+        .push(ClipWarning::CoincidentEdges { count: coincident_edges });
+    }
+  }
+  apply_coincident_edge_source(&mut result, options.coincident_edge_source);
+  if let Some(tolerance) = options.duplicate_vertex_tolerance {
+    for contour_index in 0..result.polygon.contours.len() {
+      let kept_vertices = vertices_apart_from_next(
+        &result.polygon.contours[contour_index],
+        tolerance,
+      );
+      result.retain_contour_vertices(contour_index, &kept_vertices);
+    }
+    result.update_bounds();
+    let keep = result
+      .polygon
+      .contours
+      .iter()
+      .map(|contour| contour.len() >= 3)
+      .collect::<Vec<_>>();
+    result.retain_contours_by_index(|index| keep[index]);
+    let count = keep.iter().filter(|&&keep| !keep).count();
+    if options.collect_warnings && count > 0 {
+      result.warnings.push(ClipWarning::CollapsedContoursRemoved { count });
+    }
+  }
+  if options.merge_collinear_edges {
+    merge_collinear_edges(&mut result);
+  }
+  if options.min_contour_area > 0.0 {
+    let keep = result
+      .polygon
+      .contours
+      .iter()
+      .map(|contour| {
+        contour_signed_area(contour).abs() >= options.min_contour_area
+      })
+      .collect::<Vec<_>>();
+    result.retain_contours_by_index(|index| keep[index]);
+    let count = keep.iter().filter(|&&keep| !keep).count();
+    if options.collect_warnings && count > 0 {
+      result.warnings.push(ClipWarning::SliverContoursRemoved { count });
     }
   }
+  if !is_trivial {
+    // Trivially computed results keep the orientation of the inputs, rather
+    // than deriving the hierarchy just to orient the copied contours.
+    let contour_parents = std::mem::take(&mut result.contour_parents);
+    orient_contours(&mut result, &contour_parents, options.output_orientation);
+    result.contour_parents = contour_parents;
+  }
+  order_contours(&mut result, options.contour_order);
+  if !options.track_hierarchy {
+    result.contour_parents.clear();
+  }
+  if !options.track_overlap_segments {
+    result.overlap_segments.clear();
+  }
+  #[cfg(feature = "timings")]
+  if !options.collect_timings {
+    result.timings = None;
+  }
+  if !options.track_contact_points {
+    result.contact_points.clear();
+  }
+  if !options.collect_warnings {
+    result.warnings.clear();
+  }
+  if !options.classify_relation {
+    result.relation = None;
+  } else if result.relation.is_none() {
+    // Results are only computed trivially if the polygons cannot overlap.
+    result.relation = Some(PolygonRelation::Disjoint);
+  }
+  if options.track_unmodified_edges {
+    result.contour_unmodified_edges =
+      compute_unmodified_edges(&result, subject, clip);
+  }
+  if options.track_edge_parameters {
+    result.contour_edge_parameters =
+      compute_edge_parameters(&result, subject, clip);
+  }
+  if options.track_vertex_errors {
+    result.contour_vertex_errors =
+      compute_vertex_errors(&result, subject, clip);
+  }
+  if options.track_input_equality {
+    result.equals_subject =
+      result_equals_input(&result, subject, /* is_subject= */ true);
+    result.equals_clip =
+      result_equals_input(&result, clip, /* is_subject= */ false);
+  }
+  result.is_empty = result.polygon.contours.is_empty();
+  result
+}
+
+// Turns `polygon` into the corresponding `BooleanResult`.
+fn polygon_to_boolean_result(
+  polygon: &Polygon,
+  is_subject: bool,
+) -> BooleanResult {
+  let mut result = BooleanResult {
+    polygon: polygon.clone(),
+    contour_source_edges: polygon
+      .contours
+      .iter()
+      .enumerate()
+      .map(|(contour_index, contour)| {
+        (0..contour.len())
+          .map(|index| SourceEdge {
+            is_from_subject: is_subject,
+            contour: contour_index,
+            edge: index,
+          })
+          .collect()
+      })
+      .collect(),
+    ..Default::default()
+  };
+  result.update_bounds();
+  result
+}
 
+// Computes the polygon and source edges of the boolean operation of `subject`
+// and `clip`.
+fn compute_boolean(
+  subject: &Polygon,
+  clip: &Polygon,
+  rule: Rule,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  compute_boolean_with_bounds(
+    subject,
+    clip,
+    subject.compute_bounds(),
+    clip.compute_bounds(),
+    rule,
+    options,
+  )
+}
+
+// Same as `compute_boolean`, but with the bounds of `subject` and `clip`
+// already computed.
+fn compute_boolean_with_bounds(
+  subject: &Polygon,
+  clip: &Polygon,
+  subject_bounds: Option<(Vec2, Vec2)>,
+  clip_bounds: Option<(Vec2, Vec2)>,
+  rule: Rule,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
   // This is just an optimization. If the bounding boxes of each polygon do not
   // intersect, we can trivially compute the boolean operation. This does mean
   // we won't "normalize" the polygons (e.g., removing empty contours), but that
   // is a totally fine tradeoff for the speed.
-  let subject_bounds = subject.compute_bounds();
-  let clip_bounds = clip.compute_bounds();
+  let Rule::Operation(operation) = rule else {
+    // Custom rules may keep edges anywhere, so they are always swept. An empty
+    // polygon has no edges, so it can take the bounds of the other polygon.
+    let Some(bounds) = subject_bounds.or(clip_bounds) else {
+      return Ok(BooleanResult::default());
+    };
+    return sweep_with_precision_fallback(
+      subject,
+      clip,
+      subject_bounds.unwrap_or(bounds),
+      clip_bounds.unwrap_or(bounds),
+      rule,
+      options,
+    );
+  };
   match (subject_bounds, clip_bounds) {
-    (None, None) => {
-      return BooleanResult {
-        polygon: Polygon { contours: vec![] },
-        contour_source_edges: vec![],
-      }
-    }
+    (None, None) => return Ok(BooleanResult::default()),
     (Some(_), None) => {
-      return if operation == Operation::Intersection {
-        BooleanResult {
-          polygon: Polygon { contours: vec![] },
-          contour_source_edges: vec![],
-        }
+      return Ok(if operation == Operation::Intersection {
+        BooleanResult::default()
       } else {
         polygon_to_boolean_result(subject, /* is_subject= */ true)
-      };
+      });
     }
     (None, Some(_)) => {
-      return if operation == Operation::Intersection
-        || operation == Operation::Difference
-      {
-        BooleanResult {
-          polygon: Polygon { contours: vec![] },
-          contour_source_edges: vec![],
-        }
-      } else {
-        polygon_to_boolean_result(clip, /* is_subject= */ false)
-      };
+      return Ok(
+        if operation == Operation::Intersection
+          || operation == Operation::Difference
+        {
+          BooleanResult::default()
+        } else {
+          polygon_to_boolean_result(clip, /* is_subject= */ false)
+        },
+      );
     }
     (Some((subject_min, subject_max)), Some((clip_min, clip_max))) => {
       if subject_max.x < clip_min.x
@@ -145,49 +1364,201 @@ fn perform_boolean(
         || clip_max.x < subject_min.x
         || clip_max.y < subject_min.y
       {
-        return match operation {
-          Operation::Intersection => BooleanResult {
-            polygon: Polygon { contours: vec![] },
-            contour_source_edges: vec![],
-          },
+        return Ok(match operation {
+          Operation::Intersection => BooleanResult::default(),
           Operation::Difference => {
             polygon_to_boolean_result(subject, /* is_subject= */ true)
           }
           Operation::Union | Operation::XOR => {
             let mut subject_result =
               polygon_to_boolean_result(subject, /* is_subject= */ true);
-            let mut clip_result =
-              polygon_to_boolean_result(clip, /* is_subject= */ false);
-            subject_result
-              .polygon
-              .contours
-              .append(&mut clip_result.polygon.contours);
-            subject_result
-              .contour_source_edges
-              .append(&mut clip_result.contour_source_edges);
+            subject_result.merge(polygon_to_boolean_result(
+              clip, /* is_subject= */ false,
+            ));
             subject_result
           }
-        };
+        });
       }
     }
   }
 
-  // We know the bounds are not None, since those cases are trivially computed.
-  let subject_bounds = subject_bounds.unwrap();
-  let clip_bounds = clip_bounds.unwrap();
+  // We know the bounds are not None, since those cases are trivially computed.
+  sweep_with_precision_fallback(
+    subject,
+    clip,
+    subject_bounds.unwrap(),
+    clip_bounds.unwrap(),
+    rule,
+    options,
+  )
+}
+
+// Computes the boolean operation of `subject` and `clip` by sweeping, in the
+// normalized frame if `options` asks for it (unless normalizing would merge
+// distinct input points). If rounding errors make the sweep inconsistent, it
+// is recomputed in f64 (see `sweep_with_f64_retry`). `subject_bounds` and
+// `clip_bounds` are the bounds of each polygon.
+fn sweep_with_precision_fallback(
+  subject: &Polygon,
+  clip: &Polygon,
+  subject_bounds: (Vec2, Vec2),
+  clip_bounds: (Vec2, Vec2),
+  rule: Rule,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  let normalize_coordinates = options.normalize_coordinates
+    || (options.auto_precision
+      && Precision::new((
+        subject_bounds.0.min(clip_bounds.0),
+        subject_bounds.1.max(clip_bounds.1),
+      ))
+      .should_normalize());
+  let max_events = options.max_events.unwrap_or(usize::MAX);
+  // Normalizing around the origin is more precise for coordinates far from the
+  // origin, but it is skipped if it would merge distinct input points.
+  let normalized = normalize_coordinates
+    .then(|| {
+      let mut normalization =
+        CoordinateNormalization::new(subject_bounds, clip_bounds);
+      let subject = normalization.normalize_polygon(subject);
+      let clip = normalization.normalize_polygon(clip);
+      (normalization, subject, clip)
+    })
+    .filter(|(normalization, _, _)| !normalization.merges_points());
+  let (mut result, precision_escalated) =
+    if let Some((normalization, normalized_subject, normalized_clip)) =
+      &normalized
+    {
+      sweep_with_f64_retry(|arithmetic| {
+        let mut result = sweep_boolean(
+          normalized_subject,
+          normalized_clip,
+          normalization.normalize_bounds(subject_bounds),
+          normalization.normalize_bounds(clip_bounds),
+          rule,
+          arithmetic,
+          options,
+        )
+        .map_err(|mut error| {
+          // Report where the sweep failed in the original coordinates.
+          if let SweepError::Inconsistent { point, .. } = &mut error {
+            normalization.denormalize_points(std::slice::from_mut(point));
+          }
+          error
+        })?;
+        normalization.denormalize_polygon(&mut result.polygon);
+        // Restored input points are not exactly the denormalized points, so the
+        // bounds are computed again.
+        result.update_bounds();
+        normalization.denormalize_segments(&mut result.overlap_segments);
+        normalization.denormalize_points(&mut result.contact_points);
+        Ok(result)
+      })
+    } else {
+      sweep_with_f64_retry(|arithmetic| {
+        sweep_boolean(
+          subject,
+          clip,
+          subject_bounds,
+          clip_bounds,
+          rule,
+          arithmetic,
+          options,
+        )
+      })
+    }
+    .map_err(|error| error.into_clip_error(max_events))?;
+  result.precision_escalated = precision_escalated;
+  Ok(result)
+}
+
+// The reasons a sweep can fail.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum SweepError {
+  // The sweep became inconsistent: an edge could not be found in the sweep
+  // line where its own ordering says it must be, or the result edges could not
+  // be joined into contours. This happens when rounding errors cause edges to
+  // compare differently over the course of the sweep. The fields match those
+  // of `ClipError::InconsistentSweep`.
+  Inconsistent {
+    stage: SweepStage,
+    point: Vec2,
+    source_edge: SourceEdge,
+    sweep_line_len: usize,
+  },
+  // The sweep needed more events than allowed.
+  CapacityExceeded,
+}
+
+impl SweepError {
+  // Creates an `Inconsistent` error for the sweep failing in `stage` while
+  // processing `event`, with `sweep_line_len` edges in the sweep line.
+  fn inconsistent(
+    stage: SweepStage,
+    event: &Event,
+    event_relations: &[EventRelation],
+    sweep_line_len: usize,
+  ) -> Self {
+    SweepError::Inconsistent {
+      stage,
+      point: event.point,
+      source_edge: event_relations[event.event_id].source_edge,
+      sweep_line_len,
+    }
+  }
+
+  // Converts the error into the corresponding `ClipError`. `max_events` is the
+  // limit on the number of events that the sweep was run with.
+  fn into_clip_error(self, max_events: usize) -> ClipError {
+    match self {
+      SweepError::Inconsistent {
+        stage,
+        point,
+        source_edge,
+        sweep_line_len,
+      } => ClipError::InconsistentSweep {
+        stage,
+        point,
+        source_edge,
+        sweep_line_len,
+      },
+      SweepError::CapacityExceeded => {
+        ClipError::CapacityExceeded { limit: max_events }
+      }
+    }
+  }
+}
 
+// Computes the boolean operation of `subject` and `clip` by sweeping over their
+// edges. `subject_bounds` and `clip_bounds` are the bounds of each polygon. The
+// sweep is computed with `arithmetic`, and fails if it needs more than
+// `BooleanOptions::max_events` events. Only the optional data requested by
+// `options` is collected.
+fn sweep_boolean(
+  subject: &Polygon,
+  clip: &Polygon,
+  subject_bounds: (Vec2, Vec2),
+  clip_bounds: (Vec2, Vec2),
+  rule: Rule,
+  arithmetic: Arithmetic,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, SweepError> {
+  #[cfg(feature = "timings")]
+  let start_time = std::time::Instant::now();
   let mut event_queue = BinaryHeap::new();
   let mut event_relations = Vec::new();
 
-  let x_limit = match operation {
-    Operation::Intersection => subject_bounds.1.x.min(clip_bounds.1.x),
-    Operation::Difference => subject_bounds.1.x,
-    Operation::Union | Operation::XOR => INFINITY,
+  let x_limit = match rule {
+    Rule::Operation(operation) => {
+      compute_x_limit(subject_bounds, clip_bounds, operation)
+    }
+    Rule::Custom(_) => f32::INFINITY,
   };
 
   create_events_for_polygon(
     subject,
     /* is_subject= */ true,
+    arithmetic,
     &mut event_queue,
     &mut event_relations,
     x_limit,
@@ -195,14 +1566,102 @@ fn perform_boolean(
   create_events_for_polygon(
     clip,
     /* is_subject= */ false,
+    arithmetic,
     &mut event_queue,
     &mut event_relations,
     x_limit,
   );
+  #[cfg(feature = "timings")]
+  let event_creation = start_time.elapsed();
+
+  let result = sweep_event_queue(
+    event_queue,
+    event_relations,
+    subject_bounds,
+    clip_bounds,
+    &rule,
+    x_limit,
+    options,
+  )?;
+  #[cfg(feature = "timings")]
+  let result = BooleanResult {
+    timings: result
+      .timings
+      .map(|timings| Timings { event_creation, ..timings }),
+    ..result
+  };
+  Ok(result)
+}
+
+// Computes the x coordinate past which no event can affect the result of
+// `operation`, given the bounds of each polygon.
+fn compute_x_limit(
+  subject_bounds: (Vec2, Vec2),
+  clip_bounds: (Vec2, Vec2),
+  operation: Operation,
+) -> f32 {
+  match operation {
+    Operation::Intersection => subject_bounds.1.x.min(clip_bounds.1.x),
+    Operation::Difference => subject_bounds.1.x,
+    Operation::Union | Operation::XOR => INFINITY,
+  }
+}
 
-  let result_events =
-    subdivide_edges(event_queue, &mut event_relations, operation, x_limit);
-  join_contours(result_events, event_relations, operation)
+// Computes the boolean operation from the events of both polygons (see
+// `sweep_boolean`), which have already been created.
+fn sweep_event_queue(
+  event_queue: BinaryHeap<Reverse<Event>>,
+  mut event_relations: Vec<EventRelation>,
+  subject_bounds: (Vec2, Vec2),
+  clip_bounds: (Vec2, Vec2),
+  rule: &dyn BooleanRule,
+  x_limit: f32,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, SweepError> {
+  #[cfg(feature = "timings")]
+  let start_time = std::time::Instant::now();
+  let max_events = options.max_events.unwrap_or(usize::MAX);
+  let processed_events =
+    sweep_events(event_queue, &mut event_relations, rule, x_limit, max_events)?;
+  #[cfg(feature = "timings")]
+  let subdivision = start_time.elapsed();
+  #[cfg(feature = "timings")]
+  let start_time = std::time::Instant::now();
+  let relation = options.classify_relation.then(|| {
+    classify_relation(
+      &processed_events,
+      &event_relations,
+      subject_bounds,
+      clip_bounds,
+      x_limit,
+    )
+  });
+  // Overlap segments are also counted by `ClipWarning::CoincidentEdges`.
+  let overlap_segments =
+    if options.track_overlap_segments || options.collect_warnings {
+      collect_overlap_segments(&processed_events, &event_relations)
+    } else {
+      Vec::new()
+    };
+  let contact_points = if options.track_contact_points {
+    collect_contact_points(&processed_events, &event_relations)
+  } else {
+    Vec::new()
+  };
+  let result_events = select_result_events(processed_events, &event_relations);
+  let mut result = join_contours(result_events, event_relations, rule)?;
+  result.relation = relation;
+  result.overlap_segments = overlap_segments;
+  result.contact_points = contact_points;
+  #[cfg(feature = "timings")]
+  {
+    result.timings = Some(Timings {
+      event_creation: std::time::Duration::ZERO,
+      subdivision,
+      contour_join: start_time.elapsed(),
+    });
+  }
+  Ok(result)
 }
 
 // An "event" of an edge. Each edge of a polygon is comprised of a "left" event
@@ -223,6 +1682,9 @@ struct Event {
   // It is just provided to determine the line that the edge sits on (which
   // also can never change).
   other_point: Vec2,
+  // The arithmetic this event is ordered and intersected with. This is the
+  // same for every event of a sweep.
+  arithmetic: Arithmetic,
 }
 
 impl PartialEq for Event {
@@ -231,22 +1693,28 @@ impl PartialEq for Event {
   }
 }
 
-impl PartialOrd for Event {
-  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+impl Ord for Event {
+  fn cmp(&self, other: &Self) -> std::cmp::Ordering {
     // This is primarily used for a min heap, so here we will say "prefer" to
     // mean less.
 
+    // Events are equal iff they have the same ID. The rest of the event (e.g.
+    // `other_point`) may be reconstructed for searching, so don't compare it.
+    if self.event_id == other.event_id {
+      return std::cmp::Ordering::Equal;
+    }
+
     // The first thing that matters is the order of points.
     match lex_order_points(&self.point, &other.point) {
       std::cmp::Ordering::Equal => {}
-      ord => return Some(ord),
+      ord => return ord,
     }
     // Prefer right events to left events. This way the sweep line will contain
     // fewer edges (and be more accurate) as right events remove edges from the
     // sweep line.
     match self.left.cmp(&other.left) {
       std::cmp::Ordering::Equal => {}
-      ord => return Some(ord),
+      ord => return ord,
     }
     // Prefer horizontal edges to vertical edges. Edges use the previous edge in
     // the sweep line to determine whether they are in the result. If we don't
@@ -255,11 +1723,11 @@ impl PartialOrd for Event {
     // vertical edge will have nothing in the sweep line to compare against).
     match self.is_vertical().cmp(&other.is_vertical()) {
       std::cmp::Ordering::Equal => {}
-      ord => return Some(ord),
+      ord => return ord,
     }
     // We know the events share the same point. Prefer the line which slopes
     // above the other one.
-    match point_relative_to_line(
+    match self.arithmetic.point_relative_to_line(
       self.point,
       self.other_point,
       other.other_point,
@@ -267,108 +1735,111 @@ impl PartialOrd for Event {
       std::cmp::Ordering::Equal => {}
       // If this is a right point, then the point and other_point are in the
       // wrong order, so reverse the ordering.
-      ord => return if self.left { Some(ord) } else { Some(ord.reverse()) },
+      ord => return if self.left { ord } else { ord.reverse() },
     }
     // Prefer subject edges over clip edges.
     match self.is_subject.cmp(&other.is_subject) {
       std::cmp::Ordering::Equal => {}
-      ord => return Some(ord.reverse()),
+      ord => return ord.reverse(),
+    }
+    // The edges are collinear and in the same direction, so prefer the shorter
+    // edge. This keeps the order (and so the result) independent of the order
+    // that events were created in (e.g. the order of the input contours).
+    match lex_order_points(&self.other_point, &other.other_point) {
+      std::cmp::Ordering::Equal => {}
+      ord => return if self.left { ord } else { ord.reverse() },
     }
 
-    Some(self.event_id.cmp(&other.event_id))
+    // The edges are identical, so the only remaining difference is the ID.
+    self.event_id.cmp(&other.event_id)
+  }
+}
+
+impl PartialOrd for Event {
+  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    Some(self.cmp(other))
   }
 }
 
 impl Eq for Event {}
 
-impl Ord for Event {
-  fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-    self.partial_cmp(other).unwrap()
-  }
+// Compares `a` and `b`. This never fails: NaN (which `validate_input` rules
+// out) is ordered after every other value rather than panicking, so a bad value
+// can at worst produce a bad result.
+fn compare_f32(a: f32, b: f32) -> std::cmp::Ordering {
+  a.partial_cmp(&b).unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
 }
 
 // Determine the lexical order of `a` and `b`. In other words, sort by x, then
 // y.
 fn lex_order_points(a: &Vec2, b: &Vec2) -> std::cmp::Ordering {
-  match a.x.partial_cmp(&b.x) {
-    Some(std::cmp::Ordering::Equal) => {}
-    Some(ord) => return ord,
-    None => panic!(),
-  }
-  match a.y.partial_cmp(&b.y) {
-    Some(std::cmp::Ordering::Equal) => {}
-    Some(ord) => return ord,
-    None => panic!(),
-  }
-  std::cmp::Ordering::Equal
+  compare_f32(a.x, b.x).then_with(|| compare_f32(a.y, b.y))
 }
 
 impl Event {
-  // Determines whether the edge is a vertical edge.
+  // Determines whether the edge is a vertical edge (see the "Vertical edges"
+  // section of the README).
   fn is_vertical(&self) -> bool {
     (self.point.x - self.other_point.x).abs() < EPSILON
   }
 
-  // Determine whether `self` and `relation` imply the edge is in the result
-  // based on the operation.
-  fn in_result(&self, relation: &EventRelation, operation: Operation) -> bool {
-    if relation.edge_coincidence_type != EdgeCoincidenceType::NoCoincidence {
-      return relation.edge_coincidence_type.in_result(operation);
-    }
-    match operation {
-      // The edge is in the result iff it is inside the other polygon, aka if
-      // the closest edge below is an out-in transition.
-      Operation::Intersection => !relation.other_in_out,
-      // The edge is in the result iff it is outside the other polygon, aka if
-      // the closest edge below is an in-out transition (or is non-existent).
-      Operation::Union => relation.other_in_out,
-      // The edge is in the result iff it is either the subject and we are
-      // outside the clip polygon (the closest edge below is an in-out
-      // transition), or it is the clip and we are inside the clip polygon (the
-      // closest edge below is an out-in transition).
-      Operation::Difference => self.is_subject == relation.other_in_out,
-      // Every edge is part of the result.
-      Operation::XOR => true,
+  // Describes the edge of `self` and `relation` for a `BooleanRule`.
+  fn edge_context(&self, relation: &EventRelation) -> EdgeContext {
+    let inside_self_above = !relation.in_out;
+    // Coincident edges change both polygons, which either agree or disagree
+    // with each other on the transition.
+    let (inside_other_below, inside_other_above) =
+      match relation.edge_coincidence_type {
+        EdgeCoincidenceType::NoCoincidence
+        | EdgeCoincidenceType::DuplicateCoincidence => {
+          (!relation.other_in_out, !relation.other_in_out)
+        }
+        EdgeCoincidenceType::SameTransition => {
+          (!inside_self_above, inside_self_above)
+        }
+        EdgeCoincidenceType::DifferentTransition => {
+          (inside_self_above, !inside_self_above)
+        }
+      };
+    let (self_regions, other_regions) = (
+      (!inside_self_above, inside_self_above),
+      (inside_other_below, inside_other_above),
+    );
+    let (subject_regions, clip_regions) = if self.is_subject {
+      (self_regions, other_regions)
+    } else {
+      (other_regions, self_regions)
+    };
+    EdgeContext {
+      is_subject: self.is_subject,
+      in_subject_below: subject_regions.0,
+      in_subject_above: subject_regions.1,
+      in_clip_below: clip_regions.0,
+      in_clip_above: clip_regions.1,
     }
   }
 
+  // Determine whether `self` and `relation` imply the edge is in the result
+  // according to `rule`. Only one of a pair of coincident edges can be in the
+  // result.
+  fn in_result(
+    &self,
+    relation: &EventRelation,
+    rule: &dyn BooleanRule,
+  ) -> bool {
+    relation.edge_coincidence_type != EdgeCoincidenceType::DuplicateCoincidence
+      && rule.in_result(&self.edge_context(relation))
+  }
+
   // Determines whether `self` and `relation` that are in the result is an
   // in-out transition or not.
   fn result_in_out(
     &self,
     relation: &EventRelation,
-    operation: Operation,
+    rule: &dyn BooleanRule,
   ) -> bool {
-    // These variables make the logic below more intuitive (e.g. a && b for
-    // intersection).
-    let is_inside_self = !relation.in_out;
-    let is_inside_other = !relation.other_in_out;
-
-    // For all of these cases, we already know the edge is in the result.
-    let result_out_in = match operation {
-      // The edge is an out-in transition iff we are inside both this polygon
-      // and the other polygon.
-      Operation::Intersection => is_inside_self && is_inside_other,
-      // The edge is an out-in transition iff we are inside either this polygon
-      // or the other polygon.
-      Operation::Union => is_inside_self || is_inside_other,
-      // The edge is an out-in transition iff we are in the subject polygon and
-      // not in the clip polygon (nothing was subtracted), or we are in the clip
-      // polygon and not in the subject (since we know the edge is in the
-      // result, we must have just left the subject polygon).
-      Operation::Difference => {
-        if self.is_subject {
-          is_inside_self && !is_inside_other
-        } else {
-          !is_inside_self && is_inside_other
-        }
-      }
-      // The edge is an out-in transition iff the polygons are in opposite
-      // states.
-      Operation::XOR => is_inside_self != is_inside_other,
-    };
-
-    !result_out_in
+    rule.result_transition(&self.edge_context(relation))
+      == InOut::InsideToOutside
   }
 }
 
@@ -376,7 +1847,65 @@ impl Event {
 // by `a` and `b`. Note if b is to the left of a, the returned ordering will be
 // reversed.
 fn point_relative_to_line(a: Vec2, b: Vec2, point: Vec2) -> std::cmp::Ordering {
-  0.0.partial_cmp(&(b - a).perp_dot(point - a)).unwrap()
+  compare_f32(0.0, (b - a).perp_dot(point - a))
+}
+
+// The arithmetic a sweep orders and intersects edges with. Sweeps are computed
+// in f32 first, since that is faster, and recomputed in f64 if rounding errors
+// made them inconsistent (see `sweep_with_f64_retry`).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Arithmetic {
+  F32,
+  // Orientation tests are computed in f64, where the products of nearby f32
+  // coordinates are exact, and intersections use `edge_intersection_f64`.
+  // Only split points are rounded back to f32.
+  F64,
+}
+
+impl Arithmetic {
+  // Same as `point_relative_to_line`, but computed with this arithmetic.
+  fn point_relative_to_line(
+    self,
+    a: Vec2,
+    b: Vec2,
+    point: Vec2,
+  ) -> std::cmp::Ordering {
+    match self {
+      Arithmetic::F32 => point_relative_to_line(a, b, point),
+      Arithmetic::F64 => {
+        let (a, b, point) = (a.as_dvec2(), b.as_dvec2(), point.as_dvec2());
+        let cross = (b - a).perp_dot(point - a);
+        // Like `compare_f32`, NaN is ordered after every other value.
+        0.0f64.partial_cmp(&cross).unwrap_or(std::cmp::Ordering::Less)
+      }
+    }
+  }
+
+  // Finds the intersection of two edges with this arithmetic (see
+  // `edge_intersection`).
+  fn edge_intersection(
+    self,
+    line_1: (Vec2, Vec2),
+    line_2: (Vec2, Vec2),
+  ) -> EdgeIntersectionResult {
+    match self {
+      Arithmetic::F32 => edge_intersection_in_local_frame(line_1, line_2),
+      Arithmetic::F64 => edge_intersection_f64(line_1, line_2),
+    }
+  }
+}
+
+// Runs `sweep` in f32, and again in f64 if rounding errors made it
+// inconsistent. Returns the result along with whether f64 was needed.
+fn sweep_with_f64_retry<T>(
+  mut sweep: impl FnMut(Arithmetic) -> Result<T, SweepError>,
+) -> Result<(T, bool), SweepError> {
+  match sweep(Arithmetic::F32) {
+    Err(SweepError::Inconsistent { .. }) => {
+      sweep(Arithmetic::F64).map(|result| (result, true))
+    }
+    result => result.map(|result| (result, false)),
+  }
 }
 
 // The relationship of the event to the rest of the edges. While `Event` is
@@ -399,11 +1928,20 @@ struct EventRelation {
   in_result: bool,
   // The ID of the previous event in the sweep line that was in the result.
   prev_in_result: Option<usize>,
+  // The ID of the event below this one in the sweep line that its flags were
+  // last computed from. Unlike `prev_in_result`, this does not depend on the
+  // rule, so the flags can be recomputed for other rules (see
+  // `boolean_all`).
+  prev_event_id: Option<usize>,
   // The type of coincidence between another edge.
   edge_coincidence_type: EdgeCoincidenceType,
   // The edge that this event comes from. This can change for coincident edges
   // to prefer to report the subject edge.
   source_edge: SourceEdge,
+  // The clip edge that coincides with this edge, if this is the primary edge
+  // of a coincident subject and clip edge (in which case `source_edge` is the
+  // subject edge).
+  coincident_source_edge: Option<SourceEdge>,
 }
 
 // The type of edge coincidence (overlapping edges).
@@ -423,26 +1961,12 @@ enum EdgeCoincidenceType {
   DuplicateCoincidence,
 }
 
-impl EdgeCoincidenceType {
-  fn in_result(&self, operation: Operation) -> bool {
-    match self {
-      EdgeCoincidenceType::NoCoincidence => panic!(),
-      EdgeCoincidenceType::DuplicateCoincidence => false,
-      EdgeCoincidenceType::SameTransition => {
-        operation == Operation::Intersection || operation == Operation::Union
-      }
-      EdgeCoincidenceType::DifferentTransition => {
-        operation == Operation::Difference
-      }
-    }
-  }
-}
-
-// Creates a left and right event for each edge in the polygon. Returns the
-// bounds of the polygon for convenience.
+// Creates a left and right event for each edge in the polygon, computed with
+// `arithmetic`.
 fn create_events_for_polygon(
   polygon: &Polygon,
   is_subject: bool,
+  arithmetic: Arithmetic,
   event_queue: &mut BinaryHeap<Reverse<Event>>,
   event_relations: &mut Vec<EventRelation>,
   x_limit: f32,
@@ -459,55 +1983,117 @@ fn create_events_for_polygon(
       if x_limit < point_1.x.min(point_2.x) {
         continue;
       }
-      let (event_1_left, event_2_left) =
-        match lex_order_points(&point_1, &point_2) {
-          std::cmp::Ordering::Equal => continue, // Ignore degenerate edges.
-          std::cmp::Ordering::Less => (true, false),
-          std::cmp::Ordering::Greater => (false, true),
-        };
-
-      let event_id_1 = event_relations.len();
-      let event_id_2 = event_relations.len() + 1;
-
-      event_queue.push(Reverse(Event {
-        event_id: event_id_1,
-        point: point_1,
-        left: event_1_left,
-        is_subject,
-        other_point: point_2,
-      }));
-      event_queue.push(Reverse(Event {
-        event_id: event_id_2,
-        point: point_2,
-        left: event_2_left,
-        is_subject,
-        other_point: point_1,
-      }));
-
-      event_relations.push(EventRelation {
-        sibling_id: event_id_2,
-        sibling_point: point_2,
-        source_edge: SourceEdge {
-          is_from_subject: is_subject,
-          contour: contour_index,
-          edge: point_index,
-        },
-        ..Default::default()
-      });
-      event_relations.push(EventRelation {
-        sibling_id: event_id_1,
-        sibling_point: point_1,
-        source_edge: SourceEdge {
-          is_from_subject: is_subject,
-          contour: contour_index,
-          edge: point_index,
-        },
-        ..Default::default()
-      });
+      let source_edge = SourceEdge {
+        is_from_subject: is_subject,
+        contour: contour_index,
+        edge: point_index,
+      };
+      if let Some(events) = create_events_for_edge(
+        point_1,
+        point_2,
+        source_edge,
+        arithmetic,
+        event_relations,
+      ) {
+        event_queue.extend(events.map(Reverse));
+      }
     }
   }
 }
 
+// Splits the edges of `polygon` wherever they intersect each other (including
+// where they overlap), by sweeping over `polygon` on its own. Returns each
+// split edge as its left and right points along with its source edge, in the
+// order the sweep processed them. Overlapping edges are all kept. The sweep is
+// computed with `arithmetic`.
+fn split_polygon_edges(
+  polygon: &Polygon,
+  is_subject: bool,
+  arithmetic: Arithmetic,
+) -> Result<Vec<(Vec2, Vec2, SourceEdge)>, SweepError> {
+  let mut event_queue = BinaryHeap::new();
+  let mut event_relations = Vec::new();
+  create_events_for_polygon(
+    polygon,
+    is_subject,
+    arithmetic,
+    &mut event_queue,
+    &mut event_relations,
+    f32::INFINITY,
+  );
+  // XOR does not skip any events.
+  let processed_events = sweep_events(
+    event_queue,
+    &mut event_relations,
+    &Operation::XOR,
+    f32::INFINITY,
+    /* max_events= */ usize::MAX,
+  )?;
+  Ok(
+    processed_events
+      .iter()
+      .filter(|event| event.left)
+      .map(|event| {
+        let relation = &event_relations[event.event_id];
+        (event.point, relation.sibling_point, relation.source_edge)
+      })
+      .collect(),
+  )
+}
+
+// Creates the two events of the edge from `point_1` to `point_2` (and their
+// relations in `event_relations`), computed with `arithmetic`. Returns None for
+// degenerate edges, which have no events.
+fn create_events_for_edge(
+  point_1: Vec2,
+  point_2: Vec2,
+  source_edge: SourceEdge,
+  arithmetic: Arithmetic,
+  event_relations: &mut Vec<EventRelation>,
+) -> Option<[Event; 2]> {
+  let (event_1_left, event_2_left) = match lex_order_points(&point_1, &point_2)
+  {
+    std::cmp::Ordering::Equal => return None, // Ignore degenerate edges.
+    std::cmp::Ordering::Less => (true, false),
+    std::cmp::Ordering::Greater => (false, true),
+  };
+
+  let event_id_1 = event_relations.len();
+  let event_id_2 = event_relations.len() + 1;
+  let is_subject = source_edge.is_from_subject;
+
+  event_relations.push(EventRelation {
+    sibling_id: event_id_2,
+    sibling_point: point_2,
+    source_edge,
+    ..Default::default()
+  });
+  event_relations.push(EventRelation {
+    sibling_id: event_id_1,
+    sibling_point: point_1,
+    source_edge,
+    ..Default::default()
+  });
+  Some([
+    Event {
+      event_id: event_id_1,
+      point: point_1,
+      left: event_1_left,
+      is_subject,
+      other_point: point_2,
+      arithmetic,
+    },
+    Event {
+      event_id: event_id_2,
+      point: point_2,
+      left: event_2_left,
+      is_subject,
+      other_point: point_1,
+      arithmetic,
+    },
+  ])
+}
+
 // An event that can be sorted into the sweep line. The sweep line data
 // structure will hold the edges currently intersecting the sweep line in
 // order from top to bottom. Note the event will always be a left event, since
@@ -520,68 +2106,74 @@ impl PartialEq for SweepLineEvent {
     self.0.event_id == other.0.event_id
   }
 }
-impl PartialOrd for SweepLineEvent {
-  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+impl Ord for SweepLineEvent {
+  fn cmp(&self, other: &Self) -> std::cmp::Ordering {
     // We want to sort the events (i.e., edges) by their height. For edges that
     // cross, we want to order by their leftmost points. Note since these events
     // are part of the sweep line, we can assume the sweep line intersects both
     // lines at at least one X coordinate).
 
-    // Pick the leftmost point.
-    if self.0.point.x < other.0.point.x {
+    // Pick the leftmost point. If the edges start at the same X coordinate,
+    // never pick a vertical edge, since its line cannot order points at its own
+    // X coordinate. This keeps the ordering antisymmetric.
+    if self.0.point.x < other.0.point.x
+      || (self.0.point.x == other.0.point.x
+        && other.0.is_vertical()
+        && !self.0.is_vertical())
+    {
       // Use `self's line to determine the ordering.
-      match point_relative_to_line(
+      match self.0.arithmetic.point_relative_to_line(
         self.0.point,
         self.0.other_point,
         other.0.point,
       ) {
         std::cmp::Ordering::Equal => {}
-        order => return Some(order),
+        order => return order,
       }
 
       // `other`s left point is on `self`s line, so use `other`s right point to
       // order the edges.
-      match point_relative_to_line(
+      match self.0.arithmetic.point_relative_to_line(
         self.0.point,
         self.0.other_point,
         other.0.other_point,
       ) {
         std::cmp::Ordering::Equal => {}
-        order => return Some(order),
+        order => return order,
       }
     } else {
       // Use `other`s line to determine the ordering.
-      match point_relative_to_line(
+      match self.0.arithmetic.point_relative_to_line(
         other.0.point,
         other.0.other_point,
         self.0.point,
       ) {
         std::cmp::Ordering::Equal => {}
-        order => return Some(order.reverse()),
+        order => return order.reverse(),
       }
 
       // `self`s left point is on `other`s line, so use `self`s right point to
       // order the edges.
-      match point_relative_to_line(
+      match self.0.arithmetic.point_relative_to_line(
         other.0.point,
         other.0.other_point,
         self.0.other_point,
       ) {
         std::cmp::Ordering::Equal => {}
-        order => return Some(order.reverse()),
+        order => return order.reverse(),
       }
     }
 
     // The lines are colinear. Just order by the events to disambiguate.
-    self.0.partial_cmp(&other.0)
+    self.0.cmp(&other.0)
   }
 }
-impl Eq for SweepLineEvent {}
-impl Ord for SweepLineEvent {
-  fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-    self.partial_cmp(other).unwrap()
+impl PartialOrd for SweepLineEvent {
+  fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+    Some(self.cmp(other))
   }
 }
+impl Eq for SweepLineEvent {}
 
 // Check for intersections between two events in the sweep line. `new_event` is
 // the event just inserted into the sweep line and `existing_event` is the event
@@ -591,9 +2183,9 @@ fn check_for_intersection(
   existing_event: &Event,
   event_queue: &mut BinaryHeap<Reverse<Event>>,
   event_relations: &mut Vec<EventRelation>,
-  operation: Operation,
+  rule: &dyn BooleanRule,
 ) {
-  match edge_intersection(
+  match new_event.arithmetic.edge_intersection(
     (new_event.point, event_relations[new_event.event_id].sibling_point),
     (
       existing_event.point,
@@ -687,11 +2279,19 @@ fn check_for_intersection(
       // We say the "primary" coincident edge is the one that will represent
       // both edges. The "duplicate" coincident edge will not contribute to the
       // final polygon.
-      let (primary_edge_event_id, duplicate_edge_event_id) =
+      let (primary_edge_event_id, duplicate_edge_event_id, primary_event) =
         if event_relations[existing_event_coincident_event_id].in_result {
-          (existing_event_coincident_event_id, new_event_coincident_event_id)
+          (
+            existing_event_coincident_event_id,
+            new_event_coincident_event_id,
+            existing_event,
+          )
         } else {
-          (new_event_coincident_event_id, existing_event_coincident_event_id)
+          (
+            new_event_coincident_event_id,
+            existing_event_coincident_event_id,
+            new_event,
+          )
         };
       // In the final result, we want to prefer subject edges over clip edges,
       // so change the primary edge (which is the only one possibly in the
@@ -703,19 +2303,28 @@ fn check_for_intersection(
         // Neither edge is "preferred", so just go with the defaults.
         (true, true) => {}
         (false, false) => {}
-        // The subject edge should be preferred, so assign those.
-        (true, false) => {
-          let source_edge = event_relations[new_event.event_id].source_edge;
-          event_relations[primary_edge_event_id].source_edge = source_edge;
-          let sibling_id = event_relations[primary_edge_event_id].sibling_id;
-          event_relations[sibling_id].source_edge = source_edge;
-        }
-        (false, true) => {
-          let source_edge =
-            event_relations[existing_event.event_id].source_edge;
-          event_relations[primary_edge_event_id].source_edge = source_edge;
+        // The subject edge should be preferred, so assign those. The clip
+        // edge is kept as well, so the preference can be changed afterwards
+        // (see `CoincidentEdgeSource`).
+        (true, false) | (false, true) => {
+          let (subject_event_id, clip_event_id) = if event_relations
+            [new_event.event_id]
+            .source_edge
+            .is_from_subject
+          {
+            (new_event.event_id, existing_event.event_id)
+          } else {
+            (existing_event.event_id, new_event.event_id)
+          };
+          let source_edge = event_relations[subject_event_id].source_edge;
+          let coincident_source_edge =
+            Some(event_relations[clip_event_id].source_edge);
           let sibling_id = event_relations[primary_edge_event_id].sibling_id;
-          event_relations[sibling_id].source_edge = source_edge;
+          for event_id in [primary_edge_event_id, sibling_id] {
+            event_relations[event_id].source_edge = source_edge;
+            event_relations[event_id].coincident_source_edge =
+              coincident_source_edge;
+          }
         }
       }
 
@@ -726,7 +2335,7 @@ fn check_for_intersection(
         EdgeCoincidenceType::DifferentTransition
       };
       primary_edge_relation.in_result =
-        primary_edge_relation.edge_coincidence_type.in_result(operation);
+        primary_event.in_result(primary_edge_relation, rule);
 
       let duplicate_edge_relation =
         &mut event_relations[duplicate_edge_event_id];
@@ -746,9 +2355,14 @@ fn split_edge(
   event_queue: &mut BinaryHeap<Reverse<Event>>,
   event_relations: &mut Vec<EventRelation>,
 ) -> usize {
-  let (sibling_id, sibling_point, source_edge) = {
+  let (sibling_id, sibling_point, source_edge, coincident_source_edge) = {
     let relation = &event_relations[edge_event.event_id];
-    (relation.sibling_id, relation.sibling_point, relation.source_edge)
+    (
+      relation.sibling_id,
+      relation.sibling_point,
+      relation.source_edge,
+      relation.coincident_source_edge,
+    )
   };
 
   let split_1_id = event_relations.len();
@@ -760,6 +2374,7 @@ fn split_edge(
     left: false,
     is_subject: edge_event.is_subject,
     other_point: edge_event.point,
+    arithmetic: edge_event.arithmetic,
   }));
   event_queue.push(Reverse(Event {
     event_id: split_2_id,
@@ -767,18 +2382,21 @@ fn split_edge(
     left: true,
     is_subject: edge_event.is_subject,
     other_point: edge_event.other_point,
+    arithmetic: edge_event.arithmetic,
   }));
 
   event_relations.push(EventRelation {
     sibling_id: edge_event.event_id,
     sibling_point: edge_event.point,
     source_edge,
+    coincident_source_edge,
     ..Default::default()
   });
   event_relations.push(EventRelation {
     sibling_id,
     sibling_point,
     source_edge,
+    coincident_source_edge,
     ..Default::default()
   });
 
@@ -799,18 +2417,20 @@ fn split_edge(
 fn set_information(
   (event, event_relation): (&Event, &mut EventRelation),
   prev_event: Option<(&Event, &EventRelation)>,
-  operation: Operation,
+  rule: &dyn BooleanRule,
 ) {
   match prev_event {
     None => {
       // There is no previous event, so this must be the external contour of
       // one of the polygons.
+      event_relation.prev_event_id = None;
       event_relation.in_out = false;
       // Even if there is no previous event, we mark it as an in-out
       // transition since this treats the other as being "outside".
       event_relation.other_in_out = true;
     }
     Some((prev_event, prev_event_relation)) => {
+      event_relation.prev_event_id = Some(prev_event.event_id);
       if event.is_subject == prev_event.is_subject {
         // The events are from the same polygon, so this event should be the
         // opposite of `prev_event`.
@@ -847,21 +2467,79 @@ fn set_information(
     }
   }
 
-  event_relation.in_result = event.in_result(event_relation, operation);
+  event_relation.in_result = event.in_result(event_relation, rule);
+}
+
+// Selects the events of `processed_events` (from `sweep_events`) corresponding
+// to the edges that are in the final result.
+fn select_result_events(
+  mut result: Vec<Event>,
+  event_relations: &[EventRelation],
+) -> Vec<Event> {
+  // Only keep events that are still in the result by the end. With no
+  // coincident edges, in_result can never change after the first pass. However,
+  // with coincident edges, an edge that previously thought it was in the result
+  // may no longer be in the result. Consider the subject and the clip being the
+  // same, and the operation being difference or XOR. The first subject edge
+  // will have no previous event in the sweep line, so it will think it is in
+  // the result. Then the clip edge will be processed and now the edge is no
+  // longer in the result.
+  result.retain(|event| event_relations[event.event_id].in_result);
+
+  result
+}
+
+// Goes through the `event_queue`, subdividing intersecting edges and computing
+// the flags of each event. Returns every processed event in the order they
+// were processed, or an error if the sweep failed. Events to the right of
+// `x_limit` will be skipped. Subdividing edges creates new events, and the
+// sweep fails once there are more than `max_events` events.
+fn sweep_events(
+  event_queue: BinaryHeap<Reverse<Event>>,
+  event_relations: &mut Vec<EventRelation>,
+  rule: &dyn BooleanRule,
+  x_limit: f32,
+  max_events: usize,
+) -> Result<Vec<Event>, SweepError> {
+  sweep_events_until(
+    event_queue,
+    event_relations,
+    rule,
+    x_limit,
+    max_events,
+    |_, _| false,
+  )
 }
 
-// Goes through the `event_queue` and subdivides intersecting edges. Returns a
-// Vec of events corresponding to the edges that are in the final result based
-// on `operation`. Events to the right of `x_limit` will be skipped.
-fn subdivide_edges(
+// Same as `sweep_events`, but stops right after processing an event for which
+// `should_stop` (given the event and the event relations) returns true.
+fn sweep_events_until(
   mut event_queue: BinaryHeap<Reverse<Event>>,
   event_relations: &mut Vec<EventRelation>,
-  operation: Operation,
+  rule: &dyn BooleanRule,
   x_limit: f32,
-) -> Vec<Event> {
+  max_events: usize,
+  mut should_stop: impl FnMut(&Event, &[EventRelation]) -> bool,
+) -> Result<Vec<Event>, SweepError> {
   let mut sweep_line = Vec::new();
-  let mut result = Vec::new();
+  let mut processed_events = Vec::new();
   while let Some(Reverse(event)) = event_queue.pop() {
+    #[cfg(feature = "debug-render")]
+    debug_render::render_step(
+      &event,
+      &sweep_line,
+      &event_queue,
+      &processed_events,
+      event_relations,
+    );
+    // Each iteration only creates a few events, so checking here keeps the
+    // number of events close to the limit.
+    if max_events < event_relations.len() {
+      #[cfg(feature = "debug-render")]
+      debug_render::render_failure();
+      return Err(SweepError::CapacityExceeded);
+    }
+
     // Every event in `event_queue` must have a greater X value, so we can skip
     // all remaining events.
     if x_limit < event.point.x {
@@ -870,48 +2548,78 @@ fn subdivide_edges(
 
     if event.left {
       let sweep_line_event = SweepLineEvent(event.clone());
-      let pos = sweep_line
-        .binary_search(&sweep_line_event)
-        .expect_err("event is new and must be inserted");
+      // Events only compare equal to themselves, so the event can only be
+      // found if it was queued twice. It is already in the sweep line (and its
+      // flags are computed), so just skip the duplicate.
+      let Err(pos) = sweep_line.binary_search(&sweep_line_event) else {
+        continue;
+      };
       sweep_line.insert(pos, sweep_line_event);
-      if pos == 0 {
+      #[cfg(feature = "strict-checks")]
+      strict::check_sweep_line_order(&sweep_line);
+      // Edges that started before `event` and that it overlaps will be split at
+      // its left point, and the split off part will coincide with `event`. So
+      // these edges are not really below `event`, and using them would count
+      // the same transition twice.
+      let mut info_pos = pos;
+      while 0 < info_pos
+        && overlaps_from_before(&sweep_line[info_pos - 1].0, &event)
+      {
+        info_pos -= 1;
+      }
+      if info_pos == 0 {
         set_information(
           (&event, &mut event_relations[event.event_id]),
           /* prev_event= */ None,
-          operation,
+          rule,
         )
       } else {
+        let info_event = &sweep_line[info_pos - 1].0;
+        let (event_relation, info_event_relation) =
+          borrow_two_mut(event_relations, event.event_id, info_event.event_id);
+        set_information(
+          (&event, event_relation),
+          Some((info_event, info_event_relation)),
+          rule,
+        );
+      }
+      if 0 < pos {
         let prev_event = &sweep_line[pos - 1].0;
-        {
-          let (event_relation, prev_event_relation) = borrow_two_mut(
-            event_relations,
-            event.event_id,
-            prev_event.event_id,
-          );
-          set_information(
-            (&event, event_relation),
-            Some((prev_event, prev_event_relation)),
-            operation,
-          );
-        }
         check_for_intersection(
           &event,
           prev_event,
           &mut event_queue,
           event_relations,
-          operation,
+          rule,
         );
       }
       if pos + 1 < sweep_line.len() {
         // If the inserted event isn't last, check for intersection with next
         // event.
         let next_event = &sweep_line[pos + 1].0;
+        // If the next edge coincides with `event`, it was inserted first and
+        // skipped over the edge that `event` was split from. Now `event` is
+        // really the edge below it, so recompute its information.
+        if next_event.point == event.point
+          && overlaps_from_before_or_at(next_event, &event)
+        {
+          let (next_event_relation, event_relation) = borrow_two_mut(
+            event_relations,
+            next_event.event_id,
+            event.event_id,
+          );
+          set_information(
+            (next_event, next_event_relation),
+            Some((&event, event_relation)),
+            rule,
+          );
+        }
         check_for_intersection(
           &event,
           next_event,
           &mut event_queue,
           event_relations,
-          operation,
+          rule,
         );
       }
     } else {
@@ -919,10 +2627,33 @@ fn subdivide_edges(
       // the result.
       event_relations[event.event_id].in_result =
         event_relations[event_relations[event.event_id].sibling_id].in_result;
-      let pos = sweep_line
-        .binary_search(&order_sibling(&event, &event_relations[event.event_id]))
-        .expect("this is a right event, so the left event must have already been inserted.");
+      // This is a right event, so the left event must have already been
+      // inserted. The left event keeps the line of the edge before it was
+      // split, while the sibling is rebuilt from the (rounded) split point, so
+      // the two can order differently against nearby edges. In that case, find
+      // the left event by its ID instead.
+      let sibling = order_sibling(&event, &event_relations[event.event_id]);
+      let Some(pos) = sweep_line.binary_search(&sibling).ok().or_else(|| {
+        sweep_line.iter().position(|sweep_line_event| {
+          sweep_line_event.0.event_id == sibling.0.event_id
+        })
+      }) else {
+        // The left event is not in the sweep line, which happens when rounding
+        // a split point moves the left event after its right event. Continuing
+        // would leave the left event in the sweep line forever, so report the
+        // inconsistency (which recomputes the sweep in f64).
+        #[cfg(feature = "debug-render")]
+        debug_render::render_failure();
+        return Err(SweepError::inconsistent(
+          SweepStage::Subdivision,
+          &event,
+          event_relations,
+          sweep_line.len(),
+        ));
+      };
       sweep_line.remove(pos);
+      #[cfg(feature = "strict-checks")]
+      strict::check_sweep_line_order(&sweep_line);
       if 0 < pos && pos < sweep_line.len() {
         let (prev_event, next_event) =
           (&sweep_line[pos - 1].0, &sweep_line[pos].0);
@@ -931,27 +2662,44 @@ fn subdivide_edges(
           next_event,
           &mut event_queue,
           event_relations,
-          operation,
+          rule,
         );
       }
     }
 
-    if event_relations[event.event_id].in_result {
-      result.push(event);
+    #[cfg(feature = "strict-checks")]
+    strict::check_siblings(event_relations);
+    let stop = should_stop(&event, event_relations);
+    processed_events.push(event);
+    if stop {
+      break;
     }
   }
 
-  // Only keep events that are still in the result by the end. With no
-  // coincident edges, in_result can never change after the first pass. However,
-  // with coincident edges, an edge that previously thought it was in the result
-  // may no longer be in the result. Consider the subject and the clip being the
-  // same, and the operation being difference or XOR. The first subject edge
-  // will have no previous event in the sweep line, so it will think it is in
-  // the result. Then the clip edge will be processed and now the edge is no
-  // longer in the result.
-  result.retain(|event| event_relations[event.event_id].in_result);
+  Ok(processed_events)
+}
 
-  result
+// Determines whether `event` lies on the edge of `sweep_line_event` (which is
+// in the sweep line), with the edge starting before `event`.
+fn overlaps_from_before(sweep_line_event: &Event, event: &Event) -> bool {
+  sweep_line_event.point != event.point
+    && overlaps_from_before_or_at(sweep_line_event, event)
+}
+
+// Determines whether `event` lies on the edge of `sweep_line_event` (which is
+// in the sweep line), with the edge starting before or at `event`.
+fn overlaps_from_before_or_at(sweep_line_event: &Event, event: &Event) -> bool {
+  let arithmetic = sweep_line_event.arithmetic;
+  arithmetic.point_relative_to_line(
+    sweep_line_event.point,
+    sweep_line_event.other_point,
+    event.point,
+  ) == std::cmp::Ordering::Equal
+    && arithmetic.point_relative_to_line(
+      sweep_line_event.point,
+      sweep_line_event.other_point,
+      event.other_point,
+    ) == std::cmp::Ordering::Equal
 }
 
 // Borrows two elements from a slice mutably. It should be unreachable to ever
@@ -981,6 +2729,7 @@ fn order_sibling(
     left: true,
     is_subject: event.is_subject,
     other_point: event.point,
+    arithmetic: event.arithmetic,
   })
 }
 
@@ -1014,7 +2763,12 @@ fn compute_depth(
     Some(prev_in_result) => {
       let prev_contour_flags = &event_id_to_contour_flags[&prev_in_result];
 
-      if !prev_contour_flags.result_in_out {
+      // The contour is nested in the contour below it iff the area just above
+      // the edge below is inside that contour. For a shell, that area is in
+      // the result (an out-in transition), and for a hole, it is not (an
+      // in-out transition).
+      let prev_is_hole = prev_contour_flags.depth % 2 == 1;
+      if prev_contour_flags.result_in_out == prev_is_hole {
         (prev_contour_flags.depth + 1, Some(prev_contour_flags.contour_id))
       } else {
         (prev_contour_flags.depth, prev_contour_flags.parent_id)
@@ -1023,8 +2777,17 @@ fn compute_depth(
   }
 }
 
-// Computes the contour starting at `start_event`. Events that are part of the
-// contour will be assigned the `depth`, `contour_id`, and `parent_contour_id`.
+// The source edge and coincident source edge (see
+// `EventRelation::coincident_source_edge`) of an edge in a result contour.
+type EdgeSources = (SourceEdge, Option<SourceEdge>);
+
+// Computes the contour starting at `start_event`, along with the sources of
+// each edge. Events that are part of the contour will be assigned the `depth`,
+// `contour_id`, and `parent_contour_id`.
+// Fails if the events are not connected into a closed loop. Every step of the
+// walk uses up an edge, so a walk with more steps than there are result edges
+// is stuck in a loop that never returns to `start_event` (which is reported as
+// the event that failed).
 fn compute_contour(
   start_event: &Event,
   contour_id: usize,
@@ -1033,11 +2796,21 @@ fn compute_contour(
   event_relations: &[EventRelation],
   event_id_to_contour_flags: &mut HashMap<usize, EventContourFlags>,
   result_events: &[Event],
-) -> (Vec<Vec2>, Vec<SourceEdge>) {
+) -> Result<(Vec<Vec2>, Vec<EdgeSources>), SweepError> {
+  let source_edges = |event: &Event| {
+    let relation = &event_relations[event.event_id];
+    (relation.source_edge, relation.coincident_source_edge)
+  };
   let mut contour = Vec::new();
   let mut contour_source_edges = Vec::new();
   contour.push(start_event.point);
-  contour_source_edges.push(event_relations[start_event.event_id].source_edge);
+  contour_source_edges.push(source_edges(start_event));
+  // The walk starts along the edge of `start_event` towards its sibling, so the
+  // result is on the left of the walk iff it is above a left event's edge (or
+  // below a right event's edge).
+  let result_on_left = event_id_to_contour_flags[&start_event.event_id]
+    .result_in_out
+    != start_event.left;
   let mut current_event = event_to_sibling_and_mark(
     start_event,
     contour_id,
@@ -1045,13 +2818,29 @@ fn compute_contour(
     parent_contour_id,
     event_relations,
     event_id_to_contour_flags,
-    &result_events,
-  );
+    result_events,
+  )?;
 
   while current_event.point != start_event.point {
     let result_id =
       event_id_to_contour_flags[&current_event.event_id].result_id;
-    if 0 < result_id
+    if let Some(next_event) = unprocessed_event_at_point(
+      current_event,
+      result_id,
+      result_on_left,
+      event_id_to_contour_flags,
+      result_events,
+    ) {
+      // Prefer events that have not been used yet. Where several contours
+      // touch at a point (e.g. vertical edges meeting end to end), the adjacent
+      // event may already belong to this contour, and following it again
+      // would never return to `start_event`.
+      current_event = next_event;
+      event_id_to_contour_flags
+        .get_mut(&current_event.event_id)
+        .unwrap()
+        .processed = true;
+    } else if 0 < result_id
       && result_events[result_id - 1]
         .point
         .abs_diff_eq(current_event.point, EPSILON)
@@ -1063,43 +2852,95 @@ fn compute_contour(
         .processed = true;
     } else {
       // One of the adjacent events in `result_events` must be connected to
-      // the current event, panic otherwise.
-      debug_assert!(result_id + 1 < result_events.len());
-      debug_assert!(
-        result_events[result_id + 1]
-          .point
-          .abs_diff_eq(current_event.point, EPSILON),
-        "left={}, right={}, result_id={}, event_id={}",
-        result_events[result_id + 1].point,
-        current_event.point,
-        result_id,
-        current_event.event_id,
-      );
-      current_event = &result_events[result_id + 1];
+      // the current event, otherwise the sweep was inconsistent.
+      match result_events.get(result_id + 1) {
+        Some(next_event)
+          if next_event.point.abs_diff_eq(current_event.point, EPSILON) =>
+        {
+          current_event = next_event;
+        }
+        _ => {
+          return Err(SweepError::inconsistent(
+            SweepStage::ContourJoining,
+            current_event,
+            event_relations,
+            /* sweep_line_len= */ 0,
+          ));
+        }
+      }
       event_id_to_contour_flags
         .get_mut(&current_event.event_id)
         .unwrap()
         .processed = true;
     }
     contour.push(current_event.point);
-    contour_source_edges
-      .push(event_relations[current_event.event_id].source_edge);
+    contour_source_edges.push(source_edges(current_event));
+    if contour.len() > result_events.len() / 2 {
+      return Err(SweepError::inconsistent(
+        SweepStage::ContourJoining,
+        start_event,
+        event_relations,
+        /* sweep_line_len= */ 0,
+      ));
+    }
     current_event = event_to_sibling_and_mark(
       current_event,
       contour_id,
       depth,
       parent_contour_id,
-      &event_relations,
+      event_relations,
       event_id_to_contour_flags,
-      &result_events,
-    );
+      result_events,
+    )?;
   }
 
-  (contour, contour_source_edges)
+  Ok((contour, contour_source_edges))
+}
+
+// Finds the event at the same point as `result_events[result_id]` (which is
+// `event`, the end of the edge just walked along) that has not been processed
+// yet and continues the walk around the same region. Where contours touch at a
+// point (e.g. the pieces of an XOR meeting at an intersection), several edges
+// leave the point. Taking the sharpest turn towards the result (left if
+// `result_on_left`, otherwise right) keeps the walk on the boundary of the
+// region it started on, rather than crossing over to another region and
+// producing a contour that wraps around some area twice.
+fn unprocessed_event_at_point<'a>(
+  event: &Event,
+  result_id: usize,
+  result_on_left: bool,
+  event_id_to_contour_flags: &HashMap<usize, EventContourFlags>,
+  result_events: &'a [Event],
+) -> Option<&'a Event> {
+  let at_point = |other: &&Event| other.point.abs_diff_eq(event.point, EPSILON);
+  let unprocessed =
+    |other: &&Event| !event_id_to_contour_flags[&other.event_id].processed;
+  let incoming = event.point - event.other_point;
+  let turn = |other: &Event| {
+    let outgoing = other.other_point - other.point;
+    let angle = incoming.perp_dot(outgoing).atan2(incoming.dot(outgoing));
+    if result_on_left {
+      angle
+    } else {
+      -angle
+    }
+  };
+  result_events[..result_id]
+    .iter()
+    .rev()
+    .take_while(at_point)
+    .chain(result_events[result_id + 1..].iter().take_while(at_point))
+    .filter(unprocessed)
+    // `min_by` keeps the first of equal turns, so ties still prefer the
+    // closest earlier event.
+    .min_by(|a, b| compare_f32(turn(b), turn(a)))
 }
 
-// Finds the sibling of `event`, sets its flags to match the provided arguments,
-// and returns the sibling event.
+// Finds the sibling of `event`, sets the flags of both events to match the
+// provided arguments, and returns the sibling event. Both events must be
+// marked, since later contours look up their depth through whichever event of
+// an edge is the left event. Fails if the sibling is not in the result, which
+// means the sweep was inconsistent.
 fn event_to_sibling_and_mark<'a>(
   event: &Event,
   contour_id: usize,
@@ -1108,23 +2949,38 @@ fn event_to_sibling_and_mark<'a>(
   event_relations: &[EventRelation],
   event_id_to_contour_flags: &mut HashMap<usize, EventContourFlags>,
   result_events: &'a [Event],
-) -> &'a Event {
+) -> Result<&'a Event, SweepError> {
+  if let Some(event_relation) =
+    event_id_to_contour_flags.get_mut(&event.event_id)
+  {
+    event_relation.contour_id = contour_id;
+    event_relation.depth = depth;
+    event_relation.parent_id = parent_contour_id;
+  }
   let sibling_id = event_relations[event.event_id].sibling_id;
   let contour_relation =
-    event_id_to_contour_flags.get_mut(&sibling_id).unwrap();
+    event_id_to_contour_flags.get_mut(&sibling_id).ok_or_else(|| {
+      SweepError::inconsistent(
+        SweepStage::ContourJoining,
+        event,
+        event_relations,
+        /* sweep_line_len= */ 0,
+      )
+    })?;
   contour_relation.processed = true;
   contour_relation.contour_id = contour_id;
   contour_relation.depth = depth;
   contour_relation.parent_id = parent_contour_id;
-  &result_events[contour_relation.result_id]
+  Ok(&result_events[contour_relation.result_id])
 }
 
 // Determines the contours of the result polygon from the `result_events`.
+// Fails if the events cannot be joined into closed contours.
 fn join_contours(
   result_events: Vec<Event>,
   event_relations: Vec<EventRelation>,
-  operation: Operation,
-) -> BooleanResult {
+  rule: &dyn BooleanRule,
+) -> Result<BooleanResult, SweepError> {
   let mut event_id_to_contour_flags = result_events
     .iter()
     .enumerate()
@@ -1134,7 +2990,7 @@ fn join_contours(
         event.event_id,
         EventContourFlags {
           result_id,
-          result_in_out: event.result_in_out(event_meta, operation),
+          result_in_out: event.result_in_out(event_meta, rule),
           ..Default::default()
         },
       )
@@ -1143,13 +2999,17 @@ fn join_contours(
 
   let mut contours = Vec::new();
   let mut contour_source_edges = Vec::new();
+  let mut contour_coincident_source_edges = Vec::new();
+  let mut contour_parents = Vec::new();
+  let mut contour_bounds = Vec::new();
+  let mut bounds = None;
   for result_event in result_events.iter() {
     if event_id_to_contour_flags[&result_event.event_id].processed {
       continue;
     }
     let (depth, parent_contour_id) =
       compute_depth(result_event, &event_relations, &event_id_to_contour_flags);
-    let (mut contour, mut source_edges_for_contour) = compute_contour(
+    let (contour, source_edges_for_contour) = compute_contour(
       result_event,
       contours.len(),
       depth,
@@ -1157,18 +3017,35 @@ fn join_contours(
       &event_relations,
       &mut event_id_to_contour_flags,
       &result_events,
-    );
+    )?;
 
-    if depth % 2 == 1 {
-      contour.reverse();
-      source_edges_for_contour.reverse();
+    // The contour was just built, so its points are still cheap to scan.
+    let bounds_for_contour = contour.iter().copied().fold(None, extend_bounds);
+    if let Some(bounds_for_contour) = bounds_for_contour {
+      bounds = union_bounds(bounds, bounds_for_contour);
     }
 
     contours.push(contour);
+    let (source_edges_for_contour, coincident_source_edges_for_contour) =
+      source_edges_for_contour.into_iter().unzip();
     contour_source_edges.push(source_edges_for_contour);
+    contour_coincident_source_edges.push(coincident_source_edges_for_contour);
+    contour_parents.push(parent_contour_id);
+    contour_bounds.push(bounds_for_contour);
   }
 
-  BooleanResult { polygon: Polygon { contours }, contour_source_edges }
+  let result = BooleanResult {
+    polygon: Polygon { contours },
+    contour_source_edges,
+    contour_coincident_source_edges,
+    contour_parents,
+    contour_bounds,
+    bounds,
+    ..Default::default()
+  };
+  #[cfg(feature = "strict-checks")]
+  strict::check_contours(&result);
+  Ok(result)
 }
 
 #[cfg(test)]
@@ -0,0 +1,172 @@
+use glam::Vec2;
+
+use crate::{
+  hierarchy::{compute_contour_parents, contour_depth},
+  util::contour_signed_area,
+  BooleanResult, Polygon,
+};
+
+impl Polygon {
+  // Computes the area of the polygon, as the sum of the signed areas of its
+  // contours (see `contour_signed_area`). Results of boolean operations have
+  // counter-clockwise shells and clockwise holes by default (see
+  // `BooleanOptions::output_orientation`), so their holes are subtracted. Other
+  // polygons get the sum of their contours' signed areas, which is negative if
+  // they wind clockwise.
+  pub fn area(&self) -> f32 {
+    self.contours.iter().map(|contour| contour_signed_area(contour)).sum()
+  }
+
+  // Computes the signed area of each contour (see `contour_signed_area`).
+  pub fn contour_signed_areas(&self) -> Vec<f32> {
+    self.contours.iter().map(|contour| contour_signed_area(contour)).collect()
+  }
+
+  // Computes the centroid (center of mass) of the polygon, weighting each
+  // contour by its signed area like `area`, so holes (oriented opposite to
+  // their shells) are subtracted. Returns None if the area is zero.
+  pub fn centroid(&self) -> Option<Vec2> {
+    // Measuring from a vertex of the polygon keeps the products small, so they
+    // lose less precision far from the origin.
+    let origin = *self.contours.iter().flatten().next()?;
+    let mut double_area = 0.0;
+    let mut weighted_sum = Vec2::ZERO;
+    for (_, _, start, end) in self.iter_edges() {
+      let (start, end) = (start - origin, end - origin);
+      let cross = start.perp_dot(end);
+      double_area += cross;
+      weighted_sum += (start + end) * cross;
+    }
+    if double_area == 0.0 {
+      return None;
+    }
+    Some(origin + weighted_sum / (3.0 * double_area))
+  }
+
+  // Computes the total length of the edges of all the contours.
+  pub fn perimeter(&self) -> f32 {
+    self.iter_edges().map(|(_, _, start, end)| start.distance(end)).sum()
+  }
+
+  // Computes the total length of the edges of the shells, leaving out the
+  // holes. Holes are found by nesting (a contour inside an odd number of other
+  // contours), so their orientation does not matter, but the contours must not
+  // cross each other (e.g. the result of a boolean operation).
+  pub fn shell_perimeter(&self) -> f32 {
+    shell_perimeter(self, &compute_contour_parents(self))
+  }
+}
+
+impl BooleanResult {
+  // Computes the area of the resulting polygon (see `Polygon::area`).
+  pub fn area(&self) -> f32 {
+    self.polygon.area()
+  }
+
+  // Computes the centroid of the resulting polygon (see `Polygon::centroid`).
+  pub fn centroid(&self) -> Option<Vec2> {
+    self.polygon.centroid()
+  }
+
+  // Computes the perimeter of the resulting polygon (see
+  // `Polygon::perimeter`).
+  pub fn perimeter(&self) -> f32 {
+    self.polygon.perimeter()
+  }
+
+  // Computes the perimeter of the shells of the resulting polygon (see
+  // `Polygon::shell_perimeter`). This uses `contour_parents` if it was
+  // computed.
+  pub fn shell_perimeter(&self) -> f32 {
+    if self.contour_parents.len() == self.polygon.contours.len() {
+      shell_perimeter(&self.polygon, &self.contour_parents)
+    } else {
+      self.polygon.shell_perimeter()
+    }
+  }
+}
+
+// Computes the total length of the contours of `polygon` that are not holes,
+// given the parent of each contour.
+fn shell_perimeter(
+  polygon: &Polygon,
+  contour_parents: &[Option<usize>],
+) -> f32 {
+  polygon
+    .iter_edges()
+    .filter(|&(contour, ..)| {
+      contour_depth(contour_parents, contour).is_multiple_of(2)
+    })
+    .map(|(_, _, start, end)| start.distance(end))
+    .sum()
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square;
+  use crate::{contour_signed_area, difference, Polygon};
+
+  #[test]
+  fn holes_have_negative_area() {
+    let mut hole = square(Vec2::ONE, 2.0);
+    hole.reverse();
+    let polygon = Polygon { contours: vec![square(Vec2::ZERO, 4.0), hole] };
+    assert_eq!(polygon.contour_signed_areas(), [16.0, -4.0]);
+    assert_eq!(polygon.area(), 12.0);
+    assert_eq!(contour_signed_area(&polygon.contours[1]), -4.0);
+    assert_eq!(Polygon::default().area(), 0.0);
+  }
+
+  #[test]
+  fn boolean_results_subtract_holes() {
+    let subject = Polygon { contours: vec![square(Vec2::ZERO, 4.0)] };
+    let clip = Polygon { contours: vec![square(Vec2::ONE, 2.0)] };
+    let result = difference(&subject, &clip);
+    assert_eq!(result.polygon.contours.len(), 2);
+    assert_eq!(result.area(), 12.0);
+  }
+
+  #[test]
+  fn centroid_accounts_for_holes() {
+    let square_polygon = Polygon { contours: vec![square(Vec2::ONE, 2.0)] };
+    assert_eq!(square_polygon.centroid(), Some(Vec2::splat(2.0)));
+
+    // An L shape: a 4x4 square missing its top right 2x2 quarter.
+    let subject = Polygon { contours: vec![square(Vec2::ZERO, 4.0)] };
+    let clip = Polygon { contours: vec![square(Vec2::splat(2.0), 2.0)] };
+    let l_shape = difference(&subject, &clip);
+    // The full square's centroid minus the quarter's, weighted by area.
+    let expected = (Vec2::splat(2.0) * 16.0 - Vec2::splat(3.0) * 4.0) / 12.0;
+    assert!(l_shape.centroid().unwrap().abs_diff_eq(expected, 1e-6));
+
+    // A hole off to one side moves the centroid the other way.
+    let mut hole = square(Vec2::new(2.5, 1.5), 1.0);
+    hole.reverse();
+    let with_hole = Polygon { contours: vec![square(Vec2::ZERO, 4.0), hole] };
+    let expected = (Vec2::splat(2.0) * 16.0 - Vec2::new(3.0, 2.0)) / 15.0;
+    assert!(with_hole.centroid().unwrap().abs_diff_eq(expected, 1e-6));
+
+    // Degenerate polygons have no centroid.
+    assert_eq!(Polygon::default().centroid(), None);
+    let line = Polygon { contours: vec![vec![Vec2::ZERO, Vec2::ONE]] };
+    assert_eq!(line.centroid(), None);
+  }
+
+  #[test]
+  fn perimeter_optionally_excludes_holes() {
+    let subject = Polygon { contours: vec![square(Vec2::ZERO, 4.0)] };
+    let clip = Polygon { contours: vec![square(Vec2::ONE, 1.0)] };
+    // The hole keeps the orientation of the input.
+    let polygon = Polygon {
+      contours: vec![square(Vec2::ZERO, 4.0), square(Vec2::ONE, 1.0)],
+    };
+    assert_eq!(polygon.perimeter(), 16.0 + 4.0);
+    assert_eq!(polygon.shell_perimeter(), 16.0);
+
+    let result = difference(&subject, &clip);
+    assert_eq!(result.perimeter(), 16.0 + 4.0);
+    assert_eq!(result.shell_perimeter(), 16.0);
+  }
+}
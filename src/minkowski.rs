@@ -0,0 +1,285 @@
+use glam::Vec2;
+
+use crate::{
+  convex_hull, try_intersection_all_with_options,
+  try_intersection_with_options, try_union_with_options, unwrap_boolean,
+  validate_input, BooleanOptions, BooleanResult, ClipError, FillRule, Polygon,
+};
+
+// Computes the Minkowski difference (erosion) of `region` by `footprint`: the
+// points `p` where `footprint` translated by `p` fits entirely inside `region`.
+// For example, if `footprint` is the shape of an agent around its origin, this
+// is where the agent's origin can stand without overlapping the walls of
+// `region`. An empty footprint fits anywhere, so it leaves `region` as is.
+//
+// Each contour of `region` is offset by the point of `footprint` that reaches
+// farthest across each edge, following the boundary of `footprint` around
+// corners, and the offset contours are then resolved with
+// `FillRule::Positive` (like `offset`). Non-convex footprints are split into
+// convex pieces, and the erosions by each piece are intersected. The source
+// edges of the result refer to the offset contours rather than `region`. This
+// panics if either polygon cannot be used in a boolean operation (see
+// `try_erode`).
+pub fn erode(region: &Polygon, footprint: &Polygon) -> BooleanResult {
+  unwrap_boolean(try_erode_with_options(
+    region,
+    footprint,
+    &BooleanOptions::default(),
+  ))
+}
+
+// Same as `erode`, but with `options`. Both `region` and `footprint` are
+// resolved with `BooleanOptions::fill_rule`.
+pub fn erode_with_options(
+  region: &Polygon,
+  footprint: &Polygon,
+  options: &BooleanOptions,
+) -> BooleanResult {
+  unwrap_boolean(try_erode_with_options(region, footprint, options))
+}
+
+// Same as `erode`, but returns an error instead of panicking. Errors in
+// `region` are reported in the subject, and errors in `footprint` are reported
+// in the clip.
+pub fn try_erode(
+  region: &Polygon,
+  footprint: &Polygon,
+) -> Result<BooleanResult, ClipError> {
+  try_erode_with_options(region, footprint, &BooleanOptions::default())
+}
+
+// Same as `erode_with_options`, but returns an error instead of panicking.
+pub fn try_erode_with_options(
+  region: &Polygon,
+  footprint: &Polygon,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  validate_input(region, footprint)?;
+  let region = region.try_normalize(options.fill_rule)?;
+  let pieces = if footprint.is_convex() {
+    vec![convex_hull(footprint)]
+  } else {
+    let footprint = footprint.try_normalize(options.fill_rule)?;
+    let points =
+      footprint.contours.iter().flatten().copied().collect::<Vec<_>>();
+    convex_pieces(&points, footprint.triangulate())
+      .into_iter()
+      .map(|piece| piece.into_iter().map(|index| points[index]).collect())
+      .collect()
+  };
+  // Footprints without any area (e.g. a segment) erode by their convex hull.
+  let pieces =
+    if pieces.is_empty() { vec![convex_hull(footprint)] } else { pieces };
+
+  // Each piece is resolved on its own, and the results are then intersected.
+  // Pieces that share points of the footprint erode to exactly coincident
+  // edges, which the regular intersection does not always handle, so this
+  // falls back to resolving both results together in that case.
+  let positive_options = options.clone().with_fill_rule(FillRule::Positive);
+  let even_odd_options = options.clone().with_fill_rule(FillRule::EvenOdd);
+  let mut eroded: Option<BooleanResult> = None;
+  for piece in pieces.iter() {
+    let eroded_piece = try_union_with_options(
+      &Polygon {
+        contours: region
+          .contours
+          .iter()
+          .map(|contour| erode_contour(contour, piece))
+          .collect(),
+      },
+      &Polygon::default(),
+      &positive_options,
+    )?;
+    eroded = Some(match eroded {
+      None => eroded_piece,
+      Some(eroded) => try_intersection_with_options(
+        &eroded.polygon,
+        &eroded_piece.polygon,
+        &even_odd_options,
+      )
+      .or_else(|_| {
+        try_intersection_all_with_options(
+          &[eroded.polygon, eroded_piece.polygon],
+          &positive_options,
+        )
+      })?,
+    });
+  }
+  Ok(eroded.unwrap())
+}
+
+// Merges the counter-clockwise `triangles` (indices into `points`) into
+// fewer convex pieces, by removing the diagonals between triangles wherever the
+// pieces on both sides still form a convex piece together. Fewer pieces means
+// fewer erosions to intersect.
+fn convex_pieces(
+  points: &[Vec2],
+  triangles: Vec<[usize; 3]>,
+) -> Vec<Vec<usize>> {
+  let is_convex = |piece: &[usize]| {
+    (0..piece.len()).all(|index| {
+      let previous = points[piece[(index + piece.len() - 1) % piece.len()]];
+      let vertex = points[piece[index]];
+      let next = points[piece[(index + 1) % piece.len()]];
+      (vertex - previous).perp_dot(next - vertex) >= 0.0
+    })
+  };
+  let mut pieces =
+    triangles.into_iter().map(|triangle| triangle.to_vec()).collect::<Vec<_>>();
+  let mut merged_any = true;
+  while merged_any {
+    merged_any = false;
+    'search: for first in 0..pieces.len() {
+      for second in first + 1..pieces.len() {
+        // Find an edge of `first` that `second` has in the opposite direction.
+        for a in 0..pieces[first].len() {
+          let start = pieces[first][a];
+          let end = pieces[first][(a + 1) % pieces[first].len()];
+          let Some(b) = (0..pieces[second].len()).find(|&b| {
+            pieces[second][b] == end
+              && pieces[second][(b + 1) % pieces[second].len()] == start
+          }) else {
+            continue;
+          };
+          // Walk `first` from `end` around to `start`, then the rest of
+          // `second` from after `start` around to before `end`.
+          let mut merged = Vec::new();
+          for offset in 1..=pieces[first].len() {
+            merged.push(pieces[first][(a + offset) % pieces[first].len()]);
+          }
+          for offset in 2..pieces[second].len() {
+            merged.push(pieces[second][(b + offset) % pieces[second].len()]);
+          }
+          if is_convex(&merged) {
+            pieces[first] = merged;
+            pieces.swap_remove(second);
+            merged_any = true;
+            break 'search;
+          }
+        }
+      }
+    }
+  }
+  pieces
+}
+
+// Offsets each edge of `contour` (which has the inside of the region on its
+// left) by the point of `-piece` farthest to its left, where `piece` is convex
+// and counter-clockwise. Between consecutive edges, this follows the boundary
+// of `-piece` in the direction the contour turns. Where the contour turns
+// right, that fills the gap between the offset edges. Where it turns left, the
+// offset edges overlap instead, and the boundary of `-piece` forms a loop that
+// winds the opposite way to the contour, so `FillRule::Positive` removes it.
+fn erode_contour(contour: &[Vec2], piece: &[Vec2]) -> Vec<Vec2> {
+  if piece.is_empty() {
+    return contour.to_vec();
+  }
+  let reflected = piece.iter().map(|&point| -point).collect::<Vec<_>>();
+  let left_normal = |index: usize| {
+    let next = contour[(index + 1) % contour.len()];
+    (next - contour[index]).perp()
+  };
+  // The index of the point of `reflected` farthest along `direction`.
+  let support = |direction: Vec2| {
+    (0..reflected.len())
+      .max_by(|&a, &b| {
+        direction.dot(reflected[a]).total_cmp(&direction.dot(reflected[b]))
+      })
+      .unwrap()
+  };
+  let mut eroded = Vec::with_capacity(contour.len() * 2);
+  let mut current = support(left_normal(contour.len() - 1));
+  for (index, &vertex) in contour.iter().enumerate() {
+    eroded.push(vertex + reflected[current]);
+    let incoming = left_normal((index + contour.len() - 1) % contour.len());
+    let outgoing = left_normal(index);
+    // The farthest point moves around `reflected` the same way the normal
+    // turns. Reversing edges could go either way, so they go clockwise.
+    let step =
+      if incoming.perp_dot(outgoing) > 0.0 { 1 } else { reflected.len() - 1 };
+    // `reflected` is convex, so the distance along `outgoing` increases
+    // until the farthest point is reached.
+    loop {
+      let next = (current + step) % reflected.len();
+      if outgoing.dot(reflected[next]) <= outgoing.dot(reflected[current]) {
+        break;
+      }
+      current = next;
+      eroded.push(vertex + reflected[current]);
+    }
+  }
+  eroded
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::rectangle;
+  use crate::{erode, union_all, Polygon};
+
+  #[test]
+  fn erodes_room_around_pillar() {
+    // A 10x10 room with a 2x2 pillar in the middle.
+    let mut pillar = rectangle(Vec2::splat(4.0), Vec2::splat(6.0));
+    pillar.reverse();
+    let room = Polygon {
+      contours: vec![rectangle(Vec2::ZERO, Vec2::splat(10.0)), pillar],
+    };
+    let agent = Polygon { contours: vec![rectangle(-Vec2::ONE, Vec2::ONE)] };
+    let standable = erode(&room, &agent);
+    // The walls move in by 1 and the pillar grows by 1 on each side.
+    assert_eq!(standable.polygon.contours.len(), 2);
+    assert_eq!(standable.area(), 8.0 * 8.0 - 4.0 * 4.0);
+
+    // The footprint does not need to contain its origin.
+    let offset_agent =
+      Polygon { contours: vec![rectangle(Vec2::ZERO, Vec2::splat(2.0))] };
+    let standable = erode(&room, &offset_agent);
+    assert_eq!(standable.bounds, Some((Vec2::ZERO, Vec2::splat(8.0))));
+    assert_eq!(standable.area(), 8.0 * 8.0 - 4.0 * 4.0);
+  }
+
+  #[test]
+  fn erodes_by_non_convex_footprints() {
+    let room =
+      Polygon { contours: vec![rectangle(Vec2::ZERO, Vec2::splat(10.0))] };
+    // An L shape, which fits wherever both of its arms fit.
+    let footprint = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(2.0, 1.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(1.0, 2.0),
+        Vec2::new(0.0, 2.0),
+      ]],
+    };
+    let standable = erode(&room, &footprint);
+    assert_eq!(standable.bounds, Some((Vec2::ZERO, Vec2::splat(8.0))));
+    assert_eq!(standable.area(), 64.0);
+  }
+
+  #[test]
+  fn footprints_do_not_fit_through_narrow_gaps() {
+    // Two rooms joined by a corridor 1 unit wide.
+    let rooms = union_all(&[
+      Polygon { contours: vec![rectangle(Vec2::ZERO, Vec2::splat(4.0))] },
+      Polygon {
+        contours: vec![rectangle(Vec2::new(4.0, 1.5), Vec2::new(6.0, 2.5))],
+      },
+      Polygon {
+        contours: vec![rectangle(Vec2::new(6.0, 0.0), Vec2::new(10.0, 4.0))],
+      },
+    ])
+    .polygon;
+    let small = Polygon {
+      contours: vec![rectangle(Vec2::splat(-0.25), Vec2::splat(0.25))],
+    };
+    assert_eq!(erode(&rooms, &small).polygon.contours.len(), 1);
+    let large = Polygon { contours: vec![rectangle(-Vec2::ONE, Vec2::ONE)] };
+    let standable = erode(&rooms, &large);
+    assert_eq!(standable.polygon.contours.len(), 2);
+    assert_eq!(standable.area(), 2.0 * 2.0 * 2.0);
+  }
+}
@@ -0,0 +1,344 @@
+use std::{cmp::Ordering, collections::HashSet, f32::consts::TAU};
+
+use glam::Vec2;
+
+use crate::{
+  hierarchy::{compute_contour_parents, contour_depth},
+  lex_order_points,
+  util::contour_signed_area,
+  BooleanResult, Polygon,
+};
+
+impl Polygon {
+  // Partitions the polygon into x-monotone pieces (see
+  // `BooleanResult::monotone_partition`). Holes are found by nesting, so the
+  // contours must not cross each other.
+  pub fn monotone_partition(&self) -> Vec<Vec<Vec2>> {
+    monotone_partition(self, &compute_contour_parents(self))
+  }
+}
+
+impl BooleanResult {
+  // Partitions the resulting polygon into x-monotone pieces: counter-clockwise
+  // contours without holes that every vertical line crosses at most twice.
+  // Together the pieces cover the polygon exactly. Pieces are split off along
+  // diagonals between vertices of the polygon, so the pieces only use the
+  // polygon's vertices. This uses `contour_parents` if it was computed.
+  pub fn monotone_partition(&self) -> Vec<Vec<Vec2>> {
+    if self.contour_parents.len() == self.polygon.contours.len() {
+      monotone_partition(&self.polygon, &self.contour_parents)
+    } else {
+      self.polygon.monotone_partition()
+    }
+  }
+}
+
+// How a vertex affects the pieces as the sweep passes over it, given the
+// interior is on the left of the contour.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum VertexKind {
+  // Both neighbors come later in the sweep and the interior angle is convex,
+  // so a new piece starts here.
+  Start,
+  // Both neighbors come later in the sweep but the interior angle is reflex,
+  // so the vertex splits a piece in two.
+  Split,
+  // Both neighbors came earlier in the sweep and the interior angle is convex,
+  // so a piece ends here.
+  End,
+  // Both neighbors came earlier in the sweep but the interior angle is reflex,
+  // so two pieces merge here.
+  Merge,
+  // One neighbor comes earlier in the sweep and the other comes later.
+  Regular,
+}
+
+// Partitions `polygon`, whose contours are nested as described by
+// `contour_parents`, into x-monotone pieces. This sweeps over the vertices in
+// the same order as the boolean operations, adding a diagonal at each split
+// vertex (to an earlier vertex) and at each merge vertex (to a later vertex),
+// and then walks the faces of the polygon's edges and the diagonals. Vertices
+// are identified by their index rather than their position, so contours that
+// touch at a vertex stay separate.
+pub(crate) fn monotone_partition(
+  polygon: &Polygon,
+  contour_parents: &[Option<usize>],
+) -> Vec<Vec<Vec2>> {
+  let points = polygon.contours.iter().flatten().copied().collect::<Vec<_>>();
+  // The neighbors of each vertex, going around its contour with the interior
+  // on the left. Vertices of degenerate contours have no neighbors.
+  let mut next = vec![usize::MAX; points.len()];
+  let mut previous = vec![usize::MAX; points.len()];
+  let mut first_index = 0;
+  for (contour_index, contour) in polygon.contours.iter().enumerate() {
+    let indices = first_index..first_index + contour.len();
+    first_index += contour.len();
+    if contour.len() < 3 {
+      continue;
+    }
+    // Shells are counter-clockwise and holes are clockwise, so the interior is
+    // always on the left.
+    let is_hole = contour_depth(contour_parents, contour_index) % 2 == 1;
+    let reverse = (contour_signed_area(contour) < 0.0) != is_hole;
+    for index in indices.clone() {
+      let following =
+        if index + 1 == indices.end { indices.start } else { index + 1 };
+      let (from, to) =
+        if reverse { (following, index) } else { (index, following) };
+      next[from] = to;
+      previous[to] = from;
+    }
+  }
+
+  // Ties between duplicate points are broken by index so every vertex is
+  // strictly before or after every other vertex.
+  let sweep_order = |a: usize, b: usize| {
+    lex_order_points(&points[a], &points[b]).then(a.cmp(&b))
+  };
+  let mut vertices = (0..points.len())
+    .filter(|&index| next[index] != usize::MAX)
+    .collect::<Vec<_>>();
+  vertices.sort_by(|&a, &b| sweep_order(a, b));
+
+  let kinds = (0..points.len())
+    .map(|vertex| {
+      if next[vertex] == usize::MAX {
+        return VertexKind::Regular;
+      }
+      let (before, after) = (previous[vertex], next[vertex]);
+      let before_is_later = sweep_order(before, vertex) == Ordering::Greater;
+      let after_is_later = sweep_order(after, vertex) == Ordering::Greater;
+      // Collinear spikes are treated as convex, so they never add diagonals.
+      let is_reflex = (points[vertex] - points[before])
+        .perp_dot(points[after] - points[vertex])
+        < 0.0;
+      match (before_is_later, after_is_later, is_reflex) {
+        (true, true, false) => VertexKind::Start,
+        (true, true, true) => VertexKind::Split,
+        (false, false, false) => VertexKind::End,
+        (false, false, true) => VertexKind::Merge,
+        _ => VertexKind::Regular,
+      }
+    })
+    .collect::<Vec<_>>();
+
+  // The edges (identified by their start vertex) crossing the sweep line with
+  // the interior above them, and the helper of each: the latest vertex seen
+  // directly above the edge, which a later split or merge vertex connects to.
+  let mut sweep_line: Vec<usize> = Vec::new();
+  let mut helpers = vec![usize::MAX; points.len()];
+  let mut diagonals = Vec::new();
+  let edge_y_at = |edge: usize, x: f32| {
+    let (start, end) = (points[edge], points[next[edge]]);
+    if start.x == end.x {
+      start.y.max(end.y)
+    } else {
+      start.y + (end.y - start.y) * ((x - start.x) / (end.x - start.x))
+    }
+  };
+  let is_merge =
+    |helper: usize| helper != usize::MAX && kinds[helper] == VertexKind::Merge;
+  for &vertex in vertices.iter() {
+    let point = points[vertex];
+    // The directions of the edges leaving `vertex`. The interior is the wedge
+    // going counter-clockwise from `forward` to `backward`.
+    let forward = points[next[vertex]] - point;
+    let backward = points[previous[vertex]] - point;
+    let in_interior = |direction: Vec2| {
+      if forward.perp_dot(backward) > 0.0 {
+        forward.perp_dot(direction) > 0.0 && direction.perp_dot(backward) > 0.0
+      } else {
+        !(backward.perp_dot(direction) >= 0.0
+          && direction.perp_dot(forward) >= 0.0)
+      }
+    };
+    // Finds the edge directly below `vertex` on the sweep line. Edges of other
+    // contours touching `vertex` are only below it if they reach into its
+    // interior (e.g. a shell touching one of its holes), rather than lying on
+    // the other side of its edges.
+    let edge_below = |sweep_line: &[usize]| {
+      sweep_line
+        .iter()
+        .copied()
+        .filter(|&edge| {
+          let (start, end) = (points[edge], points[next[edge]]);
+          if start == point {
+            in_interior(end - point)
+          } else if end == point {
+            in_interior(start - point)
+          } else {
+            true
+          }
+        })
+        .map(|edge| (edge, edge_y_at(edge, point.x)))
+        .filter(|&(_, y)| y <= point.y)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(edge, _)| edge)
+    };
+    // The incoming edge ends here. If its helper is a merge vertex, that
+    // vertex still needs a diagonal to a later vertex, so it connects here.
+    if matches!(kinds[vertex], VertexKind::End | VertexKind::Merge)
+      || (kinds[vertex] == VertexKind::Regular
+        && sweep_order(next[vertex], vertex) == Ordering::Greater)
+    {
+      let edge = previous[vertex];
+      if let Some(position) = sweep_line.iter().position(|&e| e == edge) {
+        if is_merge(helpers[edge]) {
+          diagonals.push((vertex, helpers[edge]));
+        }
+        sweep_line.remove(position);
+      }
+    }
+    // Split vertices, merge vertices and vertices with the interior below them
+    // become the helper of the edge below them. A split vertex always connects
+    // to the old helper, while other vertices only connect to merge vertices.
+    if matches!(kinds[vertex], VertexKind::Split | VertexKind::Merge)
+      || (kinds[vertex] == VertexKind::Regular
+        && sweep_order(next[vertex], vertex) == Ordering::Less)
+    {
+      if let Some(edge) = edge_below(&sweep_line) {
+        if kinds[vertex] == VertexKind::Split || is_merge(helpers[edge]) {
+          diagonals.push((vertex, helpers[edge]));
+        }
+        helpers[edge] = vertex;
+      }
+    }
+    // Outgoing edges going forward in the sweep have the interior above them,
+    // so they join the sweep line.
+    if sweep_order(next[vertex], vertex) == Ordering::Greater {
+      sweep_line.push(vertex);
+      helpers[vertex] = vertex;
+    }
+  }
+
+  let mut outgoing = (0..points.len())
+    .map(
+      |vertex| {
+        if next[vertex] == usize::MAX {
+          vec![]
+        } else {
+          vec![next[vertex]]
+        }
+      },
+    )
+    .collect::<Vec<_>>();
+  for &(a, b) in diagonals.iter() {
+    outgoing[a].push(b);
+    outgoing[b].push(a);
+  }
+  // Walks each face, keeping it on the left by taking the sharpest left turn
+  // (the outgoing edge that is first clockwise from the way back).
+  let turn_after = |from: usize, at: usize| {
+    let back = points[from] - points[at];
+    outgoing[at]
+      .iter()
+      .copied()
+      .max_by(|&a, &b| {
+        let angle = |to: usize| {
+          let direction = points[to] - points[at];
+          let angle = back.perp_dot(direction).atan2(back.dot(direction));
+          if angle < 0.0 {
+            angle + TAU
+          } else {
+            angle
+          }
+        };
+        angle(a).total_cmp(&angle(b))
+      })
+      .expect("every vertex of a face has an outgoing edge")
+  };
+  let mut visited = HashSet::new();
+  let mut pieces = Vec::new();
+  for &vertex in vertices.iter() {
+    for &to in outgoing[vertex].iter() {
+      if visited.contains(&(vertex, to)) {
+        continue;
+      }
+      let mut piece = Vec::new();
+      let (mut from, mut at) = (vertex, to);
+      while visited.insert((from, at)) {
+        piece.push(points[from]);
+        (from, at) = (at, turn_after(from, at));
+      }
+      pieces.push(piece);
+    }
+  }
+  pieces
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square;
+  use crate::{contour_signed_area, difference, Polygon};
+
+  // Whether every vertical line crosses `contour` at most twice, i.e. the
+  // edges switch between going left and going right at most twice.
+  fn is_x_monotone(contour: &[Vec2]) -> bool {
+    let directions = (0..contour.len())
+      .map(|index| contour[(index + 1) % contour.len()].x - contour[index].x)
+      .filter(|&dx| dx != 0.0)
+      .map(|dx| dx > 0.0)
+      .collect::<Vec<_>>();
+    (0..directions.len())
+      .filter(|&index| {
+        directions[index] != directions[(index + 1) % directions.len()]
+      })
+      .count()
+      <= 2
+  }
+
+  fn assert_partitions(polygon: &Polygon, pieces: &[Vec<Vec2>]) {
+    for piece in pieces {
+      assert!(is_x_monotone(piece), "{piece:?} is not x-monotone");
+      assert!(contour_signed_area(piece) > 0.0, "{piece:?} is not CCW");
+    }
+    let area = pieces.iter().map(|piece| contour_signed_area(piece)).sum();
+    assert_eq!(polygon.area().abs(), area);
+  }
+
+  #[test]
+  fn splits_around_holes() {
+    let result = difference(
+      &Polygon { contours: vec![square(Vec2::ZERO, 4.0)] },
+      &Polygon { contours: vec![square(Vec2::ONE, 2.0)] },
+    );
+    let pieces = result.monotone_partition();
+    // The hole's left vertices split the square and its right vertices merge
+    // it back together, so the pieces go above and below the hole.
+    assert_eq!(pieces.len(), 2);
+    assert_partitions(&result.polygon, &pieces);
+    assert_eq!(result.polygon.monotone_partition().len(), 2);
+  }
+
+  #[test]
+  fn splits_combs_at_each_tooth() {
+    // A comb with three teeth pointing right and a notch on the left.
+    let comb = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(4.0, 0.0),
+        Vec2::new(4.0, 1.0),
+        Vec2::new(2.0, 1.0),
+        Vec2::new(2.0, 2.0),
+        Vec2::new(4.0, 2.0),
+        Vec2::new(4.0, 3.0),
+        Vec2::new(2.0, 3.0),
+        Vec2::new(2.0, 4.0),
+        Vec2::new(4.0, 4.0),
+        Vec2::new(4.0, 5.0),
+        Vec2::new(0.0, 5.0),
+        Vec2::new(0.0, 3.0),
+        Vec2::new(1.0, 2.5),
+        Vec2::new(0.0, 2.0),
+      ]],
+    };
+    let pieces = comb.monotone_partition();
+    assert_partitions(&comb, &pieces);
+    // Already monotone polygons are left alone.
+    let convex = Polygon { contours: vec![square(Vec2::ZERO, 1.0)] };
+    assert_eq!(convex.monotone_partition().len(), 1);
+    assert!(Polygon::default().monotone_partition().is_empty());
+  }
+}
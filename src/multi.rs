@@ -0,0 +1,435 @@
+use glam::Vec2;
+
+use crate::{
+  compute_boolean, degenerate_edge_warnings, finish_boolean,
+  hierarchy::{compute_contour_parents, contour_depth},
+  orientation::orient_contours,
+  polygon_to_boolean_result, try_boolean_with_options, unwrap_boolean,
+  validate_input, BooleanOptions, BooleanResult, ClipError, FillRule,
+  Operation, Polygon, Rule, SourceEdge,
+};
+
+// A set of disjoint polygons, each with its own holes (e.g. the islands of a
+// country or the rooms of a level). Unlike a `Polygon`, which is just a list of
+// contours, each polygon is kept apart, so polygons far from the other operand
+// of a boolean operation can skip the sweep entirely.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct MultiPolygon {
+  pub polygons: Vec<Polygon>,
+}
+
+impl MultiPolygon {
+  // Splits `polygon` into one polygon per shell, holding the shell and the
+  // holes directly inside it. Shells inside holes become separate polygons.
+  // Contours are assumed to not cross each other (e.g. the result of a boolean
+  // operation).
+  pub fn from_polygon(polygon: &Polygon) -> MultiPolygon {
+    group_contours(polygon, &compute_contour_parents(polygon))
+  }
+
+  // Concatenates the contours of all the polygons into a single polygon. Since
+  // the polygons are disjoint, this covers the same area.
+  pub fn to_polygon(&self) -> Polygon {
+    Polygon {
+      contours: self
+        .polygons
+        .iter()
+        .flat_map(|polygon| polygon.contours.iter().cloned())
+        .collect(),
+    }
+  }
+
+  // Computes the bounding box (min, max) of all the polygons. Returns None if
+  // there are no vertices.
+  pub fn compute_bounds(&self) -> Option<(Vec2, Vec2)> {
+    self.polygons.iter().filter_map(Polygon::compute_bounds).fold(
+      None,
+      |bounds, (min, max)| {
+        Some(match bounds {
+          None => (min, max),
+          Some((bounds_min, bounds_max)) => {
+            (bounds_min.min(min), bounds_max.max(max))
+          }
+        })
+      },
+    )
+  }
+
+  // Computes the union of this and `other`. This panics if the operation
+  // cannot be computed (see `try_boolean_multi`).
+  pub fn union(&self, other: &MultiPolygon) -> MultiPolygon {
+    boolean_multi(self, other, Operation::Union).to_multi_polygon()
+  }
+
+  // Computes the intersection of this and `other`. This panics if the
+  // operation cannot be computed (see `try_boolean_multi`).
+  pub fn intersection(&self, other: &MultiPolygon) -> MultiPolygon {
+    boolean_multi(self, other, Operation::Intersection).to_multi_polygon()
+  }
+
+  // Computes the difference of this and `other` (the area of this outside
+  // `other`). This panics if the operation cannot be computed (see
+  // `try_boolean_multi`).
+  pub fn difference(&self, other: &MultiPolygon) -> MultiPolygon {
+    boolean_multi(self, other, Operation::Difference).to_multi_polygon()
+  }
+
+  // Computes the XOR of this and `other`. This panics if the operation cannot
+  // be computed (see `try_boolean_multi`).
+  pub fn xor(&self, other: &MultiPolygon) -> MultiPolygon {
+    boolean_multi(self, other, Operation::XOR).to_multi_polygon()
+  }
+}
+
+impl BooleanResult {
+  // Splits the resulting polygon into one polygon per shell (see
+  // `MultiPolygon::from_polygon`). This uses `contour_parents` if it was
+  // computed.
+  pub fn to_multi_polygon(&self) -> MultiPolygon {
+    if self.contour_parents.len() == self.polygon.contours.len() {
+      group_contours(&self.polygon, &self.contour_parents)
+    } else {
+      MultiPolygon::from_polygon(&self.polygon)
+    }
+  }
+}
+
+// Groups each shell of `polygon` with the holes directly inside it, given the
+// parent of each contour.
+fn group_contours(
+  polygon: &Polygon,
+  contour_parents: &[Option<usize>],
+) -> MultiPolygon {
+  let mut polygons = Vec::new();
+  // The index in `polygons` of the polygon for each shell.
+  let mut shell_polygons = vec![None; polygon.contours.len()];
+  for (index, contour) in polygon.contours.iter().enumerate() {
+    if contour_depth(contour_parents, index).is_multiple_of(2) {
+      shell_polygons[index] = Some(polygons.len());
+      polygons.push(Polygon { contours: vec![contour.clone()] });
+    }
+  }
+  for (index, contour) in polygon.contours.iter().enumerate() {
+    if shell_polygons[index].is_some() {
+      continue;
+    }
+    // Holes always have a parent, which is a shell.
+    let shell_polygon = contour_parents[index]
+      .and_then(|parent| shell_polygons[parent])
+      .expect("holes are nested in a shell");
+    polygons[shell_polygon].contours.push(contour.clone());
+  }
+  MultiPolygon { polygons }
+}
+
+// Computes the boolean operation of `subject` and `clip`. Only the polygons
+// whose bounding boxes overlap a polygon of the other operand are swept; the
+// rest are kept or dropped as a whole, depending on `operation`. The source
+// edges of the result count the contours of all the polygons of each operand
+// in order (like `MultiPolygon::to_polygon`). This panics if the operation
+// cannot be computed (see `try_boolean_multi`).
+pub fn boolean_multi(
+  subject: &MultiPolygon,
+  clip: &MultiPolygon,
+  operation: Operation,
+) -> BooleanResult {
+  unwrap_boolean(try_boolean_multi(subject, clip, operation))
+}
+
+// Same as `boolean_multi`, but with `options`. Some options relate polygons
+// of the same operand (e.g. `weld_tolerance`), so with those every polygon is
+// swept.
+pub fn boolean_multi_with_options(
+  subject: &MultiPolygon,
+  clip: &MultiPolygon,
+  operation: Operation,
+  options: &BooleanOptions,
+) -> BooleanResult {
+  unwrap_boolean(try_boolean_multi_with_options(
+    subject, clip, operation, options,
+  ))
+}
+
+// Same as `boolean_multi`, but returns an error instead of panicking. The
+// contours of errors count the contours of all the polygons like the source
+// edges of the result do.
+pub fn try_boolean_multi(
+  subject: &MultiPolygon,
+  clip: &MultiPolygon,
+  operation: Operation,
+) -> Result<BooleanResult, ClipError> {
+  try_boolean_multi_with_options(
+    subject,
+    clip,
+    operation,
+    &BooleanOptions::default(),
+  )
+}
+
+// Same as `boolean_multi_with_options`, but returns an error instead of
+// panicking.
+pub fn try_boolean_multi_with_options(
+  subject: &MultiPolygon,
+  clip: &MultiPolygon,
+  operation: Operation,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  let flat_subject = subject.to_polygon();
+  let flat_clip = clip.to_polygon();
+  // Filling, welding and aligning can join polygons of the same operand, and
+  // the relation is between whole operands, so these sweep everything.
+  if options.fill_rules() != (FillRule::EvenOdd, FillRule::EvenOdd)
+    || options.weld_tolerance > 0.0
+    || options.boundary_tolerance > 0.0
+    || options.classify_relation
+  {
+    return try_boolean_with_options(
+      &flat_subject,
+      &flat_clip,
+      operation,
+      options,
+    );
+  }
+
+  validate_input(&flat_subject, &flat_clip)?;
+  if let Some(max_events) = options.max_events {
+    let edge_count =
+      flat_subject.num_edges().saturating_add(flat_clip.num_edges());
+    if max_events < edge_count.saturating_mul(2) {
+      return Err(ClipError::CapacityExceeded { limit: max_events });
+    }
+  }
+
+  let subject_parts = SplitOperand::new(subject, clip);
+  let clip_parts = SplitOperand::new(clip, subject);
+  let mut result = compute_boolean(
+    &subject_parts.swept,
+    &clip_parts.swept,
+    Rule::Operation(operation),
+    options,
+  )
+  .map_err(|mut error| {
+    if let ClipError::InconsistentSweep { source_edge, .. } = &mut error {
+      remap_source_edge(source_edge, &subject_parts, &clip_parts);
+    }
+    error
+  })?;
+  for source_edge in result.contour_source_edges.iter_mut().flatten() {
+    remap_source_edge(source_edge, &subject_parts, &clip_parts);
+  }
+  for source_edge in
+    result.contour_coincident_source_edges.iter_mut().flatten().flatten()
+  {
+    remap_source_edge(source_edge, &subject_parts, &clip_parts);
+  }
+
+  // The isolated polygons are copied like other trivially computed results, so
+  // they keep the orientation of the inputs. Only the swept contours are
+  // oriented (which needs their hierarchy from the sweep).
+  if result.contour_parents.len() == result.polygon.contours.len() {
+    let contour_parents = std::mem::take(&mut result.contour_parents);
+    orient_contours(&mut result, &contour_parents, options.output_orientation);
+    result.contour_parents = contour_parents;
+  }
+
+  // The isolated polygons cannot touch the other operand, so they are either
+  // entirely in the result or entirely outside it.
+  let keep_isolated_subject = operation != Operation::Intersection;
+  let keep_isolated_clip =
+    matches!(operation, Operation::Union | Operation::XOR);
+  for (parts, is_subject, keep) in [
+    (&subject_parts, true, keep_isolated_subject),
+    (&clip_parts, false, keep_isolated_clip),
+  ] {
+    if !keep || parts.isolated.contours.is_empty() {
+      continue;
+    }
+    let mut isolated_result =
+      polygon_to_boolean_result(&parts.isolated, is_subject);
+    for source_edge in isolated_result.contour_source_edges.iter_mut().flatten()
+    {
+      source_edge.contour = parts.isolated_contours[source_edge.contour];
+    }
+    result.merge(isolated_result);
+  }
+
+  if options.collect_warnings {
+    let mut warnings =
+      degenerate_edge_warnings(&flat_subject, &flat_clip).collect::<Vec<_>>();
+    warnings.append(&mut result.warnings);
+    result.warnings = warnings;
+  }
+  Ok(finish_boolean(result, &flat_subject, &flat_clip, options))
+}
+
+// The polygons of an operand, split by whether they can overlap the other
+// operand.
+struct SplitOperand {
+  // The contours of the polygons whose bounding boxes overlap a polygon of the
+  // other operand.
+  swept: Polygon,
+  // The index in the whole operand of each contour in `swept`.
+  swept_contours: Vec<usize>,
+  // The contours of the remaining polygons.
+  isolated: Polygon,
+  // The index in the whole operand of each contour in `isolated`.
+  isolated_contours: Vec<usize>,
+}
+
+impl SplitOperand {
+  fn new(operand: &MultiPolygon, other: &MultiPolygon) -> Self {
+    let other_bounds = other
+      .polygons
+      .iter()
+      .filter_map(Polygon::compute_bounds)
+      .collect::<Vec<_>>();
+    let mut split = SplitOperand {
+      swept: Polygon::default(),
+      swept_contours: Vec::new(),
+      isolated: Polygon::default(),
+      isolated_contours: Vec::new(),
+    };
+    let mut contour_offset = 0;
+    for polygon in operand.polygons.iter() {
+      let overlaps = polygon.compute_bounds().is_some_and(|(min, max)| {
+        other_bounds.iter().any(|(other_min, other_max)| {
+          !(max.x < other_min.x
+            || max.y < other_min.y
+            || other_max.x < min.x
+            || other_max.y < min.y)
+        })
+      });
+      let (contours, indices) = if overlaps {
+        (&mut split.swept, &mut split.swept_contours)
+      } else {
+        (&mut split.isolated, &mut split.isolated_contours)
+      };
+      contours.contours.extend(polygon.contours.iter().cloned());
+      indices.extend(contour_offset..contour_offset + polygon.contours.len());
+      contour_offset += polygon.contours.len();
+    }
+    split
+  }
+}
+
+// Maps `source_edge` from the swept polygons to the whole operands.
+fn remap_source_edge(
+  source_edge: &mut SourceEdge,
+  subject_parts: &SplitOperand,
+  clip_parts: &SplitOperand,
+) {
+  let parts =
+    if source_edge.is_from_subject { subject_parts } else { clip_parts };
+  source_edge.contour = parts.swept_contours[source_edge.contour];
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square;
+  use crate::{
+    boolean_multi, boolean_multi_with_options, boolean_with_options,
+    BooleanOptions, ContourOrder, MultiPolygon, Operation, Polygon,
+    TrackingLevel,
+  };
+
+  #[test]
+  fn matches_operations_on_flattened_polygons() {
+    let subject = MultiPolygon {
+      polygons: vec![
+        Polygon {
+          contours: vec![
+            square(Vec2::ZERO, 4.0),
+            square(Vec2::new(1.0, 1.0), 1.0),
+          ],
+        },
+        // Far from the clip, so it is never swept.
+        Polygon { contours: vec![square(Vec2::new(20.0, 0.0), 2.0)] },
+        Polygon { contours: vec![square(Vec2::new(6.0, 0.0), 2.0)] },
+      ],
+    };
+    let clip = MultiPolygon {
+      polygons: vec![
+        Polygon { contours: vec![square(Vec2::new(3.0, 3.0), 4.0)] },
+        Polygon { contours: vec![square(Vec2::new(0.0, 20.0), 2.0)] },
+      ],
+    };
+    // The order of the contours depends on which polygons were swept.
+    // Classifying the relation would sweep every polygon.
+    let options = BooleanOptions {
+      classify_relation: false,
+      ..BooleanOptions::new()
+        .with_tracking(TrackingLevel::Full)
+        .with_contour_order(ContourOrder::Spatial)
+    };
+    for operation in Operation::ALL {
+      assert_eq!(
+        boolean_multi_with_options(&subject, &clip, operation, &options),
+        boolean_with_options(
+          &subject.to_polygon(),
+          &clip.to_polygon(),
+          operation,
+          &options
+        ),
+        "{operation:?}"
+      );
+    }
+  }
+
+  #[test]
+  fn operations_return_separate_polygons() {
+    let a = MultiPolygon {
+      polygons: vec![
+        Polygon { contours: vec![square(Vec2::ZERO, 2.0)] },
+        Polygon { contours: vec![square(Vec2::new(10.0, 0.0), 2.0)] },
+      ],
+    };
+    let b = MultiPolygon {
+      polygons: vec![Polygon {
+        contours: vec![square(Vec2::new(1.0, 1.0), 2.0)],
+      }],
+    };
+
+    let union = a.union(&b);
+    assert_eq!(union.polygons.len(), 2);
+    assert!(union.polygons.iter().all(|polygon| polygon.contours.len() == 1));
+    assert_eq!(a.intersection(&b).polygons.len(), 1);
+    assert_eq!(a.difference(&b).polygons.len(), 2);
+    // The XOR of overlapping squares touches itself at two corners, which are
+    // traced as two polygons.
+    assert_eq!(a.xor(&b).polygons.len(), 3);
+    assert_eq!(
+      boolean_multi(&a, &b, Operation::Union).to_multi_polygon(),
+      union
+    );
+  }
+
+  #[test]
+  fn from_polygon_groups_holes_with_their_shell() {
+    let polygon = Polygon {
+      contours: vec![
+        square(Vec2::ZERO, 10.0),
+        square(Vec2::new(2.0, 2.0), 6.0),
+        // An island in the hole.
+        square(Vec2::new(4.0, 4.0), 2.0),
+        square(Vec2::new(20.0, 0.0), 1.0),
+      ],
+    };
+    assert_eq!(
+      MultiPolygon::from_polygon(&polygon),
+      MultiPolygon {
+        polygons: vec![
+          Polygon {
+            contours: vec![
+              square(Vec2::ZERO, 10.0),
+              square(Vec2::new(2.0, 2.0), 6.0),
+            ],
+          },
+          Polygon { contours: vec![square(Vec2::new(4.0, 4.0), 2.0)] },
+          Polygon { contours: vec![square(Vec2::new(20.0, 0.0), 1.0)] },
+        ],
+      }
+    );
+  }
+}
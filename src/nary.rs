@@ -0,0 +1,573 @@
+use crate::{
+  degenerate_edge_warnings,
+  fill::{fill_contours, overlay_contours, Coverage},
+  finish_boolean, unwrap_boolean, validate_input, BooleanOptions,
+  BooleanResult, ClipError, FillRule, Polygon, SourceEdge,
+};
+
+// Computes the union of all of `polygons` in a single sweep, which is faster
+// than folding pairwise unions and only rounds each intersection once. The
+// source edges of the result are all from the subject, and their contours
+// count the contours of all the polygons in order (e.g. the first contour of
+// the second polygon comes right after the last contour of the first polygon).
+// This panics if any polygon cannot be used in a boolean operation (see
+// `try_union_all`).
+pub fn union_all(polygons: &[Polygon]) -> BooleanResult {
+  unwrap_boolean(try_union_all_with_options(
+    polygons,
+    &BooleanOptions::default(),
+  ))
+}
+
+// Same as `union_all`, but with `options`. Each polygon is resolved with
+// `BooleanOptions::fill_rule` (except the clips of `difference_all`, which use
+// `BooleanOptions::clip_fill_rule` if it is set). The options for aligning the
+// subject and clip (`weld_tolerance` and `boundary_tolerance`) do not apply.
+pub fn union_all_with_options(
+  polygons: &[Polygon],
+  options: &BooleanOptions,
+) -> BooleanResult {
+  unwrap_boolean(try_union_all_with_options(polygons, options))
+}
+
+// Same as `union_all`, but returns an error instead of panicking. The contours
+// of errors count the contours of all the polygons like the source edges of
+// the result do.
+pub fn try_union_all(polygons: &[Polygon]) -> Result<BooleanResult, ClipError> {
+  try_union_all_with_options(polygons, &BooleanOptions::default())
+}
+
+// Same as `union_all_with_options`, but returns an error instead of panicking.
+pub fn try_union_all_with_options(
+  polygons: &[Polygon],
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  combine_polygons(polygons, &[], options, |coverage| coverage.count > 0)
+}
+
+// Computes the intersection of all of `polygons` (the region inside every one
+// of them) in a single sweep, without computing any intermediate intersections.
+// The intersection of no polygons is empty. Source edges and errors count the
+// contours of all the polygons like `union_all`. This panics if any polygon
+// cannot be used in a boolean operation (see `try_intersection_all`).
+pub fn intersection_all(polygons: &[Polygon]) -> BooleanResult {
+  unwrap_boolean(try_intersection_all_with_options(
+    polygons,
+    &BooleanOptions::default(),
+  ))
+}
+
+// Same as `intersection_all`, but with `options` (see
+// `union_all_with_options`).
+pub fn intersection_all_with_options(
+  polygons: &[Polygon],
+  options: &BooleanOptions,
+) -> BooleanResult {
+  unwrap_boolean(try_intersection_all_with_options(polygons, options))
+}
+
+// Same as `intersection_all`, but returns an error instead of panicking.
+pub fn try_intersection_all(
+  polygons: &[Polygon],
+) -> Result<BooleanResult, ClipError> {
+  try_intersection_all_with_options(polygons, &BooleanOptions::default())
+}
+
+// Same as `intersection_all_with_options`, but returns an error instead of
+// panicking.
+pub fn try_intersection_all_with_options(
+  polygons: &[Polygon],
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  combine_polygons(polygons, &[], options, |coverage| {
+    coverage.count == polygons.len()
+  })
+}
+
+// Computes the difference of `subject` and all of `clips` (the region inside
+// `subject` but outside every clip) in a single sweep, which is much faster
+// than subtracting the clips one at a time from an ever-growing subject. The
+// source edges of the result from the clips count the contours of all the clips
+// in order (like `union_all`). This panics if any polygon cannot be used in a
+// boolean operation (see `try_difference_all`).
+pub fn difference_all(subject: &Polygon, clips: &[Polygon]) -> BooleanResult {
+  unwrap_boolean(try_difference_all_with_options(
+    subject,
+    clips,
+    &BooleanOptions::default(),
+  ))
+}
+
+// Same as `difference_all`, but with `options` (see `union_all_with_options`).
+pub fn difference_all_with_options(
+  subject: &Polygon,
+  clips: &[Polygon],
+  options: &BooleanOptions,
+) -> BooleanResult {
+  unwrap_boolean(try_difference_all_with_options(subject, clips, options))
+}
+
+// Same as `difference_all`, but returns an error instead of panicking.
+pub fn try_difference_all(
+  subject: &Polygon,
+  clips: &[Polygon],
+) -> Result<BooleanResult, ClipError> {
+  try_difference_all_with_options(subject, clips, &BooleanOptions::default())
+}
+
+// Same as `difference_all_with_options`, but returns an error instead of
+// panicking.
+pub fn try_difference_all_with_options(
+  subject: &Polygon,
+  clips: &[Polygon],
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  // The subject is the first polygon, so it must be the only one containing a
+  // point.
+  combine_polygons(std::slice::from_ref(subject), clips, options, |coverage| {
+    coverage.in_first && coverage.count == 1
+  })
+}
+
+// A connected region of the overlay of several polygons, with its holes (see
+// `overlay_all`).
+#[derive(Clone, PartialEq, Debug)]
+pub struct CoverageFace {
+  // The polygons covering the face, as a bitmask where bit i is set if the
+  // i-th polygon covers it.
+  pub mask: u64,
+  pub polygon: Polygon,
+}
+
+impl CoverageFace {
+  // The number of polygons covering the face.
+  pub fn count(&self) -> u32 {
+    self.mask.count_ones()
+  }
+
+  // Whether the polygon at `index` covers the face.
+  pub fn is_covered_by(&self, index: usize) -> bool {
+    index < 64 && self.mask & (1 << index) != 0
+  }
+}
+
+// Computes every face of the arrangement of `polygons` (the connected regions
+// covered by any of them), each with the bitmask of the polygons covering it
+// (e.g. which sensors see each region). All the polygons are split in a single
+// sweep, and the regions covered by each set of polygons are joined from the
+// same split edges, so neighbouring faces share their points exactly. The
+// faces are ordered by mask. Source edges count the contours of all the
+// polygons like `union_all`. This panics if there are more than 64 polygons
+// (even in `try_overlay_all`) or if any polygon cannot be used in a boolean
+// operation.
+pub fn overlay_all(polygons: &[Polygon]) -> Vec<CoverageFace> {
+  try_overlay_all(polygons).unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `overlay_all`, but with `options` (see `union_all_with_options`).
+pub fn overlay_all_with_options(
+  polygons: &[Polygon],
+  options: &BooleanOptions,
+) -> Vec<CoverageFace> {
+  try_overlay_all_with_options(polygons, options)
+    .unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `overlay_all`, but returns an error instead of panicking (except for
+// too many polygons).
+pub fn try_overlay_all(
+  polygons: &[Polygon],
+) -> Result<Vec<CoverageFace>, ClipError> {
+  try_overlay_all_with_options(polygons, &BooleanOptions::default())
+}
+
+// Same as `overlay_all_with_options`, but returns an error instead of
+// panicking (except for too many polygons).
+pub fn try_overlay_all_with_options(
+  polygons: &[Polygon],
+  options: &BooleanOptions,
+) -> Result<Vec<CoverageFace>, ClipError> {
+  assert!(
+    polygons.len() <= 64,
+    "overlay_all supports at most 64 polygons, but got {}",
+    polygons.len()
+  );
+  let combined = CombinedPolygons::new(polygons, &[], options)?;
+  let regions = overlay_contours(
+    &combined.all_contours,
+    &combined.contour_polygons,
+    &combined.fill_rules,
+  )
+  .map_err(|error| combined.remap_error(error))?;
+  Ok(
+    regions
+      .into_iter()
+      .flat_map(|(mask, result)| {
+        combined
+          .finish(result, options)
+          .to_multi_polygon()
+          .polygons
+          .into_iter()
+          .map(move |polygon| CoverageFace { mask, polygon })
+      })
+      .collect(),
+  )
+}
+
+// Combines `subjects` and `clips` in a single sweep, keeping the points whose
+// `Coverage` satisfies `is_inside` (where the polygons are numbered with the
+// subjects first). The contours of all the polygons are swept as one polygon,
+// whose windings are counted separately for each polygon. Source edges (and
+// errors) count the contours of all the subjects, or of all the clips.
+fn combine_polygons(
+  subjects: &[Polygon],
+  clips: &[Polygon],
+  options: &BooleanOptions,
+  is_inside: impl Fn(Coverage) -> bool,
+) -> Result<BooleanResult, ClipError> {
+  let combined = CombinedPolygons::new(subjects, clips, options)?;
+  let result = fill_contours(
+    &combined.all_contours,
+    &combined.contour_polygons,
+    &combined.fill_rules,
+    is_inside,
+  )
+  .map_err(|error| combined.remap_error(error))?;
+  Ok(combined.finish(result, options))
+}
+
+// The contours of several subjects and clips, concatenated to be swept as one
+// polygon.
+struct CombinedPolygons {
+  // The contours of all the subjects.
+  subject_contours: Polygon,
+  // The contours of all the clips.
+  clip_contours: Polygon,
+  // The contours of all the subjects, then all the clips.
+  all_contours: Polygon,
+  // The index of the polygon each contour of `all_contours` belongs to.
+  contour_polygons: Vec<usize>,
+  // The fill rule of each polygon.
+  fill_rules: Vec<FillRule>,
+}
+
+impl CombinedPolygons {
+  // Concatenates the contours of `subjects` and `clips`, checking that they
+  // are valid and fit in `BooleanOptions::max_events`.
+  fn new(
+    subjects: &[Polygon],
+    clips: &[Polygon],
+    options: &BooleanOptions,
+  ) -> Result<Self, ClipError> {
+    let concatenate = |polygons: &[Polygon]| Polygon {
+      contours: polygons
+        .iter()
+        .flat_map(|polygon| polygon.contours.iter().cloned())
+        .collect(),
+    };
+    let (subject_contours, clip_contours) =
+      (concatenate(subjects), concatenate(clips));
+    validate_input(&subject_contours, &clip_contours)?;
+    if let Some(max_events) = options.max_events {
+      let edge_count =
+        subject_contours.num_edges().saturating_add(clip_contours.num_edges());
+      if max_events < edge_count.saturating_mul(2) {
+        return Err(ClipError::CapacityExceeded { limit: max_events });
+      }
+    }
+
+    let all_contours = Polygon {
+      contours: subject_contours
+        .contours
+        .iter()
+        .chain(clip_contours.contours.iter())
+        .cloned()
+        .collect(),
+    };
+    let contour_polygons = subjects
+      .iter()
+      .chain(clips.iter())
+      .enumerate()
+      .flat_map(|(index, polygon)| {
+        std::iter::repeat_n(index, polygon.contours.len())
+      })
+      .collect();
+    let (subject_fill_rule, clip_fill_rule) = options.fill_rules();
+    let fill_rules = std::iter::repeat_n(subject_fill_rule, subjects.len())
+      .chain(std::iter::repeat_n(clip_fill_rule, clips.len()))
+      .collect();
+    Ok(Self {
+      subject_contours,
+      clip_contours,
+      all_contours,
+      contour_polygons,
+      fill_rules,
+    })
+  }
+
+  // Maps an edge of `all_contours` to the edge of the subjects or the clips.
+  fn remap_source_edge(&self, source_edge: &mut SourceEdge) {
+    let subject_contour_count = self.subject_contours.contours.len();
+    if source_edge.contour >= subject_contour_count {
+      source_edge.is_from_subject = false;
+      source_edge.contour -= subject_contour_count;
+    }
+  }
+
+  // Maps the source edge of `error` like `remap_source_edge`.
+  fn remap_error(&self, mut error: ClipError) -> ClipError {
+    if let ClipError::InconsistentSweep { source_edge, .. } = &mut error {
+      self.remap_source_edge(source_edge);
+    }
+    error
+  }
+
+  // Finishes `result`, which was swept from `all_contours`.
+  fn finish(
+    &self,
+    mut result: BooleanResult,
+    options: &BooleanOptions,
+  ) -> BooleanResult {
+    for source_edge in result.contour_source_edges.iter_mut().flatten() {
+      self.remap_source_edge(source_edge);
+    }
+    if options.collect_warnings {
+      let mut warnings =
+        degenerate_edge_warnings(&self.subject_contours, &self.clip_contours)
+          .collect::<Vec<_>>();
+      warnings.append(&mut result.warnings);
+      result.warnings = warnings;
+    }
+    finish_boolean(result, &self.subject_contours, &self.clip_contours, options)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square_polygon;
+  use crate::{
+    difference, difference_all, intersection, intersection_all, overlay_all,
+    try_difference_all, try_union_all, union, union_all,
+    util::contour_signed_area, ClipError, Polygon, SourceEdge,
+  };
+
+  fn area(polygon: &Polygon) -> f32 {
+    polygon.contours.iter().map(|contour| contour_signed_area(contour)).sum()
+  }
+
+  #[test]
+  fn unions_many_polygons() {
+    // A row of overlapping squares, plus a disjoint one.
+    let mut polygons = (0..5)
+      .map(|i| square_polygon(Vec2::new(i as f32 * 2.0, 0.0), 3.0))
+      .collect::<Vec<_>>();
+    polygons.push(square_polygon(Vec2::new(0.0, 10.0), 1.0));
+
+    let result = union_all(&polygons);
+    assert_eq!(result.polygon.contours.len(), 2);
+    assert_eq!(area(&result.polygon), 11.0 * 3.0 + 1.0);
+    let folded =
+      polygons.iter().fold(Polygon::default(), |union_so_far, polygon| {
+        union(&union_so_far, polygon).polygon
+      });
+    assert_eq!(area(&result.polygon), area(&folded));
+
+    // Source edges count the contours of all the polygons.
+    assert!(result.contour_source_edges.iter().flatten().any(|source_edge| {
+      *source_edge == SourceEdge { is_from_subject: true, contour: 5, edge: 0 }
+    }));
+  }
+
+  #[test]
+  fn keeps_holes_of_each_polygon() {
+    let mut ring = square_polygon(Vec2::ZERO, 6.0);
+    ring.contours.push(vec![
+      Vec2::new(1.0, 1.0),
+      Vec2::new(1.0, 5.0),
+      Vec2::new(5.0, 5.0),
+      Vec2::new(5.0, 1.0),
+    ]);
+    // The hole is only partly covered by the other squares, and the hole is
+    // wound the same way as the shell (which the even-odd rule ignores).
+    let mut same_winding_ring = ring.clone();
+    same_winding_ring.contours[1].reverse();
+    for ring in [ring, same_winding_ring] {
+      let polygons = [
+        ring,
+        square_polygon(Vec2::new(1.0, 1.0), 2.0),
+        square_polygon(Vec2::new(2.0, 2.0), 2.0),
+      ];
+      let result = union_all(&polygons);
+      assert_eq!(area(&result.polygon), 36.0 - 16.0 + 7.0);
+    }
+  }
+
+  #[test]
+  fn identical_polygons_union_to_one() {
+    let polygons = vec![square_polygon(Vec2::ZERO, 1.0); 3];
+    assert_eq!(union_all(&polygons).polygon, polygons[0]);
+    assert!(union_all(&[]).is_empty);
+  }
+
+  #[test]
+  fn intersects_many_polygons() {
+    let polygons = [
+      square_polygon(Vec2::ZERO, 4.0),
+      square_polygon(Vec2::new(1.0, 0.0), 4.0),
+      square_polygon(Vec2::new(2.0, 1.0), 4.0),
+    ];
+    let result = intersection_all(&polygons);
+    assert_eq!(result.polygon.contours.len(), 1);
+    assert_eq!(area(&result.polygon), 2.0 * 3.0);
+    let folded = intersection(
+      &intersection(&polygons[0], &polygons[1]).polygon,
+      &polygons[2],
+    );
+    assert_eq!(area(&result.polygon), area(&folded.polygon));
+
+    // A polygon disjoint from the others empties the intersection.
+    let mut with_disjoint = polygons.to_vec();
+    with_disjoint.push(square_polygon(Vec2::new(10.0, 10.0), 1.0));
+    assert!(intersection_all(&with_disjoint).is_empty);
+    assert!(intersection_all(&[]).is_empty);
+  }
+
+  #[test]
+  fn intersection_keeps_holes() {
+    let mut ring = square_polygon(Vec2::ZERO, 6.0);
+    ring.contours.push(vec![
+      Vec2::new(2.0, 2.0),
+      Vec2::new(2.0, 4.0),
+      Vec2::new(4.0, 4.0),
+      Vec2::new(4.0, 2.0),
+    ]);
+    let polygons = [
+      ring.clone(),
+      square_polygon(Vec2::new(1.0, 1.0), 4.0),
+      square_polygon(Vec2::new(1.0, 1.0), 4.0),
+    ];
+    let result = intersection_all(&polygons);
+    assert_eq!(area(&result.polygon), 16.0 - 4.0);
+    assert_eq!(result.polygon.contours.len(), 2);
+    assert_eq!(intersection_all(&[ring.clone()]).polygon.contours.len(), 2);
+  }
+
+  #[test]
+  fn subtracts_many_clips() {
+    let subject = square_polygon(Vec2::ZERO, 10.0);
+    // Overlapping obstacles inside the subject, plus one crossing its boundary
+    // and one outside it.
+    let clips = [
+      square_polygon(Vec2::new(1.0, 1.0), 2.0),
+      square_polygon(Vec2::new(2.0, 2.0), 2.0),
+      square_polygon(Vec2::new(6.0, 6.0), 1.0),
+      square_polygon(Vec2::new(9.0, 4.0), 2.0),
+      square_polygon(Vec2::new(20.0, 20.0), 1.0),
+    ];
+    let result = difference_all(&subject, &clips);
+    assert_eq!(area(&result.polygon), 100.0 - 7.0 - 1.0 - 2.0);
+    let folded = clips.iter().fold(subject.clone(), |subject, clip| {
+      difference(&subject, clip).polygon
+    });
+    assert_eq!(area(&result.polygon), area(&folded));
+
+    // Source edges from the clips count the contours of all the clips.
+    let source_edges =
+      result.contour_source_edges.iter().flatten().collect::<Vec<_>>();
+    assert!(source_edges.contains(&&SourceEdge {
+      is_from_subject: true,
+      contour: 0,
+      edge: 0
+    }));
+    assert!(source_edges.contains(&&SourceEdge {
+      is_from_subject: false,
+      contour: 3,
+      edge: 3
+    }));
+    assert!(!source_edges.iter().any(|source_edge| {
+      !source_edge.is_from_subject && source_edge.contour == 4
+    }));
+
+    assert_eq!(difference_all(&subject, &[]).polygon, subject);
+    assert!(difference_all(&subject, std::slice::from_ref(&subject)).is_empty);
+  }
+
+  #[test]
+  fn reports_errors_across_polygons() {
+    let mut bad = square_polygon(Vec2::ZERO, 1.0);
+    bad.contours[0][2].x = f32::NAN;
+    assert_eq!(
+      try_union_all(&[square_polygon(Vec2::ZERO, 1.0), bad.clone()]),
+      Err(ClipError::InvalidInput {
+        is_from_subject: true,
+        contour: 1,
+        vertex: 2
+      })
+    );
+    assert_eq!(
+      try_difference_all(
+        &square_polygon(Vec2::ZERO, 1.0),
+        &[square_polygon(Vec2::ZERO, 1.0), bad]
+      ),
+      Err(ClipError::InvalidInput {
+        is_from_subject: false,
+        contour: 1,
+        vertex: 2
+      })
+    );
+  }
+
+  #[test]
+  fn overlay_all_labels_faces_with_masks() {
+    // Three squares overlapping in a row, and a fourth far away.
+    let polygons = [
+      square_polygon(Vec2::ZERO, 4.0),
+      square_polygon(Vec2::new(2.0, 0.0), 4.0),
+      square_polygon(Vec2::new(3.0, 1.0), 4.0),
+      square_polygon(Vec2::splat(10.0), 1.0),
+    ];
+    let faces = overlay_all(&polygons);
+    let masks = faces.iter().map(|face| face.mask).collect::<Vec<_>>();
+    assert_eq!(masks, [0b0001, 0b0010, 0b0011, 0b0100, 0b0110, 0b0111, 0b1000]);
+    // The faces covered by a polygon make up that polygon.
+    for (index, polygon) in polygons.iter().enumerate() {
+      let covered_area = faces
+        .iter()
+        .filter(|face| face.is_covered_by(index))
+        .map(|face| area(&face.polygon))
+        .sum::<f32>();
+      assert_eq!(covered_area, area(polygon), "polygon {index}");
+    }
+    let triple = faces.iter().find(|face| face.count() == 3).unwrap();
+    assert_eq!(area(&triple.polygon), 1.0 * 3.0);
+  }
+
+  #[test]
+  fn overlay_all_splits_disconnected_faces() {
+    // The second polygon cuts the first into two faces it does not cover.
+    let polygons = [
+      Polygon {
+        contours: vec![vec![
+          Vec2::new(0.0, 0.0),
+          Vec2::new(6.0, 0.0),
+          Vec2::new(6.0, 2.0),
+          Vec2::new(0.0, 2.0),
+        ]],
+      },
+      Polygon {
+        contours: vec![vec![
+          Vec2::new(2.0, -1.0),
+          Vec2::new(4.0, -1.0),
+          Vec2::new(4.0, 3.0),
+          Vec2::new(2.0, 3.0),
+        ]],
+      },
+    ];
+    let faces = overlay_all(&polygons);
+    let masks = faces.iter().map(|face| face.mask).collect::<Vec<_>>();
+    assert_eq!(masks, [0b01, 0b01, 0b10, 0b10, 0b11]);
+    assert!(faces.iter().all(|face| face.polygon.contours.len() == 1));
+  }
+}
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use crate::{
+  hierarchy::compute_contour_parents, triangulate::triangulate,
+  try_difference_all, try_union_all, BooleanResult, ClipError, Polygon,
+  SourceEdge,
+};
+
+// Where a wall of a navmesh (an edge on the boundary of the walkable area)
+// comes from.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WallSource {
+  // The edge at `edge` of the contour at `contour` of `walkable[polygon]`.
+  Walkable { polygon: usize, contour: usize, edge: usize },
+  // The edge at `edge` of the contour at `contour` of `obstacles[obstacle]`.
+  Obstacle { obstacle: usize, contour: usize, edge: usize },
+}
+
+// What is on the other side of an edge of a navmesh triangle.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NavEdge {
+  // Another triangle (at this index), so agents can walk across the edge.
+  Portal(usize),
+  // The boundary of the walkable area.
+  Wall(WallSource),
+}
+
+// The walkable area left after carving obstacles out of walkable polygons,
+// along with its triangulation.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct NavMesh {
+  // The walkable area. Shells are counter-clockwise and holes are clockwise.
+  pub polygon: Polygon,
+  // The source of each edge in `polygon`. This has the same layout as
+  // `BooleanResult::contour_source_edges`.
+  pub contour_wall_sources: Vec<Vec<WallSource>>,
+  // The vertices of `polygon` in contour order, which `triangles` refer to.
+  pub vertices: Vec<Vec2>,
+  // The counter-clockwise triangles covering `polygon`, as indices into
+  // `vertices`.
+  pub triangles: Vec<[usize; 3]>,
+  // What is across each edge of each triangle. Edge `i` of a triangle goes
+  // from its vertex `i` to its vertex `(i + 1) % 3`.
+  pub triangle_edges: Vec<[NavEdge; 3]>,
+}
+
+// Builds a navmesh covering the union of the `walkable` polygons, with the
+// union of the `obstacles` carved out of it. The walkable polygons are
+// combined in one sweep and the obstacles carved out in another. Every wall
+// remembers which input edge it came from, so e.g. walls from obstacles can be
+// treated differently from the edges of the level. Returns an error if any of
+// the boolean operations fails.
+pub fn build_navmesh(
+  walkable: &[Polygon],
+  obstacles: &[Polygon],
+) -> Result<NavMesh, ClipError> {
+  let walkable_area = try_union_all(walkable)?;
+  let area = try_difference_all(&walkable_area.polygon, obstacles)?;
+  // The union of the walkable polygons only has edges from the subjects.
+  let walkable_sources = walkable_area
+    .contour_source_edges
+    .iter()
+    .map(|source_edges| {
+      source_edges
+        .iter()
+        .map(|source_edge| {
+          let (polygon, contour) =
+            locate_contour(walkable, source_edge.contour);
+          WallSource::Walkable { polygon, contour, edge: source_edge.edge }
+        })
+        .collect()
+    })
+    .collect::<Vec<_>>();
+  let wall_sources =
+    compose_wall_sources(&area, &walkable_sources, |source_edge| {
+      let (obstacle, contour) = locate_contour(obstacles, source_edge.contour);
+      WallSource::Obstacle { obstacle, contour, edge: source_edge.edge }
+    });
+
+  let triangles =
+    triangulate(&area.polygon, &compute_contour_parents(&area.polygon));
+  let triangle_edges =
+    connect_triangles(&area.polygon, &wall_sources, &triangles);
+  Ok(NavMesh {
+    vertices: area.polygon.contours.iter().flatten().copied().collect(),
+    polygon: area.polygon,
+    contour_wall_sources: wall_sources,
+    triangles,
+    triangle_edges,
+  })
+}
+
+// Finds the polygon of `polygons` containing the contour at `contour` of all
+// their contours (in order), and the index of the contour in that polygon.
+fn locate_contour(polygons: &[Polygon], mut contour: usize) -> (usize, usize) {
+  for (index, polygon) in polygons.iter().enumerate() {
+    if contour < polygon.contours.len() {
+      return (index, contour);
+    }
+    contour -= polygon.contours.len();
+  }
+  unreachable!("the contour is not in any polygon")
+}
+
+// Computes the wall sources of `result`, whose subject had `subject_sources`.
+// Edges from the clip are mapped with `clip_source`.
+fn compose_wall_sources(
+  result: &BooleanResult,
+  subject_sources: &[Vec<WallSource>],
+  clip_source: impl Fn(SourceEdge) -> WallSource,
+) -> Vec<Vec<WallSource>> {
+  result
+    .contour_source_edges
+    .iter()
+    .map(|source_edges| {
+      source_edges
+        .iter()
+        .map(|&source_edge| {
+          if source_edge.is_from_subject {
+            subject_sources[source_edge.contour][source_edge.edge]
+          } else {
+            clip_source(source_edge)
+          }
+        })
+        .collect()
+    })
+    .collect()
+}
+
+// Finds what is across each edge of `triangles` (which index the vertices of
+// `polygon` in contour order).
+fn connect_triangles(
+  polygon: &Polygon,
+  wall_sources: &[Vec<WallSource>],
+  triangles: &[[usize; 3]],
+) -> Vec<[NavEdge; 3]> {
+  // Edges are keyed by their vertex indices (smallest first). The two sides of
+  // a hole's bridge share vertex indices, so the triangles on either side are
+  // connected like any other pair.
+  let key = |a: usize, b: usize| (a.min(b), a.max(b));
+  let mut walls = HashMap::new();
+  // The index of the vertex after each vertex in its contour.
+  let mut next_vertex = Vec::new();
+  for (contour, sources) in polygon.contours.iter().zip(wall_sources.iter()) {
+    let first_index = next_vertex.len();
+    for (edge, &source) in sources.iter().enumerate() {
+      let next_index = first_index + (edge + 1) % contour.len();
+      walls.insert(key(first_index + edge, next_index), source);
+      next_vertex.push(next_index);
+    }
+  }
+  let mut edge_triangles = HashMap::<_, Vec<usize>>::new();
+  for (triangle_index, triangle) in triangles.iter().enumerate() {
+    for edge in 0..3 {
+      edge_triangles
+        .entry(key(triangle[edge], triangle[(edge + 1) % 3]))
+        .or_default()
+        .push(triangle_index);
+    }
+  }
+
+  triangles
+    .iter()
+    .enumerate()
+    .map(|(triangle_index, triangle)| {
+      std::array::from_fn(|edge| {
+        let edge_key = key(triangle[edge], triangle[(edge + 1) % 3]);
+        let neighbor = edge_triangles[&edge_key]
+          .iter()
+          .copied()
+          .find(|&other| other != triangle_index);
+        match (neighbor, walls.get(&edge_key)) {
+          (Some(neighbor), _) => NavEdge::Portal(neighbor),
+          (None, Some(&source)) => NavEdge::Wall(source),
+          // Only happens if the triangulation skipped degenerate vertices, so
+          // the edge spans several walls. Report the wall leaving its start.
+          (None, None) => NavEdge::Wall(
+            walls[&key(triangle[edge], next_vertex[triangle[edge]])],
+          ),
+        }
+      })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::rectangle;
+  use crate::{build_navmesh, NavEdge, Polygon, WallSource};
+
+  #[test]
+  fn carves_obstacles_and_connects_triangles() {
+    // Two overlapping rooms with a pillar in the middle of the first one.
+    let walkable = [
+      Polygon { contours: vec![rectangle(Vec2::ZERO, Vec2::new(10.0, 10.0))] },
+      Polygon {
+        contours: vec![rectangle(Vec2::new(8.0, 2.0), Vec2::new(16.0, 8.0))],
+      },
+    ];
+    let obstacles = [Polygon {
+      contours: vec![rectangle(Vec2::new(4.0, 4.0), Vec2::new(6.0, 6.0))],
+    }];
+    let navmesh = build_navmesh(&walkable, &obstacles).unwrap();
+
+    let points = &navmesh.vertices;
+    let area = navmesh
+      .triangles
+      .iter()
+      .map(|&[a, b, c]| (points[b] - points[a]).perp_dot(points[c] - points[a]))
+      .sum::<f32>()
+      * 0.5;
+    assert_eq!(area, 100.0 + 36.0 - 4.0);
+
+    let mut walls = Vec::new();
+    for (triangle_index, edges) in navmesh.triangle_edges.iter().enumerate() {
+      for edge in edges {
+        match *edge {
+          NavEdge::Portal(neighbor) => {
+            // Portals are symmetric.
+            assert!(navmesh.triangle_edges[neighbor]
+              .contains(&NavEdge::Portal(triangle_index)));
+          }
+          NavEdge::Wall(source) => walls.push(source),
+        }
+      }
+    }
+    // The outline of the union has 8 edges, and the pillar has 4.
+    assert_eq!(walls.len(), 12);
+    let mut pillar_walls = walls
+      .iter()
+      .filter_map(|wall| match *wall {
+        WallSource::Obstacle { obstacle, contour, edge } => {
+          Some((obstacle, contour, edge))
+        }
+        WallSource::Walkable { .. } => None,
+      })
+      .collect::<Vec<_>>();
+    pillar_walls.sort();
+    assert_eq!(pillar_walls, [(0, 0, 0), (0, 0, 1), (0, 0, 2), (0, 0, 3)]);
+    assert!(walls.contains(&WallSource::Walkable {
+      polygon: 1,
+      contour: 0,
+      edge: 1
+    }));
+  }
+
+  #[test]
+  fn fully_blocked_area_is_empty() {
+    let walkable =
+      [Polygon { contours: vec![rectangle(Vec2::ZERO, Vec2::ONE)] }];
+    let obstacles = [Polygon {
+      contours: vec![rectangle(Vec2::splat(-1.0), Vec2::splat(2.0))],
+    }];
+    let navmesh = build_navmesh(&walkable, &obstacles).unwrap();
+    assert!(navmesh.polygon.contours.is_empty());
+    assert!(navmesh.triangles.is_empty());
+  }
+}
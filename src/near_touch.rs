@@ -0,0 +1,194 @@
+use glam::Vec2;
+
+use crate::{
+  util::{closest_point_on_segment, edge_intersection, EdgeIntersectionResult},
+  BooleanResult,
+};
+
+// A place where the boundary of a result comes close to itself: either two
+// contours approach each other, or a contour approaches itself (e.g. a narrow
+// channel). These are often produced by boolean operations on nearly touching
+// geometry.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct NearTouch {
+  // The indices of the two contours. These are the same for a contour that
+  // approaches itself.
+  pub contours: [usize; 2],
+  // The index of the edge in each contour that comes closest.
+  pub edges: [usize; 2],
+  // The closest points on each edge.
+  pub points: [Vec2; 2],
+  // The distance between `points`.
+  pub distance: f32,
+}
+
+// Finds the pairs of contours of `result` (including a contour with itself)
+// whose boundaries come within `eps` of each other, reporting the closest
+// approach of each pair. Adjacent edges of a contour always touch, so they are
+// ignored, as are edges joined by a path along the contour shorter than `eps`
+// (e.g. the corners of tiny edges). This compares every pair of edges, so it is
+// intended as an analysis of results rather than something to run on every
+// operation.
+pub fn find_near_touches(result: &BooleanResult, eps: f32) -> Vec<NearTouch> {
+  let contours = &result.polygon.contours;
+  // The length of each contour up to the start of each edge (and the total
+  // length as a final entry).
+  let cumulative_lengths = contours
+    .iter()
+    .map(|contour| {
+      let mut lengths = Vec::with_capacity(contour.len() + 1);
+      let mut length = 0.0;
+      lengths.push(length);
+      for (index, &point) in contour.iter().enumerate() {
+        length += point.distance(contour[(index + 1) % contour.len()]);
+        lengths.push(length);
+      }
+      lengths
+    })
+    .collect::<Vec<_>>();
+
+  let mut near_touches = Vec::new();
+  for contour_a in 0..contours.len() {
+    for contour_b in contour_a..contours.len() {
+      let mut closest: Option<NearTouch> = None;
+      for edge_a in 0..contours[contour_a].len() {
+        let start_b = if contour_a == contour_b { edge_a + 1 } else { 0 };
+        for edge_b in start_b..contours[contour_b].len() {
+          if contour_a == contour_b
+            && path_length_between_edges(
+              &cumulative_lengths[contour_a],
+              edge_a,
+              edge_b,
+            ) < eps
+          {
+            continue;
+          }
+          let segment = |contour: &[Vec2], edge: usize| {
+            (contour[edge], contour[(edge + 1) % contour.len()])
+          };
+          let (point_a, point_b) = closest_points_on_segments(
+            segment(&contours[contour_a], edge_a),
+            segment(&contours[contour_b], edge_b),
+          );
+          let distance = point_a.distance(point_b);
+          if eps < distance
+            || closest.is_some_and(|closest| closest.distance <= distance)
+          {
+            continue;
+          }
+          closest = Some(NearTouch {
+            contours: [contour_a, contour_b],
+            edges: [edge_a, edge_b],
+            points: [point_a, point_b],
+            distance,
+          });
+        }
+      }
+      near_touches.extend(closest);
+    }
+  }
+  near_touches
+}
+
+// Computes the length of the shortest path along a contour between edges
+// `edge_a` and `edge_b` (where `edge_a < edge_b`), not including the edges
+// themselves. `cumulative_lengths` is the length of the contour up to the start
+// of each edge, followed by the total length. Adjacent edges have a path of
+// length 0.
+fn path_length_between_edges(
+  cumulative_lengths: &[f32],
+  edge_a: usize,
+  edge_b: usize,
+) -> f32 {
+  let total_length = cumulative_lengths[cumulative_lengths.len() - 1];
+  // The path going forward from the end of `edge_a` to the start of `edge_b`.
+  let forward = cumulative_lengths[edge_b] - cumulative_lengths[edge_a + 1];
+  // The path going forward from the end of `edge_b` (wrapping around) to the
+  // start of `edge_a`.
+  let backward =
+    total_length - cumulative_lengths[edge_b + 1] + cumulative_lengths[edge_a];
+  forward.min(backward)
+}
+
+// Computes the closest points on the segments `a` and `b`.
+fn closest_points_on_segments(
+  a: (Vec2, Vec2),
+  b: (Vec2, Vec2),
+) -> (Vec2, Vec2) {
+  match edge_intersection(a, b) {
+    EdgeIntersectionResult::PointIntersection(point) => return (point, point),
+    EdgeIntersectionResult::LineIntersection(point, _) => {
+      return (point, point)
+    }
+    EdgeIntersectionResult::NoIntersection => {}
+  }
+  // Segments that do not cross are closest at an end point of one of them.
+  [
+    (a.0, closest_point_on_segment(b, a.0)),
+    (a.1, closest_point_on_segment(b, a.1)),
+    (closest_point_on_segment(a, b.0), b.0),
+    (closest_point_on_segment(a, b.1), b.1),
+  ]
+  .into_iter()
+  .min_by(|(a_0, b_0), (a_1, b_1)| {
+    a_0.distance_squared(*b_0).total_cmp(&a_1.distance_squared(*b_1))
+  })
+  .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::rectangle;
+  use crate::{union, BooleanResult, Polygon};
+
+  use super::{find_near_touches, NearTouch};
+
+  #[test]
+  fn finds_nearly_touching_contours() {
+    let result = union(
+      &Polygon { contours: vec![rectangle(Vec2::ZERO, Vec2::new(2.0, 2.0))] },
+      &Polygon {
+        contours: vec![rectangle(Vec2::new(2.05, 0.0), Vec2::new(4.0, 1.0))],
+      },
+    );
+
+    assert_eq!(find_near_touches(&result, 0.01), []);
+    let near_touches = find_near_touches(&result, 0.1);
+    assert_eq!(near_touches.len(), 1);
+    let NearTouch { contours, points, distance, .. } = near_touches[0];
+    assert_eq!(contours, [0, 1]);
+    assert_eq!(points[0].x, 2.0);
+    assert_eq!(points[1].x, 2.05);
+    assert!((distance - 0.05).abs() < 1e-6);
+  }
+
+  #[test]
+  fn finds_narrow_channel_in_contour() {
+    // A U shape whose arms are 0.05 apart.
+    let result = BooleanResult {
+      polygon: Polygon {
+        contours: vec![vec![
+          Vec2::new(0.0, 0.0),
+          Vec2::new(2.05, 0.0),
+          Vec2::new(2.05, 2.0),
+          Vec2::new(1.05, 2.0),
+          Vec2::new(1.05, 1.0),
+          Vec2::new(1.0, 1.0),
+          Vec2::new(1.0, 2.0),
+          Vec2::new(0.0, 2.0),
+        ]],
+      },
+      ..Default::default()
+    };
+
+    let near_touches = find_near_touches(&result, 0.1);
+    assert_eq!(near_touches.len(), 1);
+    assert_eq!(near_touches[0].contours, [0, 0]);
+    assert!((near_touches[0].distance - 0.05).abs() < 1e-6);
+    // The bottom of the channel is a short edge, but its corners are not
+    // reported.
+    assert_eq!(find_near_touches(&result, 0.04), []);
+  }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use crate::Polygon;
+
+// Maps polygons into a well-conditioned range around the origin (and back).
+// Large coordinates leave few bits of the f32 mantissa for the differences
+// between points, so computing intersections (and ordering events) near the
+// origin is more accurate.
+pub(crate) struct CoordinateNormalization {
+  // The point that is mapped to the origin.
+  center: Vec2,
+  // The scale applied after translating. This is always a power of two, so
+  // scaling (and unscaling) is exact.
+  scale: f32,
+  // The original points keyed by the bits of their normalized point. Mapping
+  // back to the original coordinates is not exact, so points that came from
+  // the inputs are restored from here instead.
+  original_points: HashMap<(u32, u32), Vec2>,
+  // Whether distinct input points were mapped to the same normalized point.
+  merges_points: bool,
+}
+
+impl CoordinateNormalization {
+  // Creates a normalization that maps the combined bounds of `subject_bounds`
+  // and `clip_bounds` into the range [-1, 1].
+  pub(crate) fn new(
+    subject_bounds: (Vec2, Vec2),
+    clip_bounds: (Vec2, Vec2),
+  ) -> Self {
+    let min = subject_bounds.0.min(clip_bounds.0);
+    let max = subject_bounds.1.max(clip_bounds.1);
+    let half_extent = (max - min).max_element() * 0.5;
+    let scale =
+      if half_extent > 0.0 { (-half_extent.log2().ceil()).exp2() } else { 1.0 };
+    Self {
+      center: (min + max) * 0.5,
+      scale,
+      original_points: HashMap::new(),
+      merges_points: false,
+    }
+  }
+
+  // Maps `point` into the normalized range.
+  fn normalize_point(&self, point: Vec2) -> Vec2 {
+    (point - self.center) * self.scale
+  }
+
+  // Maps `bounds` into the normalized range.
+  pub(crate) fn normalize_bounds(&self, bounds: (Vec2, Vec2)) -> (Vec2, Vec2) {
+    (self.normalize_point(bounds.0), self.normalize_point(bounds.1))
+  }
+
+  // Maps every point of `polygon` into the normalized range. The original
+  // points are remembered so they can be restored exactly.
+  pub(crate) fn normalize_polygon(&mut self, polygon: &Polygon) -> Polygon {
+    Polygon {
+      contours: polygon
+        .contours
+        .iter()
+        .map(|contour| {
+          contour
+            .iter()
+            .map(|&point| {
+              let normalized_point = self.normalize_point(point);
+              let original_point = *self
+                .original_points
+                .entry(point_key(normalized_point))
+                .or_insert(point);
+              self.merges_points |= original_point != point;
+              normalized_point
+            })
+            .collect()
+        })
+        .collect(),
+    }
+  }
+
+  // Whether the polygons normalized so far had distinct points that were mapped
+  // to the same normalized point (e.g. small details far from the center). The
+  // normalized polygons are then not equivalent to the inputs, and their points
+  // cannot all be restored, so the normalization should not be used.
+  pub(crate) fn merges_points(&self) -> bool {
+    self.merges_points
+  }
+
+  // Maps every point of `polygon` back from the normalized range. Points that
+  // match a normalized input point are restored to that input point.
+  pub(crate) fn denormalize_polygon(&self, polygon: &mut Polygon) {
+    for point in polygon.contours.iter_mut().flatten() {
+      *point = self.denormalize_point(*point);
+    }
+  }
+
+  // Maps the end points of every segment in `segments` back from the
+  // normalized range, just like `denormalize_polygon`.
+  pub(crate) fn denormalize_segments(&self, segments: &mut [(Vec2, Vec2)]) {
+    for (start, end) in segments.iter_mut() {
+      *start = self.denormalize_point(*start);
+      *end = self.denormalize_point(*end);
+    }
+  }
+
+  // Maps every point in `points` back from the normalized range, just like
+  // `denormalize_polygon`.
+  pub(crate) fn denormalize_points(&self, points: &mut [Vec2]) {
+    for point in points.iter_mut() {
+      *point = self.denormalize_point(*point);
+    }
+  }
+
+  // Maps `point` back from the normalized range, restoring it to the matching
+  // input point if there is one.
+  fn denormalize_point(&self, point: Vec2) -> Vec2 {
+    match self.original_points.get(&point_key(point)) {
+      Some(&original_point) => original_point,
+      None => point / self.scale + self.center,
+    }
+  }
+}
+
+// Creates a key for `point` that can be used in a HashMap.
+fn point_key(point: Vec2) -> (u32, u32) {
+  (point.x.to_bits(), point.y.to_bits())
+}
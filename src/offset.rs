@@ -0,0 +1,363 @@
+use std::f32::consts::{PI, TAU};
+
+use glam::Vec2;
+
+use crate::{
+  try_union_with_options, unwrap_boolean, BooleanOptions, BooleanResult,
+  ClipError, FillRule, Polygon,
+};
+
+// The number of segments that a full circle is split into for
+// `JoinStyle::Round`.
+const ROUND_SEGMENTS: usize = 32;
+
+// How the offset edges on the outside of a corner are joined, for `offset`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum JoinStyle {
+  // The offset edges are extended until they meet in a sharp corner, unless
+  // the corner would be farther from the vertex than `miter_limit` times the
+  // offset distance, in which case the corner is beveled instead.
+  Miter,
+  // The offset edges are joined by an arc around the vertex (approximated
+  // with segments).
+  Round,
+  // The ends of the offset edges are joined by a straight edge, cutting the
+  // corner off.
+  Bevel,
+}
+
+// Offsets the boundary of `polygon` by `delta`, growing it by `delta` if
+// `delta` is positive (inflating it) or shrinking it by `-delta` if `delta` is
+// negative (deflating it). Holes shrink as the polygon grows and vice versa.
+// Corners on the outside of the offset are joined with `join_style`, where
+// `miter_limit` limits how far (as a multiple of the offset distance) miter
+// joins may reach before they are beveled. Corners on the inside of the offset
+// are always sharp.
+//
+// The polygon is first resolved into shells and holes (see
+// `Polygon::normalize`), and each contour is then offset on its own. Wherever
+// the offset contours overlap themselves or each other (e.g. where narrow gaps
+// close up, or holes disappear), they are resolved with `FillRule::Positive`
+// in a boolean operation, so the result is always valid. The source edges of
+// the result refer to the offset contours rather than `polygon`. This panics
+// if the operation fails (see `try_offset`).
+pub fn offset(
+  polygon: &Polygon,
+  delta: f32,
+  join_style: JoinStyle,
+  miter_limit: f32,
+) -> BooleanResult {
+  unwrap_boolean(try_offset_with_options(
+    polygon,
+    delta,
+    join_style,
+    miter_limit,
+    &BooleanOptions::default(),
+  ))
+}
+
+// Same as `offset`, but with `options`. `polygon` is resolved with
+// `BooleanOptions::fill_rule`.
+pub fn offset_with_options(
+  polygon: &Polygon,
+  delta: f32,
+  join_style: JoinStyle,
+  miter_limit: f32,
+  options: &BooleanOptions,
+) -> BooleanResult {
+  unwrap_boolean(try_offset_with_options(
+    polygon,
+    delta,
+    join_style,
+    miter_limit,
+    options,
+  ))
+}
+
+// Same as `offset`, but returns an error instead of panicking. A non-finite
+// `delta` creates non-finite offset contours, which are reported as
+// `ClipError::InvalidInput` in the subject.
+pub fn try_offset(
+  polygon: &Polygon,
+  delta: f32,
+  join_style: JoinStyle,
+  miter_limit: f32,
+) -> Result<BooleanResult, ClipError> {
+  try_offset_with_options(
+    polygon,
+    delta,
+    join_style,
+    miter_limit,
+    &BooleanOptions::default(),
+  )
+}
+
+// Same as `offset_with_options`, but returns an error instead of panicking.
+pub fn try_offset_with_options(
+  polygon: &Polygon,
+  delta: f32,
+  join_style: JoinStyle,
+  miter_limit: f32,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  let resolved = polygon.try_normalize(options.fill_rule)?;
+  let offset_polygon = |miter_inner_corners| Polygon {
+    contours: resolved
+      .contours
+      .iter()
+      .filter(|contour| contour.len() >= 2)
+      .map(|contour| {
+        offset_contour(
+          contour,
+          delta,
+          join_style,
+          miter_limit,
+          miter_inner_corners,
+        )
+      })
+      .collect(),
+  };
+  // The loops through the vertices of nearly straight inner corners have
+  // nearly coincident edges, which the regular union does not always handle,
+  // so this falls back to mitering the inner corners (wherever that gives the
+  // same shape) in that case.
+  let positive_options = options.clone().with_fill_rule(FillRule::Positive);
+  try_union_with_options(
+    &offset_polygon(/* miter_inner_corners= */ false),
+    &Polygon::default(),
+    &positive_options,
+  )
+  .or_else(|_| {
+    try_union_with_options(
+      &offset_polygon(/* miter_inner_corners= */ true),
+      &Polygon::default(),
+      &positive_options,
+    )
+  })
+}
+
+// Offsets each edge of `contour` by `delta` to its right, so shells (which are
+// counter-clockwise) grow and holes (which are clockwise) shrink if `delta` is
+// positive. Where consecutive offset edges leave a gap, they are joined with
+// `join_style`. Where they overlap instead, they are connected through the
+// vertex, forming a small loop that winds the opposite way to the contour, so
+// `FillRule::Positive` removes it. If `miter_inner_corners` is set, overlapping
+// offset edges are instead cut off where they cross, as long as that point is
+// on both of them.
+fn offset_contour(
+  contour: &[Vec2],
+  delta: f32,
+  join_style: JoinStyle,
+  miter_limit: f32,
+  miter_inner_corners: bool,
+) -> Vec<Vec2> {
+  let edge_offset = |index: usize| {
+    let next = contour[(index + 1) % contour.len()];
+    let direction = (next - contour[index]).normalize_or_zero();
+    Vec2::new(direction.y, -direction.x) * delta
+  };
+  let mut offset_contour = Vec::with_capacity(contour.len() * 2);
+  for (index, &vertex) in contour.iter().enumerate() {
+    let incoming = edge_offset((index + contour.len() - 1) % contour.len());
+    let outgoing = edge_offset(index);
+    // The offsets point right, so the edges turn away from them (leaving a
+    // gap) when the edges turn left for a positive `delta`, and vice versa.
+    let turn = incoming.perp_dot(outgoing);
+    let reverses = turn == 0.0 && incoming.dot(outgoing) < 0.0;
+    if turn == 0.0 && !reverses {
+      offset_contour.push(vertex + outgoing);
+    } else if reverses || (turn > 0.0) == (delta > 0.0) {
+      join(
+        vertex,
+        incoming,
+        outgoing,
+        delta,
+        join_style,
+        miter_limit,
+        &mut offset_contour,
+      );
+    } else {
+      // The offset edges cross at the miter point (see `join`). Cutting them
+      // off there is only the same as the loop if what is left of each edge
+      // is not also cut off by the corner at its other end.
+      let previous_index = (index + contour.len() - 1) % contour.len();
+      let next_index = (index + 1) % contour.len();
+      if miter_inner_corners
+        && corner_cutback(contour, previous_index, delta)
+          + corner_cutback(contour, index, delta)
+          <= vertex.distance(contour[previous_index])
+        && corner_cutback(contour, index, delta)
+          + corner_cutback(contour, next_index, delta)
+          <= vertex.distance(contour[next_index])
+      {
+        offset_contour.push(
+          vertex
+            + (incoming + outgoing)
+              / (1.0 + incoming.dot(outgoing) / (delta * delta)),
+        );
+      } else {
+        offset_contour.extend([vertex + incoming, vertex, vertex + outgoing]);
+      }
+    }
+  }
+  offset_contour
+}
+
+// The distance along the edges next to the vertex at `index` of `contour` from
+// the vertex to where their offsets by `delta` cross. Corners on the outside of
+// the offset (or straight ones) are joined at the ends of the offset edges, so
+// they do not cut the edges back.
+fn corner_cutback(contour: &[Vec2], index: usize, delta: f32) -> f32 {
+  let vertex = contour[index];
+  let incoming = (vertex
+    - contour[(index + contour.len() - 1) % contour.len()])
+  .normalize_or_zero();
+  let outgoing =
+    (contour[(index + 1) % contour.len()] - vertex).normalize_or_zero();
+  let turn = incoming.perp_dot(outgoing);
+  if turn == 0.0 || (turn > 0.0) == (delta > 0.0) {
+    return 0.0;
+  }
+  // This is `tan(angle / 2)` times the distance, where `angle` is the angle
+  // the contour turns by at the vertex.
+  delta.abs() * turn.abs() / (1.0 + incoming.dot(outgoing))
+}
+
+// Adds the points joining the offsets `from` and `to` around `vertex` (each of
+// length `delta.abs()`) to `contour`.
+fn join(
+  vertex: Vec2,
+  from: Vec2,
+  to: Vec2,
+  delta: f32,
+  join_style: JoinStyle,
+  miter_limit: f32,
+  contour: &mut Vec<Vec2>,
+) {
+  if join_style == JoinStyle::Miter {
+    // The miter reaches `1 / cos(angle / 2)` times the distance from the
+    // vertex, where `angle` is the angle between the offsets. It replaces the
+    // ends of both offset edges, which would otherwise only add (nearly)
+    // collinear vertices. Reversing edges would need an infinitely long miter,
+    // which is NaN here, so they are always beveled.
+    let miter = (from + to) / (1.0 + from.dot(to) / (delta * delta));
+    if miter.length() <= miter_limit * delta.abs() {
+      contour.push(vertex + miter);
+      return;
+    }
+  }
+  contour.push(vertex + from);
+  if join_style == JoinStyle::Round {
+    // Reversing edges go around the vertex on the side the offsets bulge
+    // towards.
+    let mut angle = from.perp_dot(to).atan2(from.dot(to));
+    if from.perp_dot(to) == 0.0 {
+      angle = PI.copysign(delta);
+    }
+    let segments =
+      ((angle.abs() / (TAU / ROUND_SEGMENTS as f32)).ceil() as usize).max(1);
+    for segment in 1..segments {
+      let rotation = Vec2::from_angle(angle * segment as f32 / segments as f32);
+      contour.push(vertex + rotation.rotate(from));
+    }
+  }
+  contour.push(vertex + to);
+}
+
+#[cfg(test)]
+mod tests {
+  use std::f32::consts::PI;
+
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square;
+  use crate::{contains, offset, try_offset, JoinStyle, Polygon};
+
+  #[test]
+  fn inflates_with_each_join_style() {
+    let polygon = Polygon { contours: vec![square(Vec2::ZERO, 2.0)] };
+
+    let miter = offset(&polygon, 1.0, JoinStyle::Miter, 2.0);
+    assert_eq!(miter.polygon.contours.len(), 1);
+    assert_eq!(miter.area(), 16.0);
+    // Square corners need a miter reaching sqrt(2) times the distance.
+    let limited = offset(&polygon, 1.0, JoinStyle::Miter, 1.4);
+    assert_eq!(limited.area(), 16.0 - 4.0 * 0.5);
+
+    let bevel = offset(&polygon, 1.0, JoinStyle::Bevel, 2.0);
+    assert_eq!(bevel.area(), 16.0 - 4.0 * 0.5);
+
+    let round = offset(&polygon, 1.0, JoinStyle::Round, 2.0);
+    let expected = 4.0 + 4.0 * 2.0 + PI;
+    assert!((round.area() - expected).abs() < 0.05, "{}", round.area());
+  }
+
+  #[test]
+  fn deflates_shells_and_inflates_holes() {
+    let mut hole = square(Vec2::splat(2.0), 2.0);
+    hole.reverse();
+    let polygon = Polygon { contours: vec![square(Vec2::ZERO, 6.0), hole] };
+
+    let miter = offset(&polygon, -0.5, JoinStyle::Miter, 2.0);
+    assert_eq!(miter.polygon.contours.len(), 2);
+    assert_eq!(miter.area(), 25.0 - 9.0);
+
+    // Only the corners of the hole are rounded, since the shell's corners are
+    // on the inside of the offset.
+    let round = offset(&polygon, -0.5, JoinStyle::Round, 2.0);
+    let expected = 25.0 - (4.0 + 4.0 * 2.0 * 0.5 + PI * 0.25);
+    assert!((round.area() - expected).abs() < 0.01, "{}", round.area());
+
+    // Shrinking by more than half the width leaves nothing.
+    let gone = offset(&polygon, -1.5, JoinStyle::Round, 2.0);
+    assert!(gone.polygon.contours.is_empty());
+  }
+
+  #[test]
+  fn resolves_overlapping_offsets() {
+    // A U shape whose arms merge once the gap between them closes.
+    let u_shape = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(3.0, 0.0),
+        Vec2::new(3.0, 3.0),
+        Vec2::new(2.0, 3.0),
+        Vec2::new(2.0, 1.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(1.0, 3.0),
+        Vec2::new(0.0, 3.0),
+      ]],
+    };
+    let result = offset(&u_shape, 1.0, JoinStyle::Miter, 2.0);
+    assert_eq!(result.polygon.contours.len(), 1);
+    assert_eq!(result.area(), 5.0 * 5.0);
+  }
+
+  #[test]
+  fn inflates_nearly_straight_inner_corners() {
+    // The corner at (4.7667317, 5.8050537) is nearly straight, so the loop
+    // through it has nearly coincident edges that the union cannot resolve.
+    let polygon = Polygon {
+      contours: vec![vec![
+        Vec2::new(4.7667317, 5.8050537),
+        Vec2::new(5.3813915, 6.703082),
+        Vec2::new(6.6767197, 7.444399),
+        Vec2::new(8.116629, 6.321476),
+        Vec2::new(7.287633, 4.230717),
+        Vec2::new(5.9417114, 3.7396185),
+        Vec2::new(5.1790786, 4.6057453),
+        Vec2::new(4.0914783, 4.8195286),
+      ]],
+    };
+    for join_style in [JoinStyle::Miter, JoinStyle::Round, JoinStyle::Bevel] {
+      let result = try_offset(&polygon, 1.0, join_style, 2.0).unwrap();
+      assert_eq!(result.polygon.contours.len(), 1, "{join_style:?}");
+      assert!(contains(&result.polygon, &polygon), "{join_style:?}");
+      // Nothing reaches farther than the miter limit.
+      let (min, max) = polygon.compute_bounds().unwrap();
+      let (result_min, result_max) = result.polygon.compute_bounds().unwrap();
+      assert!(result_min.cmpge(min - 2.0).all(), "{join_style:?}");
+      assert!(result_max.cmple(max + 2.0).all(), "{join_style:?}");
+    }
+  }
+}
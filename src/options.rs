@@ -0,0 +1,220 @@
+use crate::{CoincidentEdgeSource, ContourOrder, FillRule, OutputOrientation};
+
+// Options to control how boolean operations are performed.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct BooleanOptions {
+  // How the contours of each input are interpreted where they overlap each
+  // other or themselves. With `FillRule::EvenOdd` (the default), inputs are
+  // swept as they are. Other rules first resolve each input into
+  // non-overlapping shells and holes (see `Polygon::normalize`), which can take
+  // O(n^2) time for n edges. Source edges still refer to the edges of the
+  // original polygons.
+  pub fill_rule: FillRule,
+  // The fill rule of the clip, if it differs from the subject's. None (the
+  // default) resolves the clip with `fill_rule` as well.
+  pub clip_fill_rule: Option<FillRule>,
+  // Whether to translate and scale both polygons into a well-conditioned range
+  // around the origin before computing the operation (mapping the result back
+  // afterwards). This is useful for polygons with large coordinates (e.g. tens
+  // of thousands), where most of the f32 mantissa is spent on the magnitude of
+  // the coordinates rather than the differences between them. Vertices of the
+  // inputs are preserved exactly, but new intersection vertices may differ
+  // slightly from the non-normalized computation. Coordinates are not
+  // normalized if that would map distinct vertices of the inputs to the same
+  // point (e.g. tiny details far from the rest of the polygons).
+  pub normalize_coordinates: bool,
+  // Whether to normalize coordinates (as in `normalize_coordinates`) only when
+  // it meaningfully improves precision, as determined by
+  // `Precision::should_normalize`.
+  pub auto_precision: bool,
+  // Whether to compute `BooleanResult::contour_unmodified_edges`.
+  pub track_unmodified_edges: bool,
+  // Whether to compute `BooleanResult::contour_edge_parameters`.
+  pub track_edge_parameters: bool,
+  // Whether to compute `BooleanResult::contour_vertex_errors`.
+  pub track_vertex_errors: bool,
+  // Whether to compute `BooleanResult::contour_parents`.
+  pub track_hierarchy: bool,
+  // Whether to compute `BooleanResult::relation`. This is derived from the
+  // flags computed while sweeping, so it adds very little cost.
+  pub classify_relation: bool,
+  // Whether to compute `BooleanResult::equals_subject` and
+  // `BooleanResult::equals_clip`. This matches the source edges of the result
+  // against every edge of both inputs after the operation.
+  pub track_input_equality: bool,
+  // Whether to compute `BooleanResult::overlap_segments`. This is useful when
+  // touching polygons matter (e.g. adjacency), since the intersection of
+  // polygons that only share an edge is empty.
+  pub track_overlap_segments: bool,
+  // Whether to compute `BooleanResult::contact_points`.
+  pub track_contact_points: bool,
+  // Which source edge to report where the subject and clip coincide. By
+  // default, the subject edge is reported.
+  pub coincident_edge_source: CoincidentEdgeSource,
+  // Whether to compute `BooleanResult::warnings`.
+  pub collect_warnings: bool,
+  // Whether to compute `BooleanResult::timings`.
+  #[cfg(feature = "timings")]
+  pub collect_timings: bool,
+  // The distance within which boundaries of the subject and clip are treated
+  // as the same boundary. Before computing the operation, clip vertices within
+  // this distance of the subject's boundary are moved onto it, and subject
+  // vertices within this distance of the clip's boundary are inserted into it.
+  // This makes slightly misaligned boundaries (e.g. from noisy data) exactly
+  // coincident, rather than producing slivers. Source edges still refer to the
+  // edges of the original polygons. A tolerance of 0 (the default) disables
+  // this.
+  pub boundary_tolerance: f32,
+  // The distance within which vertices of the subject and clip are merged
+  // (see `Polygon::weld_vertices`) before computing the operation. Unlike
+  // `boundary_tolerance`, this only moves vertices onto other vertices. Source
+  // edges still refer to the edges of the original polygons. A tolerance of 0
+  // (the default) disables this.
+  pub weld_tolerance: f32,
+  // The distance within which consecutive vertices of result contours are
+  // collapsed into one. Stacked splits of (nearly) coincident edges can leave
+  // exact or near duplicate vertices in the result. A vertex within this
+  // distance of the next vertex is removed, so the edge before it extends to
+  // the next vertex. Contours that collapse to fewer than 3 vertices are
+  // removed. Some(0.0) only collapses exact duplicates, and None (the default)
+  // keeps every vertex.
+  pub duplicate_vertex_tolerance: Option<f32>,
+  // Whether to merge consecutive collinear edges of the result into single
+  // edges (e.g. where an edge was split at a vertex of the other polygon).
+  // Each merged edge keeps the source edge of its first edge, and
+  // `BooleanResult::contour_merged_source_edges` holds the source edges of all
+  // the edges merged into it.
+  pub merge_collinear_edges: bool,
+  // The area below which contours are dropped from the result (along with
+  // their source edges and other per-contour data). Subdividing edges can leave
+  // sliver contours whose area is effectively zero (e.g. between nearly
+  // coincident edges). Contours nested in a dropped contour are kept. An area
+  // of 0 (the default) keeps every contour.
+  pub min_contour_area: f32,
+  // The maximum number of events (two per edge, plus two more each time an edge
+  // is split) the operation may use. Operations that need more panic with
+  // `ClipError::CapacityExceeded` rather than exhausting memory. None (the
+  // default) means there is no limit.
+  pub max_events: Option<usize>,
+  // The orientation of the contours in the result. By default, shells are
+  // counter-clockwise and holes are clockwise.
+  pub output_orientation: OutputOrientation,
+  // The order of the contours in the result. By default, contours are left in
+  // the order the sweep discovered them.
+  pub contour_order: ContourOrder,
+}
+
+// How much optional data boolean operations compute, for
+// `BooleanOptions::with_tracking`. Each level includes the data of the levels
+// before it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TrackingLevel {
+  // Only the polygon, its source edges and its bounds.
+  #[default]
+  Minimal,
+  // Also `BooleanResult::contour_parents`, `BooleanResult::relation`,
+  // `BooleanResult::equals_subject` and `BooleanResult::equals_clip`.
+  Topology,
+  // Also the per-edge provenance: `BooleanResult::contour_unmodified_edges`,
+  // `BooleanResult::contour_edge_parameters` and
+  // `BooleanResult::contour_vertex_errors`.
+  Provenance,
+  // Also `BooleanResult::overlap_segments` and
+  // `BooleanResult::contact_points`.
+  Full,
+}
+
+impl BooleanOptions {
+  // Creates the default options. Combined with the `with_*` methods, this
+  // builds options without listing every field.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  // Sets `fill_rule`.
+  pub fn with_fill_rule(mut self, fill_rule: FillRule) -> Self {
+    self.fill_rule = fill_rule;
+    self
+  }
+
+  // Sets `clip_fill_rule`.
+  pub fn with_clip_fill_rule(mut self, clip_fill_rule: FillRule) -> Self {
+    self.clip_fill_rule = Some(clip_fill_rule);
+    self
+  }
+
+  // The fill rules the subject and the clip are resolved with.
+  pub fn fill_rules(&self) -> (FillRule, FillRule) {
+    (self.fill_rule, self.clip_fill_rule.unwrap_or(self.fill_rule))
+  }
+
+  // Treats input boundaries within `epsilon` of each other as the same
+  // boundary (`boundary_tolerance`) and collapses result vertices within
+  // `epsilon` of each other (`duplicate_vertex_tolerance`).
+  pub fn with_epsilon(mut self, epsilon: f32) -> Self {
+    self.boundary_tolerance = epsilon;
+    self.duplicate_vertex_tolerance = Some(epsilon);
+    self
+  }
+
+  // Sets `output_orientation`.
+  pub fn with_output_orientation(
+    mut self,
+    output_orientation: OutputOrientation,
+  ) -> Self {
+    self.output_orientation = output_orientation;
+    self
+  }
+
+  // Sets `contour_order`.
+  pub fn with_contour_order(mut self, contour_order: ContourOrder) -> Self {
+    self.contour_order = contour_order;
+    self
+  }
+
+  // Sets the simplification of the result: whether to merge collinear edges
+  // (`merge_collinear_edges`) and the area below which contours are dropped
+  // (`min_contour_area`).
+  pub fn with_simplification(
+    mut self,
+    merge_collinear_edges: bool,
+    min_contour_area: f32,
+  ) -> Self {
+    self.merge_collinear_edges = merge_collinear_edges;
+    self.min_contour_area = min_contour_area;
+    self
+  }
+
+  // Sets which optional data is computed, replacing the individual `track_*`
+  // flags (as well as `classify_relation`).
+  pub fn with_tracking(mut self, level: TrackingLevel) -> Self {
+    let topology = level != TrackingLevel::Minimal;
+    let provenance =
+      matches!(level, TrackingLevel::Provenance | TrackingLevel::Full);
+    let full = level == TrackingLevel::Full;
+    self.track_hierarchy = topology;
+    self.classify_relation = topology;
+    self.track_input_equality = topology;
+    self.track_unmodified_edges = provenance;
+    self.track_edge_parameters = provenance;
+    self.track_vertex_errors = provenance;
+    self.track_overlap_segments = full;
+    self.track_contact_points = full;
+    self
+  }
+
+  // Sets `coincident_edge_source`.
+  pub fn with_coincident_edge_source(
+    mut self,
+    coincident_edge_source: CoincidentEdgeSource,
+  ) -> Self {
+    self.coincident_edge_source = coincident_edge_source;
+    self
+  }
+
+  // Sets `max_events`.
+  pub fn with_max_events(mut self, max_events: usize) -> Self {
+    self.max_events = Some(max_events);
+    self
+  }
+}
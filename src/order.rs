@@ -0,0 +1,166 @@
+use glam::Vec2;
+
+use crate::{lex_order_points, BooleanResult};
+
+// The order of the contours in the result of a boolean operation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ContourOrder {
+  // Contours are left in the order the sweep discovered them. This is the
+  // cheapest, but the order can change along with the implementation or the
+  // order of the input contours.
+  #[default]
+  Discovery,
+  // Contours that are not nested in any other contour are sorted by their
+  // minimum vertex (by x, then y), and every contour is directly followed by
+  // the contours nested in it (e.g. a shell by its holes), sorted the same
+  // way. This only depends on the geometry of the result, so it is
+  // reproducible across runs and versions (e.g. for diffing or serializing
+  // results).
+  Spatial,
+}
+
+// Reorders the contours of `result` (along with their per-contour data) to
+// follow `order`. `result.contour_parents` must be computed unless `order` is
+// `ContourOrder::Discovery`.
+pub(crate) fn order_contours(result: &mut BooleanResult, order: ContourOrder) {
+  if order == ContourOrder::Discovery {
+    return;
+  }
+
+  let contours = &result.polygon.contours;
+  let mut roots = Vec::new();
+  let mut children = vec![Vec::new(); contours.len()];
+  for (index, parent) in result.contour_parents.iter().enumerate() {
+    match parent {
+      None => roots.push(index),
+      Some(parent) => children[*parent].push(index),
+    }
+  }
+
+  let keys =
+    contours.iter().map(|contour| spatial_key(contour)).collect::<Vec<_>>();
+  let sort = |indices: &mut Vec<usize>| {
+    indices.sort_by(|&a, &b| compare_keys(&keys[a], &keys[b]));
+  };
+  sort(&mut roots);
+  children.iter_mut().for_each(sort);
+
+  // Visit the contours depth-first, so each contour comes right before the
+  // contours nested in it.
+  let mut new_order = Vec::with_capacity(contours.len());
+  let mut stack = roots;
+  stack.reverse();
+  while let Some(index) = stack.pop() {
+    new_order.push(index);
+    stack.extend(children[index].iter().rev());
+  }
+  result.reorder_contours(&new_order);
+}
+
+// The key that contours are sorted by: the minimum vertex, then the maximum of
+// the bounds, then the number of vertices. Empty contours have no key.
+fn spatial_key(contour: &[Vec2]) -> Option<(Vec2, Vec2, usize)> {
+  let min_vertex = contour.iter().copied().min_by(lex_order_points)?;
+  let max = contour.iter().copied().fold(min_vertex, Vec2::max);
+  Some((min_vertex, max, contour.len()))
+}
+
+// Compares the keys of two contours. Empty contours are ordered last.
+fn compare_keys(
+  a: &Option<(Vec2, Vec2, usize)>,
+  b: &Option<(Vec2, Vec2, usize)>,
+) -> std::cmp::Ordering {
+  match (a, b) {
+    (None, None) => std::cmp::Ordering::Equal,
+    (None, Some(_)) => std::cmp::Ordering::Greater,
+    (Some(_), None) => std::cmp::Ordering::Less,
+    (Some((a_min, a_max, a_len)), Some((b_min, b_max, b_len))) => {
+      lex_order_points(a_min, b_min)
+        .then_with(|| lex_order_points(a_max, b_max))
+        .then_with(|| a_len.cmp(b_len))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square;
+  use crate::{
+    union_with_options, xor_with_options, BooleanOptions, ContourOrder, Polygon,
+  };
+
+  #[test]
+  fn sorts_contours_by_minimum_vertex() {
+    let options = BooleanOptions {
+      contour_order: ContourOrder::Spatial,
+      track_hierarchy: true,
+      ..Default::default()
+    };
+    let subject = Polygon {
+      contours: vec![
+        square(Vec2::new(10.0, 0.0), 4.0),
+        square(Vec2::new(0.0, 0.0), 4.0),
+      ],
+    };
+    // Cuts a hole into each square, and adds a square nested in the hole of
+    // the right square.
+    let clip = Polygon {
+      contours: vec![
+        square(Vec2::new(11.0, 1.0), 2.0),
+        square(Vec2::new(1.0, 1.0), 2.0),
+        square(Vec2::new(11.5, 1.5), 1.0),
+      ],
+    };
+
+    let result = xor_with_options(&subject, &clip, &options);
+    let min_vertices = result
+      .polygon
+      .contours
+      .iter()
+      .map(|contour| contour[0].min(contour[1]).min(contour[2]).min(contour[3]))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      min_vertices,
+      [
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(10.0, 0.0),
+        Vec2::new(11.0, 1.0),
+        Vec2::new(11.5, 1.5),
+      ]
+    );
+    assert_eq!(result.contour_parents, [None, Some(0), None, Some(2), Some(3)]);
+    assert_eq!(result.contour_bounds[2].unwrap().0, Vec2::new(10.0, 0.0));
+  }
+
+  #[test]
+  fn spatial_order_does_not_depend_on_input_order() {
+    let options = BooleanOptions {
+      contour_order: ContourOrder::Spatial,
+      ..Default::default()
+    };
+    let contours = vec![
+      square(Vec2::new(0.0, 5.0), 1.0),
+      square(Vec2::new(3.0, 0.0), 1.0),
+      square(Vec2::new(0.0, 0.0), 1.0),
+    ];
+    let clip = Polygon { contours: vec![square(Vec2::new(3.5, 0.5), 1.0)] };
+
+    let forward = union_with_options(
+      &Polygon { contours: contours.clone() },
+      &clip,
+      &options,
+    );
+    let mut reversed_contours = contours;
+    reversed_contours.reverse();
+    let reversed = union_with_options(
+      &Polygon { contours: reversed_contours },
+      &clip,
+      &options,
+    );
+    assert_eq!(forward.polygon, reversed.polygon);
+    assert_eq!(forward.polygon.contours[0][0], Vec2::new(0.0, 0.0));
+  }
+}
@@ -0,0 +1,269 @@
+use glam::Vec2;
+
+use crate::{
+  hierarchy::{compute_contour_parents, contour_depth},
+  util::contour_signed_area,
+  BooleanResult, Polygon,
+};
+
+// The orientation of the contours in the result of a boolean operation.
+// Trivially computed results (e.g. when the bounding boxes of the polygons are
+// disjoint) are copies of the inputs, so they keep the orientation of the
+// inputs regardless of this option (see `Polygon::normalize_winding`).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum OutputOrientation {
+  // Shells are counter-clockwise and holes are clockwise.
+  #[default]
+  CounterClockwiseShells,
+  // Shells are clockwise and holes are counter-clockwise.
+  ClockwiseShells,
+  // Contours are left in the order they were traced by the sweep, so shells
+  // and holes may have any orientation. This skips the work of orienting
+  // contours.
+  AsSwept,
+}
+
+// The direction a single contour winds in, based on the sign of its area.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Winding {
+  // The contour has positive signed area, so its interior is on the left of
+  // its edges (with the y axis pointing up).
+  CounterClockwise,
+  // The contour has negative signed area, so its interior is on the right of
+  // its edges.
+  Clockwise,
+  // The contour has no area (e.g. it has fewer than 3 points, or all its points
+  // are collinear), so it has no orientation.
+  Degenerate,
+}
+
+impl Winding {
+  // Computes the winding of `contour` from its signed area (see
+  // `Polygon::contour_signed_areas`).
+  pub fn of_contour(contour: &[Vec2]) -> Self {
+    let area = contour_signed_area(contour);
+    if area > 0.0 {
+      Winding::CounterClockwise
+    } else if area < 0.0 {
+      Winding::Clockwise
+    } else {
+      Winding::Degenerate
+    }
+  }
+
+  // Returns whether this is `Winding::CounterClockwise`.
+  pub fn is_ccw(self) -> bool {
+    self == Winding::CounterClockwise
+  }
+
+  // Returns whether this is `Winding::Clockwise`.
+  pub fn is_cw(self) -> bool {
+    self == Winding::Clockwise
+  }
+}
+
+// Finds the contours in `contours` that have to be reversed to follow
+// `orientation`. `contour_parents` is the index of the contour that each
+// contour is directly nested in. Contours with no area are left as is.
+fn contours_to_reverse(
+  contours: &[Vec<Vec2>],
+  contour_parents: &[Option<usize>],
+  orientation: OutputOrientation,
+) -> Vec<usize> {
+  let shells_counter_clockwise = match orientation {
+    OutputOrientation::CounterClockwiseShells => true,
+    OutputOrientation::ClockwiseShells => false,
+    OutputOrientation::AsSwept => return Vec::new(),
+  };
+  (0..contours.len())
+    .filter(|&index| {
+      let area = contour_signed_area(&contours[index]);
+      let is_hole = contour_depth(contour_parents, index) % 2 == 1;
+      let should_be_counter_clockwise = shells_counter_clockwise != is_hole;
+      area != 0.0 && (0.0 < area) != should_be_counter_clockwise
+    })
+    .collect()
+}
+
+// Reverses the contours of `result` as needed so they follow `orientation`
+// (see `contours_to_reverse`).
+pub(crate) fn orient_contours(
+  result: &mut BooleanResult,
+  contour_parents: &[Option<usize>],
+  orientation: OutputOrientation,
+) {
+  for index in
+    contours_to_reverse(&result.polygon.contours, contour_parents, orientation)
+  {
+    result.reverse_contour(index);
+  }
+}
+
+impl Polygon {
+  // Computes the winding of the contour at `index` (see `Winding::of_contour`).
+  // This uses the same convention as `BooleanOptions::output_orientation`, so
+  // shells of results are `Winding::CounterClockwise` by default. Panics if
+  // `index` is out of bounds.
+  pub fn contour_orientation(&self, index: usize) -> Winding {
+    Winding::of_contour(&self.contours[index])
+  }
+
+  // Returns whether the contour at `index` is counter-clockwise (see
+  // `contour_orientation`). Contours with no area are neither clockwise nor
+  // counter-clockwise. Panics if `index` is out of bounds.
+  pub fn is_ccw(&self, index: usize) -> bool {
+    self.contour_orientation(index).is_ccw()
+  }
+
+  // Reverses the contours of the polygon as needed so they follow
+  // `orientation`, where shells and holes are determined by how many other
+  // contours they are nested in. Boolean operations use the even-odd rule, so
+  // they do not depend on the winding of the inputs, but many consumers do
+  // (e.g. renderers using the non-zero rule, or `normalize` with
+  // `FillRule::NonZero`). Contours are assumed to not cross each other, and
+  // contours with no area are left as is. `OutputOrientation::AsSwept` leaves
+  // every contour as is. This takes O(n^2) time for n contours.
+  pub fn normalize_winding(&mut self, orientation: OutputOrientation) {
+    if orientation == OutputOrientation::AsSwept {
+      return;
+    }
+    let contour_parents = compute_contour_parents(self);
+    for index in
+      contours_to_reverse(&self.contours, &contour_parents, orientation)
+    {
+      self.contours[index].reverse();
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square;
+  use crate::{
+    difference_with_options, union_with_options, util::contour_signed_area,
+    BooleanOptions, OutputOrientation, Polygon, SourceEdge, Winding,
+  };
+
+  // Computes the signs of the areas of the contours in the difference of a
+  // square with a hole cut out of it.
+  fn area_signs(orientation: OutputOrientation) -> Vec<f32> {
+    let subject = Polygon { contours: vec![square(Vec2::ZERO, 4.0)] };
+    let clip = Polygon { contours: vec![square(Vec2::ONE, 2.0)] };
+    let options =
+      BooleanOptions { output_orientation: orientation, ..Default::default() };
+    difference_with_options(&subject, &clip, &options)
+      .polygon
+      .contours
+      .iter()
+      .map(|contour| contour_signed_area(contour).signum())
+      .collect()
+  }
+
+  #[test]
+  fn orients_shells_and_holes() {
+    assert_eq!(
+      area_signs(OutputOrientation::CounterClockwiseShells),
+      [1.0, -1.0]
+    );
+    assert_eq!(area_signs(OutputOrientation::ClockwiseShells), [-1.0, 1.0]);
+  }
+
+  #[test]
+  fn trivially_computed_results_keep_input_orientation() {
+    let mut clockwise_square = square(Vec2::ZERO, 1.0);
+    clockwise_square.reverse();
+    let subject = Polygon { contours: vec![clockwise_square.clone()] };
+    let clip = Polygon { contours: vec![square(Vec2::new(5.0, 5.0), 1.0)] };
+
+    for orientation in [
+      OutputOrientation::CounterClockwiseShells,
+      OutputOrientation::ClockwiseShells,
+      OutputOrientation::AsSwept,
+    ] {
+      let options = BooleanOptions {
+        output_orientation: orientation,
+        ..Default::default()
+      };
+      let result = union_with_options(&subject, &clip, &options);
+      assert_eq!(
+        result.polygon.contours,
+        [clockwise_square.clone(), square(Vec2::new(5.0, 5.0), 1.0)]
+      );
+      let subject_edge =
+        |edge| SourceEdge { is_from_subject: true, contour: 0, edge };
+      assert_eq!(
+        result.contour_source_edges[0],
+        (0..4).map(subject_edge).collect::<Vec<_>>()
+      );
+    }
+  }
+
+  #[test]
+  fn queries_contour_orientation() {
+    let mut clockwise_square = square(Vec2::ONE, 2.0);
+    clockwise_square.reverse();
+    let polygon = Polygon {
+      contours: vec![
+        square(Vec2::ZERO, 4.0),
+        clockwise_square,
+        vec![Vec2::ZERO, Vec2::ONE, Vec2::splat(2.0)],
+      ],
+    };
+    assert_eq!(polygon.contour_orientation(0), Winding::CounterClockwise);
+    assert_eq!(polygon.contour_orientation(1), Winding::Clockwise);
+    assert_eq!(polygon.contour_orientation(2), Winding::Degenerate);
+    assert!(polygon.is_ccw(0));
+    assert!(!polygon.is_ccw(1));
+    assert!(!polygon.is_ccw(2));
+    assert!(!Winding::Degenerate.is_cw());
+
+    // Results follow the output orientation.
+    let result = difference_with_options(
+      &Polygon { contours: vec![square(Vec2::ZERO, 4.0)] },
+      &Polygon { contours: vec![square(Vec2::ONE, 2.0)] },
+      &BooleanOptions {
+        output_orientation: OutputOrientation::ClockwiseShells,
+        ..Default::default()
+      },
+    );
+    assert_eq!(result.polygon.contour_orientation(0), Winding::Clockwise);
+    assert_eq!(
+      result.polygon.contour_orientation(1),
+      Winding::CounterClockwise
+    );
+  }
+
+  #[test]
+  fn normalizes_winding_of_nested_contours() {
+    // Every contour starts out clockwise: a shell, its hole, and an island in
+    // the hole.
+    let contours = [
+      square(Vec2::ZERO, 6.0),
+      square(Vec2::ONE, 4.0),
+      square(Vec2::new(2.0, 2.0), 2.0),
+    ]
+    .map(|mut contour| {
+      contour.reverse();
+      contour
+    });
+    let signs = |polygon: &Polygon| {
+      polygon
+        .contours
+        .iter()
+        .map(|contour| contour_signed_area(contour).signum())
+        .collect::<Vec<_>>()
+    };
+
+    let mut polygon = Polygon { contours: contours.to_vec() };
+    polygon.normalize_winding(OutputOrientation::CounterClockwiseShells);
+    assert_eq!(signs(&polygon), [1.0, -1.0, 1.0]);
+    polygon.normalize_winding(OutputOrientation::ClockwiseShells);
+    assert_eq!(signs(&polygon), [-1.0, 1.0, -1.0]);
+
+    let mut polygon = Polygon { contours: contours.to_vec() };
+    polygon.normalize_winding(OutputOrientation::AsSwept);
+    assert_eq!(polygon.contours, contours);
+  }
+}
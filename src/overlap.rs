@@ -0,0 +1,141 @@
+use glam::Vec2;
+
+use crate::{EdgeCoincidenceType, Event, EventRelation};
+
+// Collects the segments where the boundaries of the subject and clip coincide
+// with the polygons on opposite sides, from the `processed_events` of a sweep.
+// These are the parts of the intersection with zero area, so regularized
+// operations never include them in the result polygon. Each segment is ordered
+// from left to right.
+pub(crate) fn collect_overlap_segments(
+  processed_events: &[Event],
+  event_relations: &[EventRelation],
+) -> Vec<(Vec2, Vec2)> {
+  processed_events
+    .iter()
+    .filter(|event| event.left)
+    .filter_map(|event| {
+      let relation = &event_relations[event.event_id];
+      // Only the primary edge of a coincident pair is marked with the
+      // transition, so each segment is only collected once.
+      (relation.edge_coincidence_type
+        == EdgeCoincidenceType::DifferentTransition)
+        .then_some((event.point, relation.sibling_point))
+    })
+    .collect()
+}
+
+// Collects the points where the boundaries of the subject and clip meet other
+// than along coincident edges, from the `processed_events` of a sweep. This
+// includes points where the polygons only touch (e.g. at a corner), as well as
+// points where their boundaries cross. The points are in sweep order (sorted by
+// x, then y).
+pub(crate) fn collect_contact_points(
+  processed_events: &[Event],
+  event_relations: &[EventRelation],
+) -> Vec<Vec2> {
+  // Whether the edge of `event` coincides with an edge of the other polygon.
+  // Only left events hold the coincidence, so right events check their sibling.
+  let is_coincident = |event: &Event| {
+    let relation = &event_relations[event.event_id];
+    let left_relation =
+      if event.left { relation } else { &event_relations[relation.sibling_id] };
+    left_relation.edge_coincidence_type != EdgeCoincidenceType::NoCoincidence
+  };
+
+  // Events are processed in order of their points, so all the events at a
+  // point are next to each other.
+  processed_events
+    .chunk_by(|a, b| a.point == b.point)
+    .filter(|events| {
+      events.iter().any(|event| event.is_subject)
+        && events.iter().any(|event| !event.is_subject)
+        && !events.iter().any(is_coincident)
+    })
+    .map(|events| events[0].point)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square_polygon;
+  use crate::{
+    intersection, intersection_with_options, union_with_options,
+    BooleanOptions, Polygon,
+  };
+
+  #[test]
+  fn reports_shared_edges_of_touching_polygons() {
+    let subject = square_polygon(Vec2::new(0.0, 0.0), 4.0);
+    let clip = square_polygon(Vec2::new(4.0, 2.0), 4.0);
+    let options =
+      BooleanOptions { track_overlap_segments: true, ..Default::default() };
+
+    let result = intersection_with_options(&subject, &clip, &options);
+    assert_eq!(result.polygon, Polygon::default());
+    assert_eq!(
+      result.overlap_segments,
+      [(Vec2::new(4.0, 2.0), Vec2::new(4.0, 4.0))]
+    );
+    // The segments do not depend on the operation.
+    assert_eq!(
+      union_with_options(&subject, &clip, &options).overlap_segments,
+      result.overlap_segments
+    );
+    assert_eq!(intersection(&subject, &clip).overlap_segments, []);
+  }
+
+  #[test]
+  fn reports_contact_points_of_touching_polygons() {
+    let subject = square_polygon(Vec2::new(0.0, 0.0), 4.0);
+    let options =
+      BooleanOptions { track_contact_points: true, ..Default::default() };
+
+    // Touching at a corner.
+    let result = intersection_with_options(
+      &subject,
+      &square_polygon(Vec2::new(4.0, 4.0), 4.0),
+      &options,
+    );
+    assert_eq!(result.polygon, Polygon::default());
+    assert_eq!(result.contact_points, [Vec2::new(4.0, 4.0)]);
+    // A vertex touching the interior of an edge.
+    let diamond = Polygon {
+      contours: vec![vec![
+        Vec2::new(4.0, 2.0),
+        Vec2::new(6.0, 0.0),
+        Vec2::new(8.0, 2.0),
+        Vec2::new(6.0, 4.0),
+      ]],
+    };
+    assert_eq!(
+      intersection_with_options(&subject, &diamond, &options).contact_points,
+      [Vec2::new(4.0, 2.0)]
+    );
+    // Polygons sharing an edge only have overlap segments.
+    assert_eq!(
+      intersection_with_options(
+        &subject,
+        &square_polygon(Vec2::new(4.0, 0.0), 4.0),
+        &options
+      )
+      .contact_points,
+      []
+    );
+  }
+
+  #[test]
+  fn shared_boundary_with_area_is_not_an_overlap_segment() {
+    let subject = square_polygon(Vec2::new(0.0, 0.0), 4.0);
+    let clip = square_polygon(Vec2::new(0.0, 0.0), 2.0);
+    let options =
+      BooleanOptions { track_overlap_segments: true, ..Default::default() };
+
+    assert_eq!(
+      intersection_with_options(&subject, &clip, &options).overlap_segments,
+      []
+    );
+  }
+}
@@ -0,0 +1,220 @@
+use glam::Vec2;
+
+use crate::{extend_bounds, BooleanOptions, BooleanResult, ClipError, Polygon};
+
+// The result of a boolean operation that skipped the input contours outside a
+// region of interest (see `partial_boolean`).
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct PartialResult {
+  // The result of the operation on the kept contours. Source edges refer to
+  // the original (unfiltered) polygons. `equals_subject` and `equals_clip` are
+  // false if any contour of that polygon was skipped, and `relation` only
+  // describes the kept contours.
+  pub result: BooleanResult,
+  // The indices of the subject contours that were skipped.
+  pub skipped_subject_contours: Vec<usize>,
+  // The indices of the clip contours that were skipped.
+  pub skipped_clip_contours: Vec<usize>,
+  // Whether each contour in `result.polygon` is the same as in the result of
+  // the full operation. A contour is exact if its bounds do not overlap the
+  // bounds of any skipped contour, since the skipped contours cannot change
+  // the area inside it. The other contours may be missing holes or have extra
+  // parts (e.g. a shell whose hole was skipped).
+  pub contour_is_exact: Vec<bool>,
+}
+
+// Computes a boolean operation on only the contours of `subject` and `clip`
+// whose bounding boxes (min, max) are accepted by `keep`. This is useful when
+// only part of the result is needed (e.g. the part inside a camera frustum),
+// since skipped contours are never swept. `operation` computes the operation
+// on the kept contours, e.g. `try_intersection_with_options` or a closure
+// calling `try_custom_boolean_with_options`. If `keep` accepts every contour
+// overlapping some region, the result is exact inside that region. Contours
+// without vertices are always kept.
+pub fn partial_boolean(
+  subject: &Polygon,
+  clip: &Polygon,
+  operation: impl FnOnce(
+    &Polygon,
+    &Polygon,
+    &BooleanOptions,
+  ) -> Result<BooleanResult, ClipError>,
+  options: &BooleanOptions,
+  mut keep: impl FnMut((Vec2, Vec2)) -> bool,
+) -> Result<PartialResult, ClipError> {
+  let subject_filter = FilteredPolygon::new(subject, &mut keep);
+  let clip_filter = FilteredPolygon::new(clip, &mut keep);
+  let mut result =
+    operation(&subject_filter.polygon, &clip_filter.polygon, options)?;
+
+  for source_edge in result
+    .contour_source_edges
+    .iter_mut()
+    .flatten()
+    .chain(
+      result.contour_coincident_source_edges.iter_mut().flatten().flatten(),
+    )
+    .chain(result.contour_merged_source_edges.iter_mut().flatten().flatten())
+  {
+    let filter =
+      if source_edge.is_from_subject { &subject_filter } else { &clip_filter };
+    source_edge.contour = filter.kept_contours[source_edge.contour];
+  }
+  if !subject_filter.skipped_contours.is_empty() {
+    result.equals_subject = false;
+  }
+  if !clip_filter.skipped_contours.is_empty() {
+    result.equals_clip = false;
+  }
+
+  let contour_is_exact = result
+    .contour_bounds
+    .iter()
+    .map(|&contour_bounds| {
+      let Some((min, max)) = contour_bounds else {
+        return true;
+      };
+      subject_filter
+        .skipped_bounds
+        .iter()
+        .chain(clip_filter.skipped_bounds.iter())
+        .all(|&(skipped_min, skipped_max)| {
+          max.x < skipped_min.x
+            || max.y < skipped_min.y
+            || skipped_max.x < min.x
+            || skipped_max.y < min.y
+        })
+    })
+    .collect();
+
+  Ok(PartialResult {
+    result,
+    skipped_subject_contours: subject_filter.skipped_contours,
+    skipped_clip_contours: clip_filter.skipped_contours,
+    contour_is_exact,
+  })
+}
+
+// A polygon with only the contours accepted by a region of interest.
+struct FilteredPolygon {
+  // The kept contours.
+  polygon: Polygon,
+  // The index of each contour of `polygon` in the original polygon.
+  kept_contours: Vec<usize>,
+  // The indices of the skipped contours in the original polygon.
+  skipped_contours: Vec<usize>,
+  // The bounds of each skipped contour.
+  skipped_bounds: Vec<(Vec2, Vec2)>,
+}
+
+impl FilteredPolygon {
+  fn new(
+    polygon: &Polygon,
+    keep: &mut impl FnMut((Vec2, Vec2)) -> bool,
+  ) -> Self {
+    let mut filtered = FilteredPolygon {
+      polygon: Polygon::default(),
+      kept_contours: Vec::new(),
+      skipped_contours: Vec::new(),
+      skipped_bounds: Vec::new(),
+    };
+    for (index, contour) in polygon.contours.iter().enumerate() {
+      let bounds = contour.iter().copied().fold(None, extend_bounds);
+      match bounds {
+        Some(bounds) if !keep(bounds) => {
+          filtered.skipped_contours.push(index);
+          filtered.skipped_bounds.push(bounds);
+        }
+        _ => {
+          filtered.polygon.contours.push(contour.clone());
+          filtered.kept_contours.push(index);
+        }
+      }
+    }
+    filtered
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square;
+  use crate::{
+    intersection, partial_boolean, try_intersection_with_options,
+    BooleanOptions, Polygon,
+  };
+
+  // Whether `bounds` overlaps the view from (0, 0) to (4, 4).
+  fn in_view((min, max): (Vec2, Vec2)) -> bool {
+    min.x <= 4.0 && min.y <= 4.0 && max.x >= 0.0 && max.y >= 0.0
+  }
+
+  #[test]
+  fn skips_contours_outside_region() {
+    let subject = Polygon {
+      contours: vec![
+        square(Vec2::new(10.0, 0.0), 2.0),
+        square(Vec2::new(1.0, 1.0), 2.0),
+        square(Vec2::new(20.0, 0.0), 2.0),
+      ],
+    };
+    let clip = Polygon { contours: vec![square(Vec2::new(-1.0, -1.0), 30.0)] };
+
+    let mut queried_contours = 0;
+    let partial_result = partial_boolean(
+      &subject,
+      &clip,
+      try_intersection_with_options,
+      &BooleanOptions::default(),
+      |bounds| {
+        queried_contours += 1;
+        in_view(bounds)
+      },
+    )
+    .unwrap();
+    assert_eq!(queried_contours, 4);
+    assert_eq!(partial_result.skipped_subject_contours, [0, 2]);
+    assert!(partial_result.skipped_clip_contours.is_empty());
+    assert_eq!(partial_result.contour_is_exact, [true]);
+
+    let full_result = intersection(&subject, &clip);
+    assert_eq!(
+      partial_result.result.polygon.contours,
+      [full_result.polygon.contours[0].clone()]
+    );
+    // The source edges refer to the original subject contour.
+    assert_eq!(
+      partial_result.result.contour_source_edges,
+      [full_result.contour_source_edges[0].clone()]
+    );
+    assert!(!partial_result.result.equals_subject);
+  }
+
+  #[test]
+  fn labels_contours_affected_by_skipped_contours() {
+    // The hole is outside the view, but inside the shell.
+    let subject = Polygon {
+      contours: vec![
+        square(Vec2::new(0.0, 0.0), 10.0),
+        square(Vec2::new(6.0, 6.0), 2.0),
+      ],
+    };
+    let clip = Polygon { contours: vec![square(Vec2::new(-1.0, -1.0), 12.0)] };
+
+    let partial_result = partial_boolean(
+      &subject,
+      &clip,
+      try_intersection_with_options,
+      &BooleanOptions::default(),
+      in_view,
+    )
+    .unwrap();
+    assert_eq!(partial_result.skipped_subject_contours, [1]);
+    assert_eq!(
+      partial_result.result.polygon,
+      Polygon { contours: vec![square(Vec2::new(0.0, 0.0), 10.0)] }
+    );
+    assert_eq!(partial_result.contour_is_exact, [false]);
+  }
+}
@@ -0,0 +1,196 @@
+use glam::Vec2;
+
+use crate::BooleanResult;
+
+// A route along the boundary of a polygon.
+#[derive(Clone, PartialEq, Debug)]
+pub struct BoundaryPath {
+  // The index of the contour the route follows.
+  pub contour: usize,
+  // The points of the route, starting and ending at the projected endpoints
+  // and passing through every vertex of the contour in between.
+  pub points: Vec<Vec2>,
+  // The length of the route.
+  pub length: f32,
+}
+
+// A point on the boundary of a contour.
+#[derive(Clone, Copy)]
+struct BoundaryPoint {
+  // The index of the edge the point is on.
+  edge: usize,
+  // The point itself.
+  point: Vec2,
+  // The distance along the contour from its first vertex to the point.
+  arc_length: f32,
+}
+
+// Finds the shorter route along the outline of `result.polygon` between `a`
+// and `b`. Both points are first projected onto the closest point of the
+// outline. Returns None if the polygon is empty or the projected points are on
+// different contours (so no route along the outline connects them).
+pub fn boundary_path(
+  result: &BooleanResult,
+  a: Vec2,
+  b: Vec2,
+) -> Option<BoundaryPath> {
+  let contours = &result.polygon.contours;
+  let (contour_a, a) = project_onto_boundary(contours, a)?;
+  let (contour_b, b) = project_onto_boundary(contours, b)?;
+  if contour_a != contour_b {
+    return None;
+  }
+
+  let contour = &contours[contour_a];
+  let forward = forward_path(contour, a, b);
+  let mut backward = forward_path(contour, b, a);
+  backward.points.reverse();
+  let mut path =
+    if backward.length < forward.length { backward } else { forward };
+  path.contour = contour_a;
+  Some(path)
+}
+
+// Finds the closest point to `point` on the boundary of `contours`, along with
+// the index of its contour.
+fn project_onto_boundary(
+  contours: &[Vec<Vec2>],
+  point: Vec2,
+) -> Option<(usize, BoundaryPoint)> {
+  let mut closest: Option<(f32, usize, BoundaryPoint)> = None;
+  for (contour_index, contour) in contours.iter().enumerate() {
+    let mut arc_length = 0.0;
+    for (edge, &start) in contour.iter().enumerate() {
+      let end = contour[(edge + 1) % contour.len()];
+      let direction = end - start;
+      let edge_length_squared = direction.length_squared();
+      let t = if edge_length_squared == 0.0 {
+        0.0
+      } else {
+        ((point - start).dot(direction) / edge_length_squared).clamp(0.0, 1.0)
+      };
+      let projected = start + direction * t;
+      let distance = projected.distance_squared(point);
+      if closest
+        .is_none_or(|(closest_distance, _, _)| distance < closest_distance)
+      {
+        closest = Some((
+          distance,
+          contour_index,
+          BoundaryPoint {
+            edge,
+            point: projected,
+            arc_length: arc_length + direction.length() * t,
+          },
+        ));
+      }
+      arc_length += direction.length();
+    }
+  }
+  closest.map(|(_, contour_index, point)| (contour_index, point))
+}
+
+// Creates the route from `from` to `to` following the order of the vertices of
+// `contour`.
+fn forward_path(
+  contour: &[Vec2],
+  from: BoundaryPoint,
+  to: BoundaryPoint,
+) -> BoundaryPath {
+  let perimeter = contour
+    .iter()
+    .enumerate()
+    .map(|(index, &start)| start.distance(contour[(index + 1) % contour.len()]))
+    .sum::<f32>();
+  let wraps_around = to.arc_length < from.arc_length;
+  let length = if wraps_around {
+    perimeter - (from.arc_length - to.arc_length)
+  } else {
+    to.arc_length - from.arc_length
+  };
+
+  // The route passes the end vertex of every edge from `from.edge` up to (but
+  // not including) `to.edge`, going around the whole contour if it wraps
+  // around on the same edge.
+  let mut vertex_count = (to.edge + contour.len() - from.edge) % contour.len();
+  if wraps_around && vertex_count == 0 {
+    vertex_count = contour.len();
+  }
+  let mut points = vec![from.point];
+  for offset in 1..=vertex_count {
+    points.push(contour[(from.edge + offset) % contour.len()]);
+  }
+  points.push(to.point);
+  points.dedup();
+
+  BoundaryPath { contour: 0, points, length }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square;
+  use crate::{boundary_path, union, BooleanResult, BoundaryPath, Polygon};
+
+  #[test]
+  fn takes_shorter_route_around_contour() {
+    let result = union(
+      &Polygon { contours: vec![square(Vec2::ZERO, 4.0)] },
+      &Polygon { contours: vec![square(Vec2::new(10.0, 0.0), 4.0)] },
+    );
+
+    // The points are projected onto the bottom and right edges, so the route
+    // goes around the bottom right corner.
+    assert_eq!(
+      boundary_path(&result, Vec2::new(2.0, -1.0), Vec2::new(3.5, 1.5)),
+      Some(BoundaryPath {
+        contour: 0,
+        points: vec![
+          Vec2::new(2.0, 0.0),
+          Vec2::new(4.0, 0.0),
+          Vec2::new(4.0, 1.5),
+        ],
+        length: 3.5,
+      })
+    );
+    // Going backwards through the first vertex is shorter.
+    assert_eq!(
+      boundary_path(&result, Vec2::new(1.0, 0.5), Vec2::new(0.5, 3.0)),
+      Some(BoundaryPath {
+        contour: 0,
+        points: vec![
+          Vec2::new(1.0, 0.0),
+          Vec2::new(0.0, 0.0),
+          Vec2::new(0.0, 3.0),
+        ],
+        length: 4.0,
+      })
+    );
+    // Points on the same edge are connected directly.
+    assert_eq!(
+      boundary_path(&result, Vec2::new(13.0, 4.5), Vec2::new(11.0, 3.5)),
+      Some(BoundaryPath {
+        contour: 1,
+        points: vec![Vec2::new(13.0, 4.0), Vec2::new(11.0, 4.0)],
+        length: 2.0,
+      })
+    );
+  }
+
+  #[test]
+  fn no_route_between_contours() {
+    let result = union(
+      &Polygon { contours: vec![square(Vec2::ZERO, 4.0)] },
+      &Polygon { contours: vec![square(Vec2::new(10.0, 0.0), 4.0)] },
+    );
+    assert_eq!(
+      boundary_path(&result, Vec2::new(2.0, 2.0), Vec2::new(12.0, 2.0)),
+      None
+    );
+    assert_eq!(
+      boundary_path(&BooleanResult::default(), Vec2::ZERO, Vec2::ONE),
+      None
+    );
+  }
+}
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use crate::{hierarchy::compute_contour_parents, BooleanResult};
+
+// A point where more than two edges of a result meet, i.e. a vertex shared by
+// several contours, or visited several times by the same contour (e.g. a hole
+// touching its shell at a single point).
+#[derive(Clone, PartialEq, Debug)]
+pub struct PinchPoint {
+  // The point itself.
+  pub point: Vec2,
+  // The vertices at `point`, as `(contour, vertex)` indices.
+  pub vertices: Vec<(usize, usize)>,
+}
+
+impl BooleanResult {
+  // Finds the points where more than two edges of `polygon` meet, ordered by
+  // x, then y. Boolean operations produce these wherever the result touches
+  // itself at a single point, which many downstream consumers (e.g.
+  // triangulators) reject.
+  pub fn pinch_points(&self) -> Vec<PinchPoint> {
+    let mut vertices_at_point = HashMap::<_, Vec<_>>::new();
+    for (contour_index, contour) in self.polygon.contours.iter().enumerate() {
+      for (vertex, point) in contour.iter().enumerate() {
+        vertices_at_point
+          .entry(point_key(*point))
+          .or_default()
+          .push((contour_index, vertex));
+      }
+    }
+    let mut pinch_points = vertices_at_point
+      .into_values()
+      .filter(|vertices| vertices.len() > 1)
+      .map(|vertices| {
+        let (contour, vertex) = vertices[0];
+        PinchPoint { point: self.polygon.contours[contour][vertex], vertices }
+      })
+      .collect::<Vec<_>>();
+    pinch_points.sort_by(|a, b| {
+      a.point.x.total_cmp(&b.point.x).then(a.point.y.total_cmp(&b.point.y))
+    });
+    pinch_points
+  }
+
+  // Splits every contour that visits the same point several times into
+  // separate simple contours at that point. Contours of different pieces may
+  // still touch at the pinch points, but no contour touches itself. The
+  // per-contour data (e.g. source edges) is split along with the contours, and
+  // `contour_parents` (if computed) is recomputed, since the pieces of a
+  // contour can be nested in each other (e.g. a hole that touched its shell).
+  pub fn split_pinched_contours(&mut self) {
+    // The pieces of every contour as `(contour, vertices)`.
+    let mut pieces = Vec::new();
+    let mut split_any = false;
+    for (contour_index, contour) in self.polygon.contours.iter().enumerate() {
+      let contour_pieces = split_at_repeated_points(contour);
+      split_any |= contour_pieces.len() > 1;
+      pieces
+        .extend(contour_pieces.into_iter().map(|piece| (contour_index, piece)));
+    }
+    if !split_any {
+      return;
+    }
+
+    fn split<T: Clone>(
+      values: &mut Vec<Vec<T>>,
+      pieces: &[(usize, Vec<usize>)],
+    ) {
+      if values.is_empty() {
+        return;
+      }
+      *values = pieces
+        .iter()
+        .map(|(contour, vertices)| {
+          vertices
+            .iter()
+            .map(|&vertex| values[*contour][vertex].clone())
+            .collect()
+        })
+        .collect();
+    }
+    split(&mut self.polygon.contours, &pieces);
+    split(&mut self.contour_source_edges, &pieces);
+    split(&mut self.contour_coincident_source_edges, &pieces);
+    split(&mut self.contour_merged_source_edges, &pieces);
+    split(&mut self.contour_unmodified_edges, &pieces);
+    split(&mut self.contour_edge_parameters, &pieces);
+    split(&mut self.contour_vertex_errors, &pieces);
+    self.update_bounds();
+    if !self.contour_parents.is_empty() {
+      self.contour_parents = compute_contour_parents(&self.polygon);
+    }
+  }
+}
+
+// A hashable key for `point`, treating -0 and 0 as the same.
+fn point_key(point: Vec2) -> (u32, u32) {
+  let point = point + Vec2::ZERO;
+  (point.x.to_bits(), point.y.to_bits())
+}
+
+// Splits `contour` into loops that each visit every point at most once,
+// returned as the indices of their vertices. The edge starting at each vertex
+// stays with the vertex.
+fn split_at_repeated_points(contour: &[Vec2]) -> Vec<Vec<usize>> {
+  let mut pieces = Vec::new();
+  // The vertices of the current path, and where each point is in it.
+  let mut path = Vec::<usize>::new();
+  let mut path_positions = HashMap::new();
+  for (vertex, &point) in contour.iter().enumerate() {
+    if let Some(&position) = path_positions.get(&point_key(point)) {
+      // The path returned to `point`, so the vertices since its first visit
+      // form a closed loop.
+      let piece = path.split_off(position);
+      for &piece_vertex in piece.iter() {
+        path_positions.remove(&point_key(contour[piece_vertex]));
+      }
+      pieces.push(piece);
+    }
+    path_positions.insert(point_key(point), path.len());
+    path.push(vertex);
+  }
+  pieces.push(path);
+  pieces
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::{
+    difference_with_options, union, BooleanOptions, PinchPoint, Polygon,
+  };
+
+  fn polygon(contours: &[&[(f32, f32)]]) -> Polygon {
+    Polygon {
+      contours: contours
+        .iter()
+        .map(|contour| contour.iter().map(|&(x, y)| Vec2::new(x, y)).collect())
+        .collect(),
+    }
+  }
+
+  #[test]
+  fn finds_pinch_points_between_contours() {
+    // Squares touching at a corner.
+    let result = union(
+      &polygon(&[&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]]),
+      &polygon(&[&[(4.0, 4.0), (5.0, 4.0), (5.0, 5.0), (4.0, 5.0)]]),
+    );
+    assert_eq!(
+      result.pinch_points(),
+      [PinchPoint {
+        point: Vec2::new(4.0, 4.0),
+        vertices: vec![(0, 2), (1, 0)]
+      }]
+    );
+  }
+
+  #[test]
+  fn splits_hole_touching_shell() {
+    // The clip touches the left side of the subject from the inside, so the
+    // hole and the shell are a single contour visiting (0, 2) twice.
+    let subject = polygon(&[&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]]);
+    let clip = polygon(&[&[(0.0, 2.0), (2.0, 1.0), (3.0, 2.0), (2.0, 3.0)]]);
+    let options = BooleanOptions {
+      track_hierarchy: true,
+      track_unmodified_edges: true,
+      ..Default::default()
+    };
+    let mut result = difference_with_options(&subject, &clip, &options);
+    assert_eq!(result.polygon.contours.len(), 1);
+    assert_eq!(result.pinch_points().len(), 1);
+
+    result.split_pinched_contours();
+    assert_eq!(
+      result.polygon,
+      polygon(&[
+        &[(0.0, 2.0), (2.0, 3.0), (3.0, 2.0), (2.0, 1.0)],
+        &[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0), (0.0, 2.0)],
+      ])
+    );
+    assert_eq!(result.contour_parents, [Some(1), None]);
+    assert_eq!(result.contour_source_edges[0].len(), 4);
+    assert!(result.contour_source_edges[0]
+      .iter()
+      .all(|source_edge| !source_edge.is_from_subject));
+    assert_eq!(result.contour_unmodified_edges[1].len(), 5);
+    assert_eq!(result.contour_bounds.len(), 2);
+    // The pieces still touch at (0, 2), but no contour touches itself.
+    assert_eq!(
+      result.pinch_points()[0].vertices.iter().map(|v| v.0).collect::<Vec<_>>(),
+      [0, 1]
+    );
+  }
+}
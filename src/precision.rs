@@ -0,0 +1,136 @@
+use glam::Vec2;
+
+use crate::Polygon;
+
+// The number of rounding errors (relative to the largest coordinate) that a
+// computed intersection point can accumulate. Computing an intersection takes a
+// handful of subtractions, products, and a division, each of which can round.
+const INTERSECTION_ERROR_FACTOR: f32 = 4.0;
+
+// Describes the precision of boolean operations on polygons within some
+// bounds. f32 coordinates have a fixed number of significant bits, so the
+// absolute error of the computation grows with the magnitude of the
+// coordinates, not with the size of the features. For example, polygons placed
+// kilometers from the origin cannot reliably resolve millimeter features, even
+// if the polygons themselves are small. Moving the polygons near the origin
+// (see `BooleanOptions::normalize_coordinates`) makes the error depend on the
+// size of the polygons instead.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Precision {
+  // The bounds (min, max) of the polygons.
+  bounds: (Vec2, Vec2),
+}
+
+impl Precision {
+  // Creates the precision for polygons within `bounds` (min, max).
+  pub fn new(bounds: (Vec2, Vec2)) -> Self {
+    Self { bounds }
+  }
+
+  // Creates the precision for operations on `subject` and `clip`. Returns None
+  // if both polygons are empty.
+  pub fn from_polygons(subject: &Polygon, clip: &Polygon) -> Option<Self> {
+    let bounds = match (subject.compute_bounds(), clip.compute_bounds()) {
+      (None, None) => return None,
+      (Some(bounds), None) | (None, Some(bounds)) => bounds,
+      (Some(subject_bounds), Some(clip_bounds)) => (
+        subject_bounds.0.min(clip_bounds.0),
+        subject_bounds.1.max(clip_bounds.1),
+      ),
+    };
+    Some(Self::new(bounds))
+  }
+
+  // The largest magnitude of any coordinate within the bounds.
+  fn max_magnitude(&self) -> f32 {
+    self.bounds.0.abs().max(self.bounds.1.abs()).max_element()
+  }
+
+  // The largest distance from the center of the bounds to its edge.
+  fn half_extent(&self) -> f32 {
+    (self.bounds.1 - self.bounds.0).max_element() * 0.5
+  }
+
+  // The largest error of representing a coordinate within the bounds (half the
+  // spacing between adjacent f32 values at the largest coordinate).
+  pub fn coordinate_error(&self) -> f32 {
+    self.max_magnitude() * f32::EPSILON * 0.5
+  }
+
+  // The estimated worst-case error of intersection points computed within the
+  // bounds. Vertices of the inputs are always preserved exactly.
+  pub fn intersection_error(&self) -> f32 {
+    intersection_error_for_magnitude(self.max_magnitude())
+  }
+
+  // The estimated worst-case error of intersection points if the polygons are
+  // normalized first (see `BooleanOptions::normalize_coordinates`). The
+  // intersections are computed near the origin, so only mapping them back to
+  // the original coordinates depends on the magnitude of the coordinates.
+  pub fn normalized_intersection_error(&self) -> f32 {
+    INTERSECTION_ERROR_FACTOR * self.half_extent() * f32::EPSILON
+      + self.coordinate_error()
+  }
+
+  // Whether normalizing the polygons meaningfully reduces the error of
+  // intersection points (at least halving it).
+  pub fn should_normalize(&self) -> bool {
+    self.normalized_intersection_error() * 2.0 <= self.intersection_error()
+  }
+
+  // Whether features of size `feature_size` (in the same units as the
+  // coordinates) can be reliably resolved, i.e. the errors of intersection
+  // points are much smaller than the features. If `normalize` is true, this
+  // assumes the polygons are normalized first.
+  pub fn resolves(&self, feature_size: f32, normalize: bool) -> bool {
+    // Require an order of magnitude between the error and the feature, so the
+    // error does not change which features touch or overlap.
+    const REQUIRED_RATIO: f32 = 10.0;
+    let error = if normalize {
+      self.normalized_intersection_error()
+    } else {
+      self.intersection_error()
+    };
+    error * REQUIRED_RATIO <= feature_size
+  }
+}
+
+// The estimated worst-case error of an intersection point computed from edges
+// whose coordinates are at most `magnitude` (in absolute value).
+pub(crate) fn intersection_error_for_magnitude(magnitude: f32) -> f32 {
+  INTERSECTION_ERROR_FACTOR * magnitude * f32::EPSILON
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use super::Precision;
+
+  #[test]
+  fn far_from_origin_recommends_normalizing() {
+    // A 100m wide area, 1000km from the origin.
+    let precision = Precision::new((
+      Vec2::new(1_000_000.0, 1_000_000.0),
+      Vec2::new(1_000_100.0, 1_000_100.0),
+    ));
+
+    assert!(precision.should_normalize());
+    // Meter features are unreliable without normalizing, but not with it.
+    assert!(!precision.resolves(1.0, /* normalize= */ false));
+    assert!(precision.resolves(1.0, /* normalize= */ true));
+    assert!(
+      precision.normalized_intersection_error()
+        < precision.intersection_error()
+    );
+  }
+
+  #[test]
+  fn near_origin_does_not_recommend_normalizing() {
+    let precision =
+      Precision::new((Vec2::new(-50.0, -50.0), Vec2::new(50.0, 50.0)));
+
+    assert!(!precision.should_normalize());
+    assert!(precision.resolves(0.001, /* normalize= */ false));
+  }
+}
@@ -0,0 +1,375 @@
+use std::collections::BinaryHeap;
+
+use glam::Vec2;
+
+use crate::{
+  create_events_for_polygon, sweep_events_until, validate_input, Arithmetic,
+  ClipError, EdgeCoincidenceType, Event, EventRelation, Operation, Polygon,
+  SweepError,
+};
+
+// Whether `subject` and `clip` share any point, either in their interiors or
+// on their boundaries. The sweep stops as soon as one is found, and no result
+// polygon is built. This panics if the polygons cannot be used in a boolean
+// operation (see `try_intersects`).
+pub fn intersects(subject: &Polygon, clip: &Polygon) -> bool {
+  try_intersects(subject, clip).unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `intersects`, but returns an error instead of panicking.
+pub fn try_intersects(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<bool, ClipError> {
+  let meeting = find_meeting(subject, clip, Predicate::Intersects)?;
+  Ok(meeting.interiors_overlap || meeting.boundaries_meet)
+}
+
+// Whether `subject` and `clip` share no points at all (the opposite of
+// `intersects`). This panics if the polygons cannot be used in a boolean
+// operation (see `try_disjoint`).
+pub fn disjoint(subject: &Polygon, clip: &Polygon) -> bool {
+  !intersects(subject, clip)
+}
+
+// Same as `disjoint`, but returns an error instead of panicking.
+pub fn try_disjoint(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<bool, ClipError> {
+  Ok(!try_intersects(subject, clip)?)
+}
+
+// Whether the boundaries of `subject` and `clip` meet while their interiors do
+// not overlap (e.g. squares sharing an edge or a corner). The sweep stops as
+// soon as the interiors are found to overlap. This panics if the polygons
+// cannot be used in a boolean operation (see `try_touches`).
+pub fn touches(subject: &Polygon, clip: &Polygon) -> bool {
+  try_touches(subject, clip).unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `touches`, but returns an error instead of panicking.
+pub fn try_touches(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<bool, ClipError> {
+  let meeting = find_meeting(subject, clip, Predicate::Touches)?;
+  Ok(meeting.boundaries_meet && !meeting.interiors_overlap)
+}
+
+// Whether every point of `clip` is in `subject` (including its boundary), and
+// `clip` is not empty. The sweep stops as soon as part of the clip is found
+// outside the subject, and does not go past the clip. Every polygon contains
+// itself, as long as its contours do not cross each other (or themselves);
+// crossing contours are split at points that are rounded differently for each
+// copy, so they should be resolved first (see `resolve_self_intersections`).
+// This panics if the polygons cannot be used in a boolean operation (see
+// `try_contains`).
+pub fn contains(subject: &Polygon, clip: &Polygon) -> bool {
+  try_contains(subject, clip).unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `contains`, but returns an error instead of panicking.
+pub fn try_contains(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<bool, ClipError> {
+  let meeting = find_meeting(subject, clip, Predicate::Contains)?;
+  Ok(!meeting.clip_outside_subject)
+}
+
+impl Polygon {
+  // Whether every point of `other` is in this polygon (see `contains`). The
+  // sweep stops as soon as part of `other` is found outside, without computing
+  // their difference. This panics if the polygons cannot be used in a boolean
+  // operation (see `try_contains_polygon`).
+  pub fn contains_polygon(&self, other: &Polygon) -> bool {
+    contains(self, other)
+  }
+
+  // Same as `contains_polygon`, but returns an error instead of panicking.
+  pub fn try_contains_polygon(
+    &self,
+    other: &Polygon,
+  ) -> Result<bool, ClipError> {
+    try_contains(self, other)
+  }
+}
+
+// The predicate a sweep is answering, which decides how far it has to go.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Predicate {
+  Intersects,
+  Touches,
+  Contains,
+}
+
+impl Predicate {
+  // The x coordinate past which no event can change the answer.
+  fn x_limit(
+    self,
+    subject_bounds: (Vec2, Vec2),
+    clip_bounds: (Vec2, Vec2),
+  ) -> f32 {
+    match self {
+      // The polygons can only meet where both of them are.
+      Predicate::Intersects | Predicate::Touches => {
+        subject_bounds.1.x.min(clip_bounds.1.x)
+      }
+      Predicate::Contains => clip_bounds.1.x,
+    }
+  }
+
+  // Whether `meeting` already determines the answer, so the sweep can stop.
+  fn is_settled(self, meeting: &Meeting) -> bool {
+    match self {
+      Predicate::Intersects => {
+        meeting.interiors_overlap || meeting.boundaries_meet
+      }
+      Predicate::Touches => meeting.interiors_overlap,
+      Predicate::Contains => meeting.clip_outside_subject,
+    }
+  }
+}
+
+// What a sweep found out about how the subject and clip meet.
+#[derive(Clone, Copy, Default)]
+struct Meeting {
+  // Whether the interiors of the polygons overlap.
+  interiors_overlap: bool,
+  // Whether the boundaries of the polygons share a point.
+  boundaries_meet: bool,
+  // Whether some point of the clip is outside the subject (or the clip is
+  // empty).
+  clip_outside_subject: bool,
+}
+
+impl Meeting {
+  // Updates the findings with the edge of the right `event`. The flags of its
+  // left event are final once the edge leaves the sweep line.
+  fn add_edge(&mut self, event: &Event, event_relations: &[EventRelation]) {
+    let relation = &event_relations[event_relations[event.event_id].sibling_id];
+    // Coincident edges of the same polygon do not meet the other polygon, so
+    // the primary edge is like any other edge.
+    let meets_other_polygon = relation.coincident_source_edge.is_some();
+    match relation.edge_coincidence_type {
+      EdgeCoincidenceType::SameTransition if meets_other_polygon => {
+        self.interiors_overlap = true;
+        self.boundaries_meet = true;
+      }
+      // The polygons are on opposite sides of the edge.
+      EdgeCoincidenceType::DifferentTransition if meets_other_polygon => {
+        self.boundaries_meet = true;
+        self.clip_outside_subject = true;
+      }
+      // The primary edge of the coincident pair describes both edges.
+      EdgeCoincidenceType::DuplicateCoincidence => {}
+      _ => {
+        let inside_other = !relation.other_in_out;
+        self.interiors_overlap |= inside_other;
+        // A subject edge inside the clip bounds a region of the clip outside
+        // the subject (e.g. a hole of the subject).
+        self.clip_outside_subject |= event.is_subject == inside_other;
+      }
+    }
+  }
+}
+
+// Sweeps over `subject` and `clip` until `predicate` is settled.
+fn find_meeting(
+  subject: &Polygon,
+  clip: &Polygon,
+  predicate: Predicate,
+) -> Result<Meeting, ClipError> {
+  validate_input(subject, clip)?;
+  // The polygons do not meet, so the clip is outside the subject (or empty).
+  let apart = Meeting { clip_outside_subject: true, ..Default::default() };
+  let (Some(subject_bounds), Some(clip_bounds)) =
+    (subject.compute_bounds(), clip.compute_bounds())
+  else {
+    return Ok(apart);
+  };
+  // Polygons whose bounds do not even touch cannot meet, and a clip reaching
+  // past the subject's bounds cannot be inside it.
+  if subject_bounds.1.x < clip_bounds.0.x
+    || subject_bounds.1.y < clip_bounds.0.y
+    || clip_bounds.1.x < subject_bounds.0.x
+    || clip_bounds.1.y < subject_bounds.0.y
+    || (predicate == Predicate::Contains
+      && (clip_bounds.0.cmplt(subject_bounds.0).any()
+        || clip_bounds.1.cmpgt(subject_bounds.1).any()))
+  {
+    return Ok(apart);
+  }
+
+  // The sweep is always in f64. The answer depends on recognizing coincident
+  // edges, and in f32 an edge can be split next to (rather than at) a vertex it
+  // shares with another edge, so e.g. a polygon would not contain itself.
+  sweep_meeting(
+    subject,
+    clip,
+    subject_bounds,
+    clip_bounds,
+    predicate,
+    Arithmetic::F64,
+  )
+  .map_err(|error| error.into_clip_error(usize::MAX))
+}
+
+// Sweeps over `subject` and `clip` (with the given bounds) with `arithmetic`
+// until `predicate` is settled.
+fn sweep_meeting(
+  subject: &Polygon,
+  clip: &Polygon,
+  subject_bounds: (Vec2, Vec2),
+  clip_bounds: (Vec2, Vec2),
+  predicate: Predicate,
+  arithmetic: Arithmetic,
+) -> Result<Meeting, SweepError> {
+  let x_limit = predicate.x_limit(subject_bounds, clip_bounds);
+  let mut event_queue = BinaryHeap::new();
+  let mut event_relations = Vec::new();
+  for (polygon, is_subject) in [(subject, true), (clip, false)] {
+    create_events_for_polygon(
+      polygon,
+      is_subject,
+      arithmetic,
+      &mut event_queue,
+      &mut event_relations,
+      x_limit,
+    );
+  }
+
+  let mut meeting = Meeting::default();
+  // The point of the last event, and whether events of the subject and of the
+  // clip were at that point. Events are processed in order of their points,
+  // so all the events at a point are next to each other.
+  let mut last_point = None;
+  let mut polygons_at_point = [false; 2];
+  sweep_events_until(
+    event_queue,
+    &mut event_relations,
+    // The flags the predicates use do not depend on the operation.
+    &Operation::XOR,
+    x_limit,
+    /* max_events= */ usize::MAX,
+    |event, event_relations| {
+      if last_point != Some(event.point) {
+        last_point = Some(event.point);
+        polygons_at_point = [false; 2];
+      }
+      polygons_at_point[usize::from(event.is_subject)] = true;
+      meeting.boundaries_meet |= polygons_at_point == [true; 2];
+      if !event.left {
+        meeting.add_edge(event, event_relations);
+      }
+      predicate.is_settled(&meeting)
+    },
+  )?;
+
+  Ok(meeting)
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square_polygon;
+  use crate::{contains, difference, disjoint, intersects, touches, Polygon};
+
+  #[test]
+  fn predicates_of_square_arrangements() {
+    let subject = square_polygon(Vec2::ZERO, 4.0);
+    // (clip, intersects, touches, contains)
+    let cases = [
+      (square_polygon(Vec2::splat(10.0), 1.0), false, false, false),
+      // Sharing an edge.
+      (square_polygon(Vec2::new(4.0, 1.0), 2.0), true, true, false),
+      // Sharing a corner.
+      (square_polygon(Vec2::splat(4.0), 2.0), true, true, false),
+      (square_polygon(Vec2::splat(2.0), 4.0), true, false, false),
+      (square_polygon(Vec2::ONE, 2.0), true, false, true),
+      // Inside, but touching the subject's boundary.
+      (square_polygon(Vec2::ZERO, 2.0), true, false, true),
+      (subject.clone(), true, false, true),
+      (square_polygon(Vec2::splat(-1.0), 6.0), true, false, false),
+      (Polygon::default(), false, false, false),
+    ];
+    for (clip, expected_intersects, expected_touches, expected_contains) in
+      cases
+    {
+      assert_eq!(intersects(&subject, &clip), expected_intersects, "{clip:?}");
+      assert_eq!(disjoint(&subject, &clip), !expected_intersects, "{clip:?}");
+      assert_eq!(touches(&subject, &clip), expected_touches, "{clip:?}");
+      assert_eq!(contains(&subject, &clip), expected_contains, "{clip:?}");
+    }
+  }
+
+  #[test]
+  fn polygons_contain_themselves() {
+    // A hexagon next to two overlapping pentagons. In f32, an edge of the
+    // hexagon was split next to the vertex it shares with the next edge.
+    let polygon = Polygon {
+      contours: vec![
+        vec![
+          Vec2::new(4.8319483, 6.6074014),
+          Vec2::new(4.459722, 6.018876),
+          Vec2::new(4.220068, 4.2768865),
+          Vec2::new(6.390216, 3.3070683),
+          Vec2::new(7.2207556, 5.4471307),
+          Vec2::new(6.254247, 7.001663),
+        ],
+        vec![
+          Vec2::new(4.502071, 14.098532),
+          Vec2::new(4.145477, 16.463327),
+          Vec2::new(5.969262, 16.661295),
+          Vec2::new(6.2385583, 15.42746),
+          Vec2::new(5.814107, 14.014476),
+        ],
+        vec![
+          Vec2::new(5.8645077, 13.474894),
+          Vec2::new(6.4443197, 15.405068),
+          Vec2::new(6.0764537, 17.09052),
+          Vec2::new(3.5851138, 16.820091),
+          Vec2::new(4.0722313, 13.589717),
+        ],
+      ],
+    };
+    assert!(contains(&polygon, &polygon));
+    assert!(intersects(&polygon, &polygon));
+    assert!(!touches(&polygon, &polygon));
+    let hexagon = Polygon { contours: vec![polygon.contours[0].clone()] };
+    assert!(hexagon.contains_polygon(&hexagon));
+  }
+
+  #[test]
+  fn contains_checks_holes_of_subject() {
+    let subject = Polygon {
+      contours: vec![
+        square_polygon(Vec2::ZERO, 4.0).contours.remove(0),
+        square_polygon(Vec2::new(1.5, 1.5), 1.0).contours.remove(0),
+      ],
+    };
+    // Every edge of the clip is inside the subject, but the clip covers the
+    // hole.
+    assert!(!contains(&subject, &square_polygon(Vec2::ONE, 2.0)));
+    assert!(contains(&subject, &square_polygon(Vec2::new(0.5, 0.5), 0.5)));
+    // A clip filling the hole only touches the subject.
+    let hole = square_polygon(Vec2::new(1.5, 1.5), 1.0);
+    assert!(touches(&subject, &hole));
+    assert!(!contains(&subject, &hole));
+  }
+
+  #[test]
+  fn contains_polygon_matches_difference() {
+    let container = square_polygon(Vec2::ZERO, 4.0);
+    for (piece, expected) in [
+      (square_polygon(Vec2::ONE, 2.0), true),
+      (square_polygon(Vec2::splat(3.0), 2.0), false),
+      (square_polygon(Vec2::splat(5.0), 1.0), false),
+    ] {
+      assert_eq!(container.contains_polygon(&piece), expected, "{piece:?}");
+      assert_eq!(difference(&piece, &container).is_empty, expected);
+    }
+  }
+}
@@ -0,0 +1,595 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use glam::Vec2;
+
+use crate::{
+  compute_x_limit, create_events_for_edge, create_events_for_polygon,
+  degenerate_edge_warnings, finish_boolean, split_polygon_edges,
+  sweep_event_queue, sweep_with_f64_retry, try_boolean_with_options,
+  try_intersection_with_options, validate_input, Arithmetic, BooleanOptions,
+  BooleanResult, ClipError, Event, EventRelation, FillRule, Operation, Polygon,
+  SweepError,
+};
+
+// A clip polygon that has been prepared for intersecting many subjects with it
+// (e.g. a large land/water mask). Preparing the clip creates its events once
+// and splits its edges where they intersect each other, so each operation only
+// needs to create the events of the subject and merge them in.
+#[derive(Clone, Debug)]
+pub struct PreparedClip {
+  // The original clip polygon.
+  clip: Polygon,
+  // The bounds of `clip`, or None if it has no vertices.
+  bounds: Option<(Vec2, Vec2)>,
+  // The events of the clip's (split) edges.
+  events: PreparedEvents,
+}
+
+impl PreparedClip {
+  // Prepares `clip`. This panics if `clip` cannot be used in a boolean
+  // operation (see `try_new`).
+  pub fn new(clip: &Polygon) -> Self {
+    Self::try_new(clip).unwrap_or_else(|error| panic!("{error}"))
+  }
+
+  // Prepares `clip`, returning an error if it cannot be used in a boolean
+  // operation (e.g. it contains non-finite coordinates).
+  pub fn try_new(clip: &Polygon) -> Result<Self, ClipError> {
+    validate_input(&Polygon::default(), clip)?;
+
+    let events = PreparedEvents::new(clip, /* is_subject= */ false)?;
+    Ok(Self { clip: clip.clone(), bounds: clip.compute_bounds(), events })
+  }
+
+  // The clip polygon that was prepared.
+  pub fn clip(&self) -> &Polygon {
+    &self.clip
+  }
+
+  // Computes the intersection of `subject` and the prepared clip. This is the
+  // same as `intersection(subject, self.clip())`.
+  pub fn clip_with(&self, subject: &Polygon) -> BooleanResult {
+    self
+      .try_clip_with_options(subject, &BooleanOptions::default())
+      .unwrap_or_else(|error| panic!("{error}"))
+  }
+
+  // Same as `clip_with`, but with `options`. Options that change the swept
+  // polygons (`normalize_coordinates`, `auto_precision`, `boundary_tolerance`
+  // and `weld_tolerance`) cannot reuse the prepared events, so these compute
+  // the intersection from scratch.
+  pub fn clip_with_options(
+    &self,
+    subject: &Polygon,
+    options: &BooleanOptions,
+  ) -> BooleanResult {
+    self
+      .try_clip_with_options(subject, options)
+      .unwrap_or_else(|error| panic!("{error}"))
+  }
+
+  // Same as `clip_with`, but returns an error instead of panicking.
+  pub fn try_clip_with(
+    &self,
+    subject: &Polygon,
+  ) -> Result<BooleanResult, ClipError> {
+    self.try_clip_with_options(subject, &BooleanOptions::default())
+  }
+
+  // Same as `clip_with_options`, but returns an error instead of panicking.
+  pub fn try_clip_with_options(
+    &self,
+    subject: &Polygon,
+    options: &BooleanOptions,
+  ) -> Result<BooleanResult, ClipError> {
+    if options.normalize_coordinates
+      || options.auto_precision
+      || options.boundary_tolerance > 0.0
+      || options.weld_tolerance > 0.0
+    {
+      return try_intersection_with_options(subject, &self.clip, options);
+    }
+    validate_input(subject, &Polygon::default())?;
+    let max_events = options.max_events.unwrap_or(usize::MAX);
+    if max_events
+      < subject.num_edges().saturating_mul(2) + self.events.events.len()
+    {
+      return Err(ClipError::CapacityExceeded { limit: max_events });
+    }
+
+    let (Some(subject_bounds), Some(clip_bounds)) =
+      (subject.compute_bounds(), self.bounds)
+    else {
+      return Ok(finish_boolean(
+        BooleanResult::default(),
+        subject,
+        &self.clip,
+        options,
+      ));
+    };
+    if subject_bounds.1.x < clip_bounds.0.x
+      || subject_bounds.1.y < clip_bounds.0.y
+      || clip_bounds.1.x < subject_bounds.0.x
+      || clip_bounds.1.y < subject_bounds.0.y
+    {
+      return Ok(finish_boolean(
+        BooleanResult::default(),
+        subject,
+        &self.clip,
+        options,
+      ));
+    }
+
+    match self.events.sweep_with(
+      subject,
+      /* is_subject= */ true,
+      subject_bounds,
+      clip_bounds,
+      Operation::Intersection,
+      options,
+    ) {
+      Ok(result) => Ok(finish_boolean(result, subject, &self.clip, options)),
+      // The prepared events were created with a fixed arithmetic, so the
+      // regular operation (which can retry in f64) is computed instead.
+      Err(SweepError::Inconsistent { .. }) => {
+        try_intersection_with_options(subject, &self.clip, options)
+      }
+      Err(error) => Err(error.into_clip_error(max_events)),
+    }
+  }
+}
+
+// A subject polygon that has been prepared for computing boolean operations
+// with many clips (e.g. a fixed shape rendered into many tiles). This is the
+// counterpart of `PreparedClip`: the subject's events are created (and its
+// self-intersecting edges split) once, so each operation only creates the
+// events of the clip.
+#[derive(Clone, Debug)]
+pub struct PreparedSubject {
+  // The original subject polygon.
+  subject: Polygon,
+  // The bounds of `subject`, or None if it has no vertices.
+  bounds: Option<(Vec2, Vec2)>,
+  // The events of the subject's (split) edges.
+  events: PreparedEvents,
+}
+
+impl PreparedSubject {
+  // Prepares `subject`. This panics if `subject` cannot be used in a boolean
+  // operation (see `try_new`).
+  pub fn new(subject: &Polygon) -> Self {
+    Self::try_new(subject).unwrap_or_else(|error| panic!("{error}"))
+  }
+
+  // Prepares `subject`, returning an error if it cannot be used in a boolean
+  // operation (e.g. it contains non-finite coordinates).
+  pub fn try_new(subject: &Polygon) -> Result<Self, ClipError> {
+    validate_input(subject, &Polygon::default())?;
+    let events = PreparedEvents::new(subject, /* is_subject= */ true)?;
+    Ok(Self {
+      subject: subject.clone(),
+      bounds: subject.compute_bounds(),
+      events,
+    })
+  }
+
+  // The subject polygon that was prepared.
+  pub fn subject(&self) -> &Polygon {
+    &self.subject
+  }
+
+  // Computes the intersection of the prepared subject and `clip`. This is the
+  // same as `intersection(self.subject(), clip)`.
+  pub fn intersection(&self, clip: &Polygon) -> BooleanResult {
+    self.boolean(clip, Operation::Intersection)
+  }
+
+  // Computes the union of the prepared subject and `clip`. This is the same as
+  // `union(self.subject(), clip)`.
+  pub fn union(&self, clip: &Polygon) -> BooleanResult {
+    self.boolean(clip, Operation::Union)
+  }
+
+  // Computes the difference of the prepared subject and `clip`. This is the
+  // same as `difference(self.subject(), clip)`.
+  pub fn difference(&self, clip: &Polygon) -> BooleanResult {
+    self.boolean(clip, Operation::Difference)
+  }
+
+  // Computes the XOR of the prepared subject and `clip`. This is the same as
+  // `xor(self.subject(), clip)`.
+  pub fn xor(&self, clip: &Polygon) -> BooleanResult {
+    self.boolean(clip, Operation::XOR)
+  }
+
+  // Computes `operation` of the prepared subject and `clip`. This is the same
+  // as `boolean(self.subject(), clip, operation)`.
+  pub fn boolean(&self, clip: &Polygon, operation: Operation) -> BooleanResult {
+    self
+      .try_boolean_with_options(clip, operation, &BooleanOptions::default())
+      .unwrap_or_else(|error| panic!("{error}"))
+  }
+
+  // Same as `boolean`, but with `options`. Options that change the swept
+  // polygons (the fill rules, `normalize_coordinates`, `auto_precision`,
+  // `boundary_tolerance` and `weld_tolerance`) cannot reuse the prepared
+  // events, so these compute the operation from scratch.
+  pub fn boolean_with_options(
+    &self,
+    clip: &Polygon,
+    operation: Operation,
+    options: &BooleanOptions,
+  ) -> BooleanResult {
+    self
+      .try_boolean_with_options(clip, operation, options)
+      .unwrap_or_else(|error| panic!("{error}"))
+  }
+
+  // Same as `boolean`, but returns an error instead of panicking.
+  pub fn try_boolean(
+    &self,
+    clip: &Polygon,
+    operation: Operation,
+  ) -> Result<BooleanResult, ClipError> {
+    self.try_boolean_with_options(clip, operation, &BooleanOptions::default())
+  }
+
+  // Same as `boolean_with_options`, but returns an error instead of panicking.
+  pub fn try_boolean_with_options(
+    &self,
+    clip: &Polygon,
+    operation: Operation,
+    options: &BooleanOptions,
+  ) -> Result<BooleanResult, ClipError> {
+    if options.fill_rules() != (FillRule::EvenOdd, FillRule::EvenOdd)
+      || options.normalize_coordinates
+      || options.auto_precision
+      || options.boundary_tolerance > 0.0
+      || options.weld_tolerance > 0.0
+    {
+      return try_boolean_with_options(&self.subject, clip, operation, options);
+    }
+    validate_input(&Polygon::default(), clip)?;
+    let max_events = options.max_events.unwrap_or(usize::MAX);
+    if max_events
+      < clip.num_edges().saturating_mul(2) + self.events.events.len()
+    {
+      return Err(ClipError::CapacityExceeded { limit: max_events });
+    }
+
+    let (Some(subject_bounds), Some(clip_bounds)) =
+      (self.bounds, clip.compute_bounds())
+    else {
+      // Empty polygons are computed trivially without any events.
+      return try_boolean_with_options(&self.subject, clip, operation, options);
+    };
+    if subject_bounds.1.x < clip_bounds.0.x
+      || subject_bounds.1.y < clip_bounds.0.y
+      || clip_bounds.1.x < subject_bounds.0.x
+      || clip_bounds.1.y < subject_bounds.0.y
+    {
+      // Disjoint polygons are computed trivially as well.
+      return try_boolean_with_options(&self.subject, clip, operation, options);
+    }
+
+    match self.events.sweep_with(
+      clip,
+      /* is_subject= */ false,
+      subject_bounds,
+      clip_bounds,
+      operation,
+      options,
+    ) {
+      Ok(mut result) => {
+        if options.collect_warnings {
+          let mut warnings =
+            degenerate_edge_warnings(&self.subject, clip).collect::<Vec<_>>();
+          warnings.append(&mut result.warnings);
+          result.warnings = warnings;
+        }
+        Ok(finish_boolean(result, &self.subject, clip, options))
+      }
+      // The prepared events were created with a fixed arithmetic, so the
+      // regular operation (which can retry in f64) is computed instead.
+      Err(SweepError::Inconsistent { .. }) => {
+        try_boolean_with_options(&self.subject, clip, operation, options)
+      }
+      Err(error) => Err(error.into_clip_error(max_events)),
+    }
+  }
+}
+
+// The events of a prepared polygon, which are merged with the events of the
+// other polygon for each operation.
+#[derive(Clone, Debug)]
+struct PreparedEvents {
+  // The events of the polygon's (split) edges, sorted in the order they are
+  // processed.
+  events: Vec<Event>,
+  // The relations of `events`, indexed by event ID.
+  event_relations: Vec<EventRelation>,
+  // The arithmetic `events` were created with, which the events of the other
+  // polygon must use as well.
+  arithmetic: Arithmetic,
+}
+
+impl PreparedEvents {
+  // Creates the events of the edges of `polygon`, after splitting them
+  // wherever they intersect each other. The split edges keep their source
+  // edges. If rounding errors make splitting the edges inconsistent, they are
+  // split in f64 instead (see `sweep_with_f64_retry`).
+  fn new(polygon: &Polygon, is_subject: bool) -> Result<Self, ClipError> {
+    let (split_edges, retried) = sweep_with_f64_retry(|arithmetic| {
+      split_polygon_edges(polygon, is_subject, arithmetic)
+    })
+    .map_err(|error| error.into_clip_error(usize::MAX))?;
+    let arithmetic = if retried { Arithmetic::F64 } else { Arithmetic::F32 };
+    let mut events = Vec::new();
+    let mut event_relations = Vec::new();
+    for (left_point, right_point, source_edge) in split_edges {
+      if let Some(edge_events) = create_events_for_edge(
+        left_point,
+        right_point,
+        source_edge,
+        arithmetic,
+        &mut event_relations,
+      ) {
+        events.extend(edge_events);
+      }
+    }
+    events.sort();
+    Ok(Self { events, event_relations, arithmetic })
+  }
+
+  // Computes `operation` by sweeping the prepared events together with the
+  // events of `polygon`, which is the subject if `is_subject` is set
+  // (otherwise the clip).
+  fn sweep_with(
+    &self,
+    polygon: &Polygon,
+    is_subject: bool,
+    subject_bounds: (Vec2, Vec2),
+    clip_bounds: (Vec2, Vec2),
+    operation: Operation,
+    options: &BooleanOptions,
+  ) -> Result<BooleanResult, SweepError> {
+    // Events past `x_limit` are never processed, so only the prepared events
+    // before it are merged with the polygon's events.
+    let x_limit = compute_x_limit(subject_bounds, clip_bounds, operation);
+    #[cfg(feature = "timings")]
+    let start_time = std::time::Instant::now();
+    let prepared_event_count =
+      self.events.partition_point(|event| event.point.x <= x_limit);
+    let mut event_queue = self.events[..prepared_event_count]
+      .iter()
+      .cloned()
+      .map(Reverse)
+      .collect::<BinaryHeap<_>>();
+    let mut event_relations = self.event_relations.clone();
+    create_events_for_polygon(
+      polygon,
+      is_subject,
+      self.arithmetic,
+      &mut event_queue,
+      &mut event_relations,
+      x_limit,
+    );
+    #[cfg(feature = "timings")]
+    let event_creation = start_time.elapsed();
+
+    let result = sweep_event_queue(
+      event_queue,
+      event_relations,
+      subject_bounds,
+      clip_bounds,
+      &operation,
+      x_limit,
+      options,
+    )?;
+    #[cfg(feature = "timings")]
+    let result = BooleanResult {
+      timings: result
+        .timings
+        .map(|timings| crate::Timings { event_creation, ..timings }),
+      ..result
+    };
+    Ok(result)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+  use rand::{rngs::StdRng, Rng, SeedableRng};
+
+  use crate::tests::fixtures::heptagon;
+  use crate::{
+    boolean, intersection, xor_with_options, BooleanOptions, Operation,
+    Polygon, PreparedClip, PreparedSubject, TrackingLevel,
+  };
+
+  #[test]
+  fn matches_intersection_with_self_intersecting_clip() {
+    // A bow tie, whose edges cross at (2, 2), plus a separate square.
+    let clip = Polygon {
+      contours: vec![
+        vec![
+          Vec2::new(0.0, 0.0),
+          Vec2::new(4.0, 4.0),
+          Vec2::new(4.0, 0.0),
+          Vec2::new(0.0, 4.0),
+        ],
+        vec![
+          Vec2::new(6.0, 0.0),
+          Vec2::new(8.0, 0.0),
+          Vec2::new(8.0, 2.0),
+          Vec2::new(6.0, 2.0),
+        ],
+      ],
+    };
+    let prepared_clip = PreparedClip::new(&clip);
+
+    let mut rng = StdRng::seed_from_u64(3279);
+    for _ in 0..50 {
+      // Quarter units keep the intersections exact, so neither operation needs
+      // to escalate its precision.
+      let min = Vec2::new(
+        rng.gen_range(-4..32) as f32 * 0.25,
+        rng.gen_range(-4..16) as f32 * 0.25,
+      );
+      let size = Vec2::new(
+        rng.gen_range(2..16) as f32 * 0.25,
+        rng.gen_range(2..16) as f32 * 0.25,
+      );
+      let subject = Polygon {
+        contours: vec![vec![
+          min,
+          Vec2::new(min.x + size.x, min.y),
+          min + size,
+          Vec2::new(min.x, min.y + size.y),
+        ]],
+      };
+      assert_eq!(
+        prepared_clip.clip_with(&subject),
+        intersection(&subject, &clip),
+        "subject = {subject:?}"
+      );
+    }
+  }
+
+  #[test]
+  fn prepared_subject_matches_each_operation() {
+    // A square with a hole, plus a separate triangle.
+    let subject = Polygon {
+      contours: vec![
+        vec![
+          Vec2::new(0.0, 0.0),
+          Vec2::new(6.0, 0.0),
+          Vec2::new(6.0, 6.0),
+          Vec2::new(0.0, 6.0),
+        ],
+        vec![
+          Vec2::new(2.0, 2.0),
+          Vec2::new(4.0, 2.0),
+          Vec2::new(4.0, 4.0),
+          Vec2::new(2.0, 4.0),
+        ],
+        vec![Vec2::new(8.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(9.0, 3.0)],
+      ],
+    };
+    let prepared_subject = PreparedSubject::new(&subject);
+
+    let mut rng = StdRng::seed_from_u64(3308);
+    for _ in 0..50 {
+      // Quarter units keep the intersections exact, so neither operation needs
+      // to escalate its precision.
+      let min = Vec2::new(
+        rng.gen_range(-8..48) as f32 * 0.25,
+        rng.gen_range(-8..24) as f32 * 0.25,
+      );
+      let size = Vec2::new(
+        rng.gen_range(2..16) as f32 * 0.25,
+        rng.gen_range(2..16) as f32 * 0.25,
+      );
+      let clip = Polygon {
+        contours: vec![vec![
+          min,
+          Vec2::new(min.x + size.x, min.y),
+          min + size,
+          Vec2::new(min.x, min.y + size.y),
+        ]],
+      };
+      for operation in Operation::ALL {
+        assert_eq!(
+          prepared_subject.boolean(&clip, operation),
+          boolean(&subject, &clip, operation),
+          "{operation:?} with clip = {clip:?}"
+        );
+      }
+    }
+  }
+
+  #[test]
+  fn prepared_subject_applies_options() {
+    let subject = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(4.0, 0.0),
+        Vec2::new(4.0, 4.0),
+        Vec2::new(0.0, 4.0),
+      ]],
+    };
+    // The duplicated vertex is a zero-length edge, which is warned about.
+    let clip = Polygon {
+      contours: vec![vec![
+        Vec2::new(2.0, 2.0),
+        Vec2::new(6.0, 2.0),
+        Vec2::new(6.0, 2.0),
+        Vec2::new(6.0, 6.0),
+        Vec2::new(2.0, 6.0),
+      ]],
+    };
+    let options = BooleanOptions {
+      collect_warnings: true,
+      ..BooleanOptions::new().with_tracking(TrackingLevel::Full)
+    };
+    assert_eq!(
+      PreparedSubject::new(&subject).boolean_with_options(
+        &clip,
+        Operation::XOR,
+        &options
+      ),
+      xor_with_options(&subject, &clip, &options)
+    );
+  }
+
+  #[test]
+  fn rejects_invalid_clip() {
+    let clip = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(f32::NAN, 0.0),
+        Vec2::new(0.0, 1.0),
+      ]],
+    };
+    assert!(PreparedClip::try_new(&clip).is_err());
+  }
+
+  #[test]
+  fn prepares_clip_needing_f64() {
+    let clip = heptagon();
+    let prepared_clip = PreparedClip::try_new(&clip).unwrap();
+    let subject = Polygon {
+      contours: vec![vec![
+        Vec2::new(13.142297, 13.273488),
+        Vec2::new(13.314997, 2.6289444),
+        Vec2::new(17.0, 3.0),
+      ]],
+    };
+    let area = prepared_clip.clip_with(&subject).polygon.area();
+    assert!(area > 0.0);
+    assert_eq!(area, intersection(&subject, &clip).polygon.area());
+  }
+
+  #[test]
+  fn prepares_subject_needing_f64() {
+    let subject = heptagon();
+    let prepared_subject = PreparedSubject::try_new(&subject).unwrap();
+    let clip = Polygon {
+      contours: vec![vec![
+        Vec2::new(16.0, 0.0),
+        Vec2::new(20.0, 0.0),
+        Vec2::new(20.0, 3.0),
+        Vec2::new(16.0, 3.0),
+      ]],
+    };
+    for operation in Operation::ALL {
+      // The prepared subject is split in f64, so its result may differ from
+      // the regular operation by rounding errors.
+      let area = prepared_subject.boolean(&clip, operation).polygon.area();
+      let expected_area = boolean(&subject, &clip, operation).polygon.area();
+      assert!(area > 0.0, "{operation:?}");
+      assert!((area - expected_area).abs() < 1e-4, "{operation:?}");
+    }
+  }
+}
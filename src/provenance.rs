@@ -0,0 +1,312 @@
+use glam::Vec2;
+
+use crate::{
+  precision::intersection_error_for_magnitude, BooleanResult, Polygon,
+  SourceEdge,
+};
+
+// Determines whether each edge of `result` is an exact copy of its source edge
+// in `subject` or `clip`. The returned Vec has the same layout as
+// `result.contour_source_edges`.
+pub(crate) fn compute_unmodified_edges(
+  result: &BooleanResult,
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Vec<Vec<bool>> {
+  result
+    .polygon
+    .contours
+    .iter()
+    .zip(result.contour_source_edges.iter())
+    .map(|(contour, source_edges)| {
+      source_edges
+        .iter()
+        .enumerate()
+        .map(|(index, source_edge)| {
+          let source_polygon =
+            if source_edge.is_from_subject { subject } else { clip };
+          let source_contour = &source_polygon.contours[source_edge.contour];
+          let source_start = source_contour[source_edge.edge];
+          let source_end =
+            source_contour[(source_edge.edge + 1) % source_contour.len()];
+
+          let start = contour[index];
+          let end = contour[(index + 1) % contour.len()];
+          (start == source_start && end == source_end)
+            || (start == source_end && end == source_start)
+        })
+        .collect()
+    })
+    .collect()
+}
+
+// Computes the parametric positions of the start and end of each edge of
+// `result` along its source edge in `subject` or `clip`. A position of 0 is the
+// start of the source edge (the vertex at the source edge's index) and 1 is its
+// end. The returned Vec has the same layout as `result.contour_source_edges`.
+pub(crate) fn compute_edge_parameters(
+  result: &BooleanResult,
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Vec<Vec<[f32; 2]>> {
+  result
+    .polygon
+    .contours
+    .iter()
+    .zip(result.contour_source_edges.iter())
+    .map(|(contour, source_edges)| {
+      source_edges
+        .iter()
+        .enumerate()
+        .map(|(index, source_edge)| {
+          let source_polygon =
+            if source_edge.is_from_subject { subject } else { clip };
+          let source_contour = &source_polygon.contours[source_edge.contour];
+          let source_start = source_contour[source_edge.edge];
+          let source_end =
+            source_contour[(source_edge.edge + 1) % source_contour.len()];
+
+          let source_vector = source_end - source_start;
+          let parameter = |point: Vec2| {
+            ((point - source_start).dot(source_vector)
+              / source_vector.length_squared())
+            .clamp(0.0, 1.0)
+          };
+          [
+            parameter(contour[index]),
+            parameter(contour[(index + 1) % contour.len()]),
+          ]
+        })
+        .collect()
+    })
+    .collect()
+}
+
+// Estimates the numerical error of each vertex of `result`. Vertices that are
+// exact copies of an end point of their adjacent source edges in `subject` or
+// `clip` have no error. The remaining vertices were computed as intersections,
+// so their error is estimated from the magnitude of the coordinates of their
+// adjacent source edges. The returned Vec has the same layout as
+// `result.polygon.contours`.
+pub(crate) fn compute_vertex_errors(
+  result: &BooleanResult,
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Vec<Vec<f32>> {
+  let source_end_points = |source_edge: &SourceEdge| {
+    let source_polygon =
+      if source_edge.is_from_subject { subject } else { clip };
+    let source_contour = &source_polygon.contours[source_edge.contour];
+    [
+      source_contour[source_edge.edge],
+      source_contour[(source_edge.edge + 1) % source_contour.len()],
+    ]
+  };
+  result
+    .polygon
+    .contours
+    .iter()
+    .zip(result.contour_source_edges.iter())
+    .map(|(contour, source_edges)| {
+      (0..contour.len())
+        .map(|index| {
+          // The vertex starts its own edge and ends the previous edge.
+          let previous_index =
+            if index == 0 { contour.len() - 1 } else { index - 1 };
+          let end_points = [
+            source_end_points(&source_edges[previous_index]),
+            source_end_points(&source_edges[index]),
+          ];
+          let end_points = end_points.iter().flatten();
+          if end_points.clone().any(|&point| point == contour[index]) {
+            return 0.0;
+          }
+          let magnitude = end_points
+            .map(|point| point.abs().max_element())
+            .fold(0.0, f32::max);
+          intersection_error_for_magnitude(magnitude)
+        })
+        .collect()
+    })
+    .collect()
+}
+
+// Determines whether the area covered by `result` is exactly the area covered
+// by `input` (the subject if `is_subject` is set, and the clip otherwise). This
+// is the case if every edge of `result` comes from `input`, and the edges of
+// `result` split from each (non-degenerate) edge of `input` cover that edge
+// completely. This can miss results that only equal `input` thanks to edges of
+// the other polygon (e.g. coincident edges that were taken from the other
+// polygon), so it may return false for equal areas, but never returns true for
+// different areas.
+pub(crate) fn result_equals_input(
+  result: &BooleanResult,
+  input: &Polygon,
+  is_subject: bool,
+) -> bool {
+  let mut contour_offsets = Vec::with_capacity(input.contours.len());
+  let mut num_edges = 0;
+  for contour in input.contours.iter() {
+    contour_offsets.push(num_edges);
+    num_edges += contour.len();
+  }
+
+  // The end points of the result edges split from each edge of `input`.
+  let mut edge_end_points = vec![Vec::new(); num_edges];
+  for (contour, source_edges) in
+    result.polygon.contours.iter().zip(result.contour_source_edges.iter())
+  {
+    for (index, source_edge) in source_edges.iter().enumerate() {
+      if source_edge.is_from_subject != is_subject {
+        return false;
+      }
+      let end_points = &mut edge_end_points
+        [contour_offsets[source_edge.contour] + source_edge.edge];
+      end_points.push(contour[index]);
+      end_points.push(contour[(index + 1) % contour.len()]);
+    }
+  }
+
+  // The pieces of a split edge share their split points, so the pieces cover
+  // the whole edge if only the edge's own end points are not shared (appear
+  // an odd number of times).
+  for (contour_index, contour) in input.contours.iter().enumerate() {
+    for (index, &start) in contour.iter().enumerate() {
+      let end = contour[(index + 1) % contour.len()];
+      if start == end {
+        continue;
+      }
+      let end_points =
+        &mut edge_end_points[contour_offsets[contour_index] + index];
+      end_points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+      let mut unshared_points = end_points
+        .chunk_by(|a, b| a == b)
+        .filter(|points| points.len() % 2 == 1)
+        .map(|points| points[0]);
+      let (Some(first), Some(second), None) = (
+        unshared_points.next(),
+        unshared_points.next(),
+        unshared_points.next(),
+      ) else {
+        return false;
+      };
+      if !((first == start && second == end)
+        || (first == end && second == start))
+      {
+        return false;
+      }
+    }
+  }
+  true
+}
+
+// A run of consecutive edges in a contour that share the same source edge.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SourceEdgeRun {
+  // The source of every edge in the run.
+  pub source_edge: SourceEdge,
+  // The number of edges in the run.
+  pub count: usize,
+}
+
+// A run-length encoded version of `BooleanResult::contour_source_edges`.
+// Splitting edges produces long runs of edges with the same source edge, so
+// this can use much less memory for heavily subdivided results.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct CompressedSourceEdges {
+  // The runs of source edges for each contour.
+  contour_runs: Vec<Vec<SourceEdgeRun>>,
+}
+
+impl CompressedSourceEdges {
+  // Compresses `contour_source_edges` (in the layout of
+  // `BooleanResult::contour_source_edges`).
+  pub fn new(contour_source_edges: &[Vec<SourceEdge>]) -> Self {
+    Self {
+      contour_runs: contour_source_edges
+        .iter()
+        .map(|source_edges| {
+          let mut runs: Vec<SourceEdgeRun> = Vec::new();
+          for &source_edge in source_edges.iter() {
+            match runs.last_mut() {
+              Some(run) if run.source_edge == source_edge => run.count += 1,
+              _ => runs.push(SourceEdgeRun { source_edge, count: 1 }),
+            }
+          }
+          runs
+        })
+        .collect(),
+    }
+  }
+
+  // The number of contours.
+  pub fn num_contours(&self) -> usize {
+    self.contour_runs.len()
+  }
+
+  // The runs of source edges of the contour at `contour_index`.
+  pub fn contour_runs(&self, contour_index: usize) -> &[SourceEdgeRun] {
+    &self.contour_runs[contour_index]
+  }
+
+  // Iterates over the source edge of each edge in the contour at
+  // `contour_index`.
+  pub fn iter_contour(
+    &self,
+    contour_index: usize,
+  ) -> impl Iterator<Item = SourceEdge> + '_ {
+    self.contour_runs[contour_index]
+      .iter()
+      .flat_map(|run| std::iter::repeat_n(run.source_edge, run.count))
+  }
+
+  // Expands back into the layout of `BooleanResult::contour_source_edges`.
+  pub fn expand(&self) -> Vec<Vec<SourceEdge>> {
+    (0..self.num_contours())
+      .map(|contour_index| self.iter_contour(contour_index).collect())
+      .collect()
+  }
+}
+
+impl BooleanResult {
+  // Takes the source edges out of the result in compressed form, leaving
+  // `contour_source_edges` empty. This is useful for keeping large results in
+  // memory.
+  pub fn take_compressed_source_edges(&mut self) -> CompressedSourceEdges {
+    CompressedSourceEdges::new(&std::mem::take(&mut self.contour_source_edges))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::{CompressedSourceEdges, SourceEdge, SourceEdgeRun};
+
+  #[test]
+  fn compresses_runs_of_source_edges() {
+    let source_edge =
+      |edge| SourceEdge { is_from_subject: true, contour: 0, edge };
+    let contour_source_edges = vec![
+      vec![
+        source_edge(0),
+        source_edge(0),
+        source_edge(0),
+        source_edge(1),
+        source_edge(2),
+        source_edge(2),
+      ],
+      vec![],
+    ];
+
+    let compressed = CompressedSourceEdges::new(&contour_source_edges);
+    assert_eq!(compressed.num_contours(), 2);
+    assert_eq!(
+      compressed.contour_runs(0),
+      [
+        SourceEdgeRun { source_edge: source_edge(0), count: 3 },
+        SourceEdgeRun { source_edge: source_edge(1), count: 1 },
+        SourceEdgeRun { source_edge: source_edge(2), count: 2 },
+      ]
+    );
+    assert_eq!(compressed.expand(), contour_source_edges);
+  }
+}
@@ -0,0 +1,355 @@
+// A brute-force reference implementation of boolean operations, for
+// differential testing of the sweep. It is O(n²) (or worse) and makes no
+// attempt to be fast, but every step is simple enough to check by hand: every
+// edge is split against every other edge, and each piece is kept if the
+// operation's result is inside on exactly one side of it. These are enabled by
+// the `reference` feature.
+
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use crate::{
+  try_difference, try_intersection, try_union, try_xor,
+  util::{
+    contour_signed_area, edge_intersection, point_in_contour,
+    EdgeIntersectionResult,
+  },
+  BooleanResult, ClipError, Polygon,
+};
+
+// Computes the boundary of a boolean operation between `subject` and `clip`
+// (both using the even-odd rule), where `in_result(in_subject, in_clip)`
+// determines whether a point is in the result. For example, the intersection
+// is `|in_subject, in_clip| in_subject && in_clip`. The boundary is returned as
+// unordered edges, each with the result on its left (so shells are
+// counter-clockwise and holes are clockwise, like the sweep's default). Edges
+// are split at every intersection, so consecutive edges may be collinear.
+pub fn reference_boolean(
+  subject: &Polygon,
+  clip: &Polygon,
+  in_result: impl Fn(bool, bool) -> bool,
+) -> Vec<(Vec2, Vec2)> {
+  let in_result_at = |point: Vec2| {
+    in_result(point_in_polygon(point, subject), point_in_polygon(point, clip))
+  };
+  let pieces = split_edges(subject, clip);
+  pieces
+    .iter()
+    .filter_map(|&(start, end)| {
+      let midpoint = (start + end) * 0.5;
+      // Step far enough from the midpoint to be clearly on one side of the
+      // piece, but not so far as to cross any other piece.
+      let clearance = pieces
+        .iter()
+        .filter(|&&other| other != (start, end))
+        .map(|&other| distance_to_segment(midpoint, other))
+        .fold(start.distance(end) * 0.25, f32::min);
+      let offset = (end - start).normalize().perp() * clearance * 0.5;
+      let (left, right) =
+        (in_result_at(midpoint + offset), in_result_at(midpoint - offset));
+      match (left, right) {
+        (true, false) => Some((start, end)),
+        (false, true) => Some((end, start)),
+        _ => None,
+      }
+    })
+    .collect()
+}
+
+// Computes the area enclosed by `edges`, where the enclosed region is on the
+// left of each edge (as returned by `reference_boolean`).
+pub fn reference_area(edges: &[(Vec2, Vec2)]) -> f32 {
+  edges.iter().map(|(start, end)| start.perp_dot(*end)).sum::<f32>() * 0.5
+}
+
+// Compares `result`, the result of the boolean operation between `subject` and
+// `clip` described by `in_result` (see `reference_boolean`), against the
+// reference implementation. The areas must agree, and every edge of either
+// boundary must lie along an edge of the other going the same way (within a
+// tolerance relative to the size of the inputs). `result` must use the
+// default orientation (counter-clockwise shells and clockwise holes).
+//
+// Returns a description of the first mismatch.
+pub fn compare_with_reference(
+  subject: &Polygon,
+  clip: &Polygon,
+  in_result: impl Fn(bool, bool) -> bool,
+  result: &Polygon,
+) -> Result<(), String> {
+  let reference_edges = reference_boolean(subject, clip, in_result);
+  let result_edges = result
+    .contours
+    .iter()
+    .flat_map(|contour| {
+      (0..contour.len())
+        .map(|index| (contour[index], contour[(index + 1) % contour.len()]))
+    })
+    .collect::<Vec<_>>();
+
+  let extent = subject
+    .contours
+    .iter()
+    .chain(clip.contours.iter())
+    .flatten()
+    .fold(0.0f32, |extent, point| extent.max(point.abs().max_element()));
+  let tolerance = 1e-4 * extent.max(1e-3);
+
+  let reference_area = reference_area(&reference_edges);
+  let result_area = result
+    .contours
+    .iter()
+    .map(|contour| contour_signed_area(contour))
+    .sum::<f32>();
+  if (reference_area - result_area).abs() > 1e-3 * extent.max(1e-3).powi(2) {
+    return Err(format!(
+      "area is {result_area}, but the reference area is {reference_area}"
+    ));
+  }
+
+  if let Some(&(start, end)) =
+    unmatched_edge(&result_edges, &reference_edges, tolerance)
+  {
+    return Err(format!(
+      "result edge ({start}, {end}) is not on the reference boundary"
+    ));
+  }
+  if let Some(&(start, end)) =
+    unmatched_edge(&reference_edges, &result_edges, tolerance)
+  {
+    return Err(format!(
+      "reference edge ({start}, {end}) is missing from the result"
+    ));
+  }
+  Ok(())
+}
+
+// Runs all four operations (and the reverse difference) between `subject` and
+// `clip`, comparing each against the reference implementation (see
+// `compare_with_reference`). Returns a description of the first mismatch (or
+// the first error returned by an operation).
+pub fn check_against_reference(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Result<(), String> {
+  type Operation = fn(&Polygon, &Polygon) -> Result<BooleanResult, ClipError>;
+  type InResult = fn(bool, bool) -> bool;
+  let operations: [(&str, Operation, InResult); 5] = [
+    ("intersection", try_intersection, |s, c| s && c),
+    ("union", try_union, |s, c| s || c),
+    ("difference", try_difference, |s, c| s && !c),
+    ("reverse difference", |s, c| try_difference(c, s), |s, c| !s && c),
+    ("XOR", try_xor, |s, c| s != c),
+  ];
+  for (name, operation, in_result) in operations {
+    let result =
+      operation(subject, clip).map_err(|error| format!("{name}: {error}"))?;
+    compare_with_reference(subject, clip, in_result, &result.polygon)
+      .map_err(|error| format!("{name}: {error}"))?;
+  }
+  Ok(())
+}
+
+// Determines whether `point` is inside `polygon` using the even-odd rule.
+fn point_in_polygon(point: Vec2, polygon: &Polygon) -> bool {
+  polygon
+    .contours
+    .iter()
+    .filter(|contour| point_in_contour(point, contour))
+    .count()
+    % 2
+    == 1
+}
+
+// Splits every edge of `subject` and `clip` at every point where it touches
+// another edge, returning each distinct piece once (coincident pieces from
+// several edges are only returned once).
+fn split_edges(subject: &Polygon, clip: &Polygon) -> Vec<(Vec2, Vec2)> {
+  let edges = subject
+    .contours
+    .iter()
+    .chain(clip.contours.iter())
+    .flat_map(|contour| {
+      (0..contour.len())
+        .map(|index| (contour[index], contour[(index + 1) % contour.len()]))
+    })
+    .filter(|(start, end)| start != end)
+    .collect::<Vec<_>>();
+
+  // The points each edge is split at. Each intersection is computed once and
+  // shared by both edges, so the pieces meet exactly.
+  let mut split_points = edges.iter().map(|_| Vec::new()).collect::<Vec<_>>();
+  for index in 0..edges.len() {
+    for other_index in index + 1..edges.len() {
+      let points = match edge_intersection(edges[index], edges[other_index]) {
+        EdgeIntersectionResult::NoIntersection => continue,
+        EdgeIntersectionResult::PointIntersection(point) => {
+          vec![snap_to_end_points(point, edges[index], edges[other_index])]
+        }
+        EdgeIntersectionResult::LineIntersection(start, end) => {
+          vec![start, end]
+        }
+      };
+      split_points[index].extend(points.iter().copied());
+      split_points[other_index].extend(points);
+    }
+  }
+
+  let mut pieces = HashMap::new();
+  for (&(start, end), points) in edges.iter().zip(split_points) {
+    let mut points = points;
+    points.push(start);
+    points.push(end);
+    let direction = end - start;
+    points.sort_by(|a, b| {
+      (*a - start).dot(direction).total_cmp(&(*b - start).dot(direction))
+    });
+    points.dedup();
+    for piece in points.windows(2) {
+      // Key pieces by their end points regardless of direction, so coincident
+      // pieces are only kept once.
+      let key = if (piece[0].x, piece[0].y) <= (piece[1].x, piece[1].y) {
+        (piece[0], piece[1])
+      } else {
+        (piece[1], piece[0])
+      };
+      pieces.insert(
+        [key.0.x, key.0.y, key.1.x, key.1.y].map(f32::to_bits),
+        (piece[0], piece[1]),
+      );
+    }
+  }
+  pieces.into_values().collect()
+}
+
+// Replaces `point` by an end point of either edge if it is (nearly) that end
+// point. An edge ending on the interior of another edge is reported as a
+// computed point, which can be off by rounding.
+fn snap_to_end_points(
+  point: Vec2,
+  edge: (Vec2, Vec2),
+  other_edge: (Vec2, Vec2),
+) -> Vec2 {
+  [edge.0, edge.1, other_edge.0, other_edge.1]
+    .into_iter()
+    .find(|end_point| {
+      point.distance(*end_point)
+        <= 1e-6 * end_point.abs().max_element().max(1.0)
+    })
+    .unwrap_or(point)
+}
+
+// Computes the distance from `point` to the segment from `segment.0` to
+// `segment.1`.
+fn distance_to_segment(point: Vec2, segment: (Vec2, Vec2)) -> f32 {
+  let vector = segment.1 - segment.0;
+  let t =
+    ((point - segment.0).dot(vector) / vector.length_squared()).clamp(0.0, 1.0);
+  point.distance(segment.0 + t * vector)
+}
+
+// Finds an edge of `edges` whose midpoint is not within `tolerance` of an edge
+// of `other_edges` going the same way.
+fn unmatched_edge<'a>(
+  edges: &'a [(Vec2, Vec2)],
+  other_edges: &[(Vec2, Vec2)],
+  tolerance: f32,
+) -> Option<&'a (Vec2, Vec2)> {
+  edges.iter().find(|&&(start, end)| {
+    let midpoint = (start + end) * 0.5;
+    !other_edges.iter().any(|&(other_start, other_end)| {
+      (end - start).dot(other_end - other_start) > 0.0
+        && distance_to_segment(midpoint, (other_start, other_end)) <= tolerance
+    })
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::{
+    intersection,
+    reference::{
+      check_against_reference, compare_with_reference, reference_area,
+      reference_boolean,
+    },
+    Polygon,
+  };
+
+  fn polygon(contours: &[&[(f32, f32)]]) -> Polygon {
+    Polygon {
+      contours: contours
+        .iter()
+        .map(|contour| contour.iter().map(|&(x, y)| Vec2::new(x, y)).collect())
+        .collect(),
+    }
+  }
+
+  fn squares() -> (Polygon, Polygon) {
+    (
+      polygon(&[&[(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)]]),
+      polygon(&[&[(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0)]]),
+    )
+  }
+
+  #[test]
+  fn computes_reference_boundary() {
+    let (subject, clip) = squares();
+    let mut edges = reference_boolean(&subject, &clip, |s, c| s && c);
+    edges.sort_by(|a, b| (a.0.x, a.0.y).partial_cmp(&(b.0.x, b.0.y)).unwrap());
+    assert_eq!(
+      edges,
+      [
+        (Vec2::new(1.0, 1.0), Vec2::new(2.0, 1.0)),
+        (Vec2::new(1.0, 2.0), Vec2::new(1.0, 1.0)),
+        (Vec2::new(2.0, 1.0), Vec2::new(2.0, 2.0)),
+        (Vec2::new(2.0, 2.0), Vec2::new(1.0, 2.0)),
+      ]
+    );
+    assert_eq!(reference_area(&edges), 1.0);
+    let union = reference_boolean(&subject, &clip, |s, c| s || c);
+    assert_eq!(reference_area(&union), 7.0);
+  }
+
+  #[test]
+  fn sweep_agrees_with_reference() {
+    let (subject, clip) = squares();
+    check_against_reference(&subject, &clip).unwrap();
+    // Shared edges, holes and touching vertices.
+    let subject = polygon(&[
+      &[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)],
+      &[(1.0, 1.0), (1.0, 3.0), (3.0, 3.0), (3.0, 1.0)],
+    ]);
+    let clip = polygon(&[&[(2.0, 0.0), (6.0, 0.0), (6.0, 3.0), (4.0, 4.0)]]);
+    check_against_reference(&subject, &clip).unwrap();
+  }
+
+  #[test]
+  fn reports_wrong_results() {
+    let (subject, clip) = squares();
+    // The subject instead of the intersection.
+    let error =
+      compare_with_reference(&subject, &clip, |s, c| s && c, &subject)
+        .unwrap_err();
+    assert_eq!(error, "area is 4, but the reference area is 1");
+    // The right area, but in the wrong place.
+    let error = compare_with_reference(
+      &subject,
+      &clip,
+      |s, c| s && c,
+      &polygon(&[&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]]),
+    )
+    .unwrap_err();
+    assert!(error.contains("is not on the reference boundary"), "{error}");
+    assert_eq!(
+      compare_with_reference(
+        &subject,
+        &clip,
+        |s, c| s && c,
+        &intersection(&subject, &clip).polygon
+      ),
+      Ok(())
+    );
+  }
+}
@@ -0,0 +1,130 @@
+use glam::Vec2;
+
+use crate::{EdgeCoincidenceType, Event, EventRelation};
+
+// How the regions of the subject and clip polygons relate to each other.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PolygonRelation {
+  // The polygons do not overlap (they may still touch along their
+  // boundaries). If either polygon is empty, the polygons are disjoint.
+  Disjoint,
+  // The clip is entirely inside the subject (and they are not identical).
+  SubjectContainsClip,
+  // The subject is entirely inside the clip (and they are not identical).
+  ClipContainsSubject,
+  // The polygons overlap, but neither contains the other.
+  PartialOverlap,
+  // The polygons cover exactly the same region.
+  Identical,
+}
+
+// Classifies the relation between the subject and clip from the
+// `processed_events` of a sweep. Edges to the right of `x_limit` were skipped
+// by the sweep, but these can only belong to the polygon that extends past
+// `x_limit` and lie outside the other polygon.
+pub(crate) fn classify_relation(
+  processed_events: &[Event],
+  event_relations: &[EventRelation],
+  subject_bounds: (Vec2, Vec2),
+  clip_bounds: (Vec2, Vec2),
+  x_limit: f32,
+) -> PolygonRelation {
+  let mut subject_inside_clip = false;
+  let mut subject_outside_clip = x_limit < subject_bounds.1.x;
+  let mut clip_inside_subject = false;
+  let mut clip_outside_subject = x_limit < clip_bounds.1.x;
+  let mut shared_boundary = false;
+  let mut opposite_boundary = false;
+
+  for event in processed_events.iter().filter(|event| event.left) {
+    let relation = &event_relations[event.event_id];
+    match relation.edge_coincidence_type {
+      EdgeCoincidenceType::NoCoincidence => {
+        match (event.is_subject, relation.other_in_out) {
+          (true, true) => subject_outside_clip = true,
+          (true, false) => subject_inside_clip = true,
+          (false, true) => clip_outside_subject = true,
+          (false, false) => clip_inside_subject = true,
+        }
+      }
+      EdgeCoincidenceType::SameTransition => shared_boundary = true,
+      EdgeCoincidenceType::DifferentTransition => opposite_boundary = true,
+      // The other edge of the coincident pair describes both edges.
+      EdgeCoincidenceType::DuplicateCoincidence => {}
+    }
+  }
+
+  if !subject_inside_clip && !clip_inside_subject && !shared_boundary {
+    PolygonRelation::Disjoint
+  } else if !subject_inside_clip
+    && !subject_outside_clip
+    && !clip_inside_subject
+    && !clip_outside_subject
+    && !opposite_boundary
+  {
+    PolygonRelation::Identical
+  } else if !subject_inside_clip && !clip_outside_subject && !opposite_boundary
+  {
+    PolygonRelation::SubjectContainsClip
+  } else if !clip_inside_subject && !subject_outside_clip && !opposite_boundary
+  {
+    PolygonRelation::ClipContainsSubject
+  } else {
+    PolygonRelation::PartialOverlap
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square_polygon;
+  use crate::{intersection_with_options, BooleanOptions, Polygon};
+
+  use super::PolygonRelation;
+
+  fn relation(subject: &Polygon, clip: &Polygon) -> Option<PolygonRelation> {
+    let options =
+      BooleanOptions { classify_relation: true, ..Default::default() };
+    intersection_with_options(subject, clip, &options).relation
+  }
+
+  #[test]
+  fn classifies_polygon_relations() {
+    let subject = square_polygon(Vec2::new(0.0, 0.0), 4.0);
+
+    assert_eq!(
+      relation(&subject, &square_polygon(Vec2::new(10.0, 10.0), 1.0)),
+      Some(PolygonRelation::Disjoint)
+    );
+    // Touching along an edge is still disjoint.
+    assert_eq!(
+      relation(&subject, &square_polygon(Vec2::new(4.0, 0.0), 4.0)),
+      Some(PolygonRelation::Disjoint)
+    );
+    assert_eq!(
+      relation(&subject, &square_polygon(Vec2::new(1.0, 1.0), 2.0)),
+      Some(PolygonRelation::SubjectContainsClip)
+    );
+    assert_eq!(
+      relation(&square_polygon(Vec2::new(1.0, 1.0), 2.0), &subject),
+      Some(PolygonRelation::ClipContainsSubject)
+    );
+    assert_eq!(
+      relation(&subject, &square_polygon(Vec2::new(2.0, 2.0), 4.0)),
+      Some(PolygonRelation::PartialOverlap)
+    );
+    assert_eq!(relation(&subject, &subject), Some(PolygonRelation::Identical));
+  }
+
+  #[test]
+  fn relation_is_only_computed_when_requested() {
+    let subject = square_polygon(Vec2::new(0.0, 0.0), 4.0);
+    let clip = square_polygon(Vec2::new(1.0, 1.0), 2.0);
+    assert_eq!(
+      intersection_with_options(&subject, &clip, &BooleanOptions::default())
+        .relation,
+      None
+    );
+  }
+}
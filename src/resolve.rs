@@ -0,0 +1,141 @@
+use crate::{
+  finish_boolean, sweep_with_precision_fallback, unwrap_boolean,
+  validate_input, BooleanOptions, BooleanResult, ClipError, Operation, Polygon,
+  Rule,
+};
+
+// Splits the self-intersecting contours of `polygon` (e.g. bow ties and
+// figure-eights) into simple contours covering the same area (using the
+// even-odd rule). The contours are split wherever they cross or overlap
+// themselves or each other, so the result can be used by consumers that require
+// simple polygons. The source edges of the result refer to `polygon` (as the
+// subject).
+pub fn resolve_self_intersections(polygon: &Polygon) -> BooleanResult {
+  unwrap_boolean(try_resolve_self_intersections_with_options(
+    polygon,
+    &BooleanOptions::default(),
+  ))
+}
+
+// Same as `resolve_self_intersections`, but with `options`. The options for
+// aligning the subject and clip (`weld_tolerance` and `boundary_tolerance`) do
+// not apply, since there is only one polygon.
+pub fn resolve_self_intersections_with_options(
+  polygon: &Polygon,
+  options: &BooleanOptions,
+) -> BooleanResult {
+  unwrap_boolean(try_resolve_self_intersections_with_options(polygon, options))
+}
+
+// Same as `resolve_self_intersections`, but returns an error instead of
+// panicking.
+pub fn try_resolve_self_intersections(
+  polygon: &Polygon,
+) -> Result<BooleanResult, ClipError> {
+  try_resolve_self_intersections_with_options(
+    polygon,
+    &BooleanOptions::default(),
+  )
+}
+
+// Same as `resolve_self_intersections_with_options`, but returns an error
+// instead of panicking.
+pub fn try_resolve_self_intersections_with_options(
+  polygon: &Polygon,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  let empty_polygon = Polygon::default();
+  validate_input(polygon, &empty_polygon)?;
+  if let Some(max_events) = options.max_events {
+    if max_events < polygon.num_edges().saturating_mul(2) {
+      return Err(ClipError::CapacityExceeded { limit: max_events });
+    }
+  }
+
+  // Boolean operations with an empty polygon are computed trivially by
+  // copying the other polygon, so the sweep is run directly instead. The union
+  // with nothing keeps exactly the area inside `polygon`.
+  let result = match polygon.compute_bounds() {
+    None => BooleanResult::default(),
+    Some(bounds) => sweep_with_precision_fallback(
+      polygon,
+      &empty_polygon,
+      bounds,
+      bounds,
+      Rule::Operation(Operation::Union),
+      options,
+    )?,
+  };
+  Ok(finish_boolean(result, polygon, &empty_polygon, options))
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::{
+    resolve_self_intersections, resolve_self_intersections_with_options,
+    BooleanOptions, Polygon, SourceEdge,
+  };
+
+  #[test]
+  fn splits_bow_tie_into_triangles() {
+    let bow_tie = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 2.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(0.0, 2.0),
+      ]],
+    };
+
+    let options =
+      BooleanOptions { track_input_equality: true, ..Default::default() };
+    let result = resolve_self_intersections_with_options(&bow_tie, &options);
+    assert_eq!(
+      result.polygon,
+      Polygon {
+        contours: vec![
+          vec![Vec2::new(0.0, 0.0), Vec2::new(1.0, 1.0), Vec2::new(0.0, 2.0)],
+          vec![Vec2::new(1.0, 1.0), Vec2::new(2.0, 0.0), Vec2::new(2.0, 2.0)],
+        ],
+      }
+    );
+    assert_eq!(
+      result.contour_source_edges,
+      [
+        [
+          SourceEdge { is_from_subject: true, contour: 0, edge: 0 },
+          SourceEdge { is_from_subject: true, contour: 0, edge: 2 },
+          SourceEdge { is_from_subject: true, contour: 0, edge: 3 },
+        ],
+        [
+          SourceEdge { is_from_subject: true, contour: 0, edge: 2 },
+          SourceEdge { is_from_subject: true, contour: 0, edge: 1 },
+          SourceEdge { is_from_subject: true, contour: 0, edge: 0 },
+        ],
+      ]
+    );
+    // The triangles cover the same area as the bow tie.
+    assert!(result.equals_subject);
+  }
+
+  #[test]
+  fn keeps_simple_polygons() {
+    let square = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(0.0, 1.0),
+      ]],
+    };
+    let options =
+      BooleanOptions { track_input_equality: true, ..Default::default() };
+    let result = resolve_self_intersections_with_options(&square, &options);
+    assert_eq!(result.polygon, square);
+    assert!(result.equals_subject);
+
+    assert!(resolve_self_intersections(&Polygon::default()).is_empty);
+  }
+}
@@ -0,0 +1,182 @@
+use crate::{
+  perform_boolean, unwrap_boolean, BooleanOptions, BooleanResult, ClipError,
+  Operation, Polygon, Rule,
+};
+
+// Describes an edge (after edges have been split at every intersection) for
+// deciding whether it is part of the result. The regions are the areas just
+// below and just above the edge (or just to the right and left of vertical
+// edges). Edges coincident with an edge of the other polygon are described
+// once, with both polygons changing across the edge.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct EdgeContext {
+  // Whether the edge comes from the subject (otherwise, the clip).
+  pub is_subject: bool,
+  // Whether the region below the edge is inside the subject.
+  pub in_subject_below: bool,
+  // Whether the region above the edge is inside the subject.
+  pub in_subject_above: bool,
+  // Whether the region below the edge is inside the clip.
+  pub in_clip_below: bool,
+  // Whether the region above the edge is inside the clip.
+  pub in_clip_above: bool,
+}
+
+// The transition of the result across one of its edges, going from below the
+// edge to above it.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InOut {
+  // The region below the edge is inside the result, and the region above is
+  // outside.
+  InsideToOutside,
+  // The region below the edge is outside the result, and the region above is
+  // inside.
+  OutsideToInside,
+}
+
+// Selects the edges of a boolean operation. The four built-in operations are
+// rules as well, but custom rules can select other overlays (e.g. the parts of
+// the clip outside the subject). The selected edges must form closed contours,
+// which is the case if an edge is selected exactly when the result contains
+// one of the regions next to it but not the other.
+pub trait BooleanRule {
+  // Whether the edge described by `edge` is part of the result.
+  fn in_result(&self, edge: &EdgeContext) -> bool;
+
+  // The transition of the result across the edge described by `edge`. This is
+  // only called for edges in the result.
+  fn result_transition(&self, edge: &EdgeContext) -> InOut;
+}
+
+impl Operation {
+  // Whether a region inside the subject (if `in_subject`) and the clip (if
+  // `in_clip`) is inside the result of the operation.
+  fn contains(self, in_subject: bool, in_clip: bool) -> bool {
+    match self {
+      Operation::Intersection => in_subject && in_clip,
+      Operation::Union => in_subject || in_clip,
+      Operation::Difference => in_subject && !in_clip,
+      Operation::XOR => in_subject != in_clip,
+    }
+  }
+}
+
+impl BooleanRule for Operation {
+  fn in_result(&self, edge: &EdgeContext) -> bool {
+    self.contains(edge.in_subject_below, edge.in_clip_below)
+      != self.contains(edge.in_subject_above, edge.in_clip_above)
+  }
+
+  fn result_transition(&self, edge: &EdgeContext) -> InOut {
+    if self.contains(edge.in_subject_above, edge.in_clip_above) {
+      InOut::OutsideToInside
+    } else {
+      InOut::InsideToOutside
+    }
+  }
+}
+
+// Computes the overlay of `subject` and `clip` with the edges selected by
+// `rule`. Unlike the built-in operations, this always sweeps over both
+// polygons entirely (the rule may keep edges anywhere).
+pub fn custom_boolean(
+  subject: &Polygon,
+  clip: &Polygon,
+  rule: &dyn BooleanRule,
+) -> BooleanResult {
+  unwrap_boolean(try_custom_boolean_with_options(
+    subject,
+    clip,
+    rule,
+    &BooleanOptions::default(),
+  ))
+}
+
+pub fn custom_boolean_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  rule: &dyn BooleanRule,
+  options: &BooleanOptions,
+) -> BooleanResult {
+  unwrap_boolean(try_custom_boolean_with_options(subject, clip, rule, options))
+}
+
+pub fn try_custom_boolean(
+  subject: &Polygon,
+  clip: &Polygon,
+  rule: &dyn BooleanRule,
+) -> Result<BooleanResult, ClipError> {
+  try_custom_boolean_with_options(
+    subject,
+    clip,
+    rule,
+    &BooleanOptions::default(),
+  )
+}
+
+pub fn try_custom_boolean_with_options(
+  subject: &Polygon,
+  clip: &Polygon,
+  rule: &dyn BooleanRule,
+  options: &BooleanOptions,
+) -> Result<BooleanResult, ClipError> {
+  perform_boolean(subject, clip, Rule::Custom(rule), options)
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::rectangle_polygon;
+  use crate::{
+    custom_boolean, difference, BooleanRule, EdgeContext, InOut, Polygon,
+  };
+
+  // The parts of the clip outside the subject (difference with the polygons
+  // swapped).
+  struct ClipMinusSubject;
+
+  impl BooleanRule for ClipMinusSubject {
+    fn in_result(&self, edge: &EdgeContext) -> bool {
+      (edge.in_clip_below && !edge.in_subject_below)
+        != (edge.in_clip_above && !edge.in_subject_above)
+    }
+
+    fn result_transition(&self, edge: &EdgeContext) -> InOut {
+      if edge.in_clip_above && !edge.in_subject_above {
+        InOut::OutsideToInside
+      } else {
+        InOut::InsideToOutside
+      }
+    }
+  }
+
+  #[test]
+  fn custom_rule_matches_equivalent_operation() {
+    let subject = rectangle_polygon(Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0));
+    let clip = Polygon {
+      contours: vec![
+        rectangle_polygon(Vec2::new(1.0, 1.0), Vec2::new(3.0, 3.0))
+          .contours
+          .remove(0),
+        // Disjoint from the subject, so a trivial difference would drop it.
+        rectangle_polygon(Vec2::new(5.0, 0.0), Vec2::new(6.0, 1.0))
+          .contours
+          .remove(0),
+      ],
+    };
+
+    let custom_result = custom_boolean(&subject, &clip, &ClipMinusSubject);
+    let expected_result = difference(&clip, &subject);
+    // The source edges refer to the swapped polygons, so only compare the
+    // shapes.
+    assert_eq!(custom_result.polygon, expected_result.polygon);
+    assert_eq!(custom_result.contour_source_edges.len(), 2);
+    assert!(custom_result
+      .contour_source_edges
+      .iter()
+      .flatten()
+      .all(|source_edge| !source_edge.is_from_subject
+        || (source_edge.edge == 1 || source_edge.edge == 2)));
+  }
+}
@@ -0,0 +1,165 @@
+use glam::Vec2;
+
+use crate::{point_relative_to_line, util::contour_signed_area, Polygon};
+
+// The result of `Polygon::sanitize`.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SanitizedPolygon {
+  // The cleaned up polygon.
+  pub polygon: Polygon,
+  // The index in `polygon` of each contour of the original polygon, or None if
+  // the contour was removed. This can be used to map `SourceEdge::contour` of
+  // results computed from `polygon` back to the original polygon.
+  pub contour_map: Vec<Option<usize>>,
+  // The index in the original contour of each vertex of each contour in
+  // `polygon`. The edge starting at vertex `i` of a contour in `polygon`
+  // covers the original edges from `vertex_map[contour][i]` up to (but not
+  // including) the next kept vertex.
+  pub vertex_map: Vec<Vec<usize>>,
+}
+
+impl Polygon {
+  // Cleans up the polygon in one pass: consecutive duplicate vertices are
+  // merged, vertices that are collinear with their neighbours (including
+  // spikes that double back on themselves) are removed, and contours with fewer
+  // than 3 remaining vertices or an area of at most `area_tolerance` are
+  // discarded (this includes empty contours). The returned maps relate the
+  // cleaned up polygon to the original one.
+  pub fn sanitize(&self, area_tolerance: f32) -> SanitizedPolygon {
+    let mut sanitized = SanitizedPolygon::default();
+    for contour in self.contours.iter() {
+      let kept_vertices = simplified_vertices(contour);
+      let new_contour =
+        kept_vertices.iter().map(|&index| contour[index]).collect::<Vec<_>>();
+      if new_contour.len() < 3
+        || contour_signed_area(&new_contour).abs() <= area_tolerance
+      {
+        sanitized.contour_map.push(None);
+        continue;
+      }
+      sanitized.contour_map.push(Some(sanitized.polygon.contours.len()));
+      sanitized.polygon.contours.push(new_contour);
+      sanitized.vertex_map.push(kept_vertices);
+    }
+    sanitized
+  }
+}
+
+// Computes the indices of the vertices of `contour` that remain after merging
+// consecutive duplicate vertices and removing vertices that are collinear with
+// their neighbours. This takes linear time: vertices are kept on a stack, and
+// collinear vertices are popped as soon as the next vertex shows they are
+// collinear.
+fn simplified_vertices(contour: &[Vec2]) -> Vec<usize> {
+  let is_collinear = |a: usize, b: usize, c: usize| {
+    point_relative_to_line(contour[a], contour[c], contour[b])
+      == std::cmp::Ordering::Equal
+  };
+
+  let mut kept = Vec::<usize>::with_capacity(contour.len());
+  for index in 0..contour.len() {
+    if kept.last().is_some_and(|&last| contour[last] == contour[index]) {
+      continue;
+    }
+    while kept.len() >= 2
+      && is_collinear(kept[kept.len() - 2], kept[kept.len() - 1], index)
+    {
+      kept.pop();
+    }
+    kept.push(index);
+  }
+
+  // The contour is closed, so vertices at the end may be duplicates of or
+  // collinear with the vertices at the start (and vice versa).
+  let mut start = 0;
+  loop {
+    let kept_len = kept.len() - start;
+    let Some(last) = kept.len().checked_sub(1) else {
+      break;
+    };
+    if (kept_len >= 2 && contour[kept[last]] == contour[kept[start]])
+      || (kept_len >= 3
+        && is_collinear(kept[last - 1], kept[last], kept[start]))
+    {
+      kept.pop();
+    } else if kept_len >= 3
+      && is_collinear(kept[last], kept[start], kept[start + 1])
+    {
+      start += 1;
+    } else {
+      break;
+    }
+  }
+  kept.drain(..start);
+  kept
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::{Polygon, SanitizedPolygon};
+
+  #[test]
+  fn sanitize_removes_duplicate_and_collinear_vertices() {
+    let polygon = Polygon {
+      contours: vec![vec![
+        Vec2::new(1.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(2.0, 2.0),
+        // A spike that doubles back on itself.
+        Vec2::new(2.0, 3.0),
+        Vec2::new(2.0, 2.0),
+        Vec2::new(0.0, 2.0),
+        Vec2::new(0.0, 0.0),
+        Vec2::new(0.5, 0.0),
+      ]],
+    };
+
+    assert_eq!(
+      polygon.sanitize(0.0),
+      SanitizedPolygon {
+        polygon: Polygon {
+          contours: vec![vec![
+            Vec2::new(2.0, 0.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(0.0, 2.0),
+            Vec2::new(0.0, 0.0),
+          ]],
+        },
+        contour_map: vec![Some(0)],
+        vertex_map: vec![vec![1, 5, 6, 7]],
+      }
+    );
+  }
+
+  #[test]
+  fn sanitize_removes_degenerate_contours() {
+    let square = vec![
+      Vec2::new(0.0, 0.0),
+      Vec2::new(1.0, 0.0),
+      Vec2::new(1.0, 1.0),
+      Vec2::new(0.0, 1.0),
+    ];
+    let polygon = Polygon {
+      contours: vec![
+        vec![],
+        square.clone(),
+        // Collapses onto a line.
+        vec![Vec2::new(5.0, 5.0), Vec2::new(6.0, 5.0), Vec2::new(7.0, 5.0)],
+        // Too small.
+        vec![Vec2::new(5.0, 5.0), Vec2::new(5.1, 5.0), Vec2::new(5.1, 5.1)],
+        square.clone(),
+      ],
+    };
+
+    let sanitized = polygon.sanitize(0.01);
+    assert_eq!(
+      sanitized.polygon,
+      Polygon { contours: vec![square.clone(), square] }
+    );
+    assert_eq!(sanitized.contour_map, [None, Some(0), None, None, Some(1)]);
+    assert_eq!(sanitized.vertex_map, [vec![0, 1, 2, 3], vec![0, 1, 2, 3]]);
+  }
+}
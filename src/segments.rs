@@ -0,0 +1,342 @@
+use std::{
+  cmp::{Ordering, Reverse},
+  collections::BinaryHeap,
+};
+
+use glam::Vec2;
+
+use crate::{
+  create_events_for_edge, create_events_for_polygon, sweep_events,
+  sweep_with_f64_retry, validate_input, Arithmetic, ClipError, Event,
+  EventRelation, Operation, Polygon, SourceEdge, SweepError,
+};
+
+// Where a piece of a segment lies relative to a polygon (see
+// `classify_segments`).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SegmentSide {
+  Inside,
+  Outside,
+  // The piece runs along an edge of the polygon.
+  OnBoundary,
+}
+
+// A piece of a segment that lies entirely on one side of a polygon's boundary.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SegmentPiece {
+  // The index of the segment the piece is from.
+  pub segment: usize,
+  // The end points of the piece, in the same direction as the segment.
+  pub start: Vec2,
+  pub end: Vec2,
+  pub side: SegmentSide,
+}
+
+// Splits each of `segments` (given as start and end points) where it crosses
+// the boundary of `polygon`, and classifies each piece as inside, outside, or
+// on the boundary of `polygon` (using the even-odd rule). All the segments are
+// handled in a single sweep (recomputed in f64 if rounding errors make it
+// inconsistent, like boolean operations), and they do not affect each other.
+// The pieces are ordered by segment, then along the segment. Zero-length
+// segments have no pieces. This panics if the segments cannot be classified
+// (see `try_classify_segments`).
+pub fn classify_segments(
+  polygon: &Polygon,
+  segments: &[(Vec2, Vec2)],
+) -> Vec<SegmentPiece> {
+  try_classify_segments(polygon, segments)
+    .unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `classify_segments`, but returns an error instead of panicking.
+// Errors about the segments refer to them as clip contours, with the start and
+// end as vertices 0 and 1.
+pub fn try_classify_segments(
+  polygon: &Polygon,
+  segments: &[(Vec2, Vec2)],
+) -> Result<Vec<SegmentPiece>, ClipError> {
+  validate_input(
+    polygon,
+    &Polygon {
+      contours: segments.iter().map(|&(start, end)| vec![start, end]).collect(),
+    },
+  )?;
+
+  let ((processed_events, event_relations), _) =
+    sweep_with_f64_retry(|arithmetic| {
+      sweep_polygon_and_segments(polygon, segments, arithmetic)
+    })
+    .map_err(|error| error.into_clip_error(usize::MAX))?;
+
+  // The split edges of the polygon, to find the pieces along them. Coincident
+  // edges are split at the same points, so their pieces match exactly.
+  let mut boundary_pieces = Vec::new();
+  let mut pieces = Vec::new();
+  for event in processed_events.iter().filter(|event| event.left) {
+    let relation = &event_relations[event.event_id];
+    let key = piece_key(event.point, relation.sibling_point);
+    // A coincident subject and clip edge can be represented by a single
+    // event, with the clip edge as its coincident source edge.
+    for source_edge in std::iter::once(relation.source_edge)
+      .chain(relation.coincident_source_edge)
+    {
+      if source_edge.is_from_subject {
+        boundary_pieces.push(key);
+      } else {
+        // Only clip events know which side of the polygon they are on, and
+        // pieces represented by subject events are on the boundary anyway.
+        let inside = !event.is_subject && !relation.other_in_out;
+        pieces.push((source_edge.contour, key, inside));
+      }
+    }
+  }
+  boundary_pieces.sort_unstable();
+  // Coincident edges can report the same piece twice.
+  pieces.sort_unstable_by_key(|&(segment, key, _)| (segment, key));
+  pieces.dedup_by_key(|&mut (segment, key, _)| (segment, key));
+
+  let mut classified = pieces
+    .into_iter()
+    .map(|(segment, key, inside)| {
+      let (left, right) = key_points(key);
+      // The pieces of a segment all go from left to right in sweep order, so
+      // they are reversed exactly when the whole segment is (rather than
+      // checking each piece, which can be tiny and point anywhere).
+      let (start, end) = if is_reversed(segments[segment]) {
+        (right, left)
+      } else {
+        (left, right)
+      };
+      let side = if boundary_pieces.binary_search(&key).is_ok() {
+        SegmentSide::OnBoundary
+      } else if inside {
+        SegmentSide::Inside
+      } else {
+        SegmentSide::Outside
+      };
+      SegmentPiece { segment, start, end, side }
+    })
+    .collect::<Vec<_>>();
+  classified.sort_by(|a, b| {
+    let order = sweep_order(a.start, b.start);
+    a.segment.cmp(&b.segment).then(if is_reversed(segments[a.segment]) {
+      order.reverse()
+    } else {
+      order
+    })
+  });
+
+  // Other segments split each other as well, so merge consecutive pieces on
+  // the same side.
+  let mut merged: Vec<SegmentPiece> = Vec::with_capacity(classified.len());
+  for piece in classified {
+    match merged.last_mut() {
+      Some(last)
+        if last.segment == piece.segment
+          && last.side == piece.side
+          && last.end == piece.start =>
+      {
+        last.end = piece.end;
+      }
+      _ => merged.push(piece),
+    }
+  }
+  Ok(merged)
+}
+
+// Sweeps over the edges of `polygon` (as the subject) and `segments` (as the
+// clip, with one contour per segment) with `arithmetic`, splitting them
+// wherever they intersect. Returns the processed events along with their
+// relations.
+pub(crate) fn sweep_polygon_and_segments(
+  polygon: &Polygon,
+  segments: &[(Vec2, Vec2)],
+  arithmetic: Arithmetic,
+) -> Result<(Vec<Event>, Vec<EventRelation>), SweepError> {
+  let mut event_queue = BinaryHeap::new();
+  let mut event_relations = Vec::new();
+  create_events_for_polygon(
+    polygon,
+    /* is_subject= */ true,
+    arithmetic,
+    &mut event_queue,
+    &mut event_relations,
+    f32::INFINITY,
+  );
+  for (segment, &(start, end)) in segments.iter().enumerate() {
+    let source_edge =
+      SourceEdge { is_from_subject: false, contour: segment, edge: 0 };
+    if let Some(events) = create_events_for_edge(
+      start,
+      end,
+      source_edge,
+      arithmetic,
+      &mut event_relations,
+    ) {
+      event_queue.extend(events.map(Reverse));
+    }
+  }
+  // XOR does not skip any events.
+  let processed_events = sweep_events(
+    event_queue,
+    &mut event_relations,
+    &Operation::XOR,
+    f32::INFINITY,
+    /* max_events= */ usize::MAX,
+  )?;
+  Ok((processed_events, event_relations))
+}
+
+// The order in which the sweep visits `a` and `b`.
+fn sweep_order(a: Vec2, b: Vec2) -> Ordering {
+  a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y))
+}
+
+// Whether `segment` goes from right to left in sweep order.
+fn is_reversed((start, end): (Vec2, Vec2)) -> bool {
+  sweep_order(start, end) == Ordering::Greater
+}
+
+// A key identifying the piece from `left` to `right` exactly.
+fn piece_key(left: Vec2, right: Vec2) -> [u32; 4] {
+  [left.x, left.y, right.x, right.y].map(f32::to_bits)
+}
+
+// The points of the piece identified by `key`.
+fn key_points(key: [u32; 4]) -> (Vec2, Vec2) {
+  let [left_x, left_y, right_x, right_y] = key.map(f32::from_bits);
+  (Vec2::new(left_x, left_y), Vec2::new(right_x, right_y))
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use super::sweep_polygon_and_segments;
+  use crate::tests::fixtures::heptagon;
+  use crate::{
+    classify_segments, try_classify_segments, Arithmetic, ClipError, Polygon,
+    SegmentPiece, SegmentSide, SweepError,
+  };
+
+  // A 4x4 square with a 2x2 hole in the middle.
+  fn square_with_hole() -> Polygon {
+    Polygon {
+      contours: vec![
+        vec![
+          Vec2::new(0.0, 0.0),
+          Vec2::new(4.0, 0.0),
+          Vec2::new(4.0, 4.0),
+          Vec2::new(0.0, 4.0),
+        ],
+        vec![
+          Vec2::new(1.0, 1.0),
+          Vec2::new(1.0, 3.0),
+          Vec2::new(3.0, 3.0),
+          Vec2::new(3.0, 1.0),
+        ],
+      ],
+    }
+  }
+
+  #[test]
+  fn classifies_crossing_segments() {
+    let segments = [
+      // Crosses the whole polygon from right to left, through the hole.
+      (Vec2::new(5.0, 2.0), Vec2::new(-1.0, 2.0)),
+      // Crosses the first segment, which does not split it.
+      (Vec2::new(0.5, -1.0), Vec2::new(0.5, 5.0)),
+    ];
+    let piece =
+      |segment, start: (f32, f32), end: (f32, f32), side| SegmentPiece {
+        segment,
+        start: Vec2::new(start.0, start.1),
+        end: Vec2::new(end.0, end.1),
+        side,
+      };
+    assert_eq!(
+      classify_segments(&square_with_hole(), &segments),
+      [
+        piece(0, (5.0, 2.0), (4.0, 2.0), SegmentSide::Outside),
+        piece(0, (4.0, 2.0), (3.0, 2.0), SegmentSide::Inside),
+        piece(0, (3.0, 2.0), (1.0, 2.0), SegmentSide::Outside),
+        piece(0, (1.0, 2.0), (0.0, 2.0), SegmentSide::Inside),
+        piece(0, (0.0, 2.0), (-1.0, 2.0), SegmentSide::Outside),
+        piece(1, (0.5, -1.0), (0.5, 0.0), SegmentSide::Outside),
+        piece(1, (0.5, 0.0), (0.5, 4.0), SegmentSide::Inside),
+        piece(1, (0.5, 4.0), (0.5, 5.0), SegmentSide::Outside),
+      ]
+    );
+  }
+
+  #[test]
+  fn classifies_segments_along_the_boundary() {
+    let segments = [
+      // Runs along the bottom edge and past it.
+      (Vec2::new(2.0, 0.0), Vec2::new(6.0, 0.0)),
+      // Runs along the left edge of the hole, starting inside the polygon.
+      (Vec2::new(1.0, 0.5), Vec2::new(1.0, 2.0)),
+      // A zero-length segment has no pieces.
+      (Vec2::new(2.0, 2.0), Vec2::new(2.0, 2.0)),
+    ];
+    assert_eq!(
+      classify_segments(&square_with_hole(), &segments),
+      [
+        SegmentPiece {
+          segment: 0,
+          start: Vec2::new(2.0, 0.0),
+          end: Vec2::new(4.0, 0.0),
+          side: SegmentSide::OnBoundary,
+        },
+        SegmentPiece {
+          segment: 0,
+          start: Vec2::new(4.0, 0.0),
+          end: Vec2::new(6.0, 0.0),
+          side: SegmentSide::Outside,
+        },
+        SegmentPiece {
+          segment: 1,
+          start: Vec2::new(1.0, 0.5),
+          end: Vec2::new(1.0, 1.0),
+          side: SegmentSide::Inside,
+        },
+        SegmentPiece {
+          segment: 1,
+          start: Vec2::new(1.0, 1.0),
+          end: Vec2::new(1.0, 2.0),
+          side: SegmentSide::OnBoundary,
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn reports_invalid_segments_as_clip_contours() {
+    assert_eq!(
+      try_classify_segments(
+        &square_with_hole(),
+        &[(Vec2::ZERO, Vec2::ONE), (Vec2::new(1.0, f32::NAN), Vec2::ONE)]
+      ),
+      Err(ClipError::InvalidInput {
+        is_from_subject: false,
+        contour: 1,
+        vertex: 0
+      })
+    );
+  }
+
+  #[test]
+  fn classifies_segments_needing_f64() {
+    let polygon = heptagon();
+    let segments = [(Vec2::new(14.0, 3.0), Vec2::new(16.0, 3.0))];
+    assert!(matches!(
+      sweep_polygon_and_segments(&polygon, &segments, Arithmetic::F32),
+      Err(SweepError::Inconsistent { .. })
+    ));
+    let pieces = try_classify_segments(&polygon, &segments).unwrap();
+    assert_eq!(
+      pieces.iter().map(|piece| piece.side).collect::<Vec<_>>(),
+      [SegmentSide::Outside, SegmentSide::Inside]
+    );
+  }
+}
@@ -0,0 +1,413 @@
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use crate::{BooleanResult, Polygon};
+
+impl Polygon {
+  // Snaps every vertex to a uniform grid with cells of size `cell_size` and
+  // removes the edges that collapse as a result. Contours that collapse to
+  // fewer than 3 vertices are removed entirely. This is intended as a
+  // robustness pre-pass before boolean operations: distinct vertices in the
+  // output are never closer than `cell_size` to each other. This panics if
+  // `cell_size` is not positive and finite, since every vertex would be
+  // snapped to NaN.
+  pub fn quantize(&self, cell_size: f32) -> Polygon {
+    assert!(
+      cell_size > 0.0 && cell_size.is_finite(),
+      "quantize requires a positive and finite cell size, but got {cell_size}"
+    );
+    Polygon {
+      contours: self
+        .contours
+        .iter()
+        .map(|contour| {
+          let contour = contour
+            .iter()
+            .map(|&point| (point / cell_size).round() * cell_size)
+            .collect::<Vec<_>>();
+          non_degenerate_vertices(&contour)
+            .into_iter()
+            .map(|index| contour[index])
+            .collect::<Vec<_>>()
+        })
+        .filter(|contour| contour.len() >= 3)
+        .collect(),
+    }
+  }
+
+  // Merges vertices that are within `tolerance` of each other (across all
+  // contours) and removes the edges that collapse as a result. Contours that
+  // collapse to fewer than 3 vertices are removed entirely. Unlike `quantize`,
+  // vertices are only moved onto other vertices, not onto a grid.
+  pub fn weld_vertices(&self, tolerance: f32) -> Polygon {
+    let mut snapper = VertexSnapper::new(tolerance);
+    Polygon {
+      contours: self
+        .contours
+        .iter()
+        .map(|contour| {
+          let contour = contour
+            .iter()
+            .map(|&point| snapper.snap(point))
+            .collect::<Vec<_>>();
+          non_degenerate_vertices(&contour)
+            .into_iter()
+            .map(|index| contour[index])
+            .collect::<Vec<_>>()
+        })
+        .filter(|contour| contour.len() >= 3)
+        .collect(),
+    }
+  }
+}
+
+// Merges vertices of both `subject` and `clip` that are within `tolerance` of
+// each other, so nearly shared boundaries become exactly shared. Unlike
+// `Polygon::weld_vertices`, collapsed edges are kept (the sweep ignores them)
+// so that edge indices still refer to the edges of the original polygons.
+pub(crate) fn weld_polygons(
+  subject: &Polygon,
+  clip: &Polygon,
+  tolerance: f32,
+) -> (Polygon, Polygon) {
+  let mut snapper = VertexSnapper::new(tolerance);
+  let mut weld = |polygon: &Polygon| Polygon {
+    contours: polygon
+      .contours
+      .iter()
+      .map(|contour| contour.iter().map(|&point| snapper.snap(point)).collect())
+      .collect(),
+  };
+  let subject = weld(subject);
+  let clip = weld(clip);
+  (subject, clip)
+}
+
+impl BooleanResult {
+  // Merges vertices of the result polygon that are within `tolerance` of each
+  // other and removes the degenerate edges this creates. Subdividing edges can
+  // introduce vertices that are nearly (but not exactly) the same, which many
+  // downstream consumers (e.g. triangulators) do not handle well. Vertices are
+  // merged across all contours, so nearly shared vertices become exactly
+  // shared. Contours that collapse to fewer than 3 vertices are removed
+  // (along with their source edges).
+  pub fn snap_round(&mut self, tolerance: f32) {
+    let mut snapper = VertexSnapper::new(tolerance);
+    for contour in self.polygon.contours.iter_mut() {
+      for point in contour.iter_mut() {
+        let snapped = snapper.snap(*point);
+        if snapped != *point {
+          self.equals_subject = false;
+          self.equals_clip = false;
+        }
+        *point = snapped;
+      }
+    }
+
+    for contour_index in 0..self.polygon.contours.len() {
+      let kept_vertices =
+        non_degenerate_vertices(&self.polygon.contours[contour_index]);
+      self.retain_contour_vertices(contour_index, &kept_vertices);
+    }
+    self.update_bounds();
+
+    let contours = &self.polygon.contours;
+    let keep =
+      contours.iter().map(|contour| contour.len() >= 3).collect::<Vec<_>>();
+    self.retain_contours_by_index(|contour_index| keep[contour_index]);
+  }
+}
+
+// Merges points that are within a tolerance of an already snapped point. The
+// first point seen in an area becomes the representative for all later points
+// near it.
+pub(crate) struct VertexSnapper {
+  // The distance within which points are merged.
+  tolerance: f32,
+  // The representative points, bucketed into cells of size `tolerance`.
+  cells: HashMap<(i64, i64), Vec<Vec2>>,
+}
+
+impl VertexSnapper {
+  pub(crate) fn new(tolerance: f32) -> Self {
+    Self { tolerance, cells: HashMap::new() }
+  }
+
+  // Computes the cell that `point` belongs to.
+  fn cell(&self, point: Vec2) -> (i64, i64) {
+    let cell = (point / self.tolerance).floor();
+    (cell.x as i64, cell.y as i64)
+  }
+
+  // Returns the representative point for `point`. If there is no
+  // representative within the tolerance, `point` becomes a new representative.
+  pub(crate) fn snap(&mut self, point: Vec2) -> Vec2 {
+    if self.tolerance <= 0.0 {
+      return point;
+    }
+    let (cell_x, cell_y) = self.cell(point);
+    let tolerance_squared = self.tolerance * self.tolerance;
+    // Any representative within the tolerance must be in a neighbouring cell.
+    for x in cell_x - 1..=cell_x + 1 {
+      for y in cell_y - 1..=cell_y + 1 {
+        let Some(representatives) = self.cells.get(&(x, y)) else {
+          continue;
+        };
+        if let Some(&representative) =
+          representatives.iter().find(|representative| {
+            representative.distance_squared(point) <= tolerance_squared
+          })
+        {
+          return representative;
+        }
+      }
+    }
+    self.cells.entry((cell_x, cell_y)).or_default().push(point);
+    point
+  }
+}
+
+// Computes the indices of vertices in `contour` that start a non-degenerate
+// edge (the edge from the vertex to the next vertex has non-zero length). The
+// edge starting at each of these vertices is the same edge as in the original
+// contour, so source edges can be filtered with the same indices.
+pub(crate) fn non_degenerate_vertices(contour: &[Vec2]) -> Vec<usize> {
+  (0..contour.len())
+    .filter(|&index| contour[index] != contour[(index + 1) % contour.len()])
+    .collect()
+}
+
+// Computes the indices of vertices in `contour` that are farther than
+// `tolerance` from the next vertex. As with `non_degenerate_vertices`, the edge
+// starting at each of these vertices keeps its per-edge data, and now extends
+// to the next kept vertex.
+pub(crate) fn vertices_apart_from_next(
+  contour: &[Vec2],
+  tolerance: f32,
+) -> Vec<usize> {
+  (0..contour.len())
+    .filter(|&index| {
+      contour[index].distance_squared(contour[(index + 1) % contour.len()])
+        > tolerance * tolerance
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::{
+    union, union_with_options, BooleanOptions, BooleanResult, Polygon,
+    SourceEdge,
+  };
+
+  #[test]
+  fn quantize_snaps_to_grid() {
+    let polygon = Polygon {
+      contours: vec![
+        vec![
+          Vec2::new(0.1, 0.2),
+          Vec2::new(1.9, -0.1),
+          Vec2::new(2.2, 0.4),
+          Vec2::new(2.1, 1.8),
+          Vec2::new(-0.3, 2.4),
+        ],
+        // This contour collapses into a single point.
+        vec![Vec2::new(5.1, 5.1), Vec2::new(5.2, 5.1), Vec2::new(5.2, 5.2)],
+      ],
+    };
+
+    assert_eq!(
+      polygon.quantize(1.0),
+      Polygon {
+        contours: vec![vec![
+          Vec2::new(0.0, 0.0),
+          Vec2::new(2.0, 0.0),
+          Vec2::new(2.0, 2.0),
+          Vec2::new(0.0, 2.0),
+        ]]
+      }
+    );
+  }
+
+  #[test]
+  #[should_panic(
+    expected = "quantize requires a positive and finite cell size, but got 0"
+  )]
+  fn quantize_rejects_zero_cell_size() {
+    Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(1.0, 1.0),
+      ]],
+    }
+    .quantize(0.0);
+  }
+
+  #[test]
+  fn weld_vertices_merges_nearby_vertices() {
+    let polygon = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(2.001, 0.001),
+        Vec2::new(2.0, 2.0),
+        Vec2::new(0.0, 2.0),
+      ]],
+    };
+
+    assert_eq!(
+      polygon.weld_vertices(0.01),
+      Polygon {
+        contours: vec![vec![
+          Vec2::new(0.0, 0.0),
+          Vec2::new(2.0, 0.0),
+          Vec2::new(2.0, 2.0),
+          Vec2::new(0.0, 2.0),
+        ]]
+      }
+    );
+  }
+
+  #[test]
+  fn weld_tolerance_shares_nearby_boundaries() {
+    let subject = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(2.0, 2.0),
+        Vec2::new(0.0, 2.0),
+      ]],
+    };
+    let clip = Polygon {
+      contours: vec![vec![
+        Vec2::new(2.0005, 0.0),
+        Vec2::new(4.0, 0.0),
+        Vec2::new(4.0, 2.0),
+        Vec2::new(2.0005, 2.0),
+      ]],
+    };
+
+    // Without welding, there is a sliver between the polygons.
+    assert_eq!(union(&subject, &clip).polygon.contours.len(), 2);
+
+    let options = BooleanOptions { weld_tolerance: 0.01, ..Default::default() };
+    let result = union_with_options(&subject, &clip, &options);
+    assert_eq!(result.polygon.contours.len(), 1);
+    assert_eq!(result.polygon.contours[0].len(), 6);
+  }
+
+  #[test]
+  fn snap_round_merges_nearby_vertices() {
+    let source_edge =
+      |edge| SourceEdge { is_from_subject: true, contour: 0, edge };
+    let mut result = BooleanResult {
+      polygon: Polygon {
+        contours: vec![
+          vec![
+            Vec2::new(1.0, 1.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(2.001, 1.0),
+            Vec2::new(2.0, 2.0),
+            Vec2::new(1.0, 2.0),
+          ],
+          vec![
+            Vec2::new(2.0005, 1.0),
+            Vec2::new(3.0, 1.0),
+            Vec2::new(3.0, 2.0),
+            Vec2::new(2.0, 2.0001),
+          ],
+        ],
+      },
+      contour_source_edges: vec![
+        (0..5).map(source_edge).collect(),
+        (0..4).map(source_edge).collect(),
+      ],
+      ..Default::default()
+    };
+
+    result.snap_round(0.01);
+
+    assert_eq!(
+      result,
+      BooleanResult {
+        polygon: Polygon {
+          contours: vec![
+            vec![
+              Vec2::new(1.0, 1.0),
+              Vec2::new(2.0, 1.0),
+              Vec2::new(2.0, 2.0),
+              Vec2::new(1.0, 2.0),
+            ],
+            vec![
+              Vec2::new(2.0, 1.0),
+              Vec2::new(3.0, 1.0),
+              Vec2::new(3.0, 2.0),
+              Vec2::new(2.0, 2.0),
+            ],
+          ],
+        },
+        contour_source_edges: vec![
+          vec![source_edge(0), source_edge(2), source_edge(3), source_edge(4)],
+          (0..4).map(source_edge).collect(),
+        ],
+        contour_bounds: vec![
+          Some((Vec2::new(1.0, 1.0), Vec2::new(2.0, 2.0))),
+          Some((Vec2::new(2.0, 1.0), Vec2::new(3.0, 2.0))),
+        ],
+        bounds: Some((Vec2::new(1.0, 1.0), Vec2::new(3.0, 2.0))),
+        ..Default::default()
+      }
+    );
+  }
+
+  #[test]
+  fn snap_round_removes_collapsed_contours() {
+    let mut result = BooleanResult {
+      polygon: Polygon {
+        contours: vec![
+          vec![
+            Vec2::new(1.0, 1.0),
+            Vec2::new(1.001, 1.0),
+            Vec2::new(1.0, 1.001),
+          ],
+          vec![Vec2::new(5.0, 5.0), Vec2::new(6.0, 5.0), Vec2::new(6.0, 6.0)],
+        ],
+      },
+      contour_source_edges: vec![
+        vec![SourceEdge { is_from_subject: true, contour: 0, edge: 0 }; 3],
+        vec![SourceEdge { is_from_subject: false, contour: 0, edge: 0 }; 3],
+      ],
+      ..Default::default()
+    };
+
+    result.snap_round(0.01);
+
+    assert_eq!(
+      result,
+      BooleanResult {
+        polygon: Polygon {
+          contours: vec![vec![
+            Vec2::new(5.0, 5.0),
+            Vec2::new(6.0, 5.0),
+            Vec2::new(6.0, 6.0)
+          ]],
+        },
+        contour_source_edges: vec![vec![
+          SourceEdge {
+            is_from_subject: false,
+            contour: 0,
+            edge: 0
+          };
+          3
+        ]],
+        contour_bounds: vec![Some((Vec2::new(5.0, 5.0), Vec2::new(6.0, 6.0)))],
+        bounds: Some((Vec2::new(5.0, 5.0), Vec2::new(6.0, 6.0))),
+        ..Default::default()
+      }
+    );
+  }
+}
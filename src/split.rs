@@ -0,0 +1,174 @@
+use glam::Vec2;
+
+use crate::{
+  extend_bounds, try_boolean_all_with_options, BooleanOptions, BooleanResult,
+  ClipError, Polygon,
+};
+
+// Splits `polygon` by the line through `point` along `direction`, returning the
+// pieces to the left and to the right of the line (looking along
+// `direction`). Both pieces come from the same sweep, so they share the points
+// where the line cuts the polygon exactly. The edges along the line have
+// clip source edges, which are always edge 0 of contour 0. This panics if the
+// split cannot be computed (see `try_split_by_line`).
+pub fn split_by_line(
+  polygon: &Polygon,
+  point: Vec2,
+  direction: Vec2,
+) -> (BooleanResult, BooleanResult) {
+  try_split_by_line(polygon, point, direction)
+    .unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `split_by_line`, but with `options`.
+pub fn split_by_line_with_options(
+  polygon: &Polygon,
+  point: Vec2,
+  direction: Vec2,
+  options: &BooleanOptions,
+) -> (BooleanResult, BooleanResult) {
+  try_split_by_line_with_options(polygon, point, direction, options)
+    .unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `split_by_line`, but returns an error instead of panicking. A zero or
+// non-finite `direction` (or `point`) is reported as invalid clip input.
+pub fn try_split_by_line(
+  polygon: &Polygon,
+  point: Vec2,
+  direction: Vec2,
+) -> Result<(BooleanResult, BooleanResult), ClipError> {
+  try_split_by_line_with_options(
+    polygon,
+    point,
+    direction,
+    &BooleanOptions::default(),
+  )
+}
+
+// Same as `split_by_line_with_options`, but returns an error instead of
+// panicking.
+pub fn try_split_by_line_with_options(
+  polygon: &Polygon,
+  point: Vec2,
+  direction: Vec2,
+  options: &BooleanOptions,
+) -> Result<(BooleanResult, BooleanResult), ClipError> {
+  let half_plane = left_half_plane(polygon, point, direction);
+  let results = try_boolean_all_with_options(polygon, &half_plane, options)?;
+  Ok((results.intersection, results.difference))
+}
+
+// Creates a rectangle covering the part of the bounds of `polygon` to the left
+// of the line through `point` along `direction`, with its first edge along the
+// line. The rectangle extends past the bounds, so its other edges never cut the
+// polygon.
+fn left_half_plane(polygon: &Polygon, point: Vec2, direction: Vec2) -> Polygon {
+  let direction = direction.normalize();
+  let normal = direction.perp();
+  let (min, max) = extend_bounds(polygon.compute_bounds(), point).unwrap();
+  // The extent of the bounds along and away from the line, relative to
+  // `point`.
+  let corners = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)]
+    .map(|corner| corner - point);
+  let along = corners.map(|corner| corner.dot(direction));
+  let away = corners.map(|corner| corner.dot(normal));
+  let along_min = along.into_iter().fold(f32::INFINITY, f32::min);
+  let along_max = along.into_iter().fold(f32::NEG_INFINITY, f32::max);
+  let away_max = away.into_iter().fold(0.0, f32::max);
+  let margin = (max - min).max_element().max(1.0);
+
+  let start = point + direction * (along_min - margin);
+  let end = point + direction * (along_max + margin);
+  let offset = normal * (away_max + margin);
+  Polygon { contours: vec![vec![start, end, end + offset, start + offset]] }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::{
+    split_by_line, try_split_by_line, util::contour_signed_area, ClipError,
+    Polygon, SourceEdge,
+  };
+
+  // The total area of `polygon`, assuming holes are oriented opposite to
+  // shells.
+  fn area(polygon: &Polygon) -> f32 {
+    polygon.contours.iter().map(|contour| contour_signed_area(contour)).sum()
+  }
+
+  #[test]
+  fn splits_square_with_hole() {
+    let polygon = Polygon {
+      contours: vec![
+        vec![
+          Vec2::new(0.0, 0.0),
+          Vec2::new(4.0, 0.0),
+          Vec2::new(4.0, 4.0),
+          Vec2::new(0.0, 4.0),
+        ],
+        vec![
+          Vec2::new(1.0, 1.0),
+          Vec2::new(1.0, 3.0),
+          Vec2::new(3.0, 3.0),
+          Vec2::new(3.0, 1.0),
+        ],
+      ],
+    };
+
+    // A vertical line through the hole, looking up, so the left piece is at
+    // smaller x.
+    let (left, right) =
+      split_by_line(&polygon, Vec2::new(1.5, 2.0), Vec2::new(0.0, 1.0));
+    assert_eq!(area(&left.polygon), 1.5 * 4.0 - 0.5 * 2.0);
+    assert_eq!(area(&right.polygon), 2.5 * 4.0 - 1.5 * 2.0);
+    assert!(left.bounds.unwrap().1.x <= 1.5);
+    assert!(right.bounds.unwrap().0.x >= 1.5);
+    // The cut edges come from the line.
+    let line_edge = SourceEdge { is_from_subject: false, contour: 0, edge: 0 };
+    assert!(left
+      .contour_source_edges
+      .iter()
+      .flatten()
+      .any(|&edge| edge == line_edge));
+    assert!(right
+      .contour_source_edges
+      .iter()
+      .flatten()
+      .any(|&edge| edge == line_edge));
+  }
+
+  #[test]
+  fn keeps_polygon_on_one_side() {
+    let polygon = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(1.0, 2.0),
+      ]],
+    };
+    // A diagonal line below the triangle, going right, so the triangle is on
+    // the left.
+    let (left, right) =
+      split_by_line(&polygon, Vec2::new(0.0, -5.0), Vec2::new(1.0, 0.5));
+    assert_eq!(area(&left.polygon), 2.0);
+    assert!(right.is_empty);
+  }
+
+  #[test]
+  fn rejects_zero_direction() {
+    let polygon = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(1.0, 2.0),
+      ]],
+    };
+    assert!(matches!(
+      try_split_by_line(&polygon, Vec2::ZERO, Vec2::ZERO),
+      Err(ClipError::InvalidInput { is_from_subject: false, .. })
+    ));
+  }
+}
@@ -0,0 +1,97 @@
+// Invariant checks enabled by the `strict-checks` feature. These panic as soon
+// as an invariant is broken, rather than letting the sweep continue into a
+// confusing failure later on (or a wrong result). They are slow (most are
+// linear in the size of the sweep and run after every event), so they are only
+// meant for debugging and fuzzing. Note that rounding errors can legitimately
+// break the sweep line order for numerically degenerate inputs, which normal
+// builds recover from by recomputing the sweep in f64.
+
+use crate::{BooleanResult, EventRelation, SweepLineEvent};
+
+// Checks that every edge in `sweep_line` is strictly below the next one.
+pub(crate) fn check_sweep_line_order(sweep_line: &[SweepLineEvent]) {
+  for (index, pair) in sweep_line.windows(2).enumerate() {
+    assert!(
+      pair[0] < pair[1],
+      "sweep line is out of order at position {index}: {:?} is not below \
+       {:?}",
+      pair[0].0,
+      pair[1].0
+    );
+  }
+}
+
+// Checks that every event is the sibling of its sibling.
+pub(crate) fn check_siblings(event_relations: &[EventRelation]) {
+  for (event_id, event_relation) in event_relations.iter().enumerate() {
+    let sibling_id = event_relation.sibling_id;
+    assert!(
+      sibling_id < event_relations.len(),
+      "event {event_id} has sibling {sibling_id}, which does not exist"
+    );
+    assert_eq!(
+      event_relations[sibling_id].sibling_id, event_id,
+      "event {event_id} has sibling {sibling_id}, whose sibling is not \
+       event {event_id}"
+    );
+  }
+}
+
+// Checks that the contours of a swept `result` are closed loops with no
+// repeated consecutive vertices (including the last and first vertex).
+pub(crate) fn check_contours(result: &BooleanResult) {
+  for (index, contour) in result.polygon.contours.iter().enumerate() {
+    assert!(
+      contour.len() >= 3,
+      "contour {index} has only {} vertices: {contour:?}",
+      contour.len()
+    );
+    assert_eq!(
+      result.contour_source_edges[index].len(),
+      contour.len(),
+      "contour {index} has a different number of source edges than vertices"
+    );
+    for vertex in 0..contour.len() {
+      let next_vertex = (vertex + 1) % contour.len();
+      assert_ne!(
+        contour[vertex], contour[next_vertex],
+        "contour {index} repeats vertex {vertex} at vertex {next_vertex}: \
+         {contour:?}"
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::{BooleanResult, EventRelation, Polygon, SourceEdge};
+
+  use super::{check_contours, check_siblings};
+
+  #[test]
+  #[should_panic(expected = "contour 0 repeats vertex 3 at vertex 0")]
+  fn rejects_repeated_vertices() {
+    let contour = vec![
+      Vec2::new(0.0, 0.0),
+      Vec2::new(1.0, 0.0),
+      Vec2::new(1.0, 1.0),
+      Vec2::new(0.0, 0.0),
+    ];
+    check_contours(&BooleanResult {
+      contour_source_edges: vec![vec![SourceEdge::default(); 4]],
+      polygon: Polygon { contours: vec![contour] },
+      ..Default::default()
+    });
+  }
+
+  #[test]
+  #[should_panic(expected = "event 0 has sibling 1, whose sibling is not")]
+  fn rejects_one_sided_siblings() {
+    check_siblings(&[
+      EventRelation { sibling_id: 1, ..Default::default() },
+      EventRelation { sibling_id: 1, ..Default::default() },
+    ]);
+  }
+}
@@ -1,3 +1,5 @@
+pub(crate) mod fixtures;
+
 use std::{
   cmp::Reverse,
   collections::BinaryHeap,
@@ -5,12 +7,22 @@ use std::{
 };
 
 use glam::Vec2;
-use rand::seq::SliceRandom;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
 
+use crate::tests::fixtures::{heptagon, rectangle_polygon, square};
+use crate::util::contour_signed_area;
 use crate::{
-  check_for_intersection, create_events_for_polygon, difference, intersection,
-  split_edge, union, xor, BooleanResult, EdgeCoincidenceType, Event,
-  EventRelation, Operation, Polygon, SourceEdge,
+  boolean, boolean_with_options, check_for_intersection,
+  create_events_for_edge, create_events_for_polygon, difference,
+  difference_with_options, intersection, intersection_with_options,
+  join_contours, split_edge, sweep_boolean, sweep_events, try_boolean,
+  try_difference_with_options, try_intersection,
+  try_resolve_self_intersections, try_union, try_xor, try_xor_with_options,
+  union, union_with_options, validate_input, xor, xor_with_options, Arithmetic,
+  BooleanOptions, BooleanResult, ClipError, ClipWarning, EdgeCoincidenceType,
+  Event, EventRelation, FillRule, Operation, OutputOrientation, Polygon,
+  PolygonRelation, Rule, SourceEdge, SweepError, SweepLineEvent, SweepStage,
+  TrackingLevel,
 };
 
 #[test]
@@ -23,6 +35,7 @@ fn split_edge_events_ordered_correctly() {
       left: true,
       is_subject: false,
       other_point: Vec2::new(5.0, 2.0),
+      arithmetic: Arithmetic::F32,
     },
     Event {
       event_id: 90,
@@ -30,6 +43,7 @@ fn split_edge_events_ordered_correctly() {
       left: true,
       is_subject: true,
       other_point: Vec2::new(5.0, 3.0),
+      arithmetic: Arithmetic::F32,
     },
     // Edge intersection events.
     Event {
@@ -38,6 +52,7 @@ fn split_edge_events_ordered_correctly() {
       left: false,
       is_subject: true,
       other_point: Vec2::new(3.5, 1.0),
+      arithmetic: Arithmetic::F32,
     },
     Event {
       event_id: 93,
@@ -45,6 +60,7 @@ fn split_edge_events_ordered_correctly() {
       left: false,
       is_subject: false,
       other_point: Vec2::new(3.0, 2.0),
+      arithmetic: Arithmetic::F32,
     },
     Event {
       event_id: 105,
@@ -52,6 +68,7 @@ fn split_edge_events_ordered_correctly() {
       left: true,
       is_subject: false,
       other_point: Vec2::new(5.0, 2.0),
+      arithmetic: Arithmetic::F32,
     },
     Event {
       event_id: 101,
@@ -59,6 +76,7 @@ fn split_edge_events_ordered_correctly() {
       left: true,
       is_subject: true,
       other_point: Vec2::new(5.0, 3.0),
+      arithmetic: Arithmetic::F32,
     },
     // Edge end events.
     Event {
@@ -67,6 +85,7 @@ fn split_edge_events_ordered_correctly() {
       left: false,
       is_subject: false,
       other_point: Vec2::new(3.0, 2.0),
+      arithmetic: Arithmetic::F32,
     },
     Event {
       event_id: 89,
@@ -74,6 +93,7 @@ fn split_edge_events_ordered_correctly() {
       left: false,
       is_subject: true,
       other_point: Vec2::new(3.5, 1.0),
+      arithmetic: Arithmetic::F32,
     },
   ];
 
@@ -127,6 +147,46 @@ fn split_edge_events_ordered_correctly() {
   assert_eq!(sorted_events, expected_events);
 }
 
+#[test]
+fn collinear_events_ordered_independent_of_event_id() {
+  let short_edge = Event {
+    event_id: 7,
+    point: Vec2::new(1.0, 1.0),
+    left: true,
+    is_subject: true,
+    other_point: Vec2::new(2.0, 2.0),
+    arithmetic: Arithmetic::F32,
+  };
+  let long_edge = Event {
+    event_id: 3,
+    point: Vec2::new(1.0, 1.0),
+    left: true,
+    is_subject: true,
+    other_point: Vec2::new(3.0, 3.0),
+    arithmetic: Arithmetic::F32,
+  };
+  assert!(short_edge < long_edge);
+  assert!(Event { event_id: 1, ..short_edge.clone() } < long_edge);
+
+  let short_right_edge = Event {
+    event_id: 7,
+    point: Vec2::new(3.0, 3.0),
+    left: false,
+    is_subject: true,
+    other_point: Vec2::new(2.0, 2.0),
+    arithmetic: Arithmetic::F32,
+  };
+  let long_right_edge = Event {
+    event_id: 3,
+    point: Vec2::new(3.0, 3.0),
+    left: false,
+    is_subject: true,
+    other_point: Vec2::new(1.0, 1.0),
+    arithmetic: Arithmetic::F32,
+  };
+  assert!(short_right_edge < long_right_edge);
+}
+
 // Consumes the `event_queue` and turns it into a sorted Vec of events.
 fn event_queue_to_vec(event_queue: BinaryHeap<Reverse<Event>>) -> Vec<Event> {
   let mut event_queue = event_queue
@@ -164,6 +224,30 @@ fn computes_bounds_for_non_empty_polygon() {
   );
 }
 
+#[test]
+fn iterates_edges_of_polygon() {
+  let polygon = Polygon {
+    contours: vec![
+      vec![Vec2::new(1.0, 1.0), Vec2::new(3.0, 1.0), Vec2::new(3.0, 3.0)],
+      vec![],
+      vec![Vec2::new(4.0, 1.0), Vec2::new(5.0, 1.0)],
+    ],
+  };
+
+  assert_eq!(
+    polygon.iter_edges().collect::<Vec<_>>(),
+    [
+      (0, 0, Vec2::new(1.0, 1.0), Vec2::new(3.0, 1.0)),
+      (0, 1, Vec2::new(3.0, 1.0), Vec2::new(3.0, 3.0)),
+      (0, 2, Vec2::new(3.0, 3.0), Vec2::new(1.0, 1.0)),
+      (2, 0, Vec2::new(4.0, 1.0), Vec2::new(5.0, 1.0)),
+      (2, 1, Vec2::new(5.0, 1.0), Vec2::new(4.0, 1.0)),
+    ]
+  );
+  assert_eq!(polygon.num_vertices(), 5);
+  assert_eq!(polygon.num_edges(), 5);
+}
+
 #[test]
 fn creates_events_for_polygon() {
   let polygon = Polygon {
@@ -188,6 +272,7 @@ fn creates_events_for_polygon() {
   create_events_for_polygon(
     &polygon,
     /* is_subject= */ true,
+    Arithmetic::F32,
     &mut event_queue,
     &mut event_relations,
     /* x_limit= */ INFINITY,
@@ -202,6 +287,7 @@ fn creates_events_for_polygon() {
         left: true,
         is_subject: true,
         other_point: Vec2::new(3.0, 1.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 7,
@@ -209,6 +295,7 @@ fn creates_events_for_polygon() {
         left: true,
         is_subject: true,
         other_point: Vec2::new(1.0, 3.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 6,
@@ -216,6 +303,7 @@ fn creates_events_for_polygon() {
         left: false,
         is_subject: true,
         other_point: Vec2::new(1.0, 1.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 5,
@@ -223,6 +311,7 @@ fn creates_events_for_polygon() {
         left: true,
         is_subject: true,
         other_point: Vec2::new(3.0, 3.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 1,
@@ -230,6 +319,7 @@ fn creates_events_for_polygon() {
         left: false,
         is_subject: true,
         other_point: Vec2::new(1.0, 1.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 2,
@@ -237,6 +327,7 @@ fn creates_events_for_polygon() {
         left: true,
         is_subject: true,
         other_point: Vec2::new(3.0, 3.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 4,
@@ -244,6 +335,7 @@ fn creates_events_for_polygon() {
         left: false,
         is_subject: true,
         other_point: Vec2::new(1.0, 3.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 3,
@@ -251,6 +343,7 @@ fn creates_events_for_polygon() {
         left: false,
         is_subject: true,
         other_point: Vec2::new(3.0, 1.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 8,
@@ -258,6 +351,7 @@ fn creates_events_for_polygon() {
         left: true,
         is_subject: true,
         other_point: Vec2::new(5.0, 1.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 15,
@@ -265,6 +359,7 @@ fn creates_events_for_polygon() {
         left: true,
         is_subject: true,
         other_point: Vec2::new(5.0, 2.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 9,
@@ -272,6 +367,7 @@ fn creates_events_for_polygon() {
         left: false,
         is_subject: true,
         other_point: Vec2::new(4.0, 1.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 10,
@@ -279,6 +375,7 @@ fn creates_events_for_polygon() {
         left: true,
         is_subject: true,
         other_point: Vec2::new(6.0, 2.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 14,
@@ -286,6 +383,7 @@ fn creates_events_for_polygon() {
         left: false,
         is_subject: true,
         other_point: Vec2::new(4.0, 1.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 13,
@@ -293,6 +391,7 @@ fn creates_events_for_polygon() {
         left: true,
         is_subject: true,
         other_point: Vec2::new(6.0, 2.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 11,
@@ -300,6 +399,7 @@ fn creates_events_for_polygon() {
         left: false,
         is_subject: true,
         other_point: Vec2::new(5.0, 1.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 12,
@@ -307,6 +407,7 @@ fn creates_events_for_polygon() {
         left: false,
         is_subject: true,
         other_point: Vec2::new(5.0, 2.0),
+        arithmetic: Arithmetic::F32,
       },
     ]
   );
@@ -437,6 +538,7 @@ fn creates_events_for_polygon_with_x_limit() {
   create_events_for_polygon(
     &polygon,
     /* is_subject= */ true,
+    Arithmetic::F32,
     &mut event_queue,
     &mut event_relations,
     /* x_limit= */ 2.0,
@@ -451,6 +553,7 @@ fn creates_events_for_polygon_with_x_limit() {
         left: true,
         is_subject: true,
         other_point: Vec2::new(3.0, 1.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 5,
@@ -458,6 +561,7 @@ fn creates_events_for_polygon_with_x_limit() {
         left: true,
         is_subject: true,
         other_point: Vec2::new(1.0, 3.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 4,
@@ -465,6 +569,7 @@ fn creates_events_for_polygon_with_x_limit() {
         left: false,
         is_subject: true,
         other_point: Vec2::new(1.0, 1.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 3,
@@ -472,6 +577,7 @@ fn creates_events_for_polygon_with_x_limit() {
         left: true,
         is_subject: true,
         other_point: Vec2::new(3.0, 3.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 1,
@@ -479,6 +585,7 @@ fn creates_events_for_polygon_with_x_limit() {
         left: false,
         is_subject: true,
         other_point: Vec2::new(1.0, 1.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 2,
@@ -486,6 +593,7 @@ fn creates_events_for_polygon_with_x_limit() {
         left: false,
         is_subject: true,
         other_point: Vec2::new(1.0, 3.0),
+        arithmetic: Arithmetic::F32,
       },
     ]
   );
@@ -558,6 +666,7 @@ fn splits_edges() {
         left: true,
         is_subject: true,
         other_point: Vec2::new(0.0, 0.0),
+        arithmetic: Arithmetic::F32,
       },
       SPLIT_EDGE,
       &mut event_queue,
@@ -576,6 +685,7 @@ fn splits_edges() {
         left: false,
         is_subject: true,
         other_point: Vec2::new(0.0, 0.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 3,
@@ -583,6 +693,7 @@ fn splits_edges() {
         left: true,
         is_subject: true,
         other_point: Vec2::new(1.0, 1.0),
+        arithmetic: Arithmetic::F32,
       }
     ]
   );
@@ -650,6 +761,7 @@ fn check_for_intersection_finds_no_intersection() {
       left: true,
       is_subject: false,
       other_point: Vec2::new(3.0, 4.0),
+      arithmetic: Arithmetic::F32,
     },
     &Event {
       event_id: 2,
@@ -657,10 +769,11 @@ fn check_for_intersection_finds_no_intersection() {
       left: true,
       is_subject: true,
       other_point: Vec2::new(3.0, 3.0),
+      arithmetic: Arithmetic::F32,
     },
     &mut event_queue,
     &mut event_relations,
-    Operation::Union,
+    &Operation::Union,
   );
 
   // No new events.
@@ -704,6 +817,7 @@ fn check_for_intersection_finds_point_intersection() {
       left: true,
       is_subject: false,
       other_point: Vec2::new(3.0, 3.0),
+      arithmetic: Arithmetic::F32,
     },
     &Event {
       event_id: 2,
@@ -711,10 +825,11 @@ fn check_for_intersection_finds_point_intersection() {
       left: true,
       is_subject: true,
       other_point: Vec2::new(3.0, 4.0),
+      arithmetic: Arithmetic::F32,
     },
     &mut event_queue,
     &mut event_relations,
-    Operation::Union,
+    &Operation::Union,
   );
 
   let event_queue = event_queue_to_vec(event_queue);
@@ -727,6 +842,7 @@ fn check_for_intersection_finds_point_intersection() {
         left: false,
         is_subject: false,
         other_point: Vec2::new(1.0, 2.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 4,
@@ -734,6 +850,7 @@ fn check_for_intersection_finds_point_intersection() {
         left: false,
         is_subject: false,
         other_point: Vec2::new(1.0, 2.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 5,
@@ -741,6 +858,7 @@ fn check_for_intersection_finds_point_intersection() {
         left: true,
         is_subject: false,
         other_point: Vec2::new(3.0, 3.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 7,
@@ -748,6 +866,7 @@ fn check_for_intersection_finds_point_intersection() {
         left: true,
         is_subject: false,
         other_point: Vec2::new(3.0, 3.0),
+        arithmetic: Arithmetic::F32,
       },
     ]
   );
@@ -866,6 +985,7 @@ fn check_for_intersection_finds_fully_overlapped_line() {
       left: true,
       is_subject: false,
       other_point: Vec2::new(3.0, 3.0),
+      arithmetic: Arithmetic::F32,
     },
     &Event {
       event_id: 2,
@@ -873,10 +993,11 @@ fn check_for_intersection_finds_fully_overlapped_line() {
       left: true,
       is_subject: true,
       other_point: Vec2::new(2.0, 2.0),
+      arithmetic: Arithmetic::F32,
     },
     &mut event_queue,
     &mut event_relations,
-    Operation::Union,
+    &Operation::Union,
   );
 
   let event_queue = event_queue_to_vec(event_queue);
@@ -887,6 +1008,7 @@ fn check_for_intersection_finds_fully_overlapped_line() {
       left: false,
       is_subject: false,
       other_point: Vec2::new(0.0, 0.0),
+      arithmetic: Arithmetic::F32,
     },
     Event {
       event_id: 7,
@@ -894,6 +1016,7 @@ fn check_for_intersection_finds_fully_overlapped_line() {
       left: true,
       is_subject: false,
       other_point: Vec2::new(2.0, 2.0),
+      arithmetic: Arithmetic::F32,
     },
     Event {
       event_id: 4,
@@ -901,6 +1024,7 @@ fn check_for_intersection_finds_fully_overlapped_line() {
       left: false,
       is_subject: false,
       other_point: Vec2::new(0.0, 0.0),
+      arithmetic: Arithmetic::F32,
     },
     Event {
       event_id: 5,
@@ -908,6 +1032,7 @@ fn check_for_intersection_finds_fully_overlapped_line() {
       left: true,
       is_subject: false,
       other_point: Vec2::new(3.0, 3.0),
+      arithmetic: Arithmetic::F32,
     },
   ];
   assert_eq!(event_queue, expected_event_queue);
@@ -975,6 +1100,7 @@ fn check_for_intersection_finds_fully_overlapped_line() {
       left: true,
       is_subject: true,
       other_point: Vec2::new(2.0, 2.0),
+      arithmetic: Arithmetic::F32,
     },
     &Event {
       event_id: 0,
@@ -982,10 +1108,11 @@ fn check_for_intersection_finds_fully_overlapped_line() {
       left: true,
       is_subject: false,
       other_point: Vec2::new(3.0, 3.0),
+      arithmetic: Arithmetic::F32,
     },
     &mut event_queue,
     &mut event_relations,
-    Operation::Union,
+    &Operation::Union,
   );
 
   let event_queue = event_queue_to_vec(event_queue);
@@ -1060,6 +1187,7 @@ fn check_for_intersection_finds_partially_overlapped_lines() {
       left: true,
       is_subject: false,
       other_point: Vec2::new(2.0, 2.0),
+      arithmetic: Arithmetic::F32,
     },
     &Event {
       event_id: 2,
@@ -1067,10 +1195,11 @@ fn check_for_intersection_finds_partially_overlapped_lines() {
       left: true,
       is_subject: true,
       other_point: Vec2::new(3.0, 3.0),
+      arithmetic: Arithmetic::F32,
     },
     &mut event_queue,
     &mut event_relations,
-    Operation::Intersection,
+    &Operation::Intersection,
   );
 
   let event_queue = event_queue_to_vec(event_queue);
@@ -1083,6 +1212,7 @@ fn check_for_intersection_finds_partially_overlapped_lines() {
         left: false,
         is_subject: false,
         other_point: Vec2::new(0.0, 0.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 5,
@@ -1090,6 +1220,7 @@ fn check_for_intersection_finds_partially_overlapped_lines() {
         left: true,
         is_subject: false,
         other_point: Vec2::new(3.0, 3.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 6,
@@ -1097,6 +1228,7 @@ fn check_for_intersection_finds_partially_overlapped_lines() {
         left: false,
         is_subject: false,
         other_point: Vec2::new(0.0, 0.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 7,
@@ -1104,6 +1236,7 @@ fn check_for_intersection_finds_partially_overlapped_lines() {
         left: true,
         is_subject: false,
         other_point: Vec2::new(3.0, 3.0),
+        arithmetic: Arithmetic::F32,
       },
     ]
   );
@@ -1170,6 +1303,7 @@ fn check_for_intersection_finds_partially_overlapped_lines() {
       left: true,
       is_subject: true,
       other_point: Vec2::new(3.0, 3.0),
+      arithmetic: Arithmetic::F32,
     },
     &Event {
       event_id: 0,
@@ -1177,10 +1311,11 @@ fn check_for_intersection_finds_partially_overlapped_lines() {
       left: true,
       is_subject: false,
       other_point: Vec2::new(2.0, 2.0),
+      arithmetic: Arithmetic::F32,
     },
     &mut event_queue,
     &mut event_relations,
-    Operation::Difference,
+    &Operation::Difference,
   );
 
   let event_queue = event_queue_to_vec(event_queue);
@@ -1193,6 +1328,7 @@ fn check_for_intersection_finds_partially_overlapped_lines() {
         left: false,
         is_subject: false,
         other_point: Vec2::new(0.0, 0.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 7,
@@ -1200,6 +1336,7 @@ fn check_for_intersection_finds_partially_overlapped_lines() {
         left: true,
         is_subject: false,
         other_point: Vec2::new(2.0, 2.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 4,
@@ -1207,6 +1344,7 @@ fn check_for_intersection_finds_partially_overlapped_lines() {
         left: false,
         is_subject: true,
         other_point: Vec2::new(1.0, 1.0),
+        arithmetic: Arithmetic::F32,
       },
       Event {
         event_id: 5,
@@ -1214,6 +1352,7 @@ fn check_for_intersection_finds_partially_overlapped_lines() {
         left: true,
         is_subject: true,
         other_point: Vec2::new(3.0, 3.0),
+        arithmetic: Arithmetic::F32,
       },
     ]
   );
@@ -1294,7 +1433,7 @@ fn boolean_of_rhombuses() {
 
   assert_eq!(
     union(&subject, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: Polygon {
         contours: vec![vec![
           Vec2::new(1.0, 1.0),
@@ -1317,12 +1456,13 @@ fn boolean_of_rhombuses() {
         SourceEdge { is_from_subject: true, contour: 0, edge: 2 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 3 },
       ]],
-    }
+      ..Default::default()
+    })
   );
 
   assert_eq!(
     intersection(&subject, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: Polygon {
         contours: vec![vec![
           Vec2::new(3.0, 2.0),
@@ -1337,12 +1477,13 @@ fn boolean_of_rhombuses() {
         SourceEdge { is_from_subject: true, contour: 0, edge: 2 },
         SourceEdge { is_from_subject: false, contour: 0, edge: 3 },
       ]],
-    }
+      ..Default::default()
+    })
   );
 
   assert_eq!(
     difference(&subject, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: Polygon {
         contours: vec![vec![
           Vec2::new(1.0, 1.0),
@@ -1361,12 +1502,13 @@ fn boolean_of_rhombuses() {
         SourceEdge { is_from_subject: true, contour: 0, edge: 2 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 3 },
       ]],
-    }
+      ..Default::default()
+    })
   );
 
   assert_eq!(
     xor(&subject, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: Polygon {
         contours: vec![
           vec![
@@ -1405,7 +1547,8 @@ fn boolean_of_rhombuses() {
           SourceEdge { is_from_subject: false, contour: 0, edge: 3 },
         ]
       ],
-    }
+      ..Default::default()
+    })
   );
 }
 
@@ -1430,7 +1573,7 @@ fn boolean_of_squares() {
 
   assert_eq!(
     union(&subject, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: Polygon {
         contours: vec![vec![
           Vec2::new(1.0, 1.0),
@@ -1453,12 +1596,13 @@ fn boolean_of_squares() {
         SourceEdge { is_from_subject: true, contour: 0, edge: 2 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 3 },
       ]],
-    }
+      ..Default::default()
+    })
   );
 
   assert_eq!(
     intersection(&subject, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: Polygon {
         contours: vec![vec![
           Vec2::new(2.0, 2.0),
@@ -1473,12 +1617,13 @@ fn boolean_of_squares() {
         SourceEdge { is_from_subject: true, contour: 0, edge: 2 },
         SourceEdge { is_from_subject: false, contour: 0, edge: 3 },
       ]],
-    }
+      ..Default::default()
+    })
   );
 
   assert_eq!(
     difference(&subject, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: Polygon {
         contours: vec![vec![
           Vec2::new(1.0, 1.0),
@@ -1497,12 +1642,13 @@ fn boolean_of_squares() {
         SourceEdge { is_from_subject: true, contour: 0, edge: 2 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 3 },
       ]],
-    }
+      ..Default::default()
+    })
   );
 
   assert_eq!(
     xor(&subject, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: Polygon {
         contours: vec![
           vec![
@@ -1541,7 +1687,8 @@ fn boolean_of_squares() {
           SourceEdge { is_from_subject: false, contour: 0, edge: 3 },
         ]
       ],
-    }
+      ..Default::default()
+    })
   );
 }
 
@@ -1567,7 +1714,7 @@ fn add_and_remove_squares() {
   // All boolean operations between the clip and the subject.
   assert_eq!(
     intersection(&subject, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: clip.clone(),
       contour_source_edges: vec![vec![
         SourceEdge { is_from_subject: true, contour: 0, edge: 0 },
@@ -1575,7 +1722,8 @@ fn add_and_remove_squares() {
         SourceEdge { is_from_subject: false, contour: 0, edge: 2 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 3 },
       ]],
-    }
+      ..Default::default()
+    })
   );
   let expected_union = Polygon {
     contours: vec![vec![
@@ -1589,7 +1737,7 @@ fn add_and_remove_squares() {
   };
   assert_eq!(
     union(&subject, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: expected_union.clone(),
       contour_source_edges: vec![vec![
         SourceEdge { is_from_subject: true, contour: 0, edge: 0 },
@@ -1599,10 +1747,11 @@ fn add_and_remove_squares() {
         SourceEdge { is_from_subject: true, contour: 0, edge: 3 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 3 },
       ]],
-    }
+      ..Default::default()
+    })
   );
 
-  let expected_difference = BooleanResult {
+  let expected_difference = with_bounds(BooleanResult {
     polygon: Polygon {
       contours: vec![vec![
         Vec2::new(1.0, 2.0),
@@ -1621,7 +1770,8 @@ fn add_and_remove_squares() {
       SourceEdge { is_from_subject: true, contour: 0, edge: 2 },
       SourceEdge { is_from_subject: true, contour: 0, edge: 3 },
     ]],
-  };
+    ..Default::default()
+  });
   assert_eq!(difference(&subject, &clip), expected_difference);
 
   let xor_result = xor(&subject, &clip);
@@ -1629,14 +1779,16 @@ fn add_and_remove_squares() {
 
   assert_eq!(
     intersection(&xor_result.polygon, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: Polygon { contours: vec![] },
-      contour_source_edges: vec![]
-    }
+      contour_source_edges: vec![],
+      is_empty: true,
+      ..Default::default()
+    })
   );
   assert_eq!(
     union(&xor_result.polygon, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: expected_union,
       contour_source_edges: vec![vec![
         SourceEdge { is_from_subject: false, contour: 0, edge: 0 },
@@ -1646,7 +1798,8 @@ fn add_and_remove_squares() {
         SourceEdge { is_from_subject: true, contour: 0, edge: 5 },
         SourceEdge { is_from_subject: false, contour: 0, edge: 3 },
       ]],
-    }
+      ..Default::default()
+    })
   );
 }
 
@@ -1686,7 +1839,7 @@ fn cut_and_fill_hole() {
     ],
   };
 
-  let expected_difference = BooleanResult {
+  let expected_difference = with_bounds(BooleanResult {
     polygon: expected_subject_with_hole.clone(),
     contour_source_edges: vec![
       vec![
@@ -1696,64 +1849,69 @@ fn cut_and_fill_hole() {
         SourceEdge { is_from_subject: true, contour: 0, edge: 3 },
       ],
       vec![
-        SourceEdge { is_from_subject: false, contour: 0, edge: 3 },
         SourceEdge { is_from_subject: false, contour: 0, edge: 2 },
         SourceEdge { is_from_subject: false, contour: 0, edge: 1 },
         SourceEdge { is_from_subject: false, contour: 0, edge: 0 },
+        SourceEdge { is_from_subject: false, contour: 0, edge: 3 },
       ],
     ],
-  };
+    ..Default::default()
+  });
   assert_eq!(difference(&subject, &clip), expected_difference);
   assert_eq!(xor(&subject, &clip), expected_difference);
 
   assert_eq!(
     union(&expected_subject_with_hole, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: subject.clone(),
       contour_source_edges: vec![vec![
         SourceEdge { is_from_subject: true, contour: 0, edge: 0 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 1 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 2 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 3 },
-      ]]
-    }
+      ]],
+      ..Default::default()
+    })
   );
   assert_eq!(
     xor(&expected_subject_with_hole, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: subject.clone(),
       contour_source_edges: vec![vec![
         SourceEdge { is_from_subject: true, contour: 0, edge: 0 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 1 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 2 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 3 },
-      ]]
-    }
+      ]],
+      ..Default::default()
+    })
   );
 
   assert_eq!(
     union(&expected_subject_with_hole, &subject),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: subject.clone(),
       contour_source_edges: vec![vec![
         SourceEdge { is_from_subject: true, contour: 0, edge: 0 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 1 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 2 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 3 },
-      ]]
-    }
+      ]],
+      ..Default::default()
+    })
   );
   assert_eq!(
     xor(&expected_subject_with_hole, &subject),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: clip,
       contour_source_edges: vec![vec![
         SourceEdge { is_from_subject: true, contour: 1, edge: 2 },
         SourceEdge { is_from_subject: true, contour: 1, edge: 1 },
         SourceEdge { is_from_subject: true, contour: 1, edge: 0 },
         SourceEdge { is_from_subject: true, contour: 1, edge: 3 },
-      ]]
-    }
+      ]],
+      ..Default::default()
+    })
   );
 }
 
@@ -1818,7 +1976,7 @@ fn partially_overlapping_edges_are_split() {
 
   assert_eq!(
     intersection(&subject, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: subdivided_clip.clone(),
       contour_source_edges: vec![vec![
         SourceEdge { is_from_subject: false, contour: 0, edge: 7 },
@@ -1831,11 +1989,12 @@ fn partially_overlapping_edges_are_split() {
         SourceEdge { is_from_subject: false, contour: 0, edge: 5 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 6 },
       ]],
-    }
+      ..Default::default()
+    })
   );
   assert_eq!(
     intersection(&clip, &subject),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: subdivided_clip,
       contour_source_edges: vec![vec![
         SourceEdge { is_from_subject: true, contour: 0, edge: 7 },
@@ -1848,11 +2007,12 @@ fn partially_overlapping_edges_are_split() {
         SourceEdge { is_from_subject: true, contour: 0, edge: 5 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 6 },
       ]],
-    }
+      ..Default::default()
+    })
   );
   assert_eq!(
     union(&subject, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: subdivided_subject.clone(),
       contour_source_edges: vec![vec![
         SourceEdge { is_from_subject: true, contour: 0, edge: 0 },
@@ -1871,11 +2031,12 @@ fn partially_overlapping_edges_are_split() {
         SourceEdge { is_from_subject: true, contour: 0, edge: 6 },
         SourceEdge { is_from_subject: true, contour: 0, edge: 6 },
       ]],
-    }
+      ..Default::default()
+    })
   );
   assert_eq!(
     union(&clip, &subject),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: subdivided_subject,
       contour_source_edges: vec![vec![
         SourceEdge { is_from_subject: false, contour: 0, edge: 0 },
@@ -1894,11 +2055,12 @@ fn partially_overlapping_edges_are_split() {
         SourceEdge { is_from_subject: true, contour: 0, edge: 6 },
         SourceEdge { is_from_subject: false, contour: 0, edge: 6 },
       ]],
-    }
+      ..Default::default()
+    })
   );
   assert_eq!(
     difference(&subject, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: Polygon {
         contours: vec![
           vec![Vec2::new(1.0, 1.0), Vec2::new(2.0, 1.0), Vec2::new(1.0, 2.0)],
@@ -1941,14 +2103,17 @@ fn partially_overlapping_edges_are_split() {
           SourceEdge { is_from_subject: true, contour: 0, edge: 4 },
         ]
       ],
-    }
+      ..Default::default()
+    })
   );
   assert_eq!(
     difference(&clip, &subject),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: Polygon { contours: vec![] },
       contour_source_edges: vec![],
-    }
+      is_empty: true,
+      ..Default::default()
+    })
   );
 }
 
@@ -1982,7 +2147,7 @@ fn trivially_computes_operations_for_disjoint_bounding_boxes() {
     ]],
   };
 
-  let expected_union = BooleanResult {
+  let expected_union = with_bounds(BooleanResult {
     polygon: Polygon {
       contours: vec![
         // Subject contours.
@@ -2029,20 +2194,23 @@ fn trivially_computes_operations_for_disjoint_bounding_boxes() {
         SourceEdge { is_from_subject: false, contour: 0, edge: 3 },
       ],
     ],
-  };
+    ..Default::default()
+  });
   assert_eq!(union(&subject, &clip), expected_union);
   assert_eq!(xor(&subject, &clip), expected_union);
 
   assert_eq!(
     intersection(&subject, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: Polygon { contours: vec![] },
       contour_source_edges: vec![],
-    }
+      is_empty: true,
+      ..Default::default()
+    })
   );
   assert_eq!(
     difference(&subject, &clip),
-    BooleanResult {
+    with_bounds(BooleanResult {
       polygon: subject.clone(),
       contour_source_edges: vec![
         vec![
@@ -2059,7 +2227,8 @@ fn trivially_computes_operations_for_disjoint_bounding_boxes() {
           SourceEdge { is_from_subject: true, contour: 2, edge: 3 },
         ],
       ],
-    }
+      ..Default::default()
+    })
   );
 }
 
@@ -2091,11 +2260,13 @@ fn trivially_computes_operations_for_empty_polygons() {
     ],
   };
 
-  let empty_boolean_result = BooleanResult {
+  let empty_boolean_result = with_bounds(BooleanResult {
     polygon: Polygon { contours: vec![] },
     contour_source_edges: vec![],
-  };
-  let non_empty_boolean_result_as_subject = BooleanResult {
+    is_empty: true,
+    ..Default::default()
+  });
+  let non_empty_boolean_result_as_subject = with_bounds(BooleanResult {
     polygon: non_empty_polygon.clone(),
     contour_source_edges: vec![
       vec![
@@ -2112,8 +2283,9 @@ fn trivially_computes_operations_for_empty_polygons() {
         SourceEdge { is_from_subject: true, contour: 2, edge: 3 },
       ],
     ],
-  };
-  let non_empty_boolean_result_as_clip = BooleanResult {
+    ..Default::default()
+  });
+  let non_empty_boolean_result_as_clip = with_bounds(BooleanResult {
     polygon: non_empty_polygon.clone(),
     contour_source_edges: vec![
       vec![
@@ -2130,7 +2302,8 @@ fn trivially_computes_operations_for_empty_polygons() {
         SourceEdge { is_from_subject: false, contour: 2, edge: 3 },
       ],
     ],
-  };
+    ..Default::default()
+  });
   assert_eq!(
     union(&non_empty_polygon, &empty_polygon),
     non_empty_boolean_result_as_subject
@@ -2142,11 +2315,11 @@ fn trivially_computes_operations_for_empty_polygons() {
 
   assert_eq!(
     intersection(&non_empty_polygon, &empty_polygon),
-    empty_boolean_result
+    empty_boolean_result.clone()
   );
   assert_eq!(
     intersection(&empty_polygon, &non_empty_polygon),
-    empty_boolean_result
+    empty_boolean_result.clone()
   );
 
   assert_eq!(
@@ -2155,7 +2328,7 @@ fn trivially_computes_operations_for_empty_polygons() {
   );
   assert_eq!(
     difference(&empty_polygon, &non_empty_polygon),
-    empty_boolean_result
+    empty_boolean_result.clone()
   );
 
   assert_eq!(
@@ -2195,7 +2368,8 @@ fn floating_point_inaccuracy_polygons() {
     ]],
   };
 
-  let BooleanResult { polygon, contour_source_edges } = union(&subject, &clip);
+  let BooleanResult { polygon, contour_source_edges, .. } =
+    union(&subject, &clip);
   assert_eq!(
     polygon,
     Polygon {
@@ -2226,6 +2400,49 @@ fn floating_point_inaccuracy_polygons() {
   );
 }
 
+#[test]
+fn floating_point_inaccuracy_polygons_far_from_origin() {
+  // The same polygons as `floating_point_inaccuracy_polygons`, translated far
+  // from the origin. The intersection points must still land exactly on the
+  // translated vertices.
+  let offset = Vec2::new(4096.0, -2048.0);
+  let translate = |points: &[Vec2]| {
+    points.iter().map(|&point| point + offset).collect::<Vec<_>>()
+  };
+  let subject = Polygon {
+    contours: vec![translate(&[
+      Vec2::new(2.0, 0.0),
+      Vec2::new(1.0, 0.0),
+      Vec2::new(1.0, -2.0),
+      Vec2::new(2.0, -1.0),
+    ])],
+  };
+  let clip = Polygon {
+    contours: vec![translate(&[
+      Vec2::new(2.0, -0.01),
+      Vec2::new(2.0, 0.01),
+      Vec2::new(1.0, 0.01),
+      Vec2::new(1.0, -0.01),
+    ])],
+  };
+
+  assert_eq!(
+    union(&subject, &clip).polygon,
+    Polygon {
+      contours: vec![translate(&[
+        Vec2::new(1.0, -2.0),
+        Vec2::new(2.0, -1.0),
+        Vec2::new(2.0, -0.01),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(2.0, 0.01),
+        Vec2::new(1.0, 0.01),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(1.0, -0.01),
+      ])]
+    }
+  );
+}
+
 #[test]
 fn sweep_line_point_on_other_edge() {
   let subject = Polygon {
@@ -2244,7 +2461,8 @@ fn sweep_line_point_on_other_edge() {
       Vec2::new(-1.0, 0.01 + EPSILON),
     ]],
   };
-  let BooleanResult { polygon, contour_source_edges } = union(&subject, &clip);
+  let BooleanResult { polygon, contour_source_edges, .. } =
+    union(&subject, &clip);
   assert_eq!(
     polygon,
     Polygon {
@@ -2272,3 +2490,1204 @@ fn sweep_line_point_on_other_edge() {
     ]]
   );
 }
+
+#[test]
+fn inconsistent_sweeps_are_recomputed_in_f64() {
+  // In f32, the intersection of edges 1 and 2 is computed a rounding error away
+  // from their shared vertex, so the edges are split next to it and the sweep
+  // becomes inconsistent.
+  let polygon = heptagon();
+  let bounds = polygon.compute_bounds().unwrap();
+  let sweep = |arithmetic| {
+    sweep_boolean(
+      &polygon,
+      &Polygon::default(),
+      bounds,
+      bounds,
+      Rule::Operation(Operation::Union),
+      arithmetic,
+      &BooleanOptions::default(),
+    )
+  };
+  assert!(matches!(
+    sweep(Arithmetic::F32),
+    Err(SweepError::Inconsistent { .. })
+  ));
+  assert_eq!(
+    sweep(Arithmetic::F64).unwrap().polygon.area(),
+    polygon.area().abs()
+  );
+
+  let result = try_resolve_self_intersections(&polygon).unwrap();
+  assert!(result.precision_escalated);
+  assert_eq!(result.polygon.area(), polygon.area().abs());
+}
+
+#[test]
+fn normalized_coordinates_match_translated_result() {
+  let subject = Polygon {
+    contours: vec![vec![
+      Vec2::new(1.0, 1.0),
+      Vec2::new(3.0, 1.0),
+      Vec2::new(3.0, 3.0),
+      Vec2::new(1.0, 3.0),
+    ]],
+  };
+  let clip = Polygon {
+    contours: vec![vec![
+      Vec2::new(2.0, 2.0),
+      Vec2::new(4.0, 2.0),
+      Vec2::new(4.0, 4.0),
+      Vec2::new(2.0, 4.0),
+    ]],
+  };
+  let translate = |polygon: &Polygon| Polygon {
+    contours: polygon
+      .contours
+      .iter()
+      .map(|contour| {
+        contour.iter().map(|&point| point + Vec2::splat(30000.0)).collect()
+      })
+      .collect(),
+  };
+
+  let expected_result = union(&subject, &clip);
+  assert_eq!(
+    union_with_options(
+      &translate(&subject),
+      &translate(&clip),
+      &BooleanOptions { normalize_coordinates: true, ..Default::default() },
+    ),
+    with_bounds(BooleanResult {
+      polygon: translate(&expected_result.polygon),
+      contour_source_edges: expected_result.contour_source_edges,
+      ..Default::default()
+    })
+  );
+}
+
+#[test]
+fn skips_normalization_that_merges_points() {
+  // The clip only passes by the small subject, but it moves the center of the
+  // combined bounds so far away that all the vertices of the subject would be
+  // normalized to the same point.
+  let subject = Polygon {
+    contours: vec![vec![
+      Vec2::new(0.0, 0.0),
+      Vec2::new(1e-3, 0.0),
+      Vec2::new(0.0, 1e-3),
+    ]],
+  };
+  let clip = Polygon {
+    contours: vec![vec![
+      Vec2::new(-1.0, -2.0),
+      Vec2::new(2e8, -2.0),
+      Vec2::new(2e8, 2e8),
+    ]],
+  };
+
+  assert_eq!(
+    union_with_options(
+      &subject,
+      &clip,
+      &BooleanOptions { normalize_coordinates: true, ..Default::default() },
+    ),
+    union(&subject, &clip)
+  );
+}
+
+#[test]
+fn tracks_unmodified_edges() {
+  let subject = Polygon {
+    contours: vec![vec![
+      Vec2::new(1.0, 1.0),
+      Vec2::new(3.0, 1.0),
+      Vec2::new(3.0, 3.0),
+      Vec2::new(1.0, 3.0),
+    ]],
+  };
+  let clip = Polygon {
+    contours: vec![vec![
+      Vec2::new(2.0, 2.0),
+      Vec2::new(4.0, 2.0),
+      Vec2::new(4.0, 4.0),
+      Vec2::new(2.0, 4.0),
+    ]],
+  };
+
+  let options =
+    BooleanOptions { track_unmodified_edges: true, ..Default::default() };
+  assert_eq!(
+    difference_with_options(&subject, &clip, &options).contour_unmodified_edges,
+    vec![vec![true, false, false, false, false, true]]
+  );
+  // The mask is not computed unless requested.
+  assert!(difference(&subject, &clip).contour_unmodified_edges.is_empty());
+}
+
+#[test]
+fn tracks_edge_parameters() {
+  let subject = Polygon {
+    contours: vec![vec![
+      Vec2::new(1.0, 1.0),
+      Vec2::new(3.0, 1.0),
+      Vec2::new(3.0, 3.0),
+      Vec2::new(1.0, 3.0),
+    ]],
+  };
+  let clip = Polygon {
+    contours: vec![vec![
+      Vec2::new(2.0, 2.0),
+      Vec2::new(4.0, 2.0),
+      Vec2::new(4.0, 4.0),
+      Vec2::new(2.0, 4.0),
+    ]],
+  };
+
+  let options =
+    BooleanOptions { track_edge_parameters: true, ..Default::default() };
+  let result = difference_with_options(&subject, &clip, &options);
+  // The result is (1, 1), (3, 1), (3, 2), (2, 2), (2, 3), (1, 3). The hole
+  // edges run backwards along the clip's edges.
+  assert_eq!(
+    result.contour_edge_parameters,
+    [[[0.0, 1.0], [0.0, 0.5], [0.5, 0.0], [1.0, 0.5], [0.5, 1.0], [0.0, 1.0],]]
+  );
+  // The parameters are not computed unless requested.
+  assert!(difference(&subject, &clip).contour_edge_parameters.is_empty());
+}
+
+#[test]
+fn tracks_vertex_errors() {
+  let subject = Polygon {
+    contours: vec![vec![
+      Vec2::new(1.0, 1.0),
+      Vec2::new(3.0, 1.0),
+      Vec2::new(3.0, 3.0),
+      Vec2::new(1.0, 3.0),
+    ]],
+  };
+  let clip = Polygon {
+    contours: vec![vec![
+      Vec2::new(2.0, 2.0),
+      Vec2::new(4.0, 2.0),
+      Vec2::new(4.0, 4.0),
+      Vec2::new(2.0, 4.0),
+    ]],
+  };
+
+  let options =
+    BooleanOptions { track_vertex_errors: true, ..Default::default() };
+  let result = difference_with_options(&subject, &clip, &options);
+  assert_eq!(
+    result.polygon.contours,
+    [[
+      Vec2::new(1.0, 1.0),
+      Vec2::new(3.0, 1.0),
+      Vec2::new(3.0, 2.0),
+      Vec2::new(2.0, 2.0),
+      Vec2::new(2.0, 3.0),
+      Vec2::new(1.0, 3.0),
+    ]]
+  );
+  // Only the intersection vertices carry an error.
+  let intersection_error = 16.0 * f32::EPSILON;
+  assert_eq!(
+    result.contour_vertex_errors,
+    [[0.0, 0.0, intersection_error, 0.0, intersection_error, 0.0]]
+  );
+  // The errors are not computed unless requested.
+  assert!(difference(&subject, &clip).contour_vertex_errors.is_empty());
+}
+
+#[test]
+fn tracks_hierarchy() {
+  let subject = Polygon {
+    contours: vec![vec![
+      Vec2::new(0.0, 0.0),
+      Vec2::new(4.0, 0.0),
+      Vec2::new(4.0, 4.0),
+      Vec2::new(0.0, 4.0),
+    ]],
+  };
+  let clip = Polygon {
+    contours: vec![vec![
+      Vec2::new(1.0, 1.0),
+      Vec2::new(2.0, 1.0),
+      Vec2::new(2.0, 2.0),
+      Vec2::new(1.0, 2.0),
+    ]],
+  };
+  let disjoint_clip = Polygon {
+    contours: vec![vec![
+      Vec2::new(5.0, 5.0),
+      Vec2::new(6.0, 5.0),
+      Vec2::new(6.0, 6.0),
+      Vec2::new(5.0, 6.0),
+    ]],
+  };
+
+  let options = BooleanOptions { track_hierarchy: true, ..Default::default() };
+  assert_eq!(
+    difference_with_options(&subject, &clip, &options).contour_parents,
+    [None, Some(0)]
+  );
+  // Trivially computed results also have a hierarchy.
+  assert_eq!(
+    union_with_options(&subject, &disjoint_clip, &options).contour_parents,
+    [None, None]
+  );
+  // The hierarchy is not computed unless requested.
+  assert!(difference(&subject, &clip).contour_parents.is_empty());
+}
+
+#[test]
+fn merge_appends_contours_and_shifts_parents() {
+  let hole = |min: Vec2| {
+    vec![
+      min + Vec2::new(1.0, 1.0),
+      min + Vec2::new(1.0, 2.0),
+      min + Vec2::new(2.0, 2.0),
+      min + Vec2::new(2.0, 1.0),
+    ]
+  };
+  let source_edges = |contour| {
+    (0..4)
+      .map(|edge| SourceEdge { is_from_subject: true, contour, edge })
+      .collect::<Vec<_>>()
+  };
+  let tile = |min: Vec2| BooleanResult {
+    polygon: Polygon { contours: vec![square(min, 4.0), hole(min)] },
+    contour_source_edges: vec![source_edges(0), source_edges(1)],
+    contour_parents: vec![None, Some(0)],
+    ..Default::default()
+  };
+
+  let mut result = BooleanResult::default();
+  result.merge(tile(Vec2::new(0.0, 0.0)));
+  result.merge(tile(Vec2::new(10.0, 0.0)));
+
+  assert_eq!(
+    result,
+    BooleanResult {
+      polygon: Polygon {
+        contours: vec![
+          square(Vec2::new(0.0, 0.0), 4.0),
+          hole(Vec2::new(0.0, 0.0)),
+          square(Vec2::new(10.0, 0.0), 4.0),
+          hole(Vec2::new(10.0, 0.0)),
+        ]
+      },
+      contour_source_edges: vec![
+        source_edges(0),
+        source_edges(1),
+        source_edges(0),
+        source_edges(1),
+      ],
+      contour_parents: vec![None, Some(0), None, Some(2)],
+      ..Default::default()
+    }
+  );
+
+  // Merging a result without a hierarchy drops the hierarchy.
+  result.merge(BooleanResult {
+    polygon: Polygon { contours: vec![square(Vec2::new(20.0, 0.0), 4.0)] },
+    contour_source_edges: vec![source_edges(0)],
+    ..Default::default()
+  });
+  assert_eq!(result.polygon.contours.len(), 5);
+  assert!(result.contour_parents.is_empty());
+}
+
+#[test]
+fn rejects_non_finite_input() {
+  let valid_polygon = Polygon {
+    contours: vec![vec![
+      Vec2::new(1.0, 1.0),
+      Vec2::new(2.0, 1.0),
+      Vec2::new(2.0, 2.0),
+    ]],
+  };
+  let invalid_polygon = Polygon {
+    contours: vec![
+      vec![Vec2::new(1.0, 1.0), Vec2::new(2.0, 1.0), Vec2::new(2.0, 2.0)],
+      vec![
+        Vec2::new(1.0, 1.0),
+        Vec2::new(f32::NAN, 1.0),
+        Vec2::new(2.0, f32::INFINITY),
+      ],
+    ],
+  };
+
+  assert_eq!(validate_input(&valid_polygon, &valid_polygon), Ok(()));
+  assert_eq!(
+    validate_input(&valid_polygon, &invalid_polygon),
+    Err(ClipError::InvalidInput {
+      is_from_subject: false,
+      contour: 1,
+      vertex: 1
+    })
+  );
+  assert_eq!(
+    validate_input(&invalid_polygon, &valid_polygon),
+    Err(ClipError::InvalidInput {
+      is_from_subject: true,
+      contour: 1,
+      vertex: 1
+    })
+  );
+}
+
+#[test]
+fn computes_bounds_of_result_contours() {
+  let subject = Polygon {
+    contours: vec![
+      vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(4.0, 0.0),
+        Vec2::new(4.0, 4.0),
+        Vec2::new(0.0, 4.0),
+      ],
+      vec![
+        Vec2::new(6.0, 1.0),
+        Vec2::new(7.0, 1.0),
+        Vec2::new(7.0, 2.0),
+        Vec2::new(6.0, 2.0),
+      ],
+    ],
+  };
+  let clip = Polygon {
+    contours: vec![vec![
+      Vec2::new(1.0, 1.0),
+      Vec2::new(2.0, 1.0),
+      Vec2::new(2.0, 3.0),
+      Vec2::new(1.0, 3.0),
+    ]],
+  };
+
+  let result = difference(&subject, &clip);
+  let mut contour_bounds = result.contour_bounds.clone();
+  contour_bounds.sort_by(|a, b| {
+    let min = |bounds: &Option<(Vec2, Vec2)>| bounds.unwrap().0.to_array();
+    min(a).partial_cmp(&min(b)).unwrap()
+  });
+  assert_eq!(
+    contour_bounds,
+    [
+      Some((Vec2::new(0.0, 0.0), Vec2::new(4.0, 4.0))),
+      Some((Vec2::new(1.0, 1.0), Vec2::new(2.0, 3.0))),
+      Some((Vec2::new(6.0, 1.0), Vec2::new(7.0, 2.0))),
+    ]
+  );
+  assert_eq!(result.bounds, Some((Vec2::new(0.0, 0.0), Vec2::new(7.0, 4.0))));
+  assert_eq!(result.bounds, result.polygon.compute_bounds());
+
+  let mut merged_result = result.clone();
+  merged_result.merge(intersection(&subject, &clip));
+  assert_eq!(merged_result.contour_bounds.len(), 4);
+  assert_eq!(merged_result.bounds, result.bounds);
+
+  assert_eq!(intersection(&subject, &Polygon::default()).bounds, None);
+}
+
+#[test]
+fn reports_whether_result_equals_an_input() {
+  let subject = rectangle_polygon(Vec2::new(0.0, 0.0), Vec2::new(4.0, 4.0));
+  let nested_clip = rectangle_polygon(Vec2::new(1.0, 1.0), Vec2::new(2.0, 3.0));
+  // Overlaps the right edge of the subject, so that edge gets split.
+  let overlapping_clip =
+    rectangle_polygon(Vec2::new(3.0, 1.0), Vec2::new(5.0, 2.0));
+  let empty_polygon = Polygon::default();
+  let flags = |result: BooleanResult| {
+    (result.equals_subject, result.equals_clip, result.is_empty)
+  };
+  let options =
+    BooleanOptions { track_input_equality: true, ..Default::default() };
+  let union = |subject, clip| union_with_options(subject, clip, &options);
+  let intersection =
+    |subject, clip| intersection_with_options(subject, clip, &options);
+  let difference =
+    |subject, clip| difference_with_options(subject, clip, &options);
+  let xor = |subject, clip| xor_with_options(subject, clip, &options);
+
+  assert_eq!(flags(union(&subject, &nested_clip)), (true, false, false));
+  assert_eq!(flags(intersection(&subject, &nested_clip)), (false, true, false));
+  assert_eq!(flags(difference(&nested_clip, &subject)), (false, false, true));
+  assert_eq!(flags(xor(&subject, &nested_clip)), (false, false, false));
+  assert_eq!(flags(union(&subject, &overlapping_clip)), (false, false, false));
+
+  // Trivially computed results are compared as well.
+  assert_eq!(flags(union(&subject, &empty_polygon)), (true, false, false));
+  assert_eq!(
+    flags(intersection(&empty_polygon, &empty_polygon)),
+    (true, true, true)
+  );
+  // The flags are only computed if asked for.
+  assert_eq!(
+    flags(crate::union(&subject, &nested_clip)),
+    (false, false, false)
+  );
+
+  // Changing the result afterwards clears the flags.
+  let mut result = union(&subject, &nested_clip);
+  result.merge(union(&overlapping_clip, &empty_polygon));
+  assert_eq!(flags(result), (false, false, false));
+  let mut result = union(&subject, &nested_clip);
+  result.merge(BooleanResult::default());
+  assert_eq!(flags(result), (true, false, false));
+}
+
+#[test]
+fn try_variants_return_errors() {
+  let subject = rectangle_polygon(Vec2::new(1.0, 1.0), Vec2::new(3.0, 3.0));
+  let clip = rectangle_polygon(Vec2::new(2.0, 2.0), Vec2::new(4.0, 4.0));
+  let invalid_clip = Polygon {
+    contours: vec![vec![
+      Vec2::new(1.0, 1.0),
+      Vec2::new(2.0, f32::NAN),
+      Vec2::new(2.0, 2.0),
+    ]],
+  };
+
+  assert_eq!(try_union(&subject, &clip), Ok(union(&subject, &clip)));
+  assert_eq!(
+    try_intersection(&subject, &invalid_clip),
+    Err(ClipError::InvalidInput {
+      is_from_subject: false,
+      contour: 0,
+      vertex: 1
+    })
+  );
+  let options = BooleanOptions { max_events: Some(16), ..Default::default() };
+  assert_eq!(
+    try_xor_with_options(&subject, &clip, &options),
+    Err(ClipError::CapacityExceeded { limit: 16 })
+  );
+  assert_eq!(
+    try_difference_with_options(&subject, &clip, &options),
+    Err(ClipError::CapacityExceeded { limit: 16 })
+  );
+}
+
+#[test]
+fn sweep_recovers_from_inconsistent_sweep_line() {
+  let polygon = rectangle_polygon(Vec2::new(1.0, 1.0), Vec2::new(3.0, 3.0));
+  let mut event_queue = BinaryHeap::new();
+  let mut event_relations = Vec::new();
+  create_events_for_polygon(
+    &polygon,
+    /* is_subject= */ true,
+    Arithmetic::F32,
+    &mut event_queue,
+    &mut event_relations,
+    /* x_limit= */ f32::INFINITY,
+  );
+  let events = event_queue_to_vec(event_queue);
+
+  // A left event queued twice is only inserted into the sweep line once.
+  let mut duplicated_queue =
+    events.iter().cloned().map(Reverse).collect::<BinaryHeap<_>>();
+  duplicated_queue.push(Reverse(events[0].clone()));
+  let processed_events = sweep_events(
+    duplicated_queue,
+    &mut event_relations.clone(),
+    &Operation::Union,
+    f32::INFINITY,
+    /* max_events= */ usize::MAX,
+  )
+  .unwrap();
+  assert_eq!(processed_events.len(), events.len());
+
+  // A right event whose left event never entered the sweep line cannot be
+  // removed, so the sweep reports it instead of continuing with a stale sweep
+  // line.
+  let missing_left_queue = events
+    .iter()
+    .filter(|event| event.event_id != events[0].event_id)
+    .cloned()
+    .map(Reverse)
+    .collect::<BinaryHeap<_>>();
+  let error = sweep_events(
+    missing_left_queue,
+    &mut event_relations,
+    &Operation::Union,
+    f32::INFINITY,
+    /* max_events= */ usize::MAX,
+  )
+  .unwrap_err();
+  assert!(matches!(
+    error,
+    SweepError::Inconsistent { stage: SweepStage::Subdivision, .. }
+  ));
+}
+
+#[test]
+fn join_contours_reports_where_it_failed() {
+  let polygon = rectangle_polygon(Vec2::new(1.0, 1.0), Vec2::new(3.0, 3.0));
+  let mut event_queue = BinaryHeap::new();
+  let mut event_relations = Vec::new();
+  create_events_for_polygon(
+    &polygon,
+    /* is_subject= */ true,
+    Arithmetic::F32,
+    &mut event_queue,
+    &mut event_relations,
+    /* x_limit= */ f32::INFINITY,
+  );
+  let Reverse(first_event) = event_queue.pop().unwrap();
+
+  // The sibling of the only result event is missing, so the contour cannot be
+  // closed.
+  let error =
+    join_contours(vec![first_event], event_relations, &Operation::Union)
+      .unwrap_err();
+  let expected_source_edge =
+    SourceEdge { is_from_subject: true, contour: 0, edge: 0 };
+  assert_eq!(
+    error,
+    SweepError::Inconsistent {
+      stage: SweepStage::ContourJoining,
+      point: Vec2::new(1.0, 1.0),
+      source_edge: expected_source_edge,
+      sweep_line_len: 0,
+    }
+  );
+  assert_eq!(
+    error.into_clip_error(usize::MAX).to_string(),
+    "the sweep became inconsistent due to rounding errors, even in f64 \
+     (contour joining at (1, 1) on edge 0 of contour 0 of the subject \
+     polygon, with 0 edges in the sweep line)"
+  );
+}
+
+#[test]
+fn rejects_out_of_range_input() {
+  let valid_polygon = Polygon {
+    contours: vec![vec![
+      Vec2::new(1.0, 1.0),
+      Vec2::new(2.0, 1.0),
+      Vec2::new(2.0, 2.0),
+    ]],
+  };
+  // Comparing edges this large would overflow.
+  let huge_polygon = Polygon {
+    contours: vec![vec![
+      Vec2::new(-1e30, -1e30),
+      Vec2::new(1e30, -1e30),
+      Vec2::new(0.0, 1e30),
+    ]],
+  };
+
+  assert_eq!(
+    validate_input(&valid_polygon, &huge_polygon),
+    Err(ClipError::CoordinateOutOfRange {
+      is_from_subject: false,
+      contour: 0,
+      vertex: 0
+    })
+  );
+}
+
+#[test]
+fn comparisons_do_not_panic_on_nan() {
+  let event = |event_id, point| Event {
+    event_id,
+    point,
+    left: true,
+    is_subject: true,
+    other_point: Vec2::new(5.0, 5.0),
+    arithmetic: Arithmetic::F32,
+  };
+  let nan_event = event(0, Vec2::new(f32::NAN, 1.0));
+  let finite_event = event(1, Vec2::new(1.0, 1.0));
+
+  assert_eq!(nan_event.cmp(&finite_event), std::cmp::Ordering::Greater);
+  assert_eq!(finite_event.cmp(&nan_event), std::cmp::Ordering::Less);
+  // The order of the sweep line is meaningless with a NaN, but comparing must
+  // not panic.
+  let _ = SweepLineEvent(finite_event).cmp(&SweepLineEvent(nan_event));
+}
+
+#[test]
+#[should_panic(expected = "the operation requires more than 16 events")]
+fn panics_when_capacity_exceeded() {
+  let subject = Polygon {
+    contours: vec![vec![
+      Vec2::new(1.0, 1.0),
+      Vec2::new(3.0, 1.0),
+      Vec2::new(3.0, 3.0),
+      Vec2::new(1.0, 3.0),
+    ]],
+  };
+  let clip = Polygon {
+    contours: vec![vec![
+      Vec2::new(2.0, 2.0),
+      Vec2::new(4.0, 2.0),
+      Vec2::new(4.0, 4.0),
+      Vec2::new(2.0, 4.0),
+    ]],
+  };
+
+  // The initial 16 events are allowed, but splitting the crossing edges needs
+  // more.
+  let options = BooleanOptions { max_events: Some(16), ..Default::default() };
+  union_with_options(&subject, &clip, &options);
+}
+
+#[test]
+#[should_panic(
+  expected = "the clip polygon has a non-finite coordinate at contour 0, vertex 2"
+)]
+fn panics_on_non_finite_input() {
+  let subject = Polygon {
+    contours: vec![vec![
+      Vec2::new(1.0, 1.0),
+      Vec2::new(2.0, 1.0),
+      Vec2::new(2.0, 2.0),
+    ]],
+  };
+  let clip = Polygon {
+    contours: vec![vec![
+      Vec2::new(1.0, 1.0),
+      Vec2::new(2.0, 1.0),
+      Vec2::new(2.0, f32::NEG_INFINITY),
+    ]],
+  };
+  union(&subject, &clip);
+}
+
+// Fills in the bounds of `result` from its points, so expected results don't
+// need to spell them out.
+fn with_bounds(mut result: BooleanResult) -> BooleanResult {
+  result.update_bounds();
+  result
+}
+
+// Computes the total signed area of `polygon`.
+fn polygon_area(polygon: &Polygon) -> f32 {
+  polygon.contours.iter().map(|contour| contour_signed_area(contour)).sum()
+}
+
+#[test]
+fn random_axis_aligned_rectangles_match_exact_areas() {
+  // Rectangles on a small integer grid share vertical edges, have vertices on
+  // each other's vertical edges, and touch at points, so this exercises the
+  // vertical edge tie-breaks. Every area is a small integer, so the areas of
+  // the results must be exact.
+  let mut rng = StdRng::seed_from_u64(3273);
+  let mut random_rectangle = || {
+    let (x0, x1) = (rng.gen_range(0..6), rng.gen_range(0..6));
+    let (y0, y1) = (rng.gen_range(0..6), rng.gen_range(0..6));
+    (
+      Vec2::new(x0.min(x1) as f32, y0.min(y1) as f32),
+      Vec2::new(x0.max(x1) as f32 + 1.0, y0.max(y1) as f32 + 1.0),
+    )
+  };
+
+  for _ in 0..300 {
+    let (subject_min, subject_max) = random_rectangle();
+    let (clip_min, clip_max) = random_rectangle();
+    let overlap =
+      (subject_max.min(clip_max) - subject_min.max(clip_min)).max(Vec2::ZERO);
+    let overlap_area = overlap.x * overlap.y;
+
+    let subject = rectangle_polygon(subject_min, subject_max);
+    let clip = rectangle_polygon(clip_min, clip_max);
+    let subject_area = polygon_area(&subject);
+    let clip_area = polygon_area(&clip);
+    let results = [
+      (intersection(&subject, &clip), overlap_area),
+      (union(&subject, &clip), subject_area + clip_area - overlap_area),
+      (difference(&subject, &clip), subject_area - overlap_area),
+      (xor(&subject, &clip), subject_area + clip_area - 2.0 * overlap_area),
+    ];
+    for (result, expected_area) in results {
+      assert_eq!(
+        polygon_area(&result.polygon),
+        expected_area,
+        "subject={subject:?}, clip={clip:?}"
+      );
+    }
+  }
+}
+
+#[test]
+fn rectangles_touching_at_corner_of_vertical_edges() {
+  // The vertical edges meet end to end, so four result edges share a vertex.
+  let subject = rectangle_polygon(Vec2::new(3.0, 2.0), Vec2::new(6.0, 4.0));
+  let clip = rectangle_polygon(Vec2::new(1.0, 4.0), Vec2::new(3.0, 6.0));
+
+  assert_eq!(polygon_area(&union(&subject, &clip).polygon), 10.0);
+  assert_eq!(polygon_area(&intersection(&subject, &clip).polygon), 0.0);
+}
+
+#[test]
+fn rectangle_with_vertex_on_vertical_edge() {
+  // The clip's vertices are on the interior of the subject's right edge.
+  let subject = rectangle_polygon(Vec2::new(0.0, 0.0), Vec2::new(3.0, 4.0));
+  let clip = rectangle_polygon(Vec2::new(3.0, 1.0), Vec2::new(4.0, 3.0));
+
+  assert_eq!(polygon_area(&union(&subject, &clip).polygon), 14.0);
+  assert_eq!(polygon_area(&intersection(&subject, &clip).polygon), 0.0);
+  assert_eq!(polygon_area(&difference(&subject, &clip).polygon), 12.0);
+}
+
+#[test]
+fn join_contours_stops_at_loops_missing_start() {
+  // The first edge leads into a triangle that never returns to the start of
+  // the first edge, so following the edges would loop forever.
+  let points = [
+    (Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0)),
+    (Vec2::new(1.0, 0.0), Vec2::new(2.0, 1.0)),
+    (Vec2::new(2.0, 1.0), Vec2::new(2.0, 0.0)),
+    (Vec2::new(2.0, 0.0), Vec2::new(1.0, 0.0)),
+  ];
+  let mut event_relations = Vec::new();
+  let mut result_events = Vec::new();
+  for (edge, (start, end)) in points.into_iter().enumerate() {
+    result_events.extend(
+      create_events_for_edge(
+        start,
+        end,
+        SourceEdge { is_from_subject: true, contour: 0, edge },
+        Arithmetic::F32,
+        &mut event_relations,
+      )
+      .unwrap(),
+    );
+  }
+  result_events.sort();
+
+  assert_eq!(
+    join_contours(result_events, event_relations, &Operation::Union),
+    Err(SweepError::Inconsistent {
+      stage: SweepStage::ContourJoining,
+      point: Vec2::new(0.0, 0.0),
+      source_edge: SourceEdge { is_from_subject: true, contour: 0, edge: 0 },
+      sweep_line_len: 0,
+    })
+  );
+}
+#[test]
+fn finds_sibling_after_split_point_is_rounded() {
+  // The right end of the subject's bottom edge is moved by rounding when the
+  // clip edge splits it, so the sweep line entry of the left end no longer
+  // matches the edge it is searched with.
+  let subject = Polygon {
+    contours: vec![vec![
+      Vec2::new(6.0, 4.0),
+      Vec2::new(4.0, 3.0),
+      Vec2::new(3.0, 3.0),
+    ]],
+  };
+  let clip = Polygon {
+    contours: vec![vec![
+      Vec2::new(6.0, 5.0),
+      Vec2::new(5.0, 3.0),
+      Vec2::new(5.0, 4.0),
+    ]],
+  };
+  assert!(try_intersection(&subject, &clip).is_ok());
+  assert!(try_xor(&subject, &clip).is_ok());
+}
+
+#[test]
+fn xor_contours_follow_one_region_through_touching_points() {
+  // The pieces of the XOR touch at both points where the edges intersect. The
+  // contours must not cross over to the other piece at those points, or they
+  // would wrap around the intersection (which is not in the XOR).
+  let subject = Polygon {
+    contours: vec![vec![
+      Vec2::new(28.95162, 10.322595),
+      Vec2::new(16.88587, 18.356405),
+      Vec2::new(27.228876, 26.997728),
+      Vec2::new(39.436153, 20.319855),
+    ]],
+  };
+  let clip = Polygon {
+    contours: vec![vec![
+      Vec2::new(53.57137, 47.99704),
+      Vec2::new(43.59049, 50.83744),
+      Vec2::new(34.806305, 54.542732),
+      Vec2::new(30.104124, 44.307953),
+      Vec2::new(13.748295, 23.988667),
+      Vec2::new(37.981674, 22.702703),
+      Vec2::new(49.585373, 34.376694),
+      Vec2::new(67.844955, 37.130375),
+    ]],
+  };
+  let area = |result: &BooleanResult| {
+    result
+      .polygon
+      .contours
+      .iter()
+      .map(|contour| contour_signed_area(contour))
+      .sum::<f32>()
+  };
+  let xor_area = area(&xor(&subject, &clip));
+  let expected_area =
+    area(&difference(&subject, &clip)) + area(&difference(&clip, &subject));
+  assert!(
+    (xor_area - expected_area).abs() < 1e-2,
+    "xor_area = {xor_area}, expected_area = {expected_area}"
+  );
+  for contour in xor(&subject, &clip).polygon.contours.iter() {
+    let mut points = contour
+      .iter()
+      .map(|point| point.to_array().map(f32::to_bits))
+      .collect::<Vec<_>>();
+    points.sort();
+    points.dedup();
+    assert_eq!(points.len(), contour.len(), "{contour:?}");
+  }
+}
+
+#[test]
+fn drops_contours_below_min_area() {
+  let subject = Polygon {
+    contours: vec![vec![
+      Vec2::new(0.0, 0.0),
+      Vec2::new(1.0, 0.0),
+      Vec2::new(1.0, 1.0),
+      Vec2::new(0.0, 1.0),
+    ]],
+  };
+  // The first contour is slightly taller than the subject, so subtracting the
+  // subject leaves a sliver along its top edge.
+  let clip = Polygon {
+    contours: vec![
+      vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(1.0, 1.00001),
+        Vec2::new(0.0, 1.00001),
+      ],
+      vec![
+        Vec2::new(3.0, 0.0),
+        Vec2::new(4.0, 0.0),
+        Vec2::new(4.0, 1.0),
+        Vec2::new(3.0, 1.0),
+      ],
+    ],
+  };
+
+  assert_eq!(difference(&clip, &subject).polygon.contours.len(), 2);
+  let options = BooleanOptions { min_contour_area: 1e-3, ..Default::default() };
+  let result = difference_with_options(&clip, &subject, &options);
+  assert_eq!(result.polygon.contours.len(), 1);
+  assert_eq!(result.contour_bounds.len(), 1);
+  assert_eq!(
+    result.contour_source_edges,
+    [(0..4)
+      .map(|edge| SourceEdge { is_from_subject: true, contour: 1, edge })
+      .collect::<Vec<_>>()]
+  );
+}
+
+#[test]
+fn collapses_near_duplicate_vertices() {
+  // The top edge has a vertex just left of the top right corner.
+  let subject = Polygon {
+    contours: vec![vec![
+      Vec2::new(0.0, 0.0),
+      Vec2::new(2.0, 0.0),
+      Vec2::new(2.0, 1.0),
+      Vec2::new(1.995, 1.0),
+      Vec2::new(0.0, 1.0),
+    ]],
+  };
+  let clip = rectangle_polygon(Vec2::new(-1.0, -1.0), Vec2::new(1.0, 0.5));
+
+  let result = union(&subject, &clip);
+  assert!(result.polygon.contours[0].contains(&Vec2::new(2.0, 1.0)));
+  let options = BooleanOptions {
+    duplicate_vertex_tolerance: Some(0.01),
+    ..Default::default()
+  };
+  let collapsed = union_with_options(&subject, &clip, &options);
+  let contour = &collapsed.polygon.contours[0];
+  assert_eq!(contour.len(), result.polygon.contours[0].len() - 1);
+  assert!(!contour.contains(&Vec2::new(2.0, 1.0)));
+  assert!(contour.contains(&Vec2::new(1.995, 1.0)));
+  assert_eq!(collapsed.contour_source_edges[0].len(), contour.len());
+  // The right edge now ends at the remaining vertex, keeping its source.
+  let right_edge =
+    contour.iter().position(|&point| point == Vec2::new(2.0, 0.0)).unwrap();
+  assert_eq!(contour[(right_edge + 1) % contour.len()], Vec2::new(1.995, 1.0));
+  assert_eq!(
+    collapsed.contour_source_edges[0][right_edge],
+    SourceEdge { is_from_subject: true, contour: 0, edge: 1 }
+  );
+}
+
+#[test]
+fn collapses_exact_duplicate_vertices() {
+  let subject = Polygon {
+    contours: vec![
+      vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(0.0, 1.0),
+      ],
+      // Collapses to fewer than 3 vertices.
+      vec![Vec2::new(5.0, 5.0), Vec2::new(5.0, 5.0), Vec2::new(6.0, 6.0)],
+    ],
+  };
+  // The clip is far away, so the result reuses the subject's contours.
+  let clip = rectangle_polygon(Vec2::new(10.0, 10.0), Vec2::new(11.0, 11.0));
+  let options = BooleanOptions {
+    duplicate_vertex_tolerance: Some(0.0),
+    ..Default::default()
+  };
+  let result = union_with_options(&subject, &clip, &options);
+  assert_eq!(
+    result.polygon.contours,
+    [
+      rectangle_polygon(Vec2::ZERO, Vec2::ONE).contours[0].clone(),
+      rectangle_polygon(Vec2::new(10.0, 10.0), Vec2::new(11.0, 11.0)).contours
+        [0]
+        .clone(),
+    ]
+  );
+  assert_eq!(
+    result.contour_source_edges[0],
+    [0, 2, 3, 4].map(|edge| SourceEdge {
+      is_from_subject: true,
+      contour: 0,
+      edge
+    })
+  );
+}
+
+#[test]
+fn options_builder_sets_fields() {
+  let options = BooleanOptions::new()
+    .with_fill_rule(FillRule::NonZero)
+    .with_epsilon(0.01)
+    .with_output_orientation(OutputOrientation::ClockwiseShells)
+    .with_simplification(true, 0.5)
+    .with_tracking(TrackingLevel::Provenance)
+    .with_max_events(100);
+  assert_eq!(
+    options,
+    BooleanOptions {
+      fill_rule: FillRule::NonZero,
+      boundary_tolerance: 0.01,
+      duplicate_vertex_tolerance: Some(0.01),
+      output_orientation: OutputOrientation::ClockwiseShells,
+      merge_collinear_edges: true,
+      min_contour_area: 0.5,
+      track_hierarchy: true,
+      classify_relation: true,
+      track_input_equality: true,
+      track_unmodified_edges: true,
+      track_edge_parameters: true,
+      track_vertex_errors: true,
+      max_events: Some(100),
+      ..Default::default()
+    }
+  );
+  // Lower levels clear the flags of higher levels.
+  assert_eq!(
+    options.with_tracking(TrackingLevel::Minimal),
+    BooleanOptions {
+      fill_rule: FillRule::NonZero,
+      boundary_tolerance: 0.01,
+      duplicate_vertex_tolerance: Some(0.01),
+      output_orientation: OutputOrientation::ClockwiseShells,
+      merge_collinear_edges: true,
+      min_contour_area: 0.5,
+      max_events: Some(100),
+      ..Default::default()
+    }
+  );
+}
+
+#[test]
+fn fill_rule_resolves_overlapping_input_contours() {
+  // Two overlapping counter-clockwise squares. With the even-odd rule, their
+  // overlap is outside the subject.
+  let mut subject = rectangle_polygon(Vec2::ZERO, Vec2::new(2.0, 2.0));
+  subject
+    .contours
+    .append(&mut rectangle_polygon(Vec2::ONE, Vec2::new(3.0, 3.0)).contours);
+  let clip = rectangle_polygon(Vec2::ZERO, Vec2::new(4.0, 4.0));
+  let area = |result: &BooleanResult| {
+    result
+      .polygon
+      .contours
+      .iter()
+      .map(|contour| contour_signed_area(contour))
+      .sum::<f32>()
+  };
+
+  assert_eq!(area(&intersection(&subject, &clip)), 6.0);
+  let options = BooleanOptions::new().with_fill_rule(FillRule::NonZero);
+  let result = intersection_with_options(&subject, &clip, &options);
+  assert_eq!(area(&result), 7.0);
+  assert_eq!(result.polygon.contours.len(), 1);
+  // Source edges refer to the edges of the original subject. Each edge of
+  // both squares is partly on the outline.
+  let mut source_edges = result.contour_source_edges[0].clone();
+  source_edges
+    .sort_by_key(|source_edge| (source_edge.contour, source_edge.edge));
+  assert_eq!(
+    source_edges,
+    (0..2)
+      .flat_map(|contour| (0..4).map(move |edge| (contour, edge)))
+      .map(|(contour, edge)| SourceEdge {
+        is_from_subject: true,
+        contour,
+        edge
+      })
+      .collect::<Vec<_>>()
+  );
+}
+
+#[test]
+fn fill_rules_apply_to_each_operand() {
+  // The subject is two nested counter-clockwise squares, and the clip is two
+  // overlapping counter-clockwise rectangles on its right side.
+  let mut subject = rectangle_polygon(Vec2::ZERO, Vec2::new(4.0, 4.0));
+  subject
+    .contours
+    .append(&mut rectangle_polygon(Vec2::ONE, Vec2::new(3.0, 3.0)).contours);
+  let mut clip = rectangle_polygon(Vec2::new(2.0, -1.0), Vec2::new(5.0, 3.0));
+  clip.contours.append(
+    &mut rectangle_polygon(Vec2::new(2.5, 1.0), Vec2::new(6.0, 5.0)).contours,
+  );
+  let area = |result: &BooleanResult| {
+    result
+      .polygon
+      .contours
+      .iter()
+      .map(|contour| contour_signed_area(contour))
+      .sum::<f32>()
+  };
+
+  // With the even-odd rule, the subject has a hole and the clip is missing
+  // the overlap.
+  assert_eq!(area(&intersection(&subject, &clip)), 3.5);
+  // The non-zero subject is the whole square, intersected with the even-odd
+  // clip.
+  let options = BooleanOptions::new()
+    .with_fill_rule(FillRule::NonZero)
+    .with_clip_fill_rule(FillRule::EvenOdd);
+  assert_eq!(options.fill_rules(), (FillRule::NonZero, FillRule::EvenOdd));
+  assert_eq!(area(&intersection_with_options(&subject, &clip, &options)), 4.5);
+  // The even-odd subject intersected with the whole non-zero clip.
+  let options = BooleanOptions::new()
+    .with_fill_rule(FillRule::EvenOdd)
+    .with_clip_fill_rule(FillRule::NonZero);
+  assert_eq!(area(&intersection_with_options(&subject, &clip, &options)), 5.5);
+  // Clockwise contours are outside with the positive rule.
+  let mut reversed_clip = clip.clone();
+  for contour in reversed_clip.contours.iter_mut() {
+    contour.reverse();
+  }
+  let options = BooleanOptions::new().with_fill_rule(FillRule::Positive);
+  assert!(
+    intersection_with_options(&subject, &reversed_clip, &options).is_empty
+  );
+  let options = options.with_clip_fill_rule(FillRule::Negative);
+  assert_eq!(
+    area(&intersection_with_options(&subject, &reversed_clip, &options)),
+    7.5
+  );
+}
+
+#[test]
+fn collects_warnings() {
+  // The subject repeats its bottom right corner, and its right edge coincides
+  // with the left edge of the first clip contour. The second clip contour is a
+  // sliver.
+  let subject = Polygon {
+    contours: vec![vec![
+      Vec2::new(0.0, 0.0),
+      Vec2::new(2.0, 0.0),
+      Vec2::new(2.0, 0.0),
+      Vec2::new(2.0, 2.0),
+      Vec2::new(0.0, 2.0),
+    ]],
+  };
+  let mut clip = rectangle_polygon(Vec2::new(2.0, 0.0), Vec2::new(4.0, 2.0));
+  clip.contours.push(vec![
+    Vec2::new(10.0, 0.0),
+    Vec2::new(14.0, 0.0),
+    Vec2::new(14.0, 0.01),
+  ]);
+
+  let options = BooleanOptions { min_contour_area: 0.1, ..Default::default() };
+  assert_eq!(union_with_options(&subject, &clip, &options).warnings, []);
+  let options = BooleanOptions { collect_warnings: true, ..options };
+  let result = union_with_options(&subject, &clip, &options);
+  assert_eq!(
+    result.warnings,
+    [
+      ClipWarning::DegenerateEdge {
+        source_edge: SourceEdge { is_from_subject: true, contour: 0, edge: 1 }
+      },
+      ClipWarning::CoincidentEdges { count: 1 },
+      ClipWarning::SliverContoursRemoved { count: 1 },
+    ]
+  );
+  assert_eq!(
+    result.warnings[1].to_string(),
+    "the subject and clip boundaries coincide in 1 places"
+  );
+}
+
+#[test]
+fn warns_about_snapped_vertices() {
+  let subject = rectangle_polygon(Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0));
+  let clip = rectangle_polygon(Vec2::new(2.001, 0.0), Vec2::new(4.0, 2.0));
+  let options = BooleanOptions {
+    weld_tolerance: 0.01,
+    collect_warnings: true,
+    ..Default::default()
+  };
+  assert_eq!(
+    union_with_options(&subject, &clip, &options).warnings,
+    [
+      ClipWarning::VerticesSnapped { count: 2 },
+      ClipWarning::CoincidentEdges { count: 1 }
+    ]
+  );
+  // The clip's left edge is moved onto the subject's right edge.
+  let clip = rectangle_polygon(Vec2::new(2.001, 0.5), Vec2::new(4.0, 1.5));
+  let options = BooleanOptions {
+    boundary_tolerance: 0.01,
+    collect_warnings: true,
+    ..Default::default()
+  };
+  assert_eq!(
+    union_with_options(&subject, &clip, &options).warnings,
+    [
+      ClipWarning::VerticesSnapped { count: 2 },
+      ClipWarning::CoincidentEdges { count: 1 }
+    ]
+  );
+}
+
+#[test]
+fn boolean_selects_operation_at_runtime() {
+  let subject = rectangle_polygon(Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0));
+  let clip = rectangle_polygon(Vec2::new(1.0, 1.0), Vec2::new(3.0, 3.0));
+  let expected = [
+    intersection(&subject, &clip),
+    union(&subject, &clip),
+    xor(&subject, &clip),
+    difference(&subject, &clip),
+  ];
+  for (operation, expected) in Operation::ALL.into_iter().zip(expected) {
+    assert_eq!(boolean(&subject, &clip, operation), expected);
+    assert_eq!(try_boolean(&subject, &clip, operation), Ok(expected.clone()));
+    let options = BooleanOptions::new().with_tracking(TrackingLevel::Topology);
+    assert_eq!(
+      boolean_with_options(&subject, &clip, operation, &options).relation,
+      Some(PolygonRelation::PartialOverlap)
+    );
+  }
+}
@@ -0,0 +1,43 @@
+// Polygons shared by the tests of several modules.
+
+use glam::Vec2;
+
+use crate::Polygon;
+
+// The counter-clockwise contour of the axis-aligned square with the corner
+// `min` and sides of length `size`.
+pub(crate) fn square(min: Vec2, size: f32) -> Vec<Vec2> {
+  rectangle(min, min + Vec2::splat(size))
+}
+
+// The counter-clockwise contour of the axis-aligned rectangle from `min` to
+// `max`.
+pub(crate) fn rectangle(min: Vec2, max: Vec2) -> Vec<Vec2> {
+  vec![min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)]
+}
+
+// A polygon with only the contour of `square`.
+pub(crate) fn square_polygon(min: Vec2, size: f32) -> Polygon {
+  Polygon { contours: vec![square(min, size)] }
+}
+
+// A polygon with only the contour of `rectangle`.
+pub(crate) fn rectangle_polygon(min: Vec2, max: Vec2) -> Polygon {
+  Polygon { contours: vec![rectangle(min, max)] }
+}
+
+// A heptagon whose edges cannot be split consistently in f32: the intersection
+// of edges 1 and 2 is computed a rounding error away from their shared vertex.
+pub(crate) fn heptagon() -> Polygon {
+  Polygon {
+    contours: vec![vec![
+      Vec2::new(16.426622, 5.97274),
+      Vec2::new(18.344215, 5.924168),
+      Vec2::new(18.470703, 4.278999),
+      Vec2::new(17.538916, 1.5802376),
+      Vec2::new(15.208319, 1.680757),
+      Vec2::new(15.526888, 4.1016545),
+      Vec2::new(15.102979, 5.091341),
+    ]],
+  }
+}
@@ -0,0 +1,382 @@
+use std::collections::HashMap;
+
+use glam::{IVec2, Vec2};
+
+use crate::{
+  segments::sweep_polygon_and_segments, sweep_with_f64_retry, validate_input,
+  ClipError, EdgeCoincidenceType, Polygon,
+};
+
+// A regular grid of axis-aligned cells, for `tile`. Cell (x, y) covers
+// `origin + cell_size * (x, y)` to `origin + cell_size * (x + 1, y + 1)`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Grid {
+  pub origin: Vec2,
+  pub cell_size: Vec2,
+}
+
+impl Grid {
+  // Creates a grid of square cells of `cell_size` with a corner at the origin.
+  pub fn new(cell_size: f32) -> Self {
+    Self { origin: Vec2::ZERO, cell_size: Vec2::splat(cell_size) }
+  }
+
+  // Returns the same grid, but with a corner at `origin`.
+  pub fn with_origin(mut self, origin: Vec2) -> Self {
+    self.origin = origin;
+    self
+  }
+}
+
+// The piece of a polygon in one cell of a grid.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Tile {
+  // The index of the cell (see `Grid`).
+  pub cell: IVec2,
+  pub polygon: Polygon,
+}
+
+// Clips `polygon` (using the even-odd rule) to each cell of `grid`, returning
+// the non-empty tiles ordered by row, then column. The polygon and every grid
+// line are subdivided in a single sweep (recomputed in f64 if rounding errors
+// make it inconsistent), so tiles sharing a cell border use the same points
+// along it and stitch together exactly. Shells are counter-clockwise and holes
+// are clockwise. This panics if the polygon cannot be tiled (see `try_tile`).
+pub fn tile(polygon: &Polygon, grid: &Grid) -> Vec<Tile> {
+  try_tile(polygon, grid).unwrap_or_else(|error| panic!("{error}"))
+}
+
+// Same as `tile`, but returns an error instead of panicking. A grid whose cell
+// size is not positive and finite (or whose origin is not finite) is reported
+// as invalid clip input.
+pub fn try_tile(
+  polygon: &Polygon,
+  grid: &Grid,
+) -> Result<Vec<Tile>, ClipError> {
+  if !grid.origin.is_finite()
+    || !grid.cell_size.is_finite()
+    || grid.cell_size.cmple(Vec2::ZERO).any()
+  {
+    return Err(ClipError::InvalidInput {
+      is_from_subject: false,
+      contour: 0,
+      vertex: 0,
+    });
+  }
+  let Some(bounds) = polygon.compute_bounds() else {
+    validate_input(polygon, &Polygon::default())?;
+    return Ok(Vec::new());
+  };
+  let lines = GridLines::new(grid, bounds);
+  let segments = lines.segments(bounds);
+  validate_input(
+    polygon,
+    &Polygon {
+      contours: segments.iter().map(|&(start, end)| vec![start, end]).collect(),
+    },
+  )?;
+
+  let ((processed_events, event_relations), _) =
+    sweep_with_f64_retry(|arithmetic| {
+      sweep_polygon_and_segments(polygon, &segments, arithmetic)
+    })
+    .map_err(|error| error.into_clip_error(usize::MAX))?;
+
+  // The edges of each tile, oriented with the inside on their left.
+  let mut cell_edges = HashMap::<IVec2, Vec<(Vec2, Vec2)>>::new();
+  for event in processed_events.iter().filter(|event| event.left) {
+    let relation = &event_relations[event.event_id];
+    // The primary edge of a coincident pair describes both edges.
+    if relation.edge_coincidence_type
+      == EdgeCoincidenceType::DuplicateCoincidence
+    {
+      continue;
+    }
+    let (left, right) = (event.point, relation.sibling_point);
+    let context = event.edge_context(relation);
+    // The grid line the piece is along, if any.
+    let line = std::iter::once(relation.source_edge)
+      .chain(relation.coincident_source_edge)
+      .find(|source_edge| !source_edge.is_from_subject)
+      .map(|source_edge| source_edge.contour);
+
+    if context.in_subject_below != context.in_subject_above {
+      // A piece of the polygon's boundary, which only bounds the cell on its
+      // inside. Above is to the left for vertical edges, so the edge goes from
+      // left to right (or bottom to top) if the inside is above.
+      let edge =
+        if context.in_subject_above { (left, right) } else { (right, left) };
+      cell_edges.entry(lines.cell_inside(edge, line)).or_default().push(edge);
+    } else if let (Some(line), true) = (line, context.in_subject_above) {
+      // A piece of a grid line inside the polygon, which bounds the cells on
+      // both sides.
+      for edge in [(left, right), (right, left)] {
+        cell_edges
+          .entry(lines.cell_inside(edge, Some(line)))
+          .or_default()
+          .push(edge);
+      }
+    }
+  }
+
+  let mut tiles = cell_edges
+    .into_iter()
+    .map(|(cell, edges)| Tile {
+      cell,
+      polygon: Polygon { contours: join_edges(edges) },
+    })
+    .filter(|tile| !tile.polygon.contours.is_empty())
+    .collect::<Vec<_>>();
+  tiles.sort_by_key(|tile| (tile.cell.y, tile.cell.x));
+  Ok(tiles)
+}
+
+// The grid lines crossing the bounds of a polygon.
+struct GridLines {
+  // The index of the first cell column and row in the bounds.
+  first_cell: IVec2,
+  // The coordinates of the vertical and horizontal lines strictly inside the
+  // bounds, in increasing order.
+  xs: Vec<f32>,
+  ys: Vec<f32>,
+}
+
+impl GridLines {
+  fn new(grid: &Grid, (min, max): (Vec2, Vec2)) -> Self {
+    let first_cell = ((min - grid.origin) / grid.cell_size).floor().as_ivec2();
+    let lines =
+      |first: i32, origin: f32, cell_size: f32, min: f32, max: f32| {
+        (first + 1..)
+          .map(move |index| origin + index as f32 * cell_size)
+          .skip_while(move |&line| line <= min)
+          .take_while(move |&line| line < max)
+          .collect::<Vec<_>>()
+      };
+    Self {
+      first_cell,
+      xs: lines(first_cell.x, grid.origin.x, grid.cell_size.x, min.x, max.x),
+      ys: lines(first_cell.y, grid.origin.y, grid.cell_size.y, min.y, max.y),
+    }
+  }
+
+  // The segments of the lines across `bounds`. The vertical lines come first,
+  // so a segment's index is also the index of its line.
+  fn segments(&self, (min, max): (Vec2, Vec2)) -> Vec<(Vec2, Vec2)> {
+    let vertical =
+      self.xs.iter().map(|&x| (Vec2::new(x, min.y), Vec2::new(x, max.y)));
+    let horizontal =
+      self.ys.iter().map(|&y| (Vec2::new(min.x, y), Vec2::new(max.x, y)));
+    vertical.chain(horizontal).collect()
+  }
+
+  // The cell on the left of `edge`, which lies within one cell or along the
+  // grid line with index `line`.
+  fn cell_inside(
+    &self,
+    (start, end): (Vec2, Vec2),
+    line: Option<usize>,
+  ) -> IVec2 {
+    let middle = (start + end) * 0.5;
+    let mut column = self.xs.partition_point(|&x| x <= middle.x) as i32;
+    let mut row = self.ys.partition_point(|&y| y <= middle.y) as i32;
+    // Points split off a line can be rounded slightly off it, so edges along a
+    // line use its index instead. Edges going up have the cell after a vertical
+    // line on their right, and edges going left have the cell after a
+    // horizontal line on their right.
+    match line {
+      Some(line) if line < self.xs.len() => {
+        column = line as i32 + 1 - i32::from(end.y > start.y);
+      }
+      Some(line) => {
+        row = (line - self.xs.len()) as i32 + 1 - i32::from(end.x < start.x);
+      }
+      None => {}
+    }
+    self.first_cell + IVec2::new(column, row)
+  }
+}
+
+// Joins `edges` (which form closed loops) into contours. Where several loops
+// meet at a point, they are joined in an arbitrary order.
+fn join_edges(edges: Vec<(Vec2, Vec2)>) -> Vec<Vec<Vec2>> {
+  let key = |point: Vec2| [point.x.to_bits(), point.y.to_bits()];
+  let mut outgoing = HashMap::<[u32; 2], Vec<usize>>::new();
+  for (index, &(start, _)) in edges.iter().enumerate().rev() {
+    outgoing.entry(key(start)).or_default().push(index);
+  }
+
+  let mut used = vec![false; edges.len()];
+  let mut contours = Vec::new();
+  for first in 0..edges.len() {
+    if used[first] {
+      continue;
+    }
+    let mut contour = Vec::new();
+    let mut index = first;
+    loop {
+      used[index] = true;
+      let (start, end) = edges[index];
+      contour.push(start);
+      if end == contour[0] {
+        break;
+      }
+      let next = outgoing
+        .get_mut(&key(end))
+        .and_then(|indices| {
+          while let Some(&next) = indices.last() {
+            if !used[next] {
+              return Some(next);
+            }
+            indices.pop();
+          }
+          None
+        })
+        .or_else(|| {
+          // Crossing edges can be split at points a rounding error apart (see
+          // `compute_contour`), so continue from the closest point instead.
+          let tolerance = 1e-5 * end.abs().max_element().max(1.0);
+          (0..edges.len())
+            .filter(|&next| !used[next])
+            .map(|next| (next, edges[next].0.distance(end)))
+            .filter(|&(_, distance)| distance <= tolerance)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(next, _)| next)
+        });
+      match next {
+        Some(next) => index = next,
+        // The loop is closed up to a rounding error.
+        None => break,
+      }
+    }
+    if contour.len() >= 3 {
+      contours.push(contour);
+    }
+  }
+  contours
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::{IVec2, Vec2};
+
+  use super::GridLines;
+  use crate::tests::fixtures::{heptagon, rectangle};
+  use crate::{
+    intersection, segments::sweep_polygon_and_segments, tile, try_tile,
+    util::contour_signed_area, Arithmetic, ClipError, Grid, Polygon,
+    SweepError,
+  };
+
+  // The area of `polygon`, whose holes are oriented opposite to its shells.
+  fn area(polygon: &Polygon) -> f32 {
+    polygon.contours.iter().map(|contour| contour_signed_area(contour)).sum()
+  }
+
+  #[test]
+  fn tiles_square_with_hole() {
+    let polygon = Polygon {
+      contours: vec![
+        rectangle(Vec2::new(-2.0, -2.0), Vec2::new(2.0, 2.0)),
+        rectangle(Vec2::new(-0.5, -0.5), Vec2::new(0.5, 0.5)),
+      ],
+    };
+    let tiles = tile(&polygon, &Grid::new(2.0));
+    assert_eq!(
+      tiles.iter().map(|tile| tile.cell).collect::<Vec<_>>(),
+      [
+        IVec2::new(-1, -1),
+        IVec2::new(0, -1),
+        IVec2::new(-1, 0),
+        IVec2::new(0, 0)
+      ]
+    );
+    // Each tile has a quarter of the square and of the hole.
+    for tile in &tiles {
+      assert_eq!(area(&tile.polygon), 4.0 - 0.25, "{tile:?}");
+    }
+
+    // A hole inside a single cell stays a hole.
+    let tiles = tile(&polygon, &Grid::new(4.0).with_origin(Vec2::splat(-2.0)));
+    assert_eq!(tiles.len(), 1);
+    assert_eq!(tiles[0].cell, IVec2::ZERO);
+    assert_eq!(tiles[0].polygon.contours.len(), 2);
+    assert_eq!(area(&tiles[0].polygon), 16.0 - 1.0);
+  }
+
+  #[test]
+  fn tiles_match_intersections_with_cells() {
+    let polygon = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.3, 0.1),
+        Vec2::new(5.2, 1.7),
+        Vec2::new(3.9, 4.6),
+        Vec2::new(2.1, 2.2),
+        Vec2::new(0.7, 3.8),
+      ]],
+    };
+    let grid =
+      Grid { origin: Vec2::new(0.5, 0.0), cell_size: Vec2::new(1.5, 1.0) };
+    let tiles = tile(&polygon, &grid);
+    for tile in &tiles {
+      let min = grid.origin + grid.cell_size * tile.cell.as_vec2();
+      let cell =
+        Polygon { contours: vec![rectangle(min, min + grid.cell_size)] };
+      let expected = area(&intersection(&polygon, &cell).polygon);
+      let actual = area(&tile.polygon);
+      assert!((expected - actual).abs() < 1e-5, "{expected} != {actual}");
+    }
+    let total = tiles.iter().map(|tile| area(&tile.polygon)).sum::<f32>();
+    assert!((total - area(&polygon)).abs() < 1e-4);
+
+    // Tiles next to each other share the points along their border.
+    let points = |cell: IVec2| {
+      tiles
+        .iter()
+        .find(|tile| tile.cell == cell)
+        .unwrap()
+        .polygon
+        .contours
+        .concat()
+    };
+    let border_x = grid.origin.x + grid.cell_size.x * 2.0;
+    let left = points(IVec2::new(1, 1));
+    let right = points(IVec2::new(2, 1));
+    for point in left.iter().filter(|point| point.x == border_x) {
+      assert!(right.contains(point), "{point:?}");
+    }
+  }
+
+  #[test]
+  fn rejects_invalid_grid() {
+    let polygon = Polygon { contours: vec![rectangle(Vec2::ZERO, Vec2::ONE)] };
+    assert_eq!(
+      try_tile(&polygon, &Grid::new(0.0)),
+      Err(ClipError::InvalidInput {
+        is_from_subject: false,
+        contour: 0,
+        vertex: 0
+      })
+    );
+    assert_eq!(tile(&Polygon::default(), &Grid::new(1.0)), []);
+  }
+
+  #[test]
+  fn tiles_polygon_needing_f64() {
+    let polygon = heptagon();
+    let grid = Grid::new(16.0);
+    let bounds = polygon.compute_bounds().unwrap();
+    let segments = GridLines::new(&grid, bounds).segments(bounds);
+    assert!(matches!(
+      sweep_polygon_and_segments(&polygon, &segments, Arithmetic::F32),
+      Err(SweepError::Inconsistent { .. })
+    ));
+
+    let tiles = try_tile(&polygon, &grid).unwrap();
+    assert_eq!(
+      tiles.iter().map(|tile| tile.cell).collect::<Vec<_>>(),
+      [IVec2::new(0, 0), IVec2::new(1, 0)]
+    );
+    let tiled_area: f32 = tiles.iter().map(|tile| area(&tile.polygon)).sum();
+    assert!((tiled_area + area(&polygon)).abs() < 1e-4);
+  }
+}
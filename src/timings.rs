@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+// The time spent in each phase of a boolean operation. This is useful for
+// finding which phase is slow for particular inputs (e.g. to decide whether
+// to prepare the clip with `PreparedClip`).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct Timings {
+  // Creating the events of the edges of both polygons and queueing them.
+  pub event_creation: Duration,
+  // Sweeping over the events, which is where edges are subdivided at their
+  // intersections.
+  pub subdivision: Duration,
+  // Selecting the result edges and joining them into contours (including
+  // collecting the optional data derived from the sweep).
+  pub contour_join: Duration,
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square_polygon;
+  use crate::{
+    union, union_with_options, xor_with_options, BooleanOptions, BooleanResult,
+  };
+
+  #[test]
+  fn only_collects_timings_when_requested() {
+    let subject = square_polygon(Vec2::ZERO, 2.0);
+    let clip = square_polygon(Vec2::ONE, 2.0);
+    let options =
+      BooleanOptions { collect_timings: true, ..Default::default() };
+
+    let result = union_with_options(&subject, &clip, &options);
+    assert!(result.timings.is_some());
+    // The timings are the only difference.
+    assert_eq!(
+      BooleanResult { timings: None, ..result },
+      union(&subject, &clip)
+    );
+
+    // Disjoint polygons are computed trivially, without a sweep.
+    let far_clip = square_polygon(Vec2::splat(10.0), 1.0);
+    assert_eq!(xor_with_options(&subject, &far_clip, &options).timings, None);
+  }
+}
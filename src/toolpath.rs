@@ -0,0 +1,276 @@
+use std::fmt::Write;
+
+use glam::Vec2;
+
+use crate::{
+  hierarchy::{compute_contour_parents, contour_depth},
+  util::contour_signed_area,
+  BooleanResult,
+};
+
+// The language of an exported toolpath.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ToolpathFormat {
+  // G-code for CNC mills and routers, in millimeters.
+  #[default]
+  GCode,
+  // HPGL for pen plotters and vinyl cutters. Coordinates are treated as
+  // millimeters and converted to plotter units (40 per millimeter).
+  Hpgl,
+}
+
+// The direction the tool moves around the material, assuming a clockwise
+// spindle (M3).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CutDirection {
+  // The material is on the right of the tool: shells are cut clockwise and
+  // holes counter-clockwise.
+  #[default]
+  Climb,
+  // The material is on the left of the tool: shells are cut counter-clockwise
+  // and holes clockwise.
+  Conventional,
+}
+
+// Options to control how toolpaths are exported.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ToolpathOptions {
+  // The language to export.
+  pub format: ToolpathFormat,
+  // The direction to cut each contour in.
+  pub direction: CutDirection,
+  // The distance to move the tool away from the material, so the edge of the
+  // tool (rather than its center) follows the contours. This is a simple
+  // miter offset of each contour: it is only correct if the radius is small
+  // compared to the features of the result (offset contours must not overlap
+  // themselves or each other).
+  pub tool_radius: f32,
+  // The height the tool travels at between contours (G-code only).
+  pub safe_z: f32,
+  // The depth the tool cuts at, as a positive distance below zero (G-code
+  // only).
+  pub cut_depth: f32,
+  // The speed of cutting moves (G-code only).
+  pub feed_rate: f32,
+  // The speed of plunging into the material (G-code only).
+  pub plunge_rate: f32,
+}
+
+impl Default for ToolpathOptions {
+  fn default() -> Self {
+    Self {
+      format: ToolpathFormat::default(),
+      direction: CutDirection::default(),
+      tool_radius: 0.0,
+      safe_z: 5.0,
+      cut_depth: 1.0,
+      feed_rate: 300.0,
+      plunge_rate: 100.0,
+    }
+  }
+}
+
+// Exports the contours of `result` as a toolpath. Contours nested more deeply
+// are cut first (e.g. holes before their shells), so parts stay attached to
+// the stock until their outline is cut. Contours with fewer than 2 vertices
+// are skipped.
+pub fn export_toolpath(
+  result: &BooleanResult,
+  options: &ToolpathOptions,
+) -> String {
+  let paths = toolpath_contours(result, options);
+  match options.format {
+    ToolpathFormat::GCode => gcode(&paths, options),
+    ToolpathFormat::Hpgl => hpgl(&paths),
+  }
+}
+
+// Computes the path of the tool around each contour of `result`, in the order
+// they are cut. The first point is not repeated at the end.
+fn toolpath_contours(
+  result: &BooleanResult,
+  options: &ToolpathOptions,
+) -> Vec<Vec<Vec2>> {
+  let contour_parents = if result.contour_parents.is_empty() {
+    compute_contour_parents(&result.polygon)
+  } else {
+    result.contour_parents.clone()
+  };
+
+  let mut order = (0..result.polygon.contours.len()).collect::<Vec<_>>();
+  order.sort_by_key(|&index| {
+    std::cmp::Reverse(contour_depth(&contour_parents, index))
+  });
+  order
+    .into_iter()
+    .filter(|&index| result.polygon.contours[index].len() >= 2)
+    .map(|index| {
+      // Orient the contour with the material on its left, so the tool is
+      // always offset to the right.
+      let mut contour = result.polygon.contours[index].clone();
+      let is_hole = contour_depth(&contour_parents, index) % 2 == 1;
+      if (contour_signed_area(&contour) < 0.0) != is_hole {
+        reverse_keeping_start(&mut contour);
+      }
+      if options.tool_radius != 0.0 {
+        contour = offset_right(&contour, options.tool_radius);
+      }
+      if options.direction == CutDirection::Climb {
+        reverse_keeping_start(&mut contour);
+      }
+      contour
+    })
+    .collect()
+}
+
+// Reverses the direction of `contour` without changing its first vertex.
+fn reverse_keeping_start(contour: &mut [Vec2]) {
+  contour.reverse();
+  contour.rotate_right(1);
+}
+
+// Moves each vertex of `contour` so the edges are offset by `distance` to
+// their right (using miter joins).
+fn offset_right(contour: &[Vec2], distance: f32) -> Vec<Vec2> {
+  let right_normal = |from: Vec2, to: Vec2| {
+    let direction = (to - from).normalize_or_zero();
+    Vec2::new(direction.y, -direction.x)
+  };
+  (0..contour.len())
+    .map(|index| {
+      let previous = contour[(index + contour.len() - 1) % contour.len()];
+      let point = contour[index];
+      let next = contour[(index + 1) % contour.len()];
+      let previous_normal = right_normal(previous, point);
+      let next_normal = right_normal(point, next);
+      // The miter is the sum of the normals, scaled so each edge moves by
+      // exactly `distance`. Nearly reversing edges would need an unbounded
+      // miter, so those only move along the next edge's normal.
+      let denominator = 1.0 + previous_normal.dot(next_normal);
+      if denominator < 1e-3 {
+        point + next_normal * distance
+      } else {
+        point + (previous_normal + next_normal) * (distance / denominator)
+      }
+    })
+    .collect()
+}
+
+// Writes `paths` as G-code.
+fn gcode(paths: &[Vec<Vec2>], options: &ToolpathOptions) -> String {
+  let mut output = String::new();
+  // Writing to a String never fails.
+  let mut line = |text: String| writeln!(output, "{text}").unwrap();
+  line("G21".into());
+  line("G90".into());
+  line(format!("G0 Z{:.3}", options.safe_z));
+  for path in paths {
+    line(format!("G0 X{:.3} Y{:.3}", path[0].x, path[0].y));
+    line(format!("G1 Z{:.3} F{:.3}", -options.cut_depth, options.plunge_rate));
+    for (index, point) in path.iter().skip(1).chain(&path[..1]).enumerate() {
+      if index == 0 {
+        line(format!(
+          "G1 X{:.3} Y{:.3} F{:.3}",
+          point.x, point.y, options.feed_rate
+        ));
+      } else {
+        line(format!("G1 X{:.3} Y{:.3}", point.x, point.y));
+      }
+    }
+    line(format!("G0 Z{:.3}", options.safe_z));
+  }
+  line("M2".into());
+  output
+}
+
+// Writes `paths` as HPGL.
+fn hpgl(paths: &[Vec<Vec2>]) -> String {
+  let plotter_units = |point: &Vec2| {
+    format!("{},{}", (point.x * 40.0).round(), (point.y * 40.0).round())
+  };
+  let mut output = String::from("IN;SP1;");
+  for path in paths {
+    output.push_str(&format!("PU{};", plotter_units(&path[0])));
+    let drawn = path
+      .iter()
+      .skip(1)
+      .chain(&path[..1])
+      .map(plotter_units)
+      .collect::<Vec<_>>();
+    output.push_str(&format!("PD{};", drawn.join(",")));
+  }
+  output.push_str("PU;SP0;");
+  output
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::rectangle;
+  use crate::{
+    difference, export_toolpath, BooleanResult, CutDirection, Polygon,
+    ToolpathFormat, ToolpathOptions,
+  };
+
+  // A 10x10 frame with a 4x4 hole in the middle.
+  fn frame() -> BooleanResult {
+    difference(
+      &Polygon {
+        contours: vec![rectangle(Vec2::splat(0.0), Vec2::splat(10.0))],
+      },
+      &Polygon {
+        contours: vec![rectangle(Vec2::splat(3.0), Vec2::splat(7.0))],
+      },
+    )
+  }
+
+  #[test]
+  fn exports_gcode_with_holes_first() {
+    let options = ToolpathOptions {
+      direction: CutDirection::Conventional,
+      ..Default::default()
+    };
+    assert_eq!(
+      export_toolpath(&frame(), &options),
+      "G21
+G90
+G0 Z5.000
+G0 X3.000 Y7.000
+G1 Z-1.000 F100.000
+G1 X7.000 Y7.000 F300.000
+G1 X7.000 Y3.000
+G1 X3.000 Y3.000
+G1 X3.000 Y7.000
+G0 Z5.000
+G0 X0.000 Y0.000
+G1 Z-1.000 F100.000
+G1 X10.000 Y0.000 F300.000
+G1 X10.000 Y10.000
+G1 X0.000 Y10.000
+G1 X0.000 Y0.000
+G0 Z5.000
+M2
+"
+    );
+  }
+
+  #[test]
+  fn climb_offsets_away_from_material() {
+    let options = ToolpathOptions {
+      format: ToolpathFormat::Hpgl,
+      direction: CutDirection::Climb,
+      tool_radius: 0.5,
+      ..Default::default()
+    };
+    // The hole shrinks and the shell grows. Shells are cut clockwise and holes
+    // counter-clockwise.
+    assert_eq!(
+      export_toolpath(&frame(), &options),
+      "IN;SP1;\
+PU140,260;PD140,140,260,140,260,260,140,260;\
+PU-20,-20;PD-20,420,420,420,420,-20,-20,-20;\
+PU;SP0;"
+    );
+  }
+}
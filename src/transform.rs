@@ -0,0 +1,235 @@
+use glam::{Affine2, Mat3, Vec2};
+
+use crate::{BooleanResult, Polygon};
+
+impl Polygon {
+  // Moves every vertex of the polygon by `offset`.
+  pub fn translate(&mut self, offset: Vec2) {
+    self.map_vertices(|point| point + offset);
+  }
+
+  // Same as `translate`, but returns the moved polygon (e.g. for chaining).
+  pub fn translated(mut self, offset: Vec2) -> Polygon {
+    self.translate(offset);
+    self
+  }
+
+  // Rotates every vertex of the polygon counter-clockwise by `angle` (in
+  // radians) around `center`.
+  pub fn rotate_around(&mut self, center: Vec2, angle: f32) {
+    let rotation = Vec2::from_angle(angle);
+    self.map_vertices(|point| center + rotation.rotate(point - center));
+  }
+
+  // Same as `rotate_around`, but returns the rotated polygon.
+  pub fn rotated_around(mut self, center: Vec2, angle: f32) -> Polygon {
+    self.rotate_around(center, angle);
+    self
+  }
+
+  // Scales every vertex of the polygon by `factor` (per axis) relative to the
+  // origin. A negative factor along exactly one axis mirrors the polygon,
+  // which reverses the orientation of its contours (see
+  // `Polygon::normalize_winding`).
+  pub fn scale(&mut self, factor: Vec2) {
+    self.map_vertices(|point| point * factor);
+  }
+
+  // Same as `scale`, but returns the scaled polygon.
+  pub fn scaled(mut self, factor: Vec2) -> Polygon {
+    self.scale(factor);
+    self
+  }
+
+  // Applies `transform` to every vertex of the polygon. Like `scale`, a
+  // transform that mirrors the polygon (with a negative determinant) reverses
+  // the orientation of its contours.
+  pub fn transform(&mut self, transform: Affine2) {
+    self.map_vertices(|point| transform.transform_point2(point));
+  }
+
+  // Same as `transform`, but returns the transformed polygon.
+  pub fn transformed(mut self, transform: Affine2) -> Polygon {
+    self.transform(transform);
+    self
+  }
+
+  // Same as `transform`, but with a 3x3 matrix acting on homogeneous
+  // coordinates (e.g. from a scene graph). Only affine transforms are
+  // supported, so the bottom row of `matrix` is ignored.
+  pub fn transform_mat3(&mut self, matrix: Mat3) {
+    self.transform(Affine2::from_mat3(matrix));
+  }
+
+  // Replaces every vertex of the polygon with `map(vertex)`.
+  fn map_vertices(&mut self, map: impl Fn(Vec2) -> Vec2) {
+    for point in self.contours.iter_mut().flatten() {
+      *point = map(*point);
+    }
+  }
+}
+
+impl BooleanResult {
+  // Applies `transform` to the resulting polygon, along with the other
+  // geometric data (`overlap_segments`, `contact_points` and the bounds), e.g.
+  // to place a result computed in local coordinates. Contours keep their
+  // vertices in the same order, so `contour_source_edges` (and the other
+  // per-edge data) still refers to the same source edges. A transform that
+  // mirrors the result (with a negative determinant) would flip every contour's
+  // orientation, so the contours are reversed to keep the orientation they
+  // had, with their per-edge data reversed along with them.
+  // `contour_vertex_errors` grow with the largest stretch of the transform.
+  pub fn transform(&mut self, transform: Affine2) {
+    self.polygon.transform(transform);
+    for (start, end) in self.overlap_segments.iter_mut() {
+      *start = transform.transform_point2(*start);
+      *end = transform.transform_point2(*end);
+    }
+    for point in self.contact_points.iter_mut() {
+      *point = transform.transform_point2(*point);
+    }
+    let stretch = max_stretch(transform);
+    for error in self.contour_vertex_errors.iter_mut().flatten() {
+      *error *= stretch;
+    }
+    if transform.matrix2.determinant() < 0.0 {
+      for contour_index in 0..self.polygon.contours.len() {
+        self.reverse_contour(contour_index);
+      }
+    }
+    self.update_bounds();
+  }
+
+  // Same as `transform`, but returns the transformed result.
+  pub fn transformed(mut self, transform: Affine2) -> BooleanResult {
+    self.transform(transform);
+    self
+  }
+
+  // Same as `transform`, but with a 3x3 matrix acting on homogeneous
+  // coordinates (see `Polygon::transform_mat3`).
+  pub fn transform_mat3(&mut self, matrix: Mat3) {
+    self.transform(Affine2::from_mat3(matrix));
+  }
+}
+
+// Computes the largest factor that `transform` stretches any vector by (the
+// largest singular value of its linear part).
+fn max_stretch(transform: Affine2) -> f32 {
+  let matrix = transform.matrix2;
+  let squared_norm =
+    matrix.x_axis.length_squared() + matrix.y_axis.length_squared();
+  let determinant = matrix.determinant();
+  let discriminant =
+    (squared_norm * squared_norm - 4.0 * determinant * determinant).max(0.0);
+  ((squared_norm + discriminant.sqrt()) * 0.5).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+  use std::f32::consts::FRAC_PI_2;
+
+  use glam::{Affine2, Mat3, Vec2};
+
+  use crate::tests::fixtures::square_polygon;
+  use crate::{difference_with_options, BooleanOptions, Winding};
+
+  #[test]
+  fn transforms_in_place_and_by_value() {
+    let mut polygon = square_polygon(Vec2::ZERO, 1.0);
+    polygon.translate(Vec2::new(2.0, 3.0));
+    assert_eq!(
+      polygon,
+      square_polygon(Vec2::ZERO, 1.0).translated(Vec2::new(2.0, 3.0))
+    );
+    assert_eq!(
+      polygon.compute_bounds(),
+      Some((Vec2::new(2.0, 3.0), Vec2::new(3.0, 4.0)))
+    );
+
+    let mut polygon = square_polygon(Vec2::ZERO, 1.0);
+    polygon.scale(Vec2::new(2.0, 3.0));
+    assert_eq!(
+      polygon,
+      square_polygon(Vec2::ZERO, 1.0).scaled(Vec2::new(2.0, 3.0))
+    );
+    assert_eq!(polygon.area(), 6.0);
+  }
+
+  #[test]
+  fn rotates_around_center() {
+    let rotated = square_polygon(Vec2::ZERO, 1.0)
+      .rotated_around(Vec2::new(1.0, 1.0), FRAC_PI_2);
+    let (min, max) = rotated.compute_bounds().unwrap();
+    assert!(min.abs_diff_eq(Vec2::new(1.0, 0.0), 1e-6), "{min}");
+    assert!(max.abs_diff_eq(Vec2::new(2.0, 1.0), 1e-6), "{max}");
+    // Rotations keep the orientation.
+    assert_eq!(rotated.contour_orientation(0), Winding::CounterClockwise);
+  }
+
+  #[test]
+  fn mirroring_reverses_orientation() {
+    let mirrored = square_polygon(Vec2::ZERO, 1.0).scaled(Vec2::new(-1.0, 1.0));
+    assert_eq!(mirrored.contour_orientation(0), Winding::Clockwise);
+    assert_eq!(mirrored.area(), -1.0);
+  }
+
+  #[test]
+  fn applies_affine_transforms() {
+    let transform = Affine2::from_scale_angle_translation(
+      Vec2::splat(2.0),
+      FRAC_PI_2,
+      Vec2::new(5.0, 0.0),
+    );
+    let polygon = square_polygon(Vec2::ZERO, 1.0).transformed(transform);
+    let (min, max) = polygon.compute_bounds().unwrap();
+    assert!(min.abs_diff_eq(Vec2::new(3.0, 0.0), 1e-6), "{min}");
+    assert!(max.abs_diff_eq(Vec2::new(5.0, 2.0), 1e-6), "{max}");
+
+    let mut from_matrix = square_polygon(Vec2::ZERO, 1.0);
+    from_matrix.transform_mat3(Mat3::from(transform));
+    assert_eq!(from_matrix, polygon);
+  }
+
+  #[test]
+  fn transforms_results_keeping_source_edges() {
+    let subject = square_polygon(Vec2::ZERO, 1.0).scaled(Vec2::splat(3.0));
+    let hole = square_polygon(Vec2::ZERO, 1.0).translated(Vec2::ONE);
+    let options =
+      BooleanOptions { track_vertex_errors: true, ..Default::default() };
+    let result = difference_with_options(&subject, &hole, &options);
+
+    let moved =
+      result.clone().transformed(Affine2::from_translation(Vec2::X * 10.0));
+    assert_eq!(moved.contour_source_edges, result.contour_source_edges);
+    assert_eq!(moved.contour_vertex_errors, result.contour_vertex_errors);
+    assert_eq!(
+      moved.bounds,
+      Some((Vec2::new(10.0, 0.0), Vec2::new(13.0, 3.0)))
+    );
+
+    // Mirroring keeps shells counter-clockwise and holes clockwise, and each
+    // edge still lies along its (mirrored) source edge.
+    let mirror = Affine2::from_scale(Vec2::new(-1.0, 1.0));
+    let mirrored = result.clone().transformed(mirror);
+    assert_eq!(mirrored.area(), result.area());
+    let sources = [subject, hole].map(|polygon| polygon.transformed(mirror));
+    for (contour_index, contour) in mirrored.polygon.contours.iter().enumerate()
+    {
+      for (edge, source_edge) in
+        mirrored.contour_source_edges[contour_index].iter().enumerate()
+      {
+        let source = &sources[usize::from(!source_edge.is_from_subject)];
+        let source_contour = &source.contours[source_edge.contour];
+        let source_start = source_contour[source_edge.edge];
+        let source_direction = source_contour
+          [(source_edge.edge + 1) % source_contour.len()]
+          - source_start;
+        let start = contour[edge];
+        let end = contour[(edge + 1) % contour.len()];
+        assert_eq!((end - start).perp_dot(source_direction), 0.0);
+        assert_eq!((start - source_start).perp_dot(source_direction), 0.0);
+      }
+    }
+  }
+}
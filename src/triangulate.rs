@@ -0,0 +1,359 @@
+use glam::Vec2;
+
+use crate::{
+  hierarchy::{compute_contour_parents, contour_depth},
+  util::contour_signed_area,
+  BooleanResult, Polygon,
+};
+
+// Triangulates `polygon`, whose contours are nested as described by
+// `contour_parents` (see `BooleanResult::contour_parents`). Vertices are
+// numbered in contour order, so vertex `i` is the `i`-th vertex when iterating
+// over all contours. The returned triangles are counter-clockwise. Each hole is
+// bridged into its shell, and the shells are then ear clipped, so this takes
+// O(n^2) time for n vertices (or worse for numerically degenerate contours).
+pub(crate) fn triangulate(
+  polygon: &Polygon,
+  contour_parents: &[Option<usize>],
+) -> Vec<[usize; 3]> {
+  let points = polygon.contours.iter().flatten().copied().collect::<Vec<_>>();
+  let mut first_index = 0;
+  let mut rings = Vec::with_capacity(polygon.contours.len());
+  for contour in polygon.contours.iter() {
+    rings.push((first_index..first_index + contour.len()).collect::<Vec<_>>());
+    first_index += contour.len();
+  }
+
+  let mut triangles = Vec::new();
+  for (shell_index, shell) in polygon.contours.iter().enumerate() {
+    if contour_depth(contour_parents, shell_index) % 2 == 1 {
+      continue;
+    }
+    // Shells are counter-clockwise and holes are clockwise, so the interior is
+    // always on the left.
+    let mut outer = rings[shell_index].clone();
+    if contour_signed_area(shell) < 0.0 {
+      outer.reverse();
+    }
+    let mut holes = (0..polygon.contours.len())
+      .filter(|&index| contour_parents[index] == Some(shell_index))
+      .filter(|&index| polygon.contours[index].len() >= 3)
+      .map(|index| {
+        let mut hole = rings[index].clone();
+        if contour_signed_area(&polygon.contours[index]) > 0.0 {
+          hole.reverse();
+        }
+        hole
+      })
+      .collect::<Vec<_>>();
+    // Bridging the rightmost holes first keeps earlier bridges from blocking
+    // later ones.
+    holes.sort_by(|a, b| {
+      let max_x = |hole: &[usize]| {
+        hole.iter().map(|&index| points[index].x).fold(f32::MIN, f32::max)
+      };
+      max_x(b).total_cmp(&max_x(a))
+    });
+    for hole in holes {
+      bridge_hole(&points, &mut outer, &hole);
+    }
+    clip_ears(&points, outer, &mut triangles);
+  }
+  triangles
+}
+
+impl Polygon {
+  // Triangulates the polygon (see `BooleanResult::triangulate`). Holes are
+  // found by nesting, so the contours must not cross each other.
+  pub fn triangulate(&self) -> Vec<[usize; 3]> {
+    triangulate(self, &compute_contour_parents(self))
+  }
+}
+
+impl BooleanResult {
+  // Triangulates the resulting polygon, returning counter-clockwise triangles
+  // as indices into the vertices of all contours in order (so vertex `i` is the
+  // `i`-th vertex of `polygon.contours.iter().flatten()`). Each hole is bridged
+  // into the shell containing it, so no triangle covers a hole. This uses
+  // `contour_parents` if it was computed.
+  pub fn triangulate(&self) -> Vec<[usize; 3]> {
+    if self.contour_parents.len() == self.polygon.contours.len() {
+      triangulate(&self.polygon, &self.contour_parents)
+    } else {
+      self.polygon.triangulate()
+    }
+  }
+}
+
+// Determines which side of the line from `a` to `b` `point` is on: positive
+// for the left, negative for the right, and 0 for on the line.
+fn orient(a: Vec2, b: Vec2, point: Vec2) -> f32 {
+  (b - a).perp_dot(point - a)
+}
+
+// Determines whether `point` is inside (or on the boundary of) the
+// counter-clockwise triangle `a`, `b`, `c`.
+fn triangle_contains(a: Vec2, b: Vec2, c: Vec2, point: Vec2) -> bool {
+  orient(a, b, point) >= 0.0
+    && orient(b, c, point) >= 0.0
+    && orient(c, a, point) >= 0.0
+}
+
+// Determines whether the direction from `point` to `target` goes into the
+// interior of the counter-clockwise ring at `point` (between the edges to
+// `previous` and `next`).
+fn locally_inside(
+  previous: Vec2,
+  point: Vec2,
+  next: Vec2,
+  target: Vec2,
+) -> bool {
+  if orient(previous, point, next) >= 0.0 {
+    orient(point, next, target) >= 0.0 && orient(point, previous, target) <= 0.0
+  } else {
+    orient(point, previous, target) < 0.0 || orient(point, next, target) > 0.0
+  }
+}
+
+// Splices the clockwise `hole` into the counter-clockwise `outer` ring through
+// a bridge from the rightmost vertex of the hole to a vertex of `outer` that it
+// can see. Both ends of the bridge are repeated, so the ring goes around the
+// hole and back.
+fn bridge_hole(points: &[Vec2], outer: &mut Vec<usize>, hole: &[usize]) {
+  let hole_start = (0..hole.len())
+    .max_by(|&a, &b| points[hole[a]].x.total_cmp(&points[hole[b]].x))
+    .unwrap();
+  let hole_point = points[hole[hole_start]];
+
+  // Find the closest edge of `outer` hit by the ray going right from the hole.
+  // Since `outer` is counter-clockwise, the edges right of its interior go up.
+  let mut closest: Option<(f32, usize)> = None;
+  for position in 0..outer.len() {
+    let start = points[outer[position]];
+    let end = points[outer[(position + 1) % outer.len()]];
+    if !(start.y <= hole_point.y && hole_point.y <= end.y) || start.y == end.y {
+      continue;
+    }
+    let x = start.x
+      + (hole_point.y - start.y) / (end.y - start.y) * (end.x - start.x);
+    if hole_point.x <= x && closest.is_none_or(|(closest_x, _)| x < closest_x) {
+      closest = Some((x, position));
+    }
+  }
+  let Some((hit_x, edge_position)) = closest else {
+    // The hole is not inside `outer` (e.g. the hierarchy is inconsistent), so
+    // it cannot be bridged.
+    return;
+  };
+  let hit = Vec2::new(hit_x, hole_point.y);
+  let next_position = (edge_position + 1) % outer.len();
+  let mut bridge_position =
+    if points[outer[edge_position]].x > points[outer[next_position]].x {
+      edge_position
+    } else {
+      next_position
+    };
+
+  // Other vertices of `outer` inside the triangle between the hole, the hit
+  // point, and the candidate may block the bridge. Of those, the one with the
+  // smallest angle to the ray is visible.
+  let candidate = points[outer[bridge_position]];
+  if hit != candidate {
+    let (a, b, c) = if candidate.y < hole_point.y {
+      (hole_point, candidate, hit)
+    } else {
+      (hole_point, hit, candidate)
+    };
+    let mut best_tangent = f32::INFINITY;
+    for position in 0..outer.len() {
+      let point = points[outer[position]];
+      if point == candidate
+        || point.x < hole_point.x
+        || !triangle_contains(a, b, c, point)
+      {
+        continue;
+      }
+      let previous = points[outer[(position + outer.len() - 1) % outer.len()]];
+      let next = points[outer[(position + 1) % outer.len()]];
+      let tangent = (point.y - hole_point.y).abs() / (point.x - hole_point.x);
+      if tangent < best_tangent
+        && locally_inside(previous, point, next, hole_point)
+      {
+        best_tangent = tangent;
+        bridge_position = position;
+      }
+    }
+  }
+  // Where several vertices of `outer` share the bridge point (e.g. from an
+  // earlier bridge), use the one whose interior contains the bridge.
+  let bridge_point = points[outer[bridge_position]];
+  if let Some(position) = (0..outer.len()).find(|&position| {
+    points[outer[position]] == bridge_point
+      && locally_inside(
+        points[outer[(position + outer.len() - 1) % outer.len()]],
+        bridge_point,
+        points[outer[(position + 1) % outer.len()]],
+        hole_point,
+      )
+  }) {
+    bridge_position = position;
+  }
+
+  let spliced = (0..=hole.len())
+    .map(|offset| hole[(hole_start + offset) % hole.len()])
+    .chain(std::iter::once(outer[bridge_position]));
+  outer.splice(bridge_position + 1..bridge_position + 1, spliced);
+}
+
+// Ear clips the counter-clockwise `ring` (of indices into `points`), appending
+// the triangles to `triangles`.
+fn clip_ears(
+  points: &[Vec2],
+  ring: Vec<usize>,
+  triangles: &mut Vec<[usize; 3]>,
+) {
+  let mut previous = (0..ring.len())
+    .map(|index| (index + ring.len() - 1) % ring.len())
+    .collect::<Vec<_>>();
+  let mut next =
+    (0..ring.len()).map(|index| (index + 1) % ring.len()).collect::<Vec<_>>();
+  let mut remaining = ring.len();
+  let mut current = 0;
+  // The number of vertices checked since the last ear was clipped.
+  let mut checked = 0;
+  while remaining >= 3 {
+    let (a, b, c) = (previous[current], current, next[current]);
+    let (point_a, point_b, point_c) =
+      (points[ring[a]], points[ring[b]], points[ring[c]]);
+    let convex = orient(point_a, point_b, point_c) > 0.0;
+    let is_ear = convex && {
+      // An ear may not contain any other vertex of the ring. Vertices at the
+      // same point as the corners (e.g. bridge ends) do not count.
+      let mut other = next[c];
+      let mut empty = true;
+      while other != a {
+        let point = points[ring[other]];
+        if point != point_a
+          && point != point_b
+          && point != point_c
+          && triangle_contains(point_a, point_b, point_c, point)
+        {
+          empty = false;
+          break;
+        }
+        other = next[other];
+      }
+      empty
+    };
+    // If no vertex is an ear (which only happens for numerically degenerate
+    // rings), clip any convex vertex so the loop always terminates.
+    if is_ear || (checked > remaining && convex) {
+      triangles.push([ring[a], ring[b], ring[c]]);
+      next[a] = c;
+      previous[c] = a;
+      remaining -= 1;
+      checked = 0;
+      current = c;
+      continue;
+    }
+    if checked > 2 * remaining {
+      // Only collinear (or reflex) vertices are left, so the rest of the ring
+      // has no area.
+      break;
+    }
+    checked += 1;
+    current = c;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::square;
+  use crate::{difference, hierarchy::compute_contour_parents, Polygon};
+
+  use super::triangulate;
+
+  // Sums the areas of `triangles` (which must be counter-clockwise).
+  fn area(polygon: &Polygon, triangles: &[[usize; 3]]) -> f32 {
+    let points = polygon.contours.iter().flatten().copied().collect::<Vec<_>>();
+    triangles
+      .iter()
+      .map(|&[a, b, c]| {
+        let area =
+          (points[b] - points[a]).perp_dot(points[c] - points[a]) * 0.5;
+        assert!(area >= 0.0, "triangle {a}, {b}, {c} is clockwise");
+        area
+      })
+      .sum()
+  }
+
+  #[test]
+  fn triangulates_shell_with_holes() {
+    let polygon = Polygon {
+      contours: vec![
+        vec![
+          Vec2::new(0.0, 0.0),
+          Vec2::new(10.0, 0.0),
+          Vec2::new(10.0, 10.0),
+          Vec2::new(0.0, 10.0),
+        ],
+        vec![
+          Vec2::new(2.0, 2.0),
+          Vec2::new(2.0, 4.0),
+          Vec2::new(4.0, 4.0),
+          Vec2::new(4.0, 2.0),
+        ],
+        // Counter-clockwise, so it has to be reversed.
+        vec![
+          Vec2::new(6.0, 6.0),
+          Vec2::new(8.0, 6.0),
+          Vec2::new(8.0, 8.0),
+          Vec2::new(6.0, 8.0),
+        ],
+      ],
+    };
+    let triangles = triangulate(&polygon, &compute_contour_parents(&polygon));
+    // Each hole adds 6 vertices (its 4 vertices and both bridge ends again) to
+    // the ring, which is clipped into 2 triangles fewer than its vertices.
+    assert_eq!(triangles.len(), 4 + 6 + 6 - 2);
+    assert_eq!(area(&polygon, &triangles), 100.0 - 4.0 - 4.0);
+  }
+
+  #[test]
+  fn triangulates_concave_shells() {
+    // A U shape, with collinear vertices along the bottom.
+    let polygon = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(3.0, 0.0),
+        Vec2::new(3.0, 3.0),
+        Vec2::new(2.0, 3.0),
+        Vec2::new(2.0, 1.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(1.0, 3.0),
+        Vec2::new(0.0, 3.0),
+      ]],
+    };
+    let triangles = triangulate(&polygon, &compute_contour_parents(&polygon));
+    assert_eq!(triangles.len(), 8);
+    assert_eq!(area(&polygon, &triangles), 7.0);
+  }
+
+  #[test]
+  fn triangulates_boolean_results() {
+    let result = difference(
+      &Polygon { contours: vec![square(Vec2::ZERO, 4.0)] },
+      &Polygon { contours: vec![square(Vec2::ONE, 2.0)] },
+    );
+    assert_eq!(result.polygon.contours.len(), 2);
+    let triangles = result.triangulate();
+    let vertex_count = result.polygon.contours.iter().flatten().count();
+    assert!(triangles.iter().flatten().all(|&index| index < vertex_count));
+    assert_eq!(area(&result.polygon, &triangles), result.area());
+    assert_eq!(result.polygon.triangulate(), triangles);
+  }
+}
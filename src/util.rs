@@ -57,24 +57,184 @@ pub fn edge_intersection(
   let line_1_len_squared = line_1_vector.length_squared();
   let sa = relative_start.dot(line_1_vector) / line_1_len_squared;
   let sb = sa + line_1_vector.dot(line_2_vector) / line_1_len_squared;
-  let smin = sa.min(sb);
-  let smax = sa.max(sb);
+  let ((smin, min_point), (smax, max_point)) = if sa <= sb {
+    ((sa, line_2.0), (sb, line_2.1))
+  } else {
+    ((sb, line_2.1), (sa, line_2.0))
+  };
 
   if smax <= 0.0 || 1.0 <= smin {
     return EdgeIntersectionResult::NoIntersection;
   }
 
+  // Every end point of the overlap is an end point of one of the lines, so use
+  // those exactly rather than recomputing them from `smin` and `smax`.
   EdgeIntersectionResult::LineIntersection(
-    line_1.0 + smin.max(0.0) * line_1_vector,
-    line_1.0 + smax.min(1.0) * line_1_vector,
+    if smin <= 0.0 { line_1.0 } else { min_point },
+    if 1.0 <= smax { line_1.1 } else { max_point },
   )
 }
 
+// Same as `edge_intersection`, but computed in a local frame centered on the
+// bounding box of both lines. For short lines far from the origin, most of the
+// bits of each coordinate are spent on the offset from the origin. Working
+// relative to the center keeps every intermediate value small, so the split
+// point is only limited by rounding it back to the original frame.
+// Intersections at end points of the lines are the exact end points.
+pub fn edge_intersection_in_local_frame(
+  line_1: (Vec2, Vec2),
+  line_2: (Vec2, Vec2),
+) -> EdgeIntersectionResult {
+  let min = line_1.0.min(line_1.1).min(line_2.0.min(line_2.1));
+  let max = line_1.0.max(line_1.1).max(line_2.0.max(line_2.1));
+  let center = (min + max) * 0.5;
+  let local_points =
+    [line_1.0, line_1.1, line_2.0, line_2.1].map(|point| point - center);
+  // Maps a point in the local frame back, restoring end points exactly (since
+  // translating back may round).
+  let to_global = |local_point: Vec2| match local_points
+    .iter()
+    .position(|&point| point == local_point)
+  {
+    Some(index) => [line_1.0, line_1.1, line_2.0, line_2.1][index],
+    None => local_point + center,
+  };
+  match edge_intersection(
+    (local_points[0], local_points[1]),
+    (local_points[2], local_points[3]),
+  ) {
+    EdgeIntersectionResult::NoIntersection => {
+      EdgeIntersectionResult::NoIntersection
+    }
+    EdgeIntersectionResult::PointIntersection(point) => {
+      EdgeIntersectionResult::PointIntersection(to_global(point))
+    }
+    EdgeIntersectionResult::LineIntersection(start, end) => {
+      EdgeIntersectionResult::LineIntersection(to_global(start), to_global(end))
+    }
+  }
+}
+
+// Same as `edge_intersection`, but computed in f64. Every f32 converts to f64
+// exactly, and so do the differences and products of nearby f32 coordinates,
+// so lines that share an end point (or where an end point lies on the other
+// line) are recognized exactly. Split points are rounded to f32 only once, and
+// intersections at end points of the lines are the exact end points.
+pub fn edge_intersection_f64(
+  line_1: (Vec2, Vec2),
+  line_2: (Vec2, Vec2),
+) -> EdgeIntersectionResult {
+  let (start_1, end_1) = (line_1.0.as_dvec2(), line_1.1.as_dvec2());
+  let (start_2, end_2) = (line_2.0.as_dvec2(), line_2.1.as_dvec2());
+  let line_1_vector = end_1 - start_1;
+  let line_2_vector = end_2 - start_2;
+
+  let relative_start = start_2 - start_1;
+  let cross = line_1_vector.perp_dot(line_2_vector);
+
+  if cross != 0.0 {
+    let s = relative_start.perp_dot(line_2_vector) / cross;
+    if !(0.0..=1.0).contains(&s) {
+      return EdgeIntersectionResult::NoIntersection;
+    }
+
+    let t = relative_start.perp_dot(line_1_vector) / cross;
+    if !(0.0..=1.0).contains(&t) {
+      return EdgeIntersectionResult::NoIntersection;
+    }
+
+    if (s == 0.0 || s == 1.0) && (t == 0.0 || t == 1.0) {
+      return EdgeIntersectionResult::NoIntersection;
+    }
+
+    let point = if s == 0.0 {
+      line_1.0
+    } else if s == 1.0 {
+      line_1.1
+    } else if t == 0.0 {
+      line_2.0
+    } else if t == 1.0 {
+      line_2.1
+    } else {
+      (start_1 + s * line_1_vector).as_vec2()
+    };
+    return EdgeIntersectionResult::PointIntersection(point);
+  }
+  // Line segments are parallel, so either they are on the same line and
+  // overlapping, or there is no intersection.
+  if relative_start.perp_dot(line_1_vector) != 0.0 {
+    return EdgeIntersectionResult::NoIntersection;
+  }
+
+  let line_1_len_squared = line_1_vector.length_squared();
+  let sa = relative_start.dot(line_1_vector) / line_1_len_squared;
+  let sb = sa + line_1_vector.dot(line_2_vector) / line_1_len_squared;
+  let ((smin, min_point), (smax, max_point)) = if sa <= sb {
+    ((sa, line_2.0), (sb, line_2.1))
+  } else {
+    ((sb, line_2.1), (sa, line_2.0))
+  };
+
+  if smax <= 0.0 || 1.0 <= smin {
+    return EdgeIntersectionResult::NoIntersection;
+  }
+
+  EdgeIntersectionResult::LineIntersection(
+    if smin <= 0.0 { line_1.0 } else { min_point },
+    if 1.0 <= smax { line_1.1 } else { max_point },
+  )
+}
+
+// Computes the signed area of `contour` using the shoelace formula. The area is
+// positive if the contour is counter-clockwise and negative otherwise.
+pub fn contour_signed_area(contour: &[Vec2]) -> f32 {
+  let mut double_area = 0.0;
+  for index in 0..contour.len() {
+    let next_index = if index == contour.len() - 1 { 0 } else { index + 1 };
+    double_area += contour[index].perp_dot(contour[next_index]);
+  }
+  double_area * 0.5
+}
+
+// Determines whether `point` is inside `contour` (using the even-odd rule).
+// Points exactly on the boundary may be considered either inside or outside.
+pub fn point_in_contour(point: Vec2, contour: &[Vec2]) -> bool {
+  let mut inside = false;
+  for index in 0..contour.len() {
+    let next_index = if index == contour.len() - 1 { 0 } else { index + 1 };
+    let (a, b) = (contour[index], contour[next_index]);
+    // Only edges that straddle the horizontal line through `point` can cross
+    // the ray going right from `point`.
+    if (a.y > point.y) == (b.y > point.y) {
+      continue;
+    }
+    let crossing_x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+    if point.x < crossing_x {
+      inside = !inside;
+    }
+  }
+  inside
+}
+
+// Computes the closest point to `point` on `segment`.
+pub fn closest_point_on_segment(segment: (Vec2, Vec2), point: Vec2) -> Vec2 {
+  let vector = segment.1 - segment.0;
+  let length_squared = vector.length_squared();
+  if length_squared == 0.0 {
+    return segment.0;
+  }
+  let t = ((point - segment.0).dot(vector) / length_squared).clamp(0.0, 1.0);
+  segment.0 + t * vector
+}
+
 #[cfg(test)]
 mod tests {
-  use glam::Vec2;
+  use glam::{DVec2, Vec2};
 
-  use crate::util::{edge_intersection, EdgeIntersectionResult};
+  use crate::util::{
+    edge_intersection, edge_intersection_f64, edge_intersection_in_local_frame,
+    EdgeIntersectionResult,
+  };
 
   #[test]
   fn unaligned_edges_intersect() {
@@ -294,4 +454,83 @@ mod tests {
       EdgeIntersectionResult::LineIntersection(line.0, line.1)
     );
   }
+
+  // Computes the intersection point of the lines through `line_1` and `line_2`
+  // in f64.
+  fn exact_intersection(line_1: (Vec2, Vec2), line_2: (Vec2, Vec2)) -> DVec2 {
+    let (a, b) = (line_1.0.as_dvec2(), line_1.1.as_dvec2());
+    let (c, d) = (line_2.0.as_dvec2(), line_2.1.as_dvec2());
+    let s = (c - a).perp_dot(d - c) / (b - a).perp_dot(d - c);
+    a + s * (b - a)
+  }
+
+  #[test]
+  fn local_frame_split_points_are_accurate_far_from_origin() {
+    // Short crossing edges of the polygons in
+    // `tests::floating_point_inaccuracy_polygons`, translated far from the
+    // origin.
+    let line_1 = (Vec2::new(1.0, -2.0), Vec2::new(2.0, -1.0));
+    let line_2 = (Vec2::new(1.3, -1.0), Vec2::new(1.6, -2.0));
+    for offset in [1.0e3, 1.0e4, 5.0e4] {
+      let offset = Vec2::new(offset, -offset * 0.75);
+      let line_1 = (line_1.0 + offset, line_1.1 + offset);
+      let line_2 = (line_2.0 + offset, line_2.1 + offset);
+      let expected = exact_intersection(line_1, line_2);
+
+      let EdgeIntersectionResult::PointIntersection(global) =
+        edge_intersection(line_1, line_2)
+      else {
+        panic!("lines should cross");
+      };
+      let EdgeIntersectionResult::PointIntersection(local) =
+        edge_intersection_in_local_frame(line_1, line_2)
+      else {
+        panic!("lines should cross");
+      };
+      let global_error = global.as_dvec2().distance(expected);
+      let local_error = local.as_dvec2().distance(expected);
+      // The error is within rounding of the exact point (half a unit in the
+      // last place in each coordinate).
+      let half_ulp = (offset.abs().max_element() as f64) * f32::EPSILON as f64;
+      assert!(local_error <= half_ulp, "offset={offset}, error={local_error}");
+      assert!(local_error <= global_error, "offset={offset}");
+    }
+  }
+
+  #[test]
+  fn f64_intersections_recognize_shared_end_points() {
+    // Edges of `tests::inconsistent_sweeps_are_recomputed_in_f64` that share
+    // their right end point. In f32, rounding puts an intersection next to it.
+    let line_1 =
+      (Vec2::new(18.344215, 5.924168), Vec2::new(18.470703, 4.278999));
+    let line_2 =
+      (Vec2::new(17.538916, 1.5802376), Vec2::new(18.470703, 4.278999));
+    assert_ne!(
+      edge_intersection_in_local_frame(line_1, line_2),
+      EdgeIntersectionResult::NoIntersection
+    );
+    assert_eq!(
+      edge_intersection_f64(line_1, line_2),
+      EdgeIntersectionResult::NoIntersection
+    );
+
+    // End points on the interior of the other line are split points exactly.
+    assert_eq!(
+      edge_intersection_f64(
+        (Vec2::new(0.0, 0.0), Vec2::new(4.0, 2.0)),
+        (Vec2::new(2.0, 1.0), Vec2::new(2.0, 5.0))
+      ),
+      EdgeIntersectionResult::PointIntersection(Vec2::new(2.0, 1.0))
+    );
+    let crossing = (Vec2::new(18.0, 3.0), Vec2::new(18.6, 5.0));
+    let EdgeIntersectionResult::PointIntersection(point) =
+      edge_intersection_f64(line_1, crossing)
+    else {
+      panic!("lines should cross");
+    };
+    assert!(
+      point.as_dvec2().distance(exact_intersection(line_1, crossing))
+        <= 18.0 * f32::EPSILON as f64
+    );
+  }
 }
@@ -0,0 +1,233 @@
+use glam::Vec2;
+
+use crate::{
+  hierarchy::compute_contour_parents,
+  util::{contour_signed_area, edge_intersection_f64, EdgeIntersectionResult},
+  Polygon,
+};
+
+// A problem with a polygon found by `Polygon::validate`. Contours and edges are
+// referred to by their index, where edge `i` goes from vertex `i` to vertex
+// `i + 1` of its contour.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ValidationIssue {
+  // The vertex is the same as the vertex after it, so the edge between them
+  // has zero length.
+  DuplicateVertex { contour: usize, vertex: usize },
+  // The contour has fewer than 3 distinct points, so it has no area.
+  TooFewPoints { contour: usize },
+  // Two edges of the contour cross or overlap. Adjacent edges only count if
+  // they overlap (e.g. a spike that doubles back on itself).
+  SelfIntersection { contour: usize, edges: [usize; 2] },
+  // The contour has the same orientation as the contour it is directly nested
+  // in, so their winding numbers do not alternate.
+  InconsistentWinding { contour: usize },
+  // The contour is oriented as a hole (clockwise), but is not nested in any
+  // other contour.
+  HoleOutsideShell { contour: usize },
+}
+
+impl Polygon {
+  // Checks the polygon for problems that commonly break downstream consumers,
+  // returning every issue found (in order of contour). Boolean operations
+  // treat contours with the even-odd rule, so the orientation issues
+  // (`InconsistentWinding` and `HoleOutsideShell`) do not affect them, but the
+  // rest can produce surprising results. Finding self-intersections compares
+  // every pair of edges in each contour, so this takes O(n^2) time for a
+  // contour with n vertices.
+  pub fn validate(&self) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    for (contour_index, contour) in self.contours.iter().enumerate() {
+      for (vertex, &point) in contour.iter().enumerate() {
+        if contour.len() > 1 && point == contour[(vertex + 1) % contour.len()] {
+          issues.push(ValidationIssue::DuplicateVertex {
+            contour: contour_index,
+            vertex,
+          });
+        }
+      }
+      if count_distinct_points(contour) < 3 {
+        issues.push(ValidationIssue::TooFewPoints { contour: contour_index });
+        continue;
+      }
+      for edges in find_self_intersections(contour) {
+        issues.push(ValidationIssue::SelfIntersection {
+          contour: contour_index,
+          edges,
+        });
+      }
+    }
+
+    let contour_parents = compute_contour_parents(self);
+    let areas = self
+      .contours
+      .iter()
+      .map(|contour| contour_signed_area(contour))
+      .collect::<Vec<_>>();
+    for (contour_index, &area) in areas.iter().enumerate() {
+      if area == 0.0 {
+        continue;
+      }
+      match contour_parents[contour_index] {
+        None if area < 0.0 => issues
+          .push(ValidationIssue::HoleOutsideShell { contour: contour_index }),
+        Some(parent) if (0.0 < area) == (0.0 < areas[parent]) => {
+          issues.push(ValidationIssue::InconsistentWinding {
+            contour: contour_index,
+          })
+        }
+        _ => {}
+      }
+    }
+    issues
+  }
+}
+
+// Counts the number of distinct points in `contour`.
+fn count_distinct_points(contour: &[Vec2]) -> usize {
+  let mut points = contour.to_vec();
+  points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+  points.dedup();
+  points.len()
+}
+
+// Finds the pairs of edges (in increasing order) of `contour` that cross or
+// overlap. Edges with zero length are skipped, since they are reported as
+// duplicate vertices. The edges are compared in f64, since in f32 nearly
+// collinear edges (and edges sharing an end point) can look like they overlap
+// (or cross) when they only come close.
+fn find_self_intersections(contour: &[Vec2]) -> Vec<[usize; 2]> {
+  let edge =
+    |index: usize| (contour[index], contour[(index + 1) % contour.len()]);
+  let mut intersections = Vec::new();
+  for first in 0..contour.len() {
+    let first_edge = edge(first);
+    if first_edge.0 == first_edge.1 {
+      continue;
+    }
+    for second in first + 1..contour.len() {
+      let second_edge = edge(second);
+      if second_edge.0 == second_edge.1 {
+        continue;
+      }
+      let adjacent =
+        second == first + 1 || (first == 0 && second == contour.len() - 1);
+      let intersects = match edge_intersection_f64(first_edge, second_edge) {
+        EdgeIntersectionResult::NoIntersection => false,
+        // Adjacent edges always share a vertex, so only overlaps count.
+        EdgeIntersectionResult::PointIntersection(_) => !adjacent,
+        EdgeIntersectionResult::LineIntersection(..) => true,
+      };
+      if intersects {
+        intersections.push([first, second]);
+      }
+    }
+  }
+  intersections
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::rectangle;
+  use crate::{Polygon, ValidationIssue};
+
+  #[test]
+  fn valid_polygon_has_no_issues() {
+    let mut hole = rectangle(Vec2::splat(1.0), Vec2::splat(2.0));
+    hole.reverse();
+    let polygon = Polygon {
+      contours: vec![rectangle(Vec2::splat(0.0), Vec2::splat(3.0)), hole],
+    };
+    assert_eq!(polygon.validate(), []);
+  }
+
+  #[test]
+  fn reports_vertex_and_edge_issues() {
+    let polygon = Polygon {
+      contours: vec![
+        vec![
+          Vec2::new(0.0, 0.0),
+          Vec2::new(2.0, 0.0),
+          Vec2::new(2.0, 0.0),
+          Vec2::new(0.0, 2.0),
+          Vec2::new(2.0, 2.0),
+        ],
+        vec![Vec2::new(5.0, 5.0), Vec2::new(6.0, 5.0), Vec2::new(5.0, 5.0)],
+      ],
+    };
+    assert_eq!(
+      polygon.validate(),
+      [
+        ValidationIssue::DuplicateVertex { contour: 0, vertex: 1 },
+        ValidationIssue::SelfIntersection { contour: 0, edges: [2, 4] },
+        ValidationIssue::DuplicateVertex { contour: 1, vertex: 2 },
+        ValidationIssue::TooFewPoints { contour: 1 },
+      ]
+    );
+  }
+
+  #[test]
+  fn reports_spikes_as_self_intersections() {
+    let polygon = Polygon {
+      contours: vec![vec![
+        Vec2::new(0.0, 0.0),
+        Vec2::new(2.0, 0.0),
+        Vec2::new(3.0, 0.0),
+        Vec2::new(2.5, 0.0),
+        Vec2::new(2.0, 2.0),
+      ]],
+    };
+    assert_eq!(
+      polygon.validate(),
+      [
+        // The spike doubles back over the edge it started from.
+        ValidationIssue::SelfIntersection { contour: 0, edges: [1, 2] },
+        ValidationIssue::SelfIntersection { contour: 0, edges: [1, 3] },
+      ]
+    );
+  }
+
+  #[test]
+  fn nearly_collinear_edges_do_not_intersect() {
+    // A union result where edge 9 starts just past the end of edge 6, almost
+    // on the line through it. In f32 the edges look like they overlap.
+    let polygon = Polygon {
+      contours: vec![vec![
+        Vec2::new(1.181915, 6.039212),
+        Vec2::new(2.6033719, 4.308646),
+        Vec2::new(2.5857284, 4.017205),
+        Vec2::new(2.805075, 4.063081),
+        Vec2::new(4.1863465, 2.381439),
+        Vec2::new(6.361043, 3.2850156),
+        Vec2::new(6.1635184, 5.521962),
+        Vec2::new(4.9356427, 7.344666),
+        Vec2::new(5.0953617, 8.206415),
+        Vec2::new(4.593534, 7.8525047),
+        Vec2::new(3.6199741, 9.297692),
+      ]],
+    };
+    assert_eq!(polygon.validate(), []);
+  }
+
+  #[test]
+  fn reports_orientation_issues() {
+    let mut clockwise_shell = rectangle(Vec2::splat(10.0), Vec2::splat(13.0));
+    clockwise_shell.reverse();
+    let polygon = Polygon {
+      contours: vec![
+        rectangle(Vec2::splat(0.0), Vec2::splat(3.0)),
+        rectangle(Vec2::splat(1.0), Vec2::splat(2.0)),
+        clockwise_shell,
+      ],
+    };
+    assert_eq!(
+      polygon.validate(),
+      [
+        ValidationIssue::InconsistentWinding { contour: 1 },
+        ValidationIssue::HoleOutsideShell { contour: 2 },
+      ]
+    );
+  }
+}
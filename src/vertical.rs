@@ -0,0 +1,165 @@
+use crate::{precision::intersection_error_for_magnitude, Polygon, SourceEdge};
+
+// An input configuration that relies on the tie-break rules for vertical edges
+// (see the "Vertical edges" section of the README). The result is well-defined
+// for these, but it is sensitive to tiny changes of the input.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VerticalAmbiguity {
+  // The edge is not treated as vertical, but its horizontal extent is within
+  // the error of computed intersections, so it may behave as vertical once
+  // split.
+  NearlyVerticalEdge { edge: SourceEdge },
+  // A vertex of one polygon lies strictly inside a vertical edge of the other
+  // polygon (a T intersection). The vertex is the start of the edge `vertex`.
+  VertexOnVerticalEdge { vertex: SourceEdge, vertical_edge: SourceEdge },
+  // Vertical edges of the subject and clip overlap along a segment.
+  OverlappingVerticalEdges { subject_edge: SourceEdge, clip_edge: SourceEdge },
+}
+
+// A vertical edge of one of the polygons.
+struct VerticalEdge {
+  source_edge: SourceEdge,
+  x: f32,
+  // The range of y values (min, max) that the edge covers.
+  y_range: (f32, f32),
+}
+
+// Finds the configurations of `subject` and `clip` that hit the ambiguous cases
+// of the vertical edge tie-break rules. This is intended as a debugging aid
+// (e.g. when a result looks surprising), so it simply compares every pair of
+// edges and vertices.
+pub fn find_vertical_ambiguities(
+  subject: &Polygon,
+  clip: &Polygon,
+) -> Vec<VerticalAmbiguity> {
+  let mut ambiguities = Vec::new();
+  let mut vertical_edges = Vec::new();
+  for (is_from_subject, polygon) in [(true, subject), (false, clip)] {
+    for (contour_index, edge_index, start, end) in polygon.iter_edges() {
+      let source_edge = SourceEdge {
+        is_from_subject,
+        contour: contour_index,
+        edge: edge_index,
+      };
+      if (start.x - end.x).abs() < f32::EPSILON {
+        if start.y != end.y {
+          vertical_edges.push(VerticalEdge {
+            source_edge,
+            x: start.x,
+            y_range: (start.y.min(end.y), start.y.max(end.y)),
+          });
+        }
+        continue;
+      }
+      let magnitude = start.abs().max(end.abs()).max_element();
+      if (start.x - end.x).abs() <= intersection_error_for_magnitude(magnitude)
+      {
+        ambiguities
+          .push(VerticalAmbiguity::NearlyVerticalEdge { edge: source_edge });
+      }
+    }
+  }
+
+  for vertical_edge in vertical_edges.iter() {
+    let other_polygon =
+      if vertical_edge.source_edge.is_from_subject { clip } else { subject };
+    for (contour_index, contour) in other_polygon.contours.iter().enumerate() {
+      for (vertex_index, &vertex) in contour.iter().enumerate() {
+        if vertex.x == vertical_edge.x
+          && vertical_edge.y_range.0 < vertex.y
+          && vertex.y < vertical_edge.y_range.1
+        {
+          ambiguities.push(VerticalAmbiguity::VertexOnVerticalEdge {
+            vertex: SourceEdge {
+              is_from_subject: !vertical_edge.source_edge.is_from_subject,
+              contour: contour_index,
+              edge: vertex_index,
+            },
+            vertical_edge: vertical_edge.source_edge,
+          });
+        }
+      }
+    }
+  }
+
+  for subject_edge in
+    vertical_edges.iter().filter(|edge| edge.source_edge.is_from_subject)
+  {
+    for clip_edge in
+      vertical_edges.iter().filter(|edge| !edge.source_edge.is_from_subject)
+    {
+      if subject_edge.x == clip_edge.x
+        && ranges_overlap(subject_edge.y_range, clip_edge.y_range)
+      {
+        ambiguities.push(VerticalAmbiguity::OverlappingVerticalEdges {
+          subject_edge: subject_edge.source_edge,
+          clip_edge: clip_edge.source_edge,
+        });
+      }
+    }
+  }
+
+  ambiguities
+}
+
+// Determines whether the ranges (min, max) `a` and `b` share more than a single
+// value.
+fn ranges_overlap(a: (f32, f32), b: (f32, f32)) -> bool {
+  a.0.max(b.0) < a.1.min(b.1)
+}
+
+#[cfg(test)]
+mod tests {
+  use glam::Vec2;
+
+  use crate::tests::fixtures::rectangle_polygon;
+  use crate::{Polygon, SourceEdge, VerticalAmbiguity};
+
+  use super::find_vertical_ambiguities;
+
+  #[test]
+  fn finds_vertex_on_vertical_edge_and_overlapping_edges() {
+    let subject = rectangle_polygon(Vec2::new(0.0, 0.0), Vec2::new(2.0, 2.0));
+    let clip = rectangle_polygon(Vec2::new(2.0, 1.0), Vec2::new(3.0, 4.0));
+
+    let subject_edge =
+      |edge| SourceEdge { is_from_subject: true, contour: 0, edge };
+    let clip_edge =
+      |edge| SourceEdge { is_from_subject: false, contour: 0, edge };
+    assert_eq!(
+      find_vertical_ambiguities(&subject, &clip),
+      [
+        VerticalAmbiguity::VertexOnVerticalEdge {
+          vertex: clip_edge(0),
+          vertical_edge: subject_edge(1),
+        },
+        VerticalAmbiguity::VertexOnVerticalEdge {
+          vertex: subject_edge(2),
+          vertical_edge: clip_edge(3),
+        },
+        VerticalAmbiguity::OverlappingVerticalEdges {
+          subject_edge: subject_edge(1),
+          clip_edge: clip_edge(3),
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn finds_nearly_vertical_edges() {
+    let subject = Polygon {
+      contours: vec![vec![
+        Vec2::new(1000.0, 0.0),
+        Vec2::new(1000.0001, 10.0),
+        Vec2::new(990.0, 10.0),
+      ]],
+    };
+
+    assert_eq!(
+      find_vertical_ambiguities(&subject, &Polygon { contours: vec![] }),
+      [VerticalAmbiguity::NearlyVerticalEdge {
+        edge: SourceEdge { is_from_subject: true, contour: 0, edge: 0 },
+      }]
+    );
+  }
+}